@@ -0,0 +1,63 @@
+//! Correlated JSON logs for command handling
+//!
+//! Handles two commands inside `command_span`s and logs as JSON. Every line
+//! logged inside a span carries its fields under `"span"`, e.g.
+//!
+//! ```text
+//! {"timestamp":"…","level":"INFO","fields":{"message":"Command handled","events":1},
+//!  "target":"command_tracing","span":{"actor":"system:crm","causation_id":"…",
+//!  "command_type":"CreatePerson","correlation_id":"…","message_id":"…",
+//!  "person_id":"…","trace_id":"…","parent_span_id":"…","name":"person_command"}, …}
+//! ```
+//!
+//! The second command is a child of the first, so both lines share a
+//! `correlation_id` and the second's `causation_id` is the first's `message_id`.
+
+use cim_domain::formal_domain::Aggregate;
+use cim_domain_person::{
+    aggregate::{Person, PersonId},
+    commands::{AddTag, CreatePerson, PersonCommand},
+    infrastructure::PersonSubjects,
+    nats::{command_span, MessageIdentity, PersonMessageEnvelope, PersonTracingContext},
+    value_objects::{PersonName, Tag},
+};
+use tracing::info;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt().json().with_span_list(false).init();
+
+    let person_id = PersonId::new();
+    let subject = PersonSubjects::command_for(person_id);
+    let trace = PersonTracingContext::new();
+
+    let create = PersonMessageEnvelope::new(
+        PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "crm".to_string(),
+        }),
+        subject.clone(),
+        MessageIdentity::for_system("crm"),
+    );
+    let tag = PersonMessageEnvelope::new(
+        PersonCommand::AddTag(AddTag {
+            person_id,
+            tag: Tag::new("segment", "vip")?,
+        }),
+        subject,
+        create.identity.create_child(),
+    );
+
+    let mut person = Person::empty();
+    for mut envelope in [create, tag] {
+        envelope.headers = trace.create_child_span().to_headers();
+        let _span = command_span(&envelope).entered();
+
+        info!("Handling command");
+        let (next, events) = person.handle(envelope.payload.clone())?;
+        person = next;
+        info!(events = events.len(), version = person.version, "Command handled");
+    }
+
+    Ok(())
+}
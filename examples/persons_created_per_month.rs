@@ -0,0 +1,41 @@
+//! Persons created per month, folded over the event stream
+//!
+//! Walks every event in `PERSON_EVENTS` once with
+//! `StreamingClient::fold_events` and counts `Created` events by the
+//! month they were created in. Only one batch of events is held in memory at
+//! a time, however long the stream is.
+//!
+//! Usage:
+//!   NATS_URL=nats://localhost:4222 cargo run --example persons_created_per_month
+
+use cim_domain_person::{
+    events::PersonEventV2,
+    infrastructure::{StreamingClient, StreamingConfig},
+};
+use std::collections::BTreeMap;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let nats_url = env::var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".to_string());
+    let streaming = StreamingClient::new(&nats_url, StreamingConfig::default()).await?;
+    println!("Connected to NATS at {nats_url}");
+
+    let per_month = streaming
+        .fold_events(BTreeMap::<String, usize>::new(), |mut counts, envelope| {
+            if let PersonEventV2::Created { metadata, .. } = &envelope.event {
+                *counts.entry(metadata.timestamp.format("%Y-%m").to_string()).or_default() += 1;
+            }
+            counts
+        })
+        .await?;
+
+    if per_month.is_empty() {
+        println!("No persons created yet");
+    }
+    for (month, count) in &per_month {
+        println!("{month}: {count}");
+    }
+
+    Ok(())
+}
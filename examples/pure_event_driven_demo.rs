@@ -232,5 +232,9 @@ fn event_type_name(event: &PersonEvent) -> &'static str {
         PersonEvent::PersonDeactivated(_) => "PersonDeactivated",
         PersonEvent::PersonReactivated(_) => "PersonReactivated",
         PersonEvent::PersonMergedInto(_) => "PersonMergedInto",
+        PersonEvent::LifeEventRecorded(_) => "LifeEventRecorded",
+        PersonEvent::ConsentRecorded(_) => "ConsentRecorded",
+        PersonEvent::TagAdded(_) => "TagAdded",
+        PersonEvent::TagRemoved(_) => "TagRemoved",
     }
 }
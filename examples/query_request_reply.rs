@@ -0,0 +1,74 @@
+//! Person queries over NATS request/reply
+//!
+//! Starts a PersonQueryResponder in-process, then queries it the way any
+//! other service would: serialize a PersonQuery, send it as a request on its
+//! query subject and deserialize the PersonQueryResponse from the reply.
+//!
+//! Usage:
+//!   NATS_URL=nats://localhost:4222 cargo run --example query_request_reply
+
+use cim_domain_person::{
+    aggregate::PersonId,
+    events::{PersonCreated, PersonEvent},
+    infrastructure::{PersonQueryResponder, PersonSubjects},
+    projections::*,
+    queries::{PersonQuery, PersonQueryResponse, PersonQueryService},
+    value_objects::PersonName,
+};
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let nats_url = env::var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".to_string());
+    let client = async_nats::connect(&nats_url).await?;
+    println!("Connected to NATS at {nats_url}");
+
+    // Server side: a query service over a projection holding one person
+    let summaries = Arc::new(PersonSummaryProjection::new());
+    let person_id = PersonId::new();
+    summaries.handle_event(&PersonEvent::PersonCreated(PersonCreated {
+        person_id,
+        name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+        source: "example".to_string(),
+        created_at: chrono::Utc::now(),
+    })).await?;
+    let queries = Arc::new(PersonQueryService::new(
+        summaries,
+        Arc::new(PersonSearchProjection::new()),
+        Arc::new(PersonSkillsProjection::new()),
+        Arc::new(PersonNetworkProjection::new()),
+        Arc::new(PersonTimelineProjection::new()),
+    ));
+    let responder = PersonQueryResponder::new(queries, client.clone());
+    tokio::spawn(async move {
+        if let Err(e) = responder.start().await {
+            eprintln!("Query responder stopped: {e}");
+        }
+    });
+    // Give the subscription a moment to reach the server
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Client side
+    let query = PersonQuery::GetSummary { person_id };
+    let reply = client
+        .request(PersonSubjects::query_for(&query), serde_json::to_vec(&query)?.into())
+        .await?;
+    match serde_json::from_slice::<PersonQueryResponse>(&reply.payload)? {
+        PersonQueryResponse::Summary(Some(summary)) => println!("Found {}", summary.name),
+        PersonQueryResponse::Summary(None) => println!("No such person"),
+        PersonQueryResponse::Error { message } => println!("Query failed: {message}"),
+        other => println!("Unexpected response: {other:?}"),
+    }
+
+    // A malformed request still gets an answer
+    let reply = client
+        .request("person.queries.GetSummary", b"not a query".to_vec().into())
+        .await?;
+    if let PersonQueryResponse::Error { message } = serde_json::from_slice(&reply.payload)? {
+        println!("Rejected malformed request: {message}");
+    }
+
+    Ok(())
+}
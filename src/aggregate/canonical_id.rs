@@ -0,0 +1,94 @@
+//! Canonical string form of [`PersonId`] for external keys
+//!
+//! The format is `person:` followed by the lowercase, hyphenated UUID, e.g.
+//! `person:01890a5d-ac96-774b-bcce-b302099a8057`. It is part of the public
+//! contract: it does not follow changes to the serde representation of
+//! `EntityId` and will not change without a major version bump.
+
+use super::PersonId;
+use uuid::Uuid;
+
+/// Prefix of every canonical person ID
+pub const PERSON_ID_PREFIX: &str = "person:";
+
+/// A string was not a canonical person ID
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PersonIdParseError {
+    #[error("Person ID must start with 'person:': {0}")]
+    MissingPrefix(String),
+
+    #[error("Person ID is not a lowercase hyphenated UUID: {0}")]
+    InvalidUuid(String),
+}
+
+/// Stable string encoding for person IDs
+///
+/// Implemented as a trait because `PersonId` is an alias of the foreign
+/// `EntityId<PersonMarker>`; bring it into scope to call
+/// `PersonId::parse` / `id.to_canonical_string()`.
+pub trait CanonicalPersonId: Sized {
+    fn to_canonical_string(&self) -> String;
+
+    fn parse(s: &str) -> Result<Self, PersonIdParseError>;
+}
+
+impl CanonicalPersonId for PersonId {
+    fn to_canonical_string(&self) -> String {
+        format!("{PERSON_ID_PREFIX}{}", self.as_uuid().hyphenated())
+    }
+
+    fn parse(s: &str) -> Result<Self, PersonIdParseError> {
+        let encoded = s
+            .strip_prefix(PERSON_ID_PREFIX)
+            .ok_or_else(|| PersonIdParseError::MissingPrefix(s.to_string()))?;
+
+        // Only the exact canonical spelling is accepted, so every ID has one key
+        let uuid = Uuid::try_parse(encoded)
+            .ok()
+            .filter(|uuid| uuid.hyphenated().to_string() == encoded)
+            .ok_or_else(|| PersonIdParseError::InvalidUuid(s.to_string()))?;
+
+        Ok(PersonId::from_uuid(uuid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_string_round_trips() {
+        let id = PersonId::new();
+        let encoded = id.to_canonical_string();
+
+        assert!(encoded.starts_with("person:"));
+        assert_eq!(encoded.len(), PERSON_ID_PREFIX.len() + 36);
+        assert_eq!(PersonId::parse(&encoded).unwrap(), id);
+    }
+
+    #[test]
+    fn test_known_value_is_stable() {
+        let uuid = Uuid::parse_str("01890a5d-ac96-774b-bcce-b302099a8057").unwrap();
+        let id = PersonId::from_uuid(uuid);
+        assert_eq!(id.to_canonical_string(), "person:01890a5d-ac96-774b-bcce-b302099a8057");
+    }
+
+    #[test]
+    fn test_malformed_strings_are_rejected() {
+        let uuid = "01890a5d-ac96-774b-bcce-b302099a8057";
+
+        assert!(matches!(PersonId::parse(uuid), Err(PersonIdParseError::MissingPrefix(_))));
+        assert!(matches!(PersonId::parse(&format!("org:{uuid}")), Err(PersonIdParseError::MissingPrefix(_))));
+        assert!(matches!(PersonId::parse("person:not-a-uuid"), Err(PersonIdParseError::InvalidUuid(_))));
+        assert!(matches!(PersonId::parse("person:"), Err(PersonIdParseError::InvalidUuid(_))));
+        // Non-canonical spellings of a valid UUID
+        assert!(matches!(
+            PersonId::parse(&format!("person:{}", uuid.to_uppercase())),
+            Err(PersonIdParseError::InvalidUuid(_))
+        ));
+        assert!(matches!(
+            PersonId::parse(&format!("person:{}", uuid.replace('-', ""))),
+            Err(PersonIdParseError::InvalidUuid(_))
+        ));
+    }
+}
@@ -0,0 +1,450 @@
+//! Field-level differences between two versions of a person
+//!
+//! Review queues show "what changed since the last reviewed version" rather
+//! than raw events. [`Person::changes_since`] compares the current state with
+//! a baseline (an older snapshot or replay of the same person) and describes
+//! each difference in a sentence fit for display.
+//!
+//! [`Person::diff`] reports the same differences as structured data, for
+//! review UIs that render their own before/after views and for comparing
+//! unrelated snapshots such as the two sides of a merge.
+//!
+//! Attributes of the same type are paired in recording order: updates
+//! replace an attribute in place and new recordings are appended, so the
+//! n-th attribute of a type in the baseline is the n-th one now.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::canonical_id::CanonicalPersonId;
+use super::person_ecs::{Person, PersonLifecycle};
+use crate::cross_domain::person_organization::EMPLOYMENT_ATTRIBUTE_CATEGORY;
+use crate::value_objects::{AttributeType, AttributeValue, PersonAttribute, PersonName, Tag};
+
+/// How a field changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChangeKind {
+    /// The field had no value in the baseline
+    Added,
+    /// The value was replaced
+    Changed,
+    /// The value is kept but is no longer valid
+    Ended,
+    /// The value is gone
+    Removed,
+}
+
+/// One field-level change, with a human-readable description
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    /// Changed field, e.g. "legal_name" or "attribute:employment at acme"
+    pub field: String,
+    pub kind: ChangeKind,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// Sentence describing the change, e.g. "Ended employment at acme"
+    pub description: String,
+}
+
+impl ChangeEntry {
+    fn new(
+        field: impl Into<String>,
+        kind: ChangeKind,
+        before: Option<String>,
+        after: Option<String>,
+        description: String,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            kind,
+            before,
+            after,
+            description,
+        }
+    }
+}
+
+/// A scalar field whose value differs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange<T> {
+    pub before: T,
+    pub after: T,
+}
+
+/// How one entry of a collection differs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EntryChange<T> {
+    Added(T),
+    Removed(T),
+    Changed { before: T, after: T },
+}
+
+/// Structured differences between two person states, see [`Person::diff`]
+///
+/// Employments are the employment-category attributes kept by the
+/// organization integration; `attributes` holds every other attribute.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonDiff {
+    pub legal_name: Option<FieldChange<PersonName>>,
+    pub birth_date: Option<FieldChange<Option<NaiveDate>>>,
+    pub death_date: Option<FieldChange<Option<NaiveDate>>>,
+    pub is_active: Option<FieldChange<bool>>,
+    pub lifecycle: Option<FieldChange<PersonLifecycle>>,
+    pub employments: Vec<EntryChange<PersonAttribute>>,
+    pub attributes: Vec<EntryChange<PersonAttribute>>,
+    pub tags: Vec<EntryChange<Tag>>,
+}
+
+impl PersonDiff {
+    /// Whether the two states are the same, bookkeeping aside
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Person {
+    /// Field-level changes from `baseline` to this version
+    ///
+    /// Bookkeeping fields (version, timestamps) are not reported.
+    pub fn changes_since(&self, baseline: &Person) -> Vec<ChangeEntry> {
+        let mut changes = Vec::new();
+
+        if baseline.core_identity.legal_name != self.core_identity.legal_name {
+            let old_name = baseline.core_identity.legal_name.full_name();
+            let new_name = self.core_identity.legal_name.full_name();
+            changes.push(ChangeEntry::new(
+                "legal_name",
+                ChangeKind::Changed,
+                Some(old_name.clone()),
+                Some(new_name.clone()),
+                format!("Name changed from {old_name} to {new_name}"),
+            ));
+        }
+
+        changes.extend(date_change("birth_date", "birth date", baseline.core_identity.birth_date, self.core_identity.birth_date));
+        changes.extend(date_change("death_date", "date of death", baseline.core_identity.death_date, self.core_identity.death_date));
+
+        if baseline.lifecycle != self.lifecycle {
+            let (before, after) = (describe_lifecycle(&baseline.lifecycle), describe_lifecycle(&self.lifecycle));
+            changes.push(ChangeEntry::new(
+                "lifecycle",
+                ChangeKind::Changed,
+                Some(before.clone()),
+                Some(after.clone()),
+                format!("Status changed from {before} to {after}"),
+            ));
+        }
+
+        changes.extend(attribute_changes(&baseline.attributes.attributes, &self.attributes.attributes));
+        changes
+    }
+
+    /// Structured differences from this state to `other`
+    ///
+    /// The states need not be versions of the same person. Identity and
+    /// bookkeeping fields (id, version, timestamps) are not compared.
+    pub fn diff(&self, other: &Person) -> PersonDiff {
+        let (before, after) = (&self.core_identity, &other.core_identity);
+        let (employments_before, attributes_before): (Vec<_>, Vec<_>) =
+            self.attributes.attributes.iter().partition(|attr| is_employment(&attr.attribute_type));
+        let (employments_after, attributes_after): (Vec<_>, Vec<_>) =
+            other.attributes.attributes.iter().partition(|attr| is_employment(&attr.attribute_type));
+
+        PersonDiff {
+            legal_name: field_change(&before.legal_name, &after.legal_name),
+            birth_date: field_change(&before.birth_date, &after.birth_date),
+            death_date: field_change(&before.death_date, &after.death_date),
+            is_active: field_change(&self.is_active(), &other.is_active()),
+            lifecycle: field_change(&self.lifecycle, &other.lifecycle),
+            employments: attribute_entry_changes(&employments_before, &employments_after),
+            attributes: attribute_entry_changes(&attributes_before, &attributes_after),
+            tags: self.tags.difference(&other.tags).cloned().map(EntryChange::Removed)
+                .chain(other.tags.difference(&self.tags).cloned().map(EntryChange::Added))
+                .collect(),
+        }
+    }
+}
+
+fn field_change<T: Clone + PartialEq>(before: &T, after: &T) -> Option<FieldChange<T>> {
+    (before != after).then(|| FieldChange { before: before.clone(), after: after.clone() })
+}
+
+fn is_employment(attribute_type: &AttributeType) -> bool {
+    matches!(attribute_type, AttributeType::Custom(custom) if custom.category == EMPLOYMENT_ATTRIBUTE_CATEGORY)
+}
+
+/// Pair attributes by type and recording order, like [`attribute_changes`]
+fn attribute_entry_changes(before: &[&PersonAttribute], after: &[&PersonAttribute]) -> Vec<EntryChange<PersonAttribute>> {
+    let mut changes = Vec::new();
+    let mut seen_types: Vec<&AttributeType> = Vec::new();
+
+    for attribute_type in after.iter().chain(before).map(|attr| &attr.attribute_type) {
+        if seen_types.contains(&attribute_type) {
+            continue;
+        }
+        seen_types.push(attribute_type);
+
+        let of_type = |attrs: &[&PersonAttribute]| -> Vec<PersonAttribute> {
+            attrs.iter().filter(|attr| &attr.attribute_type == attribute_type).map(|attr| (*attr).clone()).collect()
+        };
+        let (old, new) = (of_type(before), of_type(after));
+        for index in 0..old.len().max(new.len()) {
+            match (old.get(index), new.get(index)) {
+                (None, Some(added)) => changes.push(EntryChange::Added(added.clone())),
+                (Some(removed), None) => changes.push(EntryChange::Removed(removed.clone())),
+                (Some(old), Some(new)) if old != new => changes.push(EntryChange::Changed {
+                    before: old.clone(),
+                    after: new.clone(),
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    changes
+}
+
+fn date_change(field: &str, label: &str, before: Option<NaiveDate>, after: Option<NaiveDate>) -> Option<ChangeEntry> {
+    let (kind, description) = match (before, after) {
+        (None, Some(new)) => (ChangeKind::Added, format!("Set {label} to {new}")),
+        (Some(old), Some(new)) if old != new => (ChangeKind::Changed, format!("Changed {label} from {old} to {new}")),
+        (Some(old), None) => (ChangeKind::Removed, format!("Removed {label} {old}")),
+        _ => return None,
+    };
+    Some(ChangeEntry::new(field, kind, before.map(|d| d.to_string()), after.map(|d| d.to_string()), description))
+}
+
+fn attribute_changes(baseline: &[PersonAttribute], current: &[PersonAttribute]) -> Vec<ChangeEntry> {
+    let mut changes = Vec::new();
+    let mut seen_types: Vec<&AttributeType> = Vec::new();
+
+    for attribute_type in current.iter().chain(baseline).map(|attr| &attr.attribute_type) {
+        if seen_types.contains(&attribute_type) {
+            continue;
+        }
+        seen_types.push(attribute_type);
+
+        let of_type = |attrs: &[PersonAttribute]| -> Vec<PersonAttribute> {
+            attrs.iter().filter(|attr| &attr.attribute_type == attribute_type).cloned().collect()
+        };
+        let (old, new) = (of_type(baseline), of_type(current));
+        let label = attribute_label(attribute_type);
+        let field = format!("attribute:{label}");
+
+        for index in 0..old.len().max(new.len()) {
+            match (old.get(index), new.get(index)) {
+                (None, Some(added)) => {
+                    let value = describe_value(&added.value);
+                    changes.push(ChangeEntry::new(
+                        field.clone(),
+                        ChangeKind::Added,
+                        None,
+                        Some(value.clone()),
+                        format!("Added {label}: {value}"),
+                    ));
+                }
+                (Some(removed), None) => {
+                    let value = describe_value(&removed.value);
+                    changes.push(ChangeEntry::new(
+                        field.clone(),
+                        ChangeKind::Removed,
+                        Some(value.clone()),
+                        None,
+                        format!("Removed {label}: {value}"),
+                    ));
+                }
+                (Some(before), Some(after)) if before.value != after.value => {
+                    let (old_value, new_value) = (describe_value(&before.value), describe_value(&after.value));
+                    changes.push(ChangeEntry::new(
+                        field.clone(),
+                        ChangeKind::Changed,
+                        Some(old_value.clone()),
+                        Some(new_value.clone()),
+                        format!("Changed {label} from {old_value} to {new_value}"),
+                    ));
+                }
+                (Some(before), Some(after)) => {
+                    if let (None, Some(until)) = (before.temporal.valid_until, after.temporal.valid_until) {
+                        let value = describe_value(&after.value);
+                        changes.push(ChangeEntry::new(
+                            field.clone(),
+                            ChangeKind::Ended,
+                            Some(value.clone()),
+                            Some(value),
+                            format!("Ended {label} on {until}"),
+                        ));
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    changes
+}
+
+/// Readable name of an attribute type, e.g. `BirthDate` → "birth date"
+fn attribute_label(attribute_type: &AttributeType) -> String {
+    let variant = match attribute_type {
+        AttributeType::Custom(custom) if custom.category == EMPLOYMENT_ATTRIBUTE_CATEGORY => {
+            return format!("employment at {}", custom.organization);
+        }
+        AttributeType::Custom(custom) => return custom.attribute_name.clone(),
+        AttributeType::Identifying(t) => format!("{t:?}"),
+        AttributeType::Physical(t) => format!("{t:?}"),
+        AttributeType::Healthcare(t) => format!("{t:?}"),
+        AttributeType::Demographic(t) => format!("{t:?}"),
+    };
+
+    let mut label = String::new();
+    for c in variant.chars() {
+        if c.is_uppercase() && !label.is_empty() {
+            label.push(' ');
+        }
+        label.extend(c.to_lowercase());
+    }
+    label
+}
+
+fn describe_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::Text(text) => text.clone(),
+        AttributeValue::Number(n) | AttributeValue::Length(n) | AttributeValue::Mass(n) => n.to_string(),
+        AttributeValue::Integer(n) => n.to_string(),
+        AttributeValue::Boolean(b) => b.to_string(),
+        AttributeValue::DateTime(at) => at.to_rfc3339(),
+        AttributeValue::Date(date) | AttributeValue::ApproximateDate { date, .. } => date.to_string(),
+        AttributeValue::YearMonth(year, month) => format!("{year}-{month:02}"),
+        AttributeValue::Year(year) => year.to_string(),
+        AttributeValue::LocationReference(location) => location.clone(),
+        AttributeValue::TextList(items) => items.join(", "),
+        AttributeValue::NotProvided { reason } => format!("not provided ({reason:?})"),
+        other => format!("{other:?}"),
+    }
+}
+
+fn describe_lifecycle(lifecycle: &PersonLifecycle) -> String {
+    match lifecycle {
+        PersonLifecycle::Active => "active".to_string(),
+        PersonLifecycle::Deactivated { reason, .. } => format!("deactivated ({reason})"),
+        PersonLifecycle::MergedInto { target_id, .. } => format!("merged into {}", target_id.to_canonical_string()),
+        PersonLifecycle::Deceased { date_of_death } => format!("deceased ({date_of_death})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::{EventSourced, PersonId};
+    use crate::events::{AttributeInvalidated, AttributeRecorded, PersonEvent, PersonUpdated};
+    use crate::value_objects::{
+        AttributeSource, ConfidenceLevel, CustomAttributeType, IdentifyingAttributeType, PersonName,
+        Provenance, TemporalValidity,
+    };
+    use chrono::Utc;
+
+    fn employment(organization: &str) -> AttributeType {
+        AttributeType::Custom(CustomAttributeType {
+            organization: organization.to_string(),
+            attribute_name: "role".to_string(),
+            category: EMPLOYMENT_ATTRIBUTE_CATEGORY.to_string(),
+        })
+    }
+
+    fn recorded(person_id: PersonId, attribute_type: AttributeType, value: AttributeValue) -> PersonEvent {
+        PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id,
+            attribute: PersonAttribute::new(
+                attribute_type,
+                value,
+                TemporalValidity::of(Utc::now()),
+                Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+            ),
+            recorded_at: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_changes_between_versions() {
+        let person_id = PersonId::new();
+        let baseline = Person::new(person_id, PersonName::new("Ada".to_string(), "Byron".to_string()))
+            .apply_event(&recorded(person_id, employment("acme"), AttributeValue::Text("engineer".to_string())))
+            .unwrap();
+
+        let current = [
+            PersonEvent::PersonUpdated(PersonUpdated {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                updated_at: Utc::now(),
+            }),
+            PersonEvent::AttributeInvalidated(AttributeInvalidated {
+                person_id,
+                attribute_type: employment("acme"),
+                invalidated_at: Utc::now(),
+                reason: Some("left".to_string()),
+                attribute_recorded_at: None,
+            }),
+            recorded(
+                person_id,
+                AttributeType::Identifying(IdentifyingAttributeType::BirthPlace),
+                AttributeValue::LocationReference("london".to_string()),
+            ),
+        ]
+        .iter()
+        .try_fold(baseline.clone(), |person, event| person.apply_event(event))
+        .unwrap();
+
+        let today = Utc::now().date_naive();
+        let descriptions: Vec<String> = current.changes_since(&baseline)
+            .into_iter()
+            .map(|change| change.description)
+            .collect();
+        assert_eq!(descriptions, vec![
+            "Name changed from Ada Byron to Ada Lovelace".to_string(),
+            format!("Ended employment at acme on {today}"),
+            "Added birth place: london".to_string(),
+        ]);
+
+        assert!(current.changes_since(&current).is_empty());
+    }
+
+    #[test]
+    fn test_diff_between_two_snapshots() {
+        let person_id = PersonId::new();
+        let vip = Tag::new("sales", "vip").unwrap();
+        let mut before = Person::new(person_id, PersonName::new("Ada".to_string(), "Byron".to_string()))
+            .apply_event(&recorded(person_id, employment("acme"), AttributeValue::Text("engineer".to_string())))
+            .unwrap();
+        before.tags.insert(vip.clone());
+
+        let mut after = before.clone();
+        after.core_identity.legal_name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        after.lifecycle = PersonLifecycle::Deactivated { reason: "left".to_string(), since: Utc::now() };
+        after.attributes.attributes[0].value = AttributeValue::Text("architect".to_string());
+        after = after
+            .apply_event(&recorded(person_id, employment("initech"), AttributeValue::Text("advisor".to_string())))
+            .unwrap();
+        after.tags.clear();
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.legal_name.as_ref().unwrap().after, after.core_identity.legal_name);
+        assert_eq!(diff.is_active, Some(FieldChange { before: true, after: false }));
+        assert!(diff.lifecycle.is_some());
+        assert_eq!(diff.birth_date, None);
+        assert!(diff.attributes.is_empty());
+        assert_eq!(diff.tags, vec![EntryChange::Removed(vip)]);
+
+        assert_eq!(diff.employments.len(), 2);
+        assert!(matches!(
+            &diff.employments[0],
+            EntryChange::Changed { before, after }
+                if before.value == AttributeValue::Text("engineer".to_string())
+                    && after.value == AttributeValue::Text("architect".to_string())
+        ));
+        assert!(matches!(&diff.employments[1], EntryChange::Added(added) if added.attribute_type == employment("initech")));
+
+        let json = serde_json::to_string(&diff).unwrap();
+        assert_eq!(serde_json::from_str::<PersonDiff>(&json).unwrap(), diff);
+        assert!(after.diff(&after).is_empty());
+    }
+}
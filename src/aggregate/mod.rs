@@ -30,7 +30,15 @@ pub trait EventSourced: Sized {
 
 // Export the person aggregate
 pub mod person_ecs;
-pub use person_ecs::{Person, PersonId, PersonMarker, CoreIdentity, PersonLifecycle};
+pub use person_ecs::{Person, PersonId, PersonMarker, CoreIdentity, PersonLifecycle, DeathRecordError, PersonCommandError};
+
+// Canonical external key encoding
+pub mod canonical_id;
+pub use canonical_id::{CanonicalPersonId, PersonIdParseError, PERSON_ID_PREFIX};
+
+// Field-level change summaries for review
+pub mod changes;
+pub use changes::{ChangeEntry, ChangeKind, EntryChange, FieldChange, PersonDiff};
 
 // State machine framework
 pub mod state_machine;
@@ -38,5 +46,6 @@ pub mod person_states;
 pub mod person_onboarding;
 
 pub use state_machine::{State, Command, StateMachine, StateMachineAggregate};
-pub use person_states::{PersonState, PersonStateCommand, create_person_state_machine};
-pub use person_onboarding::{PersonOnboarding, OnboardingState, OnboardingCommand};
+#[allow(deprecated)]
+pub use person_states::{PersonState, PersonStateCommand, TransitionError, create_person_state_machine};
+pub use person_onboarding::{PersonOnboarding, OnboardingState, OnboardingCommand, OnboardingStep};
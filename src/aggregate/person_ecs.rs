@@ -11,11 +11,15 @@ use cim_domain::formal_domain::{
 };
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::BTreeSet;
 
-use crate::value_objects::{PersonName, PersonAttributeSet, PersonAttribute, AttributeType};
+use crate::value_objects::{
+    PersonName, PersonAttributeSet, PersonAttribute, AttributeType, IdentifyingAttributeType,
+    DateZone, Tag, age_on,
+};
 use crate::commands::*;
 use crate::events::*;
-use super::person_states::{PersonState, PersonStateCommand, create_person_state_machine};
+use super::person_states::{PersonState, PersonStateCommand, TransitionError};
 
 /// Marker type for Person entities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -102,6 +106,10 @@ pub struct Person {
     /// Lifecycle state
     pub lifecycle: PersonLifecycle,
 
+    /// Tags applied by teams, kept so re-tagging emits nothing
+    #[serde(default)]
+    pub tags: BTreeSet<Tag>,
+
     /// Event sourcing version
     pub version: u64,
 }
@@ -130,6 +138,57 @@ pub struct CoreIdentity {
 // - Security domain: physical descriptions for identification
 // - Any physical data should reference PersonId, not be stored here
 
+/// A `RecordDeath` command contradicts the person's known dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DeathRecordError {
+    #[error("Date of death {date_of_death} is before birth date {birth_date}")]
+    BeforeBirth {
+        date_of_death: chrono::NaiveDate,
+        birth_date: chrono::NaiveDate,
+    },
+
+    #[error("Death already recorded on {date_of_death}")]
+    AlreadyRecorded { date_of_death: chrono::NaiveDate },
+}
+
+/// Why [`Person::try_handle`] did not handle a command
+#[derive(Debug, thiserror::Error)]
+pub enum PersonCommandError {
+    #[error(transparent)]
+    Transition(#[from] TransitionError),
+
+    #[error(transparent)]
+    DeathRecord(#[from] DeathRecordError),
+
+    /// A [`VersionedCommand`] expected another version; unlike a
+    /// `ConcurrencyConflict` from the event store, nothing raced the command
+    #[error(transparent)]
+    PreconditionFailed(#[from] PreconditionFailed),
+
+    /// The person was merged; commands go to the surviving record
+    #[error("Cannot modify a merged person")]
+    AlreadyMerged { merged_into: PersonId },
+
+    #[error("Cannot modify a deceased person")]
+    Deceased { date_of_death: chrono::NaiveDate },
+
+    /// The command needs an active person and this one is deactivated
+    #[error("Person is not active: {reason}")]
+    InactivePerson { reason: String },
+
+    #[error(transparent)]
+    Domain(#[from] DomainError),
+}
+
+impl From<PersonCommandError> for DomainError {
+    fn from(e: PersonCommandError) -> Self {
+        match e {
+            PersonCommandError::Domain(e) => e,
+            rejected => DomainError::ValidationError(rejected.to_string()),
+        }
+    }
+}
+
 /// Person lifecycle state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PersonLifecycle {
@@ -164,6 +223,7 @@ impl Person {
             },
             attributes: PersonAttributeSet::empty(),
             lifecycle: PersonLifecycle::Active,
+            tags: BTreeSet::new(),
             version: 0,
         }
     }
@@ -185,6 +245,10 @@ impl Person {
             PersonEvent::AttributeRecorded(e) => self.apply_attribute_recorded_pure(e),
             PersonEvent::AttributeUpdated(e) => self.apply_attribute_updated_pure(e),
             PersonEvent::AttributeInvalidated(e) => self.apply_attribute_invalidated_pure(e),
+            PersonEvent::LifeEventRecorded(e) => self.apply_life_event_recorded_pure(e),
+            PersonEvent::ConsentRecorded(e) => self.apply_consent_recorded_pure(e),
+            PersonEvent::TagAdded(e) => self.apply_tag_added_pure(e),
+            PersonEvent::TagRemoved(e) => self.apply_tag_removed_pure(e),
         }
     }
 
@@ -201,10 +265,35 @@ impl Person {
             },
             attributes: PersonAttributeSet::empty(),
             lifecycle: PersonLifecycle::Active,
+            tags: BTreeSet::new(),
             version: 0,
         }
     }
 
+    /// Rebuild a person as it stood at `as_of`
+    ///
+    /// `events` are the person's events in stream order; replay stops at the
+    /// first one that occurred after `as_of`, so the result is always a
+    /// consistent prefix of the history. Fails if the person did not exist yet.
+    pub fn replay_until(events: Vec<PersonEvent>, as_of: DateTime<Utc>) -> Result<Person, String> {
+        if !events.first().is_some_and(|event| event.occurred_at() <= as_of) {
+            return Err(format!("Person did not exist yet at {as_of}"));
+        }
+        Self::replay_onto(Person::empty(), &events, as_of)
+    }
+
+    /// Apply the events that occurred up to `as_of` onto `person`
+    pub(crate) fn replay_onto(
+        person: Person,
+        events: &[PersonEvent],
+        as_of: DateTime<Utc>,
+    ) -> Result<Person, String> {
+        events.iter()
+            .take_while(|event| event.occurred_at() <= as_of)
+            .try_fold(person, |person, event| person.apply_event_pure(event))
+            .map_err(|e| e.to_string())
+    }
+
     /// Check if person is active
     pub fn is_active(&self) -> bool {
         matches!(self.lifecycle, PersonLifecycle::Active)
@@ -221,18 +310,90 @@ impl Person {
     }
 
     /// Check if person can be modified (not deceased or merged)
-    pub fn can_be_modified(&self) -> Result<(), String> {
+    pub fn can_be_modified(&self) -> Result<(), PersonCommandError> {
         match &self.lifecycle {
-            PersonLifecycle::Deceased { .. } => {
-                Err("Cannot modify a deceased person".to_string())
+            PersonLifecycle::Deceased { date_of_death } => {
+                Err(PersonCommandError::Deceased { date_of_death: *date_of_death })
+            }
+            PersonLifecycle::MergedInto { target_id, .. } => {
+                Err(PersonCommandError::AlreadyMerged { merged_into: *target_id })
             }
-            PersonLifecycle::MergedInto { .. } => {
-                Err("Cannot modify a merged person".to_string())
+            _ => Ok(())
+        }
+    }
+
+    /// Check that the person is active, naming why not
+    pub fn check_active(&self) -> Result<(), PersonCommandError> {
+        self.can_be_modified()?;
+        match &self.lifecycle {
+            PersonLifecycle::Deactivated { reason, .. } => {
+                Err(PersonCommandError::InactivePerson { reason: reason.clone() })
             }
             _ => Ok(())
         }
     }
 
+    /// Check that the person's lifecycle lets it take `cmd`
+    ///
+    /// Lifecycle commands are checked by their state transition instead.
+    fn check_accepts(&self, cmd: &PersonCommand) -> Result<(), PersonCommandError> {
+        match cmd {
+            PersonCommand::UpdateName(_)
+            | PersonCommand::RecordAttribute(_)
+            | PersonCommand::RecordAttributeWithDocument(_)
+            | PersonCommand::UpdateAttribute(_)
+            | PersonCommand::InvalidateAttribute(_) => self.check_active(),
+            // Deactivated and deceased persons keep these, merged ones do not
+            PersonCommand::RecordLifeEvent(_)
+            | PersonCommand::RecordConsent(_)
+            | PersonCommand::AddTag(_) => match &self.lifecycle {
+                PersonLifecycle::MergedInto { target_id, .. } => {
+                    Err(PersonCommandError::AlreadyMerged { merged_into: *target_id })
+                }
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that death can be recorded on `date_of_death`
+    ///
+    /// Death is recorded once, and not before a known birth date. Without a
+    /// birth date any date is accepted.
+    pub fn check_death_date(&self, date_of_death: chrono::NaiveDate) -> Result<(), DeathRecordError> {
+        if let Some(recorded) = self.core_identity.death_date {
+            return Err(DeathRecordError::AlreadyRecorded { date_of_death: recorded });
+        }
+        match self.core_identity.birth_date {
+            Some(birth_date) if date_of_death < birth_date => {
+                Err(DeathRecordError::BeforeBirth { date_of_death, birth_date })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// State a lifecycle command is checked against
+    ///
+    /// A person without events is a draft that can be created. One assembled
+    /// in memory with [`Person::new`] counts as active for anything else.
+    fn transition_state(&self, cmd: &PersonStateCommand) -> PersonState {
+        if self.version == 0 && matches!(cmd, PersonStateCommand::Create) {
+            PersonState::Draft
+        } else {
+            self.state()
+        }
+    }
+
+    /// Handle a command only if this aggregate is at the expected version
+    ///
+    /// The version is checked before any events are generated, so a stale
+    /// command leaves the aggregate untouched and fails with
+    /// [`PersonCommandError::PreconditionFailed`].
+    pub fn handle_versioned(&self, command: VersionedCommand) -> Result<(Self, Vec<PersonEvent>), PersonCommandError> {
+        command.check_version(self.version)?;
+        self.clone().try_handle(command.command)
+    }
+
     // ========================================================================
     // ATTRIBUTE METHODS - Category Theory Compliance
     // ========================================================================
@@ -268,6 +429,22 @@ impl Person {
         self.attributes.currently_valid()
     }
 
+    /// Age in whole years at `instant`
+    ///
+    /// A recorded birth attribute wins, read in its own zone. The core
+    /// identity birth date carries no zone and is read as a UTC day.
+    pub fn age_at(&self, instant: DateTime<Utc>) -> Option<u32> {
+        let valid = self.attributes.valid_at(instant);
+        [
+            IdentifyingAttributeType::BirthDateTime,
+            IdentifyingAttributeType::BirthDate,
+            IdentifyingAttributeType::ApproximateBirthDate,
+        ]
+        .into_iter()
+        .find_map(|attr_type| valid.find_by_type(&AttributeType::Identifying(attr_type))?.age_at(instant))
+        .or_else(|| age_on(self.core_identity.birth_date?, DateZone::utc().local_date(instant)))
+    }
+
     /// Functor map over attributes
     ///
     /// Transforms all attributes while preserving structure.
@@ -384,8 +561,8 @@ impl MealyStateMachine for Person {
             }
 
             PersonCommand::RecordDeath(cmd) => {
-                if matches!(self.lifecycle, PersonLifecycle::Deceased { .. }) {
-                    return vec![]; // Already deceased
+                if self.check_death_date(cmd.date_of_death).is_err() {
+                    return vec![]; // Already deceased or before birth
                 }
                 vec![PersonEvent::DeathRecorded(DeathRecorded {
                     person_id: self.id,
@@ -439,6 +616,19 @@ impl MealyStateMachine for Person {
                 })]
             }
 
+            PersonCommand::RecordAttributeWithDocument(cmd) => {
+                if !self.is_active() {
+                    return vec![]; // Can only add attributes to active persons
+                }
+                let mut attribute = cmd.attribute;
+                attribute.provenance = attribute.provenance.verified_by(cmd.document);
+                vec![PersonEvent::AttributeRecorded(crate::events::AttributeRecorded {
+                    person_id: self.id,
+                    attribute,
+                    recorded_at: Utc::now(),
+                })]
+            }
+
             PersonCommand::UpdateAttribute(cmd) => {
                 if !self.is_active() {
                     return vec![]; // Can only update attributes for active persons
@@ -466,6 +656,59 @@ impl MealyStateMachine for Person {
                     attribute_type: cmd.attribute_type,
                     invalidated_at: Utc::now(),
                     reason: cmd.reason,
+                    attribute_recorded_at: cmd.attribute_recorded_at,
+                })]
+            }
+
+            PersonCommand::RecordLifeEvent(cmd) => {
+                if matches!(self.lifecycle, PersonLifecycle::MergedInto { .. }) {
+                    return vec![]; // Life events belong on the surviving record
+                }
+                vec![PersonEvent::LifeEventRecorded(LifeEventRecorded {
+                    person_id: self.id,
+                    kind: cmd.kind,
+                    date: cmd.date,
+                    note: cmd.note,
+                    recorded_at: Utc::now(),
+                })]
+            }
+
+            PersonCommand::RecordConsent(cmd) => {
+                if matches!(self.lifecycle, PersonLifecycle::MergedInto { .. }) {
+                    return vec![]; // Consent is tracked on the surviving record
+                }
+                // Deactivated and deceased persons may still have consent revoked
+                vec![PersonEvent::ConsentRecorded(ConsentRecorded {
+                    person_id: self.id,
+                    consent_type: cmd.consent_type,
+                    status: cmd.status,
+                    recorded_at: Utc::now(),
+                    policy_version: cmd.policy_version,
+                })]
+            }
+
+            PersonCommand::AddTag(cmd) => {
+                if matches!(self.lifecycle, PersonLifecycle::MergedInto { .. }) {
+                    return vec![]; // Tags belong on the surviving record
+                }
+                if self.tags.contains(&cmd.tag) {
+                    return vec![]; // Already tagged
+                }
+                vec![PersonEvent::TagAdded(TagAdded {
+                    person_id: self.id,
+                    tag: cmd.tag,
+                    added_at: Utc::now(),
+                })]
+            }
+
+            PersonCommand::RemoveTag(cmd) => {
+                if !self.tags.contains(&cmd.tag) {
+                    return vec![];
+                }
+                vec![PersonEvent::TagRemoved(TagRemoved {
+                    person_id: self.id,
+                    tag: cmd.tag,
+                    removed_at: Utc::now(),
                 })]
             }
 
@@ -498,16 +741,31 @@ impl Aggregate for Person {
     }
 
     fn handle(self, cmd: PersonCommand) -> Result<(Self, Vec<PersonEvent>), DomainError> {
+        self.try_handle(cmd).map_err(DomainError::from)
+    }
+}
+
+impl Person {
+    /// Handle a command, keeping the typed cause of a rejection
+    ///
+    /// A command the person's lifecycle does not allow is rejected rather
+    /// than producing no events. [`Aggregate::handle`] reports the same
+    /// rejections as `DomainError::ValidationError`s; match on this instead
+    /// when the cause matters.
+    pub fn try_handle(self, cmd: PersonCommand) -> Result<(Self, Vec<PersonEvent>), PersonCommandError> {
         // Get current state
         let current_state = self.state();
 
-        // Validate state transition using state machine
+        // Report inconsistent death dates before the generic state check
+        if let PersonCommand::RecordDeath(record) = &cmd {
+            self.check_death_date(record.date_of_death)?;
+        }
+
+        // Validate the lifecycle transition
         if let Some(state_cmd) = PersonStateCommand::from_person_command(&cmd) {
-            let state_machine = create_person_state_machine();
-            let _new_state = state_machine
-                .validate_transition(&current_state, &state_cmd)
-                .map_err(|e| DomainError::ValidationError(e.to_string()))?;
+            self.transition_state(&state_cmd).can_transition(&state_cmd)?;
         }
+        self.check_accepts(&cmd)?;
 
         // Compute events using MealyStateMachine::output
         let events = MealyStateMachine::output(&self, current_state.clone(), cmd.clone());
@@ -566,6 +824,7 @@ impl Person {
             },
             attributes: PersonAttributeSet::empty(),
             lifecycle: PersonLifecycle::Active,
+            tags: BTreeSet::new(),
             version: self.version + 1,
         })
     }
@@ -696,7 +955,11 @@ impl Person {
 
     fn apply_attribute_invalidated_pure(mut self, event: &crate::events::AttributeInvalidated) -> DomainResult<Self> {
         // Update the attribute's valid_until field
-        if let Some(attr) = self.attributes.attributes.iter_mut().find(|attr| &attr.attribute_type == &event.attribute_type) {
+        let targeted = |attr: &&mut PersonAttribute| {
+            attr.attribute_type == event.attribute_type
+                && event.attribute_recorded_at.map_or(true, |recorded_at| attr.temporal.recorded_at == recorded_at)
+        };
+        if let Some(attr) = self.attributes.attributes.iter_mut().find(targeted) {
             attr.temporal.valid_until = Some(event.invalidated_at.date_naive());
         }
         Ok(Self {
@@ -708,6 +971,58 @@ impl Person {
             ..self
         })
     }
+
+    fn apply_life_event_recorded_pure(self, event: &LifeEventRecorded) -> DomainResult<Self> {
+        // Life events only annotate the timeline; identity is unchanged
+        Ok(Self {
+            core_identity: CoreIdentity {
+                updated_at: event.recorded_at,
+                ..self.core_identity
+            },
+            version: self.version + 1,
+            ..self
+        })
+    }
+
+    fn apply_consent_recorded_pure(self, event: &ConsentRecorded) -> DomainResult<Self> {
+        // Consent lives in its own read model; identity is unchanged
+        Ok(Self {
+            core_identity: CoreIdentity {
+                updated_at: event.recorded_at,
+                ..self.core_identity
+            },
+            version: self.version + 1,
+            ..self
+        })
+    }
+
+    fn apply_tag_added_pure(self, event: &TagAdded) -> DomainResult<Self> {
+        let mut tags = self.tags;
+        tags.insert(event.tag.clone());
+        Ok(Self {
+            core_identity: CoreIdentity {
+                updated_at: event.added_at,
+                ..self.core_identity
+            },
+            tags,
+            version: self.version + 1,
+            ..self
+        })
+    }
+
+    fn apply_tag_removed_pure(self, event: &TagRemoved) -> DomainResult<Self> {
+        let mut tags = self.tags;
+        tags.remove(&event.tag);
+        Ok(Self {
+            core_identity: CoreIdentity {
+                updated_at: event.removed_at,
+                ..self.core_identity
+            },
+            tags,
+            version: self.version + 1,
+            ..self
+        })
+    }
 }
 
 // Command and Event structs are now in commands/mod.rs and events/mod.rs 
\ No newline at end of file
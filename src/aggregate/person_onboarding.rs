@@ -32,6 +32,53 @@ pub enum OnboardingState {
 
 impl State for OnboardingState {}
 
+impl OnboardingState {
+    /// The step that moves onboarding on from this state
+    ///
+    /// `None` once onboarding has completed or failed.
+    pub fn next_step(&self) -> Option<OnboardingStep> {
+        match self {
+            OnboardingState::Started => Some(OnboardingStep::StartOnboarding),
+            OnboardingState::AwaitingIdentityVerification => Some(OnboardingStep::VerifyIdentity),
+            OnboardingState::CollectingBasicInfo => Some(OnboardingStep::ProvideBasicInfo),
+            OnboardingState::SettingUpComponents => Some(OnboardingStep::AddComponents),
+            OnboardingState::AssigningLocation => Some(OnboardingStep::AssignLocation),
+            OnboardingState::Finalizing => Some(OnboardingStep::CompleteOnboarding),
+            OnboardingState::Completed | OnboardingState::Failed { .. } => None,
+        }
+    }
+
+    /// The state an onboarding event moved to, if it records a transition
+    pub fn from_event(event: &PersonEventV2) -> Option<Self> {
+        let PersonEventV2::Updated { updates, .. } = event else {
+            return None;
+        };
+        serde_json::from_value(updates.get("onboarding_state")?.clone()).ok()
+    }
+}
+
+/// Required onboarding steps, in the order they are taken
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OnboardingStep {
+    StartOnboarding,
+    VerifyIdentity,
+    ProvideBasicInfo,
+    AddComponents,
+    AssignLocation,
+    CompleteOnboarding,
+}
+
+impl OnboardingStep {
+    pub const ALL: &'static [OnboardingStep] = &[
+        OnboardingStep::StartOnboarding,
+        OnboardingStep::VerifyIdentity,
+        OnboardingStep::ProvideBasicInfo,
+        OnboardingStep::AddComponents,
+        OnboardingStep::AssignLocation,
+        OnboardingStep::CompleteOnboarding,
+    ];
+}
+
 /// Onboarding commands
 #[derive(Clone, Debug)]
 pub enum OnboardingCommand {
@@ -105,7 +152,7 @@ impl PersonOnboarding {
         events.push(PersonEventV2::Updated {
             person_id: self.person_id,
             updates: serde_json::json!({
-                "onboarding_state": format!("{:?}", new_state),
+                "onboarding_state": new_state,
                 "timestamp": chrono::Utc::now()
             }),
             metadata: EventMetadata::new(),
@@ -25,6 +25,78 @@ pub enum PersonState {
 
 impl State for PersonState {}
 
+/// Why a lifecycle command is not allowed in a person's current state
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TransitionError {
+    #[error("Person has not been created yet")]
+    NotCreated,
+
+    #[error("Person already exists")]
+    AlreadyCreated,
+
+    #[error("Person is already active")]
+    AlreadyActive,
+
+    #[error("Person is already suspended: {reason}")]
+    AlreadySuspended { reason: String },
+
+    #[error("Person is suspended and must be reactivated first: {reason}")]
+    Suspended { reason: String },
+
+    #[error("{command} needs a reason")]
+    MissingReason { command: &'static str },
+
+    #[error("Person is archived: {reason}")]
+    Archived { reason: String },
+
+    #[error("Person died on {date_of_death}")]
+    Deceased { date_of_death: chrono::NaiveDate },
+
+    #[error("Person was merged into {merged_into_id}")]
+    Merged { merged_into_id: PersonId },
+}
+
+impl PersonState {
+    /// Check that `cmd` is a legal transition from this state
+    ///
+    /// This is the one place the person lifecycle's transitions are defined:
+    /// a draft can only be created, a suspended person can only be
+    /// reactivated or archived, and archived, deceased and merged persons
+    /// accept no lifecycle commands at all. Suspending and archiving need a
+    /// reason.
+    pub fn can_transition(&self, cmd: &PersonStateCommand) -> Result<(), TransitionError> {
+        use PersonStateCommand as Cmd;
+
+        match (self, cmd) {
+            (PersonState::Archived { reason }, _) => Err(TransitionError::Archived { reason: reason.clone() }),
+            (PersonState::Deceased { date_of_death }, _) => {
+                Err(TransitionError::Deceased { date_of_death: *date_of_death })
+            }
+            (PersonState::MergedInto { merged_into_id, .. }, _) => {
+                Err(TransitionError::Merged { merged_into_id: *merged_into_id })
+            }
+
+            (PersonState::Draft, Cmd::Create) => Ok(()),
+            (PersonState::Draft, _) => Err(TransitionError::NotCreated),
+            (_, Cmd::Create) => Err(TransitionError::AlreadyCreated),
+
+            (PersonState::Active, Cmd::Activate) => Err(TransitionError::AlreadyActive),
+            (PersonState::Suspended { reason }, Cmd::Suspend { .. }) => {
+                Err(TransitionError::AlreadySuspended { reason: reason.clone() })
+            }
+            (_, Cmd::Suspend { reason } | Cmd::Archive { reason }) if reason.is_empty() => {
+                Err(TransitionError::MissingReason { command: cmd.name() })
+            }
+
+            (PersonState::Active, Cmd::Suspend { .. } | Cmd::Archive { .. } | Cmd::RecordDeath { .. } | Cmd::Merge { .. }) => Ok(()),
+            (PersonState::Suspended { .. }, Cmd::Activate | Cmd::Archive { .. }) => Ok(()),
+            (PersonState::Suspended { reason }, Cmd::RecordDeath { .. } | Cmd::Merge { .. }) => {
+                Err(TransitionError::Suspended { reason: reason.clone() })
+            }
+        }
+    }
+}
+
 // Implement AggregateState trait for formal Category Theory compliance
 impl AggregateState for PersonState {
     fn all_states() -> Vec<Self> {
@@ -90,6 +162,17 @@ pub enum PersonStateCommand {
 impl Command for PersonStateCommand {}
 
 impl PersonStateCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PersonStateCommand::Create => "Create",
+            PersonStateCommand::Activate => "Activate",
+            PersonStateCommand::Suspend { .. } => "Suspend",
+            PersonStateCommand::Archive { .. } => "Archive",
+            PersonStateCommand::RecordDeath { .. } => "RecordDeath",
+            PersonStateCommand::Merge { .. } => "Merge",
+        }
+    }
+
     /// Convert from PersonCommand
     pub fn from_person_command(cmd: &PersonCommand) -> Option<Self> {
         match cmd {
@@ -111,6 +194,10 @@ impl PersonStateCommand {
 }
 
 /// Create the Person state machine
+///
+/// Kept for existing callers; the aggregate checks its lifecycle with
+/// [`PersonState::can_transition`], which is the authoritative table.
+#[deprecated(note = "use PersonState::can_transition, the authoritative lifecycle table")]
 pub fn create_person_state_machine() -> StateMachine<PersonState, PersonStateCommand> {
     StateMachine::builder(PersonState::Draft)
         // Draft -> Active (on creation)
@@ -197,4 +284,112 @@ pub fn create_person_state_machine() -> StateMachine<PersonState, PersonStateCom
         })
         
         .build()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CreatePerson, DeactivatePerson, ReactivatePerson};
+    use crate::aggregate::{Person, PersonCommandError};
+    use crate::value_objects::PersonName;
+    use cim_domain::formal_domain::Aggregate;
+    use chrono::NaiveDate;
+
+    fn date_of_death() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2020, 5, 1).unwrap()
+    }
+
+    fn commands() -> [PersonStateCommand; 6] {
+        [
+            PersonStateCommand::Create,
+            PersonStateCommand::Activate,
+            PersonStateCommand::Suspend { reason: "audit".to_string() },
+            PersonStateCommand::Archive { reason: "left".to_string() },
+            PersonStateCommand::RecordDeath { date_of_death: date_of_death() },
+            PersonStateCommand::Merge { target_id: PersonId::new(), reason: MergeReason::DuplicateIdentity },
+        ]
+    }
+
+    #[test]
+    fn test_every_state_and_command() {
+        use TransitionError as E;
+
+        let merged_into_id = PersonId::new();
+        let suspended = || E::Suspended { reason: "audit".to_string() };
+        let archived = || Err(E::Archived { reason: "left".to_string() });
+        let deceased = || Err(E::Deceased { date_of_death: date_of_death() });
+        let merged = || Err(E::Merged { merged_into_id });
+        // Outcomes in the order of `commands()`
+        let table = [
+            (PersonState::Draft, [
+                Ok(()), Err(E::NotCreated), Err(E::NotCreated), Err(E::NotCreated), Err(E::NotCreated), Err(E::NotCreated),
+            ]),
+            (PersonState::Active, [
+                Err(E::AlreadyCreated), Err(E::AlreadyActive), Ok(()), Ok(()), Ok(()), Ok(()),
+            ]),
+            (PersonState::Suspended { reason: "audit".to_string() }, [
+                Err(E::AlreadyCreated),
+                Ok(()),
+                Err(E::AlreadySuspended { reason: "audit".to_string() }),
+                Ok(()),
+                Err(suspended()),
+                Err(suspended()),
+            ]),
+            (PersonState::Archived { reason: "left".to_string() }, [
+                archived(), archived(), archived(), archived(), archived(), archived(),
+            ]),
+            (PersonState::Deceased { date_of_death: date_of_death() }, [
+                deceased(), deceased(), deceased(), deceased(), deceased(), deceased(),
+            ]),
+            (PersonState::MergedInto { merged_into_id, reason: MergeReason::DuplicateIdentity }, [
+                merged(), merged(), merged(), merged(), merged(), merged(),
+            ]),
+        ];
+        assert_eq!(table.len(), PersonState::all_states().len());
+
+        for (state, expected) in table {
+            for (cmd, expected) in commands().iter().zip(expected) {
+                assert_eq!(state.can_transition(cmd), expected, "{} from {state:?}", cmd.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_suspending_and_archiving_need_a_reason() {
+        for state in [PersonState::Active, PersonState::Suspended { reason: "audit".to_string() }] {
+            assert_eq!(
+                state.can_transition(&PersonStateCommand::Archive { reason: String::new() }),
+                Err(TransitionError::MissingReason { command: "Archive" }),
+            );
+        }
+        assert_eq!(
+            PersonState::Active.can_transition(&PersonStateCommand::Suspend { reason: String::new() }),
+            Err(TransitionError::MissingReason { command: "Suspend" }),
+        );
+    }
+
+    #[test]
+    fn test_aggregate_reports_transition_errors() {
+        let person_id = PersonId::new();
+        let (person, _) = Person::empty()
+            .handle(PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                source: "test".to_string(),
+            }))
+            .unwrap();
+
+        let reactivate = PersonCommand::ReactivatePerson(ReactivatePerson { person_id, reason: "back".to_string() });
+        let err = person.clone().try_handle(reactivate.clone()).unwrap_err();
+        assert!(matches!(err, PersonCommandError::Transition(TransitionError::AlreadyActive)), "{err:?}");
+        assert!(person.clone().handle(reactivate.clone()).is_err());
+
+        let (suspended, events) = person
+            .handle(PersonCommand::DeactivatePerson(DeactivatePerson { person_id, reason: "audit".to_string() }))
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        let (reactivated, events) = suspended.handle(reactivate).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(reactivated.is_active());
+    }
+}
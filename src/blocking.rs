@@ -0,0 +1,182 @@
+//! Synchronous facade over the async command and query APIs
+//!
+//! **For non-async callers only** (CLI tools, scripts, synchronous test
+//! harnesses). Each call drives a private Tokio runtime with `block_on`, so
+//! calling these methods from inside an async context will panic. Async code
+//! should use [`AsyncCommandProcessor`] and [`PersonQueryService`] directly.
+//!
+//! Enabled with the `blocking` feature.
+
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use cim_domain::{DomainError, DomainResult};
+use tokio::runtime::Runtime;
+
+use crate::aggregate::PersonId;
+use crate::commands::PersonCommand;
+use crate::handlers::{AsyncCommandProcessor, CommandResult};
+use crate::projections::{PersonSearchResult, PersonSummary, SkillSummary, SummaryPage, TimelineEntry};
+use crate::queries::PersonQueryService;
+
+/// Blocking wrapper around a command processor and the query service
+pub struct BlockingPersonClient {
+    runtime: Runtime,
+    commands: Arc<dyn AsyncCommandProcessor>,
+    queries: Arc<PersonQueryService>,
+}
+
+impl BlockingPersonClient {
+    /// Create a client with its own single-threaded runtime
+    pub fn new(
+        commands: Arc<dyn AsyncCommandProcessor>,
+        queries: Arc<PersonQueryService>,
+    ) -> DomainResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| DomainError::generic(format!("Failed to build runtime: {e}")))?;
+
+        Ok(Self {
+            runtime,
+            commands,
+            queries,
+        })
+    }
+
+    // Commands
+
+    /// Execute a command, blocking until it has been processed
+    pub fn execute_command(&self, command: PersonCommand) -> DomainResult<CommandResult> {
+        self.runtime.block_on(self.commands.process_command(command))
+    }
+
+    /// Execute a command with a correlation ID, blocking until processed
+    pub fn execute_command_with_correlation(
+        &self,
+        command: PersonCommand,
+        correlation_id: uuid::Uuid,
+    ) -> DomainResult<CommandResult> {
+        self.runtime.block_on(
+            self.commands.process_command_with_correlation(command, correlation_id),
+        )
+    }
+
+    // Queries
+
+    pub fn get_person_summary(&self, person_id: &PersonId) -> Option<PersonSummary> {
+        self.runtime.block_on(self.queries.get_person_summary(person_id))
+    }
+
+    pub fn get_all_summaries(&self) -> Vec<PersonSummary> {
+        self.runtime.block_on(self.queries.get_all_summaries())
+    }
+
+    pub fn get_summaries_paged(&self, offset: usize, limit: usize) -> (Vec<PersonSummary>, usize) {
+        self.runtime.block_on(self.queries.get_summaries_paged(offset, limit))
+    }
+
+    pub fn get_summaries_after(&self, after: Option<PersonId>, limit: usize) -> SummaryPage {
+        self.runtime.block_on(self.queries.get_summaries_after(after, limit))
+    }
+
+    pub fn search_persons(&self, query: &str, limit: usize) -> Vec<PersonSearchResult> {
+        self.runtime.block_on(self.queries.search_persons(query, limit))
+    }
+
+    pub fn get_person_skills(&self, person_id: &PersonId) -> Vec<SkillSummary> {
+        self.runtime.block_on(self.queries.get_person_skills(person_id))
+    }
+
+    pub fn get_person_timeline(&self, person_id: &PersonId, limit: Option<usize>) -> Vec<TimelineEntry> {
+        self.runtime.block_on(self.queries.get_person_timeline(person_id, limit))
+    }
+
+    pub fn get_timeline_range(
+        &self,
+        person_id: &PersonId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<TimelineEntry> {
+        self.runtime.block_on(self.queries.get_timeline_range(person_id, start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::Person;
+    use crate::commands::CreatePerson;
+    use crate::projections::*;
+    use crate::value_objects::PersonName;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use tokio::sync::Mutex;
+
+    /// Processes commands in memory and feeds the summary projection
+    struct InMemoryProcessor {
+        people: Mutex<HashMap<PersonId, Person>>,
+        summaries: Arc<PersonSummaryProjection>,
+    }
+
+    #[async_trait]
+    impl AsyncCommandProcessor for InMemoryProcessor {
+        async fn process_command(&self, command: PersonCommand) -> DomainResult<CommandResult> {
+            self.process_command_with_correlation(command, uuid::Uuid::now_v7()).await
+        }
+
+        async fn process_command_with_correlation(
+            &self,
+            command: PersonCommand,
+            _correlation_id: uuid::Uuid,
+        ) -> DomainResult<CommandResult> {
+            use cim_domain::formal_domain::Aggregate;
+
+            let aggregate_id = command.aggregate_id();
+            let mut people = self.people.lock().await;
+            let person = people.remove(&aggregate_id).unwrap_or_else(Person::empty);
+            let (person, events) = person.handle(command)?;
+
+            for event in &events {
+                self.summaries.handle_event(event).await?;
+            }
+            let version = person.version;
+            people.insert(aggregate_id, person);
+
+            Ok(CommandResult {
+                aggregate_id,
+                version,
+                events: Vec::new(),
+                event_stream: None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_sync_create_then_query_round_trip() {
+        let summaries = Arc::new(PersonSummaryProjection::new());
+        let processor = Arc::new(InMemoryProcessor {
+            people: Mutex::new(HashMap::new()),
+            summaries: summaries.clone(),
+        });
+        let queries = Arc::new(PersonQueryService::new(
+            summaries,
+            Arc::new(PersonSearchProjection::new()),
+            Arc::new(PersonSkillsProjection::new()),
+            Arc::new(PersonNetworkProjection::new()),
+            Arc::new(PersonTimelineProjection::new()),
+        ));
+        let client = BlockingPersonClient::new(processor, queries).unwrap();
+
+        let person_id = PersonId::new();
+        client.execute_command(PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Grace".to_string(), "Hopper".to_string()),
+            source: "cli".to_string(),
+        })).unwrap();
+
+        let summary = client.get_person_summary(&person_id).expect("summary should exist");
+        assert_eq!(summary.person_id, person_id);
+        assert_eq!(summary.name, "Grace");
+        assert_eq!(client.get_all_summaries().len(), 1);
+    }
+}
@@ -8,10 +8,13 @@
 
 use cim_domain::{EntityId, formal_domain::DomainCommand as DomainCommandTrait};
 use serde::{Deserialize, Serialize};
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 
 use crate::aggregate::PersonMarker;
-use crate::value_objects::{PersonName, PersonAttribute, AttributeType};
+use crate::value_objects::{
+    PersonName, PersonAttribute, AttributeType, LifeEventKind, ConsentType, ConsentStatus,
+    DocumentReference, Tag,
+};
 
 /// Person ID type alias
 pub type PersonId = EntityId<PersonMarker>;
@@ -46,11 +49,26 @@ pub enum PersonCommand {
     /// Record an attribute
     RecordAttribute(RecordAttribute),
 
+    /// Record an attribute proven by a document
+    RecordAttributeWithDocument(RecordAttributeWithDocument),
+
     /// Update an attribute
     UpdateAttribute(UpdateAttribute),
 
     /// Invalidate an attribute
     InvalidateAttribute(InvalidateAttribute),
+
+    /// Annotate the timeline with a life event
+    RecordLifeEvent(RecordLifeEvent),
+
+    /// Grant or revoke consent
+    RecordConsent(RecordConsent),
+
+    /// Apply a tag
+    AddTag(AddTag),
+
+    /// Remove a tag
+    RemoveTag(RemoveTag),
 }
 
 // ===== Core Identity Commands =====
@@ -125,6 +143,17 @@ pub struct RecordAttribute {
     pub attribute: PersonAttribute,
 }
 
+/// Record an attribute together with the document that verified it
+///
+/// The recorded attribute's provenance becomes `DocumentVerified` and
+/// carries `document`, whatever provenance the attribute arrived with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordAttributeWithDocument {
+    pub person_id: PersonId,
+    pub attribute: PersonAttribute,
+    pub document: DocumentReference,
+}
+
 /// Update an existing attribute
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateAttribute {
@@ -139,6 +168,146 @@ pub struct InvalidateAttribute {
     pub person_id: PersonId,
     pub attribute_type: AttributeType,
     pub reason: Option<String>,
+    /// Invalidate only the attribute of this type recorded at this instant;
+    /// `None` invalidates the first attribute of the type
+    #[serde(default)]
+    pub attribute_recorded_at: Option<DateTime<Utc>>,
+}
+
+// ===== Timeline Commands =====
+
+/// Record a life event (graduated, married, relocated, ...) on the timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordLifeEvent {
+    pub person_id: PersonId,
+    pub kind: LifeEventKind,
+    pub date: NaiveDate,
+    pub note: Option<String>,
+}
+
+// ===== Consent Commands =====
+
+/// Record that a person granted or revoked consent for a purpose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordConsent {
+    pub person_id: PersonId,
+    pub consent_type: ConsentType,
+    pub status: ConsentStatus,
+    /// Version of the consent policy shown to the person, if known
+    #[serde(default)]
+    pub policy_version: Option<String>,
+}
+
+// ===== Tag Commands =====
+
+/// Tag a person; a person who already has the tag is left unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddTag {
+    pub person_id: PersonId,
+    pub tag: Tag,
+}
+
+/// Untag a person; a person without the tag is left unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveTag {
+    pub person_id: PersonId,
+    pub tag: Tag,
+}
+
+/// Apply one tag to a batch of persons
+///
+/// Expands into one [`AddTag`] per distinct person, so each person's
+/// aggregate decides on its own whether the tag is new.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagMany {
+    pub person_ids: Vec<PersonId>,
+    pub tag: Tag,
+}
+
+impl TagMany {
+    pub fn into_commands(self) -> Vec<PersonCommand> {
+        let tag = self.tag;
+        distinct(self.person_ids)
+            .into_iter()
+            .map(|person_id| PersonCommand::AddTag(AddTag { person_id, tag: tag.clone() }))
+            .collect()
+    }
+}
+
+/// Remove one tag from a batch of persons
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UntagMany {
+    pub person_ids: Vec<PersonId>,
+    pub tag: Tag,
+}
+
+impl UntagMany {
+    pub fn into_commands(self) -> Vec<PersonCommand> {
+        let tag = self.tag;
+        distinct(self.person_ids)
+            .into_iter()
+            .map(|person_id| PersonCommand::RemoveTag(RemoveTag { person_id, tag: tag.clone() }))
+            .collect()
+    }
+}
+
+/// Person ids in first-seen order without repeats
+fn distinct(person_ids: Vec<PersonId>) -> Vec<PersonId> {
+    let mut seen = std::collections::HashSet::new();
+    person_ids.into_iter().filter(|id| seen.insert(*id)).collect()
+}
+
+// ===== Conditional Execution =====
+
+/// A command that only applies if the aggregate is still at `expected_version`
+///
+/// Gives clients compare-and-set semantics: a client that read version N
+/// submits its change expecting N and is rejected if anyone wrote since.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedCommand {
+    pub command: PersonCommand,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_version: Option<u64>,
+}
+
+impl VersionedCommand {
+    /// Command applied regardless of the aggregate's version
+    pub fn unconditional(command: PersonCommand) -> Self {
+        Self {
+            command,
+            expected_version: None,
+        }
+    }
+
+    /// Command applied only if the aggregate is at `expected_version`
+    pub fn expecting(command: PersonCommand, expected_version: u64) -> Self {
+        Self {
+            command,
+            expected_version: Some(expected_version),
+        }
+    }
+
+    /// Check the expectation against the loaded aggregate's version
+    pub fn check_version(&self, actual: u64) -> Result<(), PreconditionFailed> {
+        match self.expected_version {
+            Some(expected) if expected != actual => Err(PreconditionFailed { expected, actual }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<PersonCommand> for VersionedCommand {
+    fn from(command: PersonCommand) -> Self {
+        Self::unconditional(command)
+    }
+}
+
+/// The aggregate was not at the version a [`VersionedCommand`] expected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Precondition failed: expected version {expected}, aggregate is at {actual}")]
+pub struct PreconditionFailed {
+    pub expected: u64,
+    pub actual: u64,
 }
 
 impl PersonCommand {
@@ -154,8 +323,13 @@ impl PersonCommand {
             PersonCommand::MergePersons(cmd) => cmd.source_person_id,
             PersonCommand::ArchivePerson(cmd) => cmd.person_id,
             PersonCommand::RecordAttribute(cmd) => cmd.person_id,
+            PersonCommand::RecordAttributeWithDocument(cmd) => cmd.person_id,
             PersonCommand::UpdateAttribute(cmd) => cmd.person_id,
             PersonCommand::InvalidateAttribute(cmd) => cmd.person_id,
+            PersonCommand::RecordLifeEvent(cmd) => cmd.person_id,
+            PersonCommand::RecordConsent(cmd) => cmd.person_id,
+            PersonCommand::AddTag(cmd) => cmd.person_id,
+            PersonCommand::RemoveTag(cmd) => cmd.person_id,
         }
     }
 }
@@ -173,8 +347,13 @@ impl DomainCommandTrait for PersonCommand {
             PersonCommand::MergePersons(_) => "MergePersons",
             PersonCommand::ArchivePerson(_) => "ArchivePerson",
             PersonCommand::RecordAttribute(_) => "RecordAttribute",
+            PersonCommand::RecordAttributeWithDocument(_) => "RecordAttributeWithDocument",
             PersonCommand::UpdateAttribute(_) => "UpdateAttribute",
             PersonCommand::InvalidateAttribute(_) => "InvalidateAttribute",
+            PersonCommand::RecordLifeEvent(_) => "RecordLifeEvent",
+            PersonCommand::RecordConsent(_) => "RecordConsent",
+            PersonCommand::AddTag(_) => "AddTag",
+            PersonCommand::RemoveTag(_) => "RemoveTag",
         }
     }
 }
@@ -16,12 +16,17 @@ pub mod agent_integration;
 // Re-export commonly used types
 pub use location_integration::{LocationDomainEvent, LocationEventHandler, AddressUsageType};
 pub use agent_integration::{AgentDomainEvent, AgentEventHandler, AgentType, AssignmentType, AgentPermission};
+pub use person_organization::OrganizationMembershipHandler;
 
-use cim_domain::DomainResult;
+use cim_domain::{DomainError, DomainResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use async_trait::async_trait;
+use crate::commands::PersonCommand;
 use crate::events::PersonEvent;
+use crate::infrastructure::nats_integration::execute_command;
+use crate::infrastructure::PersonRepository;
 
 
 /// Cross-domain event that Person domain listens to
@@ -83,6 +88,34 @@ pub enum CrossDomainEvent {
     },
 }
 
+/// Variant of a [`CrossDomainEvent`], used to route events to handlers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CrossDomainEventKind {
+    OrganizationCreated,
+    PersonAddedToOrganization,
+    PersonRemovedFromOrganization,
+    AddressCreated,
+    AddressAssignedToPerson,
+    CommitAuthorIdentified,
+    ContributionMetricsUpdated,
+    AgentAssignedToPerson,
+}
+
+impl CrossDomainEvent {
+    pub fn kind(&self) -> CrossDomainEventKind {
+        match self {
+            CrossDomainEvent::OrganizationCreated { .. } => CrossDomainEventKind::OrganizationCreated,
+            CrossDomainEvent::PersonAddedToOrganization { .. } => CrossDomainEventKind::PersonAddedToOrganization,
+            CrossDomainEvent::PersonRemovedFromOrganization { .. } => CrossDomainEventKind::PersonRemovedFromOrganization,
+            CrossDomainEvent::AddressCreated { .. } => CrossDomainEventKind::AddressCreated,
+            CrossDomainEvent::AddressAssignedToPerson { .. } => CrossDomainEventKind::AddressAssignedToPerson,
+            CrossDomainEvent::CommitAuthorIdentified { .. } => CrossDomainEventKind::CommitAuthorIdentified,
+            CrossDomainEvent::ContributionMetricsUpdated { .. } => CrossDomainEventKind::ContributionMetricsUpdated,
+            CrossDomainEvent::AgentAssignedToPerson { .. } => CrossDomainEventKind::AgentAssignedToPerson,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AddressType {
     Home,
@@ -132,6 +165,8 @@ pub struct CrossDomainIntegrationService {
     #[allow(dead_code)]
     event_publisher: Arc<dyn EventPublisher>,
     command_sender: Arc<dyn CommandSender>,
+    handlers: CrossDomainHandlerRegistry,
+    repository: Option<Arc<PersonRepository>>,
 }
 
 /// Trait for publishing events to other domains
@@ -149,8 +184,43 @@ pub trait CommandSender: Send + Sync {
 /// Trait for handling events from other domains
 #[async_trait]
 pub trait DomainEventHandler: Send + Sync {
-    /// Handle an event from another domain
-    async fn handle_event(&self, event: CrossDomainEvent) -> DomainResult<Vec<PersonEvent>>;
+    /// Translate an event from another domain into person commands
+    async fn handle_event(&self, event: CrossDomainEvent) -> DomainResult<Vec<PersonCommand>>;
+}
+
+/// Handlers for inbound cross-domain events, keyed by event variant
+///
+/// Several handlers may be registered for the same variant; they run in
+/// registration order and their person commands are concatenated.
+#[derive(Default)]
+pub struct CrossDomainHandlerRegistry {
+    handlers: HashMap<CrossDomainEventKind, Vec<Arc<dyn DomainEventHandler>>>,
+}
+
+impl CrossDomainHandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler for one event variant
+    pub fn register(&mut self, kind: CrossDomainEventKind, handler: Arc<dyn DomainEventHandler>) {
+        self.handlers.entry(kind).or_default().push(handler);
+    }
+
+    pub fn has_handlers(&self, kind: CrossDomainEventKind) -> bool {
+        self.handlers.get(&kind).is_some_and(|handlers| !handlers.is_empty())
+    }
+
+    /// Run every handler registered for the event's variant
+    pub async fn dispatch(&self, event: &CrossDomainEvent) -> DomainResult<Vec<PersonCommand>> {
+        let mut commands = Vec::new();
+        if let Some(handlers) = self.handlers.get(&event.kind()) {
+            for handler in handlers {
+                commands.extend(handler.handle_event(event.clone()).await?);
+            }
+        }
+        Ok(commands)
+    }
 }
 
 impl CrossDomainIntegrationService {
@@ -161,39 +231,55 @@ impl CrossDomainIntegrationService {
         Self {
             event_publisher,
             command_sender,
+            handlers: CrossDomainHandlerRegistry::new(),
+            repository: None,
         }
     }
-    
+
+    /// Use `handlers` to translate inbound events
+    pub fn with_handlers(mut self, handlers: CrossDomainHandlerRegistry) -> Self {
+        self.handlers = handlers;
+        self
+    }
+
+    /// Execute translated person commands against `repository`
+    pub fn with_repository(mut self, repository: Arc<PersonRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Register a handler for one event variant
+    pub fn register_handler(&mut self, kind: CrossDomainEventKind, handler: Arc<dyn DomainEventHandler>) {
+        self.handlers.register(kind, handler);
+    }
+
     /// Handle incoming cross-domain event
-    pub async fn handle_event(&self, event: CrossDomainEvent) -> DomainResult<()> {
-        match event {
-            CrossDomainEvent::PersonAddedToOrganization { person_id, org_id, role, .. } => {
-                // Update person's employment component
-                // This would trigger component updates in the Person domain
-                tracing::info!(
-                    "Person {} added to organization {} with role {}",
-                    person_id, org_id, role
-                );
-                Ok(())
-            }
-            CrossDomainEvent::AddressAssignedToPerson { person_id, address_id, address_type } => {
-                // Update person's address component
-                tracing::info!(
-                    "Address {} assigned to person {} as {:?}",
-                    address_id, person_id, address_type
-                );
-                Ok(())
-            }
-            CrossDomainEvent::CommitAuthorIdentified { person_id, repository, .. } => {
-                // Update person's contribution metrics
-                tracing::info!(
-                    "Commit identified for person {} in repository {}",
-                    person_id, repository
-                );
-                Ok(())
-            }
-            _ => Ok(()),
+    ///
+    /// The registered handlers translate the event into person commands.
+    /// Each command is handled by the person it targets and its events are
+    /// saved only if no other writer has appended to that person since it was
+    /// loaded. The resulting events are returned so the caller can publish
+    /// them or update projections.
+    pub async fn handle_event(&self, event: CrossDomainEvent) -> DomainResult<Vec<PersonEvent>> {
+        let kind = event.kind();
+        if !self.handlers.has_handlers(kind) {
+            tracing::debug!("No handler registered for cross-domain event {:?}", kind);
+            return Ok(Vec::new());
         }
+
+        let commands = self.handlers.dispatch(&event).await?;
+        if commands.is_empty() {
+            return Ok(Vec::new());
+        }
+        let repository = self.repository.as_ref().ok_or_else(|| {
+            DomainError::generic("Cross-domain commands need a person repository")
+        })?;
+
+        let mut person_events = Vec::new();
+        for command in commands {
+            person_events.extend(execute_command(repository, command).await?.events);
+        }
+        Ok(person_events)
     }
     
     /// Send command to another domain
@@ -207,4 +293,137 @@ impl CrossDomainIntegrationService {
         
         self.command_sender.send(target, command).await
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::commands::CreatePerson;
+    use crate::infrastructure::{EventStore, InMemoryEventStore, InMemorySnapshotStore};
+    use crate::value_objects::{AttributeType, AttributeValue, PersonName};
+
+    struct NoopPublisher;
+
+    #[async_trait]
+    impl EventPublisher for NoopPublisher {
+        async fn publish(&self, _topic: &str, _event: CrossDomainEvent) -> DomainResult<()> {
+            Ok(())
+        }
+    }
+
+    struct NoopSender;
+
+    #[async_trait]
+    impl CommandSender for NoopSender {
+        async fn send(&self, _target: &str, _command: CrossDomainCommand) -> DomainResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_organization_membership_produces_employment_event() {
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let repository = Arc::new(PersonRepository::new(
+            event_store.clone(),
+            Arc::new(InMemorySnapshotStore::new()),
+            100,
+        ));
+        let mut service = CrossDomainIntegrationService::new(Arc::new(NoopPublisher), Arc::new(NoopSender))
+            .with_repository(repository.clone());
+        for kind in [CrossDomainEventKind::PersonAddedToOrganization, CrossDomainEventKind::PersonRemovedFromOrganization] {
+            service.register_handler(kind, Arc::new(OrganizationMembershipHandler));
+        }
+
+        let person_id = PersonId::new();
+        execute_command(&repository, PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+        })).await.unwrap();
+
+        let events = service
+            .handle_event(CrossDomainEvent::PersonAddedToOrganization {
+                person_id,
+                org_id: "acme".to_string(),
+                role: "Engineer".to_string(),
+                added_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            PersonEvent::AttributeRecorded(recorded) => {
+                assert_eq!(recorded.person_id, person_id);
+                match &recorded.attribute.attribute_type {
+                    AttributeType::Custom(custom) => {
+                        assert_eq!(custom.organization, "acme");
+                        assert_eq!(custom.category, person_organization::EMPLOYMENT_ATTRIBUTE_CATEGORY);
+                    }
+                    other => panic!("expected employment attribute, got {other:?}"),
+                }
+                assert_eq!(recorded.attribute.value, AttributeValue::Text("Engineer".to_string()));
+            }
+            other => panic!("expected AttributeRecorded, got {other:?}"),
+        }
+
+        // The command's events entered the person's event stream after creation
+        assert_eq!(event_store.get_events(person_id).await.unwrap().len(), 2);
+        let person = repository.load(person_id).await.unwrap().unwrap();
+        assert_eq!(person.version, 2);
+        assert_eq!(person.attributes.currently_valid().attributes.len(), 1);
+
+        let removed = service
+            .handle_event(CrossDomainEvent::PersonRemovedFromOrganization {
+                person_id,
+                org_id: "acme".to_string(),
+                removed_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(removed.as_slice(), [PersonEvent::AttributeInvalidated(_)]));
+        assert_eq!(repository.load(person_id).await.unwrap().unwrap().version, 3);
+
+        // Variants without handlers are ignored
+        let ignored = service
+            .handle_event(CrossDomainEvent::OrganizationCreated {
+                org_id: "acme".to_string(),
+                name: "Acme".to_string(),
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+        assert!(ignored.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_membership_for_an_unknown_person_is_not_stored() {
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let repository = Arc::new(PersonRepository::new(
+            event_store.clone(),
+            Arc::new(InMemorySnapshotStore::new()),
+            100,
+        ));
+        let mut service = CrossDomainIntegrationService::new(Arc::new(NoopPublisher), Arc::new(NoopSender))
+            .with_repository(repository);
+        service.register_handler(
+            CrossDomainEventKind::PersonAddedToOrganization,
+            Arc::new(OrganizationMembershipHandler),
+        );
+
+        let person_id = PersonId::new();
+        let err = service
+            .handle_event(CrossDomainEvent::PersonAddedToOrganization {
+                person_id,
+                org_id: "acme".to_string(),
+                role: "Engineer".to_string(),
+                added_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DomainError::AggregateNotFound(_)));
+        assert!(event_store.get_events(person_id).await.unwrap().is_empty());
+    }
+}
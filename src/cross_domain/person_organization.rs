@@ -3,9 +3,17 @@
 //! This module manages employment relationships between persons and organizations
 //! without duplicating organization domain concepts.
 
+use super::{CrossDomainEvent, DomainEventHandler};
 use crate::aggregate::PersonId;
+use crate::commands::{InvalidateAttribute, PersonCommand, RecordAttribute};
+use crate::value_objects::{
+    AttributeSource, AttributeType, AttributeValue, ConfidenceLevel, CustomAttributeType,
+    PersonAttribute, Provenance, TemporalValidity,
+};
+use cim_domain::DomainResult;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 // Placeholder types until organization domain is available
@@ -110,6 +118,152 @@ pub struct Money {
     pub currency: String,
 }
 
+impl Money {
+    pub fn new(amount: f64, currency: impl Into<String>) -> Self {
+        Self {
+            amount,
+            currency: currency.into(),
+        }
+    }
+}
+
+/// Why compensation changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompensationChangeReason {
+    Hire,
+    MeritIncrease,
+    Promotion,
+    MarketAdjustment,
+    /// Pay is now denominated in a different currency (e.g. relocation)
+    CurrencyChange,
+    Other(String),
+}
+
+/// Compensation effective from a date until the next entry supersedes it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompensationEntry {
+    pub amount: Money,
+    pub effective_date: NaiveDate,
+    pub reason: CompensationChangeReason,
+}
+
+/// Compensation history could not be updated
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CompensationError {
+    #[error("Compensation effective {effective_date} has no currency")]
+    MissingCurrency { effective_date: NaiveDate },
+
+    /// Currencies may only change through an explicit `CurrencyChange` entry
+    #[error("Compensation effective {effective_date} switches currency from {from} to {to} without a CurrencyChange reason")]
+    CurrencyMismatch {
+        from: String,
+        to: String,
+        effective_date: NaiveDate,
+    },
+}
+
+/// Time series of compensation for one employment
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompensationHistory {
+    /// Ordered by effective date
+    entries: Vec<CompensationEntry>,
+}
+
+impl CompensationHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[CompensationEntry] {
+        &self.entries
+    }
+
+    /// Add an entry, keeping the history ordered by effective date
+    ///
+    /// An entry on the same date as an existing one replaces it.
+    pub fn record(&mut self, entry: CompensationEntry) -> Result<(), CompensationError> {
+        if entry.amount.currency.trim().is_empty() {
+            return Err(CompensationError::MissingCurrency { effective_date: entry.effective_date });
+        }
+
+        let position = self.entries.partition_point(|e| e.effective_date < entry.effective_date);
+        let replaces = self.entries.get(position)
+            .is_some_and(|e| e.effective_date == entry.effective_date);
+        let previous = position.checked_sub(1).and_then(|i| self.entries.get(i));
+        let next = self.entries.get(if replaces { position + 1 } else { position });
+
+        if let Some(previous) = previous {
+            check_currency(previous, &entry)?;
+        }
+        if let Some(next) = next {
+            check_currency(&entry, next)?;
+        }
+
+        if replaces {
+            self.entries[position] = entry;
+        } else {
+            self.entries.insert(position, entry);
+        }
+        Ok(())
+    }
+
+    /// Compensation in effect on `date`
+    pub fn on(&self, date: NaiveDate) -> Option<&CompensationEntry> {
+        self.entries.iter().rev().find(|e| e.effective_date <= date)
+    }
+}
+
+fn check_currency(earlier: &CompensationEntry, later: &CompensationEntry) -> Result<(), CompensationError> {
+    if earlier.amount.currency == later.amount.currency
+        || later.reason == CompensationChangeReason::CurrencyChange
+    {
+        Ok(())
+    } else {
+        Err(CompensationError::CurrencyMismatch {
+            from: earlier.amount.currency.clone(),
+            to: later.amount.currency.clone(),
+            effective_date: later.effective_date,
+        })
+    }
+}
+
+/// Compensation histories per employment, built from employment events
+#[derive(Debug, Clone, Default)]
+pub struct CompensationLedger {
+    histories: HashMap<(PersonId, OrganizationId), CompensationHistory>,
+}
+
+impl CompensationLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a `CompensationChanged` event; other events are ignored
+    pub fn apply(&mut self, event: &EmploymentEvent) -> Result<(), CompensationError> {
+        if let EmploymentEvent::CompensationChanged { person_id, organization_id, entry, .. } = event {
+            self.histories
+                .entry((*person_id, *organization_id))
+                .or_default()
+                .record(entry.clone())?;
+        }
+        Ok(())
+    }
+
+    pub fn history(&self, person_id: PersonId, organization_id: OrganizationId) -> Option<&CompensationHistory> {
+        self.histories.get(&(person_id, organization_id))
+    }
+
+    /// Compensation a person had at an organization on `date`
+    pub fn compensation_on(
+        &self,
+        person_id: PersonId,
+        organization_id: OrganizationId,
+        date: NaiveDate,
+    ) -> Option<&CompensationEntry> {
+        self.history(person_id, organization_id)?.on(date)
+    }
+}
+
 /// Bonus structure
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BonusStructure {
@@ -175,6 +329,13 @@ pub enum EmploymentCommand {
         organization_id: OrganizationId,
         new_manager_id: Option<PersonId>,
     },
+
+    /// Record a compensation change
+    ChangeCompensation {
+        person_id: PersonId,
+        organization_id: OrganizationId,
+        entry: CompensationEntry,
+    },
 }
 
 /// Employment update fields
@@ -237,6 +398,63 @@ pub enum EmploymentEvent {
         new_manager_id: Option<PersonId>,
         changed_at: DateTime<Utc>,
     },
+
+    /// Compensation changed
+    CompensationChanged {
+        person_id: PersonId,
+        organization_id: OrganizationId,
+        entry: CompensationEntry,
+        changed_at: DateTime<Utc>,
+    },
+}
+
+/// Category of the custom attributes recording organization membership
+pub const EMPLOYMENT_ATTRIBUTE_CATEGORY: &str = "employment";
+
+fn membership_attribute_type(org_id: &str) -> AttributeType {
+    AttributeType::Custom(CustomAttributeType {
+        organization: org_id.to_string(),
+        attribute_name: "role".to_string(),
+        category: EMPLOYMENT_ATTRIBUTE_CATEGORY.to_string(),
+    })
+}
+
+/// Translates organization membership events from the Identity domain
+///
+/// Membership is recorded as an employment attribute holding the role, and
+/// invalidated when the person leaves the organization.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrganizationMembershipHandler;
+
+#[async_trait::async_trait]
+impl DomainEventHandler for OrganizationMembershipHandler {
+    async fn handle_event(&self, event: CrossDomainEvent) -> DomainResult<Vec<PersonCommand>> {
+        let command = match event {
+            CrossDomainEvent::PersonAddedToOrganization { person_id, org_id, role, added_at } => {
+                let attribute = PersonAttribute::new(
+                    membership_attribute_type(&org_id),
+                    AttributeValue::Text(role),
+                    TemporalValidity::new(added_at, Some(added_at.date_naive()), None),
+                    Provenance::new(
+                        AttributeSource::Imported { system: "identity".to_string() },
+                        ConfidenceLevel::Certain,
+                    ),
+                );
+                PersonCommand::RecordAttribute(RecordAttribute { person_id, attribute })
+            }
+            CrossDomainEvent::PersonRemovedFromOrganization { person_id, org_id, .. } => {
+                PersonCommand::InvalidateAttribute(InvalidateAttribute {
+                    person_id,
+                    attribute_type: membership_attribute_type(&org_id),
+                    reason: Some(format!("Removed from organization {org_id}")),
+                    attribute_recorded_at: None,
+                })
+            }
+            _ => return Ok(Vec::new()),
+        };
+
+        Ok(vec![command])
+    }
 }
 
 /// Service for coordinating employment operations across domains
@@ -266,4 +484,66 @@ pub trait EmploymentService {
         end_date: NaiveDate,
         reason: TerminationReason,
     ) -> Result<(), String>;
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn changed(
+        person_id: PersonId,
+        organization_id: OrganizationId,
+        amount: Money,
+        effective_date: NaiveDate,
+        reason: CompensationChangeReason,
+    ) -> EmploymentEvent {
+        EmploymentEvent::CompensationChanged {
+            person_id,
+            organization_id,
+            entry: CompensationEntry { amount, effective_date, reason },
+            changed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_compensation_as_of_past_dates() {
+        let person_id = PersonId::new();
+        let org = Uuid::now_v7();
+        let mut ledger = CompensationLedger::new();
+
+        ledger.apply(&changed(person_id, org, Money::new(80_000.0, "USD"), date(2022, 1, 1), CompensationChangeReason::Hire)).unwrap();
+        ledger.apply(&changed(person_id, org, Money::new(95_000.0, "USD"), date(2024, 4, 1), CompensationChangeReason::Promotion)).unwrap();
+
+        assert!(ledger.compensation_on(person_id, org, date(2021, 12, 31)).is_none());
+        assert_eq!(ledger.compensation_on(person_id, org, date(2023, 6, 15)).unwrap().amount, Money::new(80_000.0, "USD"));
+        assert_eq!(ledger.compensation_on(person_id, org, date(2024, 4, 1)).unwrap().amount, Money::new(95_000.0, "USD"));
+    }
+
+    #[test]
+    fn test_currency_cannot_change_silently() {
+        let mut history = CompensationHistory::new();
+        history.record(CompensationEntry {
+            amount: Money::new(80_000.0, "USD"),
+            effective_date: date(2022, 1, 1),
+            reason: CompensationChangeReason::Hire,
+        }).unwrap();
+
+        let silent = history.record(CompensationEntry {
+            amount: Money::new(75_000.0, "EUR"),
+            effective_date: date(2023, 1, 1),
+            reason: CompensationChangeReason::MarketAdjustment,
+        });
+        assert!(matches!(silent, Err(CompensationError::CurrencyMismatch { .. })));
+
+        history.record(CompensationEntry {
+            amount: Money::new(75_000.0, "EUR"),
+            effective_date: date(2023, 1, 1),
+            reason: CompensationChangeReason::CurrencyChange,
+        }).unwrap();
+        assert_eq!(history.on(date(2023, 2, 1)).unwrap().amount.currency, "EUR");
+    }
+}
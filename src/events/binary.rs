@@ -0,0 +1,353 @@
+//! Compact binary encoding of person events (`binary-events` feature)
+//!
+//! Events are encoded with bincode's variable-length integer format. Enum
+//! variants are stored by position rather than name, which is what keeps the
+//! encoding small and also why event enums may only grow at the end.
+//!
+//! `PersonEventV2` is adjacently tagged for JSON, a layout bincode cannot
+//! decode; its binary form is described by an externally tagged mirror.
+//! Optional fields that JSON leaves out when absent are always written here,
+//! since bincode is not human-readable and reads fields by position.
+
+use bincode::Options;
+use chrono::NaiveDate;
+use cim_domain::{DomainError, DomainResult};
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+
+use super::{EventMetadata, PersonEvent, PersonEventV2};
+use crate::aggregate::PersonId;
+use crate::commands::MergeReason;
+use crate::infrastructure::EventEnvelope;
+use crate::value_objects::PersonName;
+
+fn options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+fn encode<T: Serialize>(value: &T) -> DomainResult<Vec<u8>> {
+    options()
+        .serialize(value)
+        .map_err(|e| DomainError::SerializationError(format!("Binary event encoding failed: {e}")))
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> DomainResult<T> {
+    options()
+        .deserialize(bytes)
+        .map_err(|e| DomainError::SerializationError(format!("Binary event decoding failed: {e}")))
+}
+
+impl PersonEvent {
+    pub fn to_bytes(&self) -> DomainResult<Vec<u8>> {
+        encode(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> DomainResult<Self> {
+        decode(bytes)
+    }
+}
+
+impl EventEnvelope {
+    pub fn to_bytes(&self) -> DomainResult<Vec<u8>> {
+        encode(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> DomainResult<Self> {
+        decode(bytes)
+    }
+}
+
+/// Binary layout of [`PersonEventV2`]; must list its variants in the same order
+#[allow(dead_code)] // Only describes the layout to serde
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PersonEventV2")]
+enum PersonEventV2Binary {
+    Created {
+        person_id: PersonId,
+        name: PersonName,
+        source: String,
+        metadata: EventMetadata,
+    },
+    Activated {
+        person_id: PersonId,
+        reason: String,
+        metadata: EventMetadata,
+    },
+    Suspended {
+        person_id: PersonId,
+        reason: String,
+        metadata: EventMetadata,
+    },
+    Archived {
+        person_id: PersonId,
+        reason: String,
+        metadata: EventMetadata,
+    },
+    Updated {
+        person_id: PersonId,
+        #[serde(with = "crate::value_objects::json_in_binary")]
+        updates: serde_json::Value,
+        metadata: EventMetadata,
+    },
+    NameUpdated {
+        person_id: PersonId,
+        old_name: PersonName,
+        new_name: PersonName,
+        change_reason: Option<String>,
+        metadata: EventMetadata,
+    },
+    BirthDateSet {
+        person_id: PersonId,
+        birth_date: NaiveDate,
+        metadata: EventMetadata,
+    },
+    DeathRecorded {
+        person_id: PersonId,
+        date_of_death: NaiveDate,
+        metadata: EventMetadata,
+    },
+    PersonMerged {
+        source_person_id: PersonId,
+        target_person_id: PersonId,
+        merge_reason: MergeReason,
+        metadata: EventMetadata,
+    },
+}
+
+#[derive(Serialize)]
+struct EncodeV2<'a>(#[serde(serialize_with = "serialize_v2")] &'a PersonEventV2);
+
+fn serialize_v2<S: Serializer>(event: &&PersonEventV2, serializer: S) -> Result<S::Ok, S::Error> {
+    PersonEventV2Binary::serialize(event, serializer)
+}
+
+#[derive(Deserialize)]
+struct DecodeV2(#[serde(with = "PersonEventV2Binary")] PersonEventV2);
+
+impl PersonEventV2 {
+    pub fn to_bytes(&self) -> DomainResult<Vec<u8>> {
+        encode(&EncodeV2(self))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> DomainResult<Self> {
+        decode::<DecodeV2>(bytes).map(|decoded| decoded.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{
+        AttributeInvalidated, AttributeRecorded, AttributeUpdated, BirthDateSet, ConsentRecorded,
+        DeathRecorded, LifeEventRecorded, NameUpdated, PersonCreated, PersonDeactivated,
+        PersonMergedInto, PersonReactivated, PersonUpdated, TagAdded, TagRemoved,
+    };
+    use crate::value_objects::{
+        AttributeSource, AttributeType, AttributeValue, ConfidenceLevel, ConsentStatus, ConsentType,
+        IdentifyingAttributeType, LifeEventKind, PersonAttribute, Provenance, Tag, TemporalValidity,
+    };
+    use chrono::Utc;
+    use cim_domain::formal_domain::DomainEvent as _;
+    use serde_json::json;
+
+    /// Binary variant index of each event; appending is fine, reordering is not
+    const PERSON_EVENT_ORDER: [&str; 15] = [
+        "PersonCreated",
+        "PersonUpdated",
+        "NameUpdated",
+        "BirthDateSet",
+        "DeathRecorded",
+        "PersonDeactivated",
+        "PersonReactivated",
+        "PersonMergedInto",
+        "AttributeRecorded",
+        "AttributeUpdated",
+        "AttributeInvalidated",
+        "LifeEventRecorded",
+        "ConsentRecorded",
+        "TagAdded",
+        "TagRemoved",
+    ];
+
+    const PERSON_EVENT_V2_ORDER: [&str; 9] = [
+        "person.created",
+        "person.activated",
+        "person.suspended",
+        "person.archived",
+        "person.updated",
+        "person.name_updated",
+        "person.birth_date_set",
+        "person.death_recorded",
+        "person.merged",
+    ];
+
+    fn assert_same_json<T: Serialize>(expected: &T, actual: &T) {
+        assert_eq!(serde_json::to_value(expected).unwrap(), serde_json::to_value(actual).unwrap());
+    }
+
+    fn all_events(person_id: PersonId) -> Vec<PersonEvent> {
+        let now = Utc::now();
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        let date = NaiveDate::from_ymd_opt(1852, 11, 27).unwrap();
+        let attribute_type = AttributeType::Identifying(IdentifyingAttributeType::BirthPlace);
+        let attribute = PersonAttribute::new(
+            attribute_type.clone(),
+            AttributeValue::Json(json!({"city": "London", "coordinates": [51.5, -0.12]})),
+            TemporalValidity::of(now),
+            Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+        );
+        let tag = Tag::new("sales", "vip").unwrap();
+
+        vec![
+            PersonEvent::PersonCreated(PersonCreated { person_id, name: name.clone(), source: "test".to_string(), created_at: now }),
+            PersonEvent::PersonUpdated(PersonUpdated { person_id, name: name.clone(), updated_at: now }),
+            PersonEvent::NameUpdated(NameUpdated {
+                person_id,
+                old_name: name.clone(),
+                new_name: PersonName::new("Augusta".to_string(), "Lovelace".to_string()),
+                reason: Some("Preferred".to_string()),
+                updated_at: now,
+            }),
+            PersonEvent::BirthDateSet(BirthDateSet { person_id, birth_date: date, set_at: now }),
+            PersonEvent::DeathRecorded(DeathRecorded { person_id, date_of_death: date, recorded_at: now }),
+            PersonEvent::PersonDeactivated(PersonDeactivated { person_id, reason: "test".to_string(), deactivated_at: now }),
+            PersonEvent::PersonReactivated(PersonReactivated { person_id, reason: "test".to_string(), reactivated_at: now }),
+            PersonEvent::PersonMergedInto(PersonMergedInto {
+                source_person_id: person_id,
+                merged_into_id: PersonId::new(),
+                merge_reason: MergeReason::DuplicateIdentity,
+                merged_at: now,
+            }),
+            PersonEvent::AttributeRecorded(AttributeRecorded { person_id, attribute: attribute.clone(), recorded_at: now }),
+            PersonEvent::AttributeUpdated(AttributeUpdated {
+                person_id,
+                attribute_type: attribute_type.clone(),
+                old_attribute: attribute.clone(),
+                new_attribute: attribute,
+                updated_at: now,
+            }),
+            PersonEvent::AttributeInvalidated(AttributeInvalidated {
+                person_id,
+                attribute_type,
+                invalidated_at: now,
+                reason: None,
+                attribute_recorded_at: None,
+            }),
+            PersonEvent::LifeEventRecorded(LifeEventRecorded {
+                person_id,
+                kind: LifeEventKind::Married,
+                date,
+                note: None,
+                recorded_at: now,
+            }),
+            PersonEvent::ConsentRecorded(ConsentRecorded {
+                person_id,
+                consent_type: ConsentType::Marketing,
+                status: ConsentStatus::Granted,
+                recorded_at: now,
+                policy_version: Some("2024-01".to_string()),
+            }),
+            PersonEvent::TagAdded(TagAdded { person_id, tag: tag.clone(), added_at: now }),
+            PersonEvent::TagRemoved(TagRemoved { person_id, tag, removed_at: now }),
+        ]
+    }
+
+    fn all_v2_events(person_id: PersonId) -> Vec<PersonEventV2> {
+        let mut metadata = EventMetadata::default();
+        metadata.context.insert("request".to_string(), json!({"ip": "10.0.0.1", "retries": 2}));
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        let date = NaiveDate::from_ymd_opt(1815, 12, 10).unwrap();
+
+        vec![
+            PersonEventV2::Created { person_id, name: name.clone(), source: "test".to_string(), metadata: metadata.clone() },
+            PersonEventV2::Activated { person_id, reason: "test".to_string(), metadata: metadata.clone() },
+            PersonEventV2::Suspended { person_id, reason: "test".to_string(), metadata: metadata.clone() },
+            PersonEventV2::Archived { person_id, reason: "test".to_string(), metadata: metadata.clone() },
+            PersonEventV2::Updated { person_id, updates: json!({"nickname": "Ada"}), metadata: metadata.clone() },
+            PersonEventV2::NameUpdated {
+                person_id,
+                old_name: name.clone(),
+                new_name: name,
+                change_reason: None,
+                metadata: metadata.clone(),
+            },
+            PersonEventV2::BirthDateSet { person_id, birth_date: date, metadata: metadata.clone() },
+            PersonEventV2::DeathRecorded { person_id, date_of_death: date, metadata: metadata.clone() },
+            PersonEventV2::PersonMerged {
+                source_person_id: person_id,
+                target_person_id: PersonId::new(),
+                merge_reason: MergeReason::UserRequested,
+                metadata,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_every_person_event_round_trips() {
+        let events = all_events(PersonId::new());
+        assert_eq!(events.len(), PERSON_EVENT_ORDER.len());
+
+        for event in events {
+            let bytes = event.to_bytes().unwrap();
+            assert_eq!(PERSON_EVENT_ORDER[bytes[0] as usize], event.name());
+            assert!(bytes.len() < serde_json::to_vec(&event).unwrap().len());
+            assert_same_json(&event, &PersonEvent::from_bytes(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_every_v2_event_round_trips() {
+        let events = all_v2_events(PersonId::new());
+        assert_eq!(events.len(), PERSON_EVENT_V2_ORDER.len());
+
+        for event in events {
+            let bytes = event.to_bytes().unwrap();
+            assert_eq!(PERSON_EVENT_V2_ORDER[bytes[0] as usize], event.event_type());
+            assert_same_json(&event, &PersonEventV2::from_bytes(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_optional_fields_left_out_of_json_round_trip() {
+        use crate::value_objects::{DateZone, DocumentReference, NameDisplayPolicy};
+
+        let person_id = PersonId::new();
+        let attribute = PersonAttribute::new(
+            AttributeType::Identifying(IdentifyingAttributeType::BirthPlace),
+            AttributeValue::Text("London".to_string()),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::DocumentVerified, ConfidenceLevel::Certain)
+                .verified_by(DocumentReference::new("doc-1", "birth_certificate", "sha256:ab")),
+        )
+        .with_date_zone(DateZone::utc());
+        let name = PersonName {
+            display_policy: Some(NameDisplayPolicy::Formal),
+            ..PersonName::new("Ada".to_string(), "Lovelace".to_string())
+        };
+
+        for event in [
+            PersonEvent::AttributeRecorded(AttributeRecorded { person_id, attribute, recorded_at: Utc::now() }),
+            PersonEvent::PersonUpdated(PersonUpdated { person_id, name, updated_at: Utc::now() }),
+        ] {
+            let decoded = PersonEvent::from_bytes(&event.to_bytes().unwrap()).unwrap();
+            assert_same_json(&event, &decoded);
+        }
+    }
+
+    #[test]
+    fn test_envelope_round_trips() {
+        let person_id = PersonId::new();
+        let envelope = EventEnvelope {
+            aggregate_id: person_id,
+            sequence: 3,
+            event: all_events(person_id).remove(8),
+            timestamp: Utc::now(),
+            correlation_id: "corr".to_string(),
+            causation_id: "cause".to_string(),
+            stream_sequence: Some(42),
+        };
+
+        let decoded = EventEnvelope::from_bytes(&envelope.to_bytes().unwrap()).unwrap();
+        assert_same_json(&envelope, &decoded);
+        assert!(EventEnvelope::from_bytes(&[0xff, 0x01]).is_err());
+    }
+}
@@ -9,6 +9,9 @@ use serde::{Deserialize, Serialize};
 use chrono::NaiveDate;
 
 /// Enhanced person events with metadata
+///
+/// The binary codec identifies variants by position, so new variants go at
+/// the end; they also have to be mirrored in the codec's description.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "event_type", content = "data")]
 pub enum PersonEventV2 {
@@ -69,6 +72,107 @@ pub enum PersonEventV2 {
 }
 
 impl PersonEventV2 {
+    /// The V2 form of a stored event, carrying `metadata`
+    ///
+    /// Events without a dedicated V2 variant become `Updated` with the
+    /// change in `updates`.
+    pub fn from_v1(event: PersonEvent, metadata: EventMetadata) -> Self {
+        match event {
+            PersonEvent::PersonCreated(e) => PersonEventV2::Created {
+                person_id: e.person_id,
+                name: e.name,
+                source: e.source,
+                metadata,
+            },
+            PersonEvent::PersonUpdated(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({ "name": e.name }),
+                metadata,
+            },
+            PersonEvent::NameUpdated(e) => PersonEventV2::NameUpdated {
+                person_id: e.person_id,
+                old_name: e.old_name,
+                new_name: e.new_name,
+                change_reason: e.reason,
+                metadata,
+            },
+            PersonEvent::BirthDateSet(e) => PersonEventV2::BirthDateSet {
+                person_id: e.person_id,
+                birth_date: e.birth_date,
+                metadata,
+            },
+            PersonEvent::DeathRecorded(e) => PersonEventV2::DeathRecorded {
+                person_id: e.person_id,
+                date_of_death: e.date_of_death,
+                metadata,
+            },
+            PersonEvent::PersonDeactivated(e) => PersonEventV2::Suspended {
+                person_id: e.person_id,
+                reason: e.reason,
+                metadata,
+            },
+            PersonEvent::PersonReactivated(e) => PersonEventV2::Activated {
+                person_id: e.person_id,
+                reason: e.reason,
+                metadata,
+            },
+            PersonEvent::PersonMergedInto(e) => PersonEventV2::PersonMerged {
+                source_person_id: e.source_person_id,
+                target_person_id: e.merged_into_id,
+                merge_reason: e.merge_reason,
+                metadata,
+            },
+            // Attribute events - convert to Updated for now
+            // In the future, PersonEventV2 should have dedicated attribute variants
+            PersonEvent::AttributeRecorded(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({ "attribute_recorded": e.attribute }),
+                metadata,
+            },
+            PersonEvent::AttributeUpdated(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({ "attribute_updated": e.new_attribute }),
+                metadata,
+            },
+            PersonEvent::AttributeInvalidated(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({ "attribute_invalidated": e.attribute_type }),
+                metadata,
+            },
+            PersonEvent::LifeEventRecorded(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({
+                    "life_event_recorded": {
+                        "kind": e.kind,
+                        "date": e.date,
+                        "note": e.note,
+                    }
+                }),
+                metadata,
+            },
+            PersonEvent::ConsentRecorded(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({
+                    "consent_recorded": {
+                        "consent_type": e.consent_type,
+                        "status": e.status,
+                    }
+                }),
+                metadata,
+            },
+            PersonEvent::TagAdded(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({ "tag_added": e.tag }),
+                metadata,
+            },
+            PersonEvent::TagRemoved(e) => PersonEventV2::Updated {
+                person_id: e.person_id,
+                updates: serde_json::json!({ "tag_removed": e.tag }),
+                metadata,
+            },
+        }
+    }
+
     /// Get the aggregate ID this event applies to
     pub fn aggregate_id(&self) -> PersonId {
         match self {
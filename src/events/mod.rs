@@ -11,13 +11,19 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc, NaiveDate};
 
 use crate::aggregate::PersonMarker;
-use crate::value_objects::{PersonName, PersonAttribute, AttributeType};
+use crate::value_objects::{
+    PersonName, PersonAttribute, AttributeType, LifeEventKind, ConsentType, ConsentStatus,
+    Tag,
+};
 use crate::commands::MergeReason;
 
 /// Person ID type alias
 pub type PersonId = EntityId<PersonMarker>;
 
 /// Events for the Person domain
+///
+/// Binary encodings identify variants by position: add new variants at the
+/// end and never reorder or remove existing ones.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PersonEvent {
     /// Person was created
@@ -52,6 +58,18 @@ pub enum PersonEvent {
 
     /// Attribute was invalidated
     AttributeInvalidated(AttributeInvalidated),
+
+    /// A life event was recorded on the timeline
+    LifeEventRecorded(LifeEventRecorded),
+
+    /// Consent was granted or revoked
+    ConsentRecorded(ConsentRecorded),
+
+    /// A tag was applied
+    TagAdded(TagAdded),
+
+    /// A tag was removed
+    TagRemoved(TagRemoved),
 }
 
 // Implement DomainEvent trait for formal Category Theory compliance
@@ -69,6 +87,54 @@ impl DomainEventTrait for PersonEvent {
             PersonEvent::AttributeRecorded(_) => "AttributeRecorded",
             PersonEvent::AttributeUpdated(_) => "AttributeUpdated",
             PersonEvent::AttributeInvalidated(_) => "AttributeInvalidated",
+            PersonEvent::LifeEventRecorded(_) => "LifeEventRecorded",
+            PersonEvent::ConsentRecorded(_) => "ConsentRecorded",
+            PersonEvent::TagAdded(_) => "TagAdded",
+            PersonEvent::TagRemoved(_) => "TagRemoved",
+        }
+    }
+}
+
+impl PersonEvent {
+    /// The person the event belongs to (the source person for merges)
+    pub fn person_id(&self) -> PersonId {
+        match self {
+            PersonEvent::PersonCreated(e) => e.person_id,
+            PersonEvent::PersonUpdated(e) => e.person_id,
+            PersonEvent::NameUpdated(e) => e.person_id,
+            PersonEvent::BirthDateSet(e) => e.person_id,
+            PersonEvent::DeathRecorded(e) => e.person_id,
+            PersonEvent::PersonDeactivated(e) => e.person_id,
+            PersonEvent::PersonReactivated(e) => e.person_id,
+            PersonEvent::PersonMergedInto(e) => e.source_person_id,
+            PersonEvent::AttributeRecorded(e) => e.person_id,
+            PersonEvent::AttributeUpdated(e) => e.person_id,
+            PersonEvent::AttributeInvalidated(e) => e.person_id,
+            PersonEvent::LifeEventRecorded(e) => e.person_id,
+            PersonEvent::ConsentRecorded(e) => e.person_id,
+            PersonEvent::TagAdded(e) => e.person_id,
+            PersonEvent::TagRemoved(e) => e.person_id,
+        }
+    }
+
+    /// When the event happened
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            PersonEvent::PersonCreated(e) => e.created_at,
+            PersonEvent::PersonUpdated(e) => e.updated_at,
+            PersonEvent::NameUpdated(e) => e.updated_at,
+            PersonEvent::BirthDateSet(e) => e.set_at,
+            PersonEvent::DeathRecorded(e) => e.recorded_at,
+            PersonEvent::PersonDeactivated(e) => e.deactivated_at,
+            PersonEvent::PersonReactivated(e) => e.reactivated_at,
+            PersonEvent::PersonMergedInto(e) => e.merged_at,
+            PersonEvent::AttributeRecorded(e) => e.recorded_at,
+            PersonEvent::AttributeUpdated(e) => e.updated_at,
+            PersonEvent::AttributeInvalidated(e) => e.invalidated_at,
+            PersonEvent::LifeEventRecorded(e) => e.recorded_at,
+            PersonEvent::ConsentRecorded(e) => e.recorded_at,
+            PersonEvent::TagAdded(e) => e.added_at,
+            PersonEvent::TagRemoved(e) => e.removed_at,
         }
     }
 }
@@ -164,6 +230,54 @@ pub struct AttributeInvalidated {
     pub attribute_type: AttributeType,
     pub invalidated_at: DateTime<Utc>,
     pub reason: Option<String>,
+    /// When the invalidated attribute was recorded, if one of several of its
+    /// type was targeted
+    #[serde(default)]
+    pub attribute_recorded_at: Option<DateTime<Utc>>,
+}
+
+// ===== Timeline Events =====
+
+/// A life event was annotated on the person's timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifeEventRecorded {
+    pub person_id: PersonId,
+    pub kind: LifeEventKind,
+    pub date: NaiveDate,
+    pub note: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+// ===== Consent Events =====
+
+/// A person granted or revoked consent for a purpose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentRecorded {
+    pub person_id: PersonId,
+    pub consent_type: ConsentType,
+    pub status: ConsentStatus,
+    pub recorded_at: DateTime<Utc>,
+    /// Version of the consent policy the decision was made under
+    #[serde(default)]
+    pub policy_version: Option<String>,
+}
+
+// ===== Tag Events =====
+
+/// A person was given a tag they did not have
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagAdded {
+    pub person_id: PersonId,
+    pub tag: Tag,
+    pub added_at: DateTime<Utc>,
+}
+
+/// A tag was taken off a person
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagRemoved {
+    pub person_id: PersonId,
+    pub tag: Tag,
+    pub removed_at: DateTime<Utc>,
 }
 
 // Enhanced events with metadata
@@ -179,9 +293,14 @@ pub use versioning::{
 };
 pub use versioned_events::{
     PersonCreatedV2, PersonNameUpdatedV2, PersonActivatedV2,
-    PersonSuspendedV2, PersonArchivedV2, ComponentAddedV2,
+    PersonSuspendedV2, PersonArchivedV2, BirthDateSetV2, DeathRecordedV2,
+    AttributeRecordedV2, ComponentAddedV2,
     ComponentUpdatedV2, ComponentRemovedV2, create_event_registry
 };
 
+// Compact binary codec
+#[cfg(feature = "binary-events")]
+mod binary;
+
 // Re-export EventMetadata from infrastructure
 pub use crate::infrastructure::EventMetadata;
@@ -1,11 +1,13 @@
 //! Versioned events for Person domain
 
 use super::versioning::{VersionedEvent, EventVersionRegistry, FunctionMigration};
+use super::{AttributeRecorded, BirthDateSet, DeathRecorded, PersonCreated, PersonEvent};
 use crate::aggregate::PersonId;
-use crate::value_objects::PersonName;
+use crate::value_objects::{PersonAttribute, PersonName};
 use crate::infrastructure::EventMetadata;
+use cim_domain::{DomainError, DomainResult};
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde_json::{json, Value};
 
 // Macro for defining versioned events
@@ -86,6 +88,77 @@ pub struct PersonArchivedV2 {
 
 versioned_event!(PersonArchivedV2, version = "2.0", event_type = "PersonArchived");
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BirthDateSetV2 {
+    pub person_id: PersonId,
+    pub birth_date: NaiveDate,
+    pub metadata: EventMetadata,
+}
+
+versioned_event!(BirthDateSetV2, version = "2.0", event_type = "BirthDateSet");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeathRecordedV2 {
+    pub person_id: PersonId,
+    pub date_of_death: NaiveDate,
+    pub metadata: EventMetadata,
+}
+
+versioned_event!(DeathRecordedV2, version = "2.0", event_type = "DeathRecorded");
+
+/// Attribute recorded, with the attribute's provenance carried unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeRecordedV2 {
+    pub person_id: PersonId,
+    pub attribute: PersonAttribute,
+    pub metadata: EventMetadata,
+}
+
+versioned_event!(AttributeRecordedV2, version = "2.0", event_type = "AttributeRecorded");
+
+// Replaying upgraded events: the metadata timestamp is the original event time
+
+impl From<PersonCreatedV2> for PersonEvent {
+    fn from(event: PersonCreatedV2) -> Self {
+        PersonEvent::PersonCreated(PersonCreated {
+            person_id: event.person_id,
+            name: event.name,
+            source: event.source,
+            created_at: event.metadata.timestamp,
+        })
+    }
+}
+
+impl From<BirthDateSetV2> for PersonEvent {
+    fn from(event: BirthDateSetV2) -> Self {
+        PersonEvent::BirthDateSet(BirthDateSet {
+            person_id: event.person_id,
+            birth_date: event.birth_date,
+            set_at: event.metadata.timestamp,
+        })
+    }
+}
+
+impl From<DeathRecordedV2> for PersonEvent {
+    fn from(event: DeathRecordedV2) -> Self {
+        PersonEvent::DeathRecorded(DeathRecorded {
+            person_id: event.person_id,
+            date_of_death: event.date_of_death,
+            recorded_at: event.metadata.timestamp,
+        })
+    }
+}
+
+impl From<AttributeRecordedV2> for PersonEvent {
+    fn from(event: AttributeRecordedV2) -> Self {
+        PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id: event.person_id,
+            attribute: event.attribute,
+            recorded_at: event.metadata.timestamp,
+        })
+    }
+}
+
 // Component events - DEPRECATED (components belong in separate domains)
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -139,6 +212,38 @@ versioned_event!(PersonCreatedV3, version = "3.0", event_type = "PersonCreated")
 
 // Migration implementations
 
+/// Move a 1.0 event's own timestamp field into 2.0 metadata
+///
+/// The correlation id is derived from the original event, so migrating the
+/// same stored event again gives the same result, and a 2.0 event passes
+/// through unchanged. A missing timestamp is an error rather than "now".
+fn upgrade_with_metadata(mut data: Value, timestamp_field: &str) -> DomainResult<Value> {
+    if data.get("version").and_then(Value::as_str) == Some("2.0") {
+        return Ok(data);
+    }
+
+    let digest = blake3::hash(data.to_string().as_bytes());
+    let mut correlation = [0u8; 16];
+    correlation.copy_from_slice(&digest.as_bytes()[..16]);
+    let correlation_id = uuid::Builder::from_random_bytes(correlation).into_uuid();
+
+    let obj = data.as_object_mut()
+        .ok_or_else(|| DomainError::SerializationError("Event data is not an object".to_string()))?;
+    let timestamp = obj.remove(timestamp_field)
+        .ok_or_else(|| DomainError::SerializationError(format!("Event missing {timestamp_field}")))?;
+
+    obj.insert("metadata".to_string(), json!({
+        "version": "1.0",
+        "correlation_id": correlation_id.to_string(),
+        "causation_id": null,
+        "timestamp": timestamp,
+        "actor": null,
+        "context": {}
+    }));
+    obj.insert("version".to_string(), json!("2.0"));
+    Ok(data)
+}
+
 /// Create and configure the event version registry
 pub fn create_event_registry() -> EventVersionRegistry {
     let mut registry = EventVersionRegistry::new();
@@ -149,6 +254,9 @@ pub fn create_event_registry() -> EventVersionRegistry {
     registry.register_event::<PersonActivatedV2>();
     registry.register_event::<PersonSuspendedV2>();
     registry.register_event::<PersonArchivedV2>();
+    registry.register_event::<BirthDateSetV2>();
+    registry.register_event::<DeathRecordedV2>();
+    registry.register_event::<AttributeRecordedV2>();
     registry.register_event::<ComponentAddedV2>();
     registry.register_event::<ComponentUpdatedV2>();
     registry.register_event::<ComponentRemovedV2>();
@@ -160,26 +268,7 @@ pub fn create_event_registry() -> EventVersionRegistry {
         "PersonCreated",
         "1.0",
         "2.0",
-        FunctionMigration::new(|mut data| {
-            if let Some(obj) = data.as_object_mut() {
-                // Remove created_at field
-                let created_at = obj.remove("created_at")
-                    .unwrap_or(json!(chrono::Utc::now()));
-                
-                // Add metadata
-                obj.insert("metadata".to_string(), json!({
-                    "version": "1.0",
-                    "correlation_id": uuid::Uuid::now_v7().to_string(),
-                    "causation_id": null,
-                    "timestamp": created_at,
-                    "actor": null,
-                    "context": {}
-                }));
-                
-                obj.insert("version".to_string(), json!("2.0"));
-            }
-            Ok(data)
-        }),
+        FunctionMigration::new(|data| upgrade_with_metadata(data, "created_at")),
     );
     
     // PersonCreated v2 -> v3 (future migration)
@@ -203,33 +292,32 @@ pub fn create_event_registry() -> EventVersionRegistry {
         "PersonNameUpdated",
         "1.0",
         "2.0",
-        FunctionMigration::new(|mut data| {
+        FunctionMigration::new(|data| {
+            let mut data = upgrade_with_metadata(data, "updated_at")?;
+            // Rename reason to change_reason
             if let Some(obj) = data.as_object_mut() {
-                // Remove updated_at field
-                let updated_at = obj.remove("updated_at")
-                    .unwrap_or(json!(chrono::Utc::now()));
-                
-                // Rename reason to change_reason
                 if let Some(reason) = obj.remove("reason") {
                     obj.insert("change_reason".to_string(), reason);
                 }
-                
-                // Add metadata
-                obj.insert("metadata".to_string(), json!({
-                    "version": "1.0",
-                    "correlation_id": uuid::Uuid::now_v7().to_string(),
-                    "causation_id": null,
-                    "timestamp": updated_at,
-                    "actor": null,
-                    "context": {}
-                }));
-                
-                obj.insert("version".to_string(), json!("2.0"));
             }
             Ok(data)
         }),
     );
     
+    // Core identity and attribute events v1 -> v2
+    for (event_type, timestamp_field) in [
+        ("BirthDateSet", "set_at"),
+        ("DeathRecorded", "recorded_at"),
+        ("AttributeRecorded", "recorded_at"),
+    ] {
+        registry.register_migration(
+            event_type,
+            "1.0",
+            "2.0",
+            FunctionMigration::new(move |data| upgrade_with_metadata(data, timestamp_field)),
+        );
+    }
+    
     registry
 }
 
@@ -264,6 +352,30 @@ pub fn migrate_legacy_event(event: &crate::events::PersonEvent) -> Result<Value,
                 "updated_at": e.updated_at,
             })
         }
+        crate::events::PersonEvent::BirthDateSet(e) => {
+            json!({
+                "version": "1.0",
+                "person_id": e.person_id,
+                "birth_date": e.birth_date,
+                "set_at": e.set_at,
+            })
+        }
+        crate::events::PersonEvent::DeathRecorded(e) => {
+            json!({
+                "version": "1.0",
+                "person_id": e.person_id,
+                "date_of_death": e.date_of_death,
+                "recorded_at": e.recorded_at,
+            })
+        }
+        crate::events::PersonEvent::AttributeRecorded(e) => {
+            json!({
+                "version": "1.0",
+                "person_id": e.person_id,
+                "attribute": e.attribute,
+                "recorded_at": e.recorded_at,
+            })
+        }
         crate::events::PersonEvent::PersonDeactivated(e) => {
             json!({
                 "version": "1.0",
@@ -340,4 +452,96 @@ mod tests {
         assert!(migrated["metadata"].is_object());
         assert!(migrated["created_at"].is_null());
     }
+
+    #[test]
+    fn test_name_updated_migration_is_deterministic() {
+        let registry = create_event_registry();
+        let v1_data = json!({
+            "version": "1.0",
+            "person_id": "12345",
+            "old_name": "John Doe",
+            "new_name": "Jon Doe",
+            "reason": "Spelling",
+            "updated_at": "2024-01-01T00:00:00Z"
+        });
+
+        let migrated = registry.migrate_to_current("PersonNameUpdated", v1_data.clone()).unwrap();
+        assert_eq!(migrated["version"], "2.0");
+        assert_eq!(migrated["change_reason"], "Spelling");
+        assert!(migrated.get("reason").is_none());
+        assert_eq!(migrated["metadata"]["timestamp"], "2024-01-01T00:00:00Z");
+        assert_eq!(registry.migrate_to_current("PersonNameUpdated", v1_data).unwrap(), migrated);
+    }
+
+    #[test]
+    fn test_mixed_v1_v2_stream_replays_to_same_state() {
+        use crate::aggregate::Person;
+        use crate::value_objects::{
+            AttributeSource, AttributeType, AttributeValue, ConfidenceLevel, IdentifyingAttributeType,
+            Provenance, TemporalValidity,
+        };
+        use cim_domain::formal_domain::DomainEvent as _;
+
+        let registry = create_event_registry();
+        let person_id = PersonId::new();
+        let born = NaiveDate::from_ymd_opt(1815, 12, 10).unwrap();
+        let at = |day: i64| Utc::now() - chrono::Duration::days(30 - day);
+        let events = vec![
+            PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                source: "test".to_string(),
+                created_at: at(0),
+            }),
+            PersonEvent::BirthDateSet(BirthDateSet { person_id, birth_date: born, set_at: at(1) }),
+            PersonEvent::AttributeRecorded(AttributeRecorded {
+                person_id,
+                attribute: PersonAttribute::new(
+                    AttributeType::Identifying(IdentifyingAttributeType::BirthPlace),
+                    AttributeValue::Text("London".to_string()),
+                    TemporalValidity::of(at(2)),
+                    Provenance::new(
+                        AttributeSource::Imported { system: "parish-register".to_string() },
+                        ConfidenceLevel::Likely,
+                    ),
+                ),
+                recorded_at: at(2),
+            }),
+            PersonEvent::DeathRecorded(DeathRecorded {
+                person_id,
+                date_of_death: NaiveDate::from_ymd_opt(1852, 11, 27).unwrap(),
+                recorded_at: at(3),
+            }),
+        ];
+        let replay = |events: &[PersonEvent]| {
+            let person = events.iter().try_fold(Person::empty(), |p, e| p.apply_event_pure(e)).unwrap();
+            serde_json::to_value(person).unwrap()
+        };
+
+        // Every other event is stored already upgraded
+        let stored: Vec<(String, Value)> = events.iter().enumerate().map(|(i, event)| {
+            let event_type = event.name().to_string();
+            let v1 = migrate_legacy_event(event).unwrap();
+            let data = if i % 2 == 1 { registry.migrate_to_current(&event_type, v1).unwrap() } else { v1 };
+            (event_type, data)
+        }).collect();
+
+        let upgraded: Vec<PersonEvent> = stored.into_iter().map(|(event_type, data)| {
+            let current = registry.migrate_to_current(&event_type, data.clone()).unwrap();
+            assert_eq!(current["version"], "2.0");
+            // Re-migrating changes nothing, whether from the stored or the upgraded form
+            assert_eq!(registry.migrate_to_current(&event_type, current.clone()).unwrap(), current);
+            assert_eq!(registry.migrate_to_current(&event_type, data).unwrap(), current);
+
+            match event_type.as_str() {
+                "PersonCreated" => serde_json::from_value::<PersonCreatedV2>(current).unwrap().into(),
+                "BirthDateSet" => serde_json::from_value::<BirthDateSetV2>(current).unwrap().into(),
+                "AttributeRecorded" => serde_json::from_value::<AttributeRecordedV2>(current).unwrap().into(),
+                "DeathRecorded" => serde_json::from_value::<DeathRecordedV2>(current).unwrap().into(),
+                other => panic!("unexpected event type {other}"),
+            }
+        }).collect();
+
+        assert_eq!(replay(&upgraded), replay(&events));
+    }
 }
\ No newline at end of file
@@ -3,15 +3,17 @@
 use async_trait::async_trait;
 use cim_domain::{DomainError, DomainResult};
 use futures::stream::{Stream, StreamExt};
+use std::collections::VecDeque;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tracing::{info, debug};
 
 use crate::aggregate::{Person, PersonId, EventSourced};
-use crate::commands::PersonCommand;
+use crate::commands::{PersonCommand, VersionedCommand};
 use crate::events::{PersonEvent, PersonEventV2, StreamingEventEnvelope, EventMetadata};
-use crate::infrastructure::{StreamingClient, EventStore};
+use crate::infrastructure::{StreamingClient, EventStore, ProcessorMetrics, QueueMonitor, QueueTicket};
+use crate::value_objects::NationalIdValidator;
 
 /// Result of command processing with streaming events
 pub struct CommandResult {
@@ -33,12 +35,27 @@ pub trait AsyncCommandProcessor: Send + Sync {
         command: PersonCommand,
         correlation_id: uuid::Uuid,
     ) -> DomainResult<CommandResult>;
+
+    /// Process a command that may carry an expected aggregate version
+    ///
+    /// Processors that cannot check versions only accept unconditional commands.
+    async fn process_versioned_command(&self, command: VersionedCommand) -> DomainResult<CommandResult> {
+        match command.expected_version {
+            None => self.process_command(command.command).await,
+            Some(_) => Err(DomainError::generic(
+                "This command processor does not support expected_version",
+            )),
+        }
+    }
 }
 
 /// Implementation of async command processor
 pub struct PersonCommandProcessor {
     event_store: Arc<dyn EventStore>,
     streaming_client: Arc<StreamingClient>,
+    national_id_validator: Option<NationalIdValidator>,
+    /// Leave publishing to an outbox relay instead of publishing after append
+    publish_via_outbox: bool,
 }
 
 impl PersonCommandProcessor {
@@ -50,7 +67,52 @@ impl PersonCommandProcessor {
         Self {
             event_store,
             streaming_client,
+            national_id_validator: None,
+            publish_via_outbox: false,
+        }
+    }
+
+    /// Validate national IDs in attribute commands before they are handled
+    pub fn with_national_id_validator(mut self, validator: NationalIdValidator) -> Self {
+        self.national_id_validator = Some(validator);
+        self
+    }
+
+    /// Don't publish events directly; the event store records them in its
+    /// outbox and an [`OutboxRelay`](crate::infrastructure::OutboxRelay)
+    /// publishes them
+    ///
+    /// Fails if the event store keeps no outbox, since its events would then
+    /// never be published.
+    pub fn with_outbox_publishing(mut self) -> DomainResult<Self> {
+        if !self.event_store.records_outbox() {
+            return Err(DomainError::ValidationError(
+                "Outbox publishing needs an event store that records an outbox".to_string(),
+            ));
         }
+        self.publish_via_outbox = true;
+        Ok(self)
+    }
+
+    /// Apply the national ID policy to recorded or updated attributes
+    fn screen_attributes(&self, mut versioned: VersionedCommand) -> DomainResult<VersionedCommand> {
+        let Some(validator) = &self.national_id_validator else {
+            return Ok(versioned);
+        };
+
+        match &mut versioned.command {
+            PersonCommand::RecordAttribute(cmd) => {
+                cmd.attribute = validator.screen_attribute(cmd.attribute.clone())?;
+            }
+            PersonCommand::RecordAttributeWithDocument(cmd) => {
+                cmd.attribute = validator.screen_attribute(cmd.attribute.clone())?;
+            }
+            PersonCommand::UpdateAttribute(cmd) => {
+                cmd.new_attribute = validator.screen_attribute(cmd.new_attribute.clone())?;
+            }
+            _ => {}
+        }
+        Ok(versioned)
     }
     
     /// Load aggregate from event store
@@ -78,71 +140,9 @@ impl PersonCommandProcessor {
         events: Vec<crate::events::PersonEvent>,
         metadata: EventMetadata,
     ) -> Vec<PersonEventV2> {
-        events.into_iter().map(|event| {
-            match event {
-                crate::events::PersonEvent::PersonCreated(e) => PersonEventV2::Created {
-                    person_id: e.person_id,
-                    name: e.name,
-                    source: e.source,
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::PersonUpdated(e) => PersonEventV2::Updated {
-                    person_id: e.person_id,
-                    updates: serde_json::json!({ "name": e.name }),
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::NameUpdated(e) => PersonEventV2::NameUpdated {
-                    person_id: e.person_id,
-                    old_name: e.old_name,
-                    new_name: e.new_name,
-                    change_reason: e.reason,
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::BirthDateSet(e) => PersonEventV2::BirthDateSet {
-                    person_id: e.person_id,
-                    birth_date: e.birth_date,
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::DeathRecorded(e) => PersonEventV2::DeathRecorded {
-                    person_id: e.person_id,
-                    date_of_death: e.date_of_death,
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::PersonDeactivated(e) => PersonEventV2::Suspended {
-                    person_id: e.person_id,
-                    reason: e.reason,
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::PersonReactivated(e) => PersonEventV2::Activated {
-                    person_id: e.person_id,
-                    reason: e.reason,
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::PersonMergedInto(e) => PersonEventV2::PersonMerged {
-                    source_person_id: e.source_person_id,
-                    target_person_id: e.merged_into_id,
-                    merge_reason: e.merge_reason,
-                    metadata: metadata.clone(),
-                },
-                // Attribute events - convert to Updated for now
-                // In the future, PersonEventV2 should have dedicated attribute variants
-                crate::events::PersonEvent::AttributeRecorded(e) => PersonEventV2::Updated {
-                    person_id: e.person_id,
-                    updates: serde_json::json!({ "attribute_recorded": e.attribute }),
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::AttributeUpdated(e) => PersonEventV2::Updated {
-                    person_id: e.person_id,
-                    updates: serde_json::json!({ "attribute_updated": e.new_attribute }),
-                    metadata: metadata.clone(),
-                },
-                crate::events::PersonEvent::AttributeInvalidated(e) => PersonEventV2::Updated {
-                    person_id: e.person_id,
-                    updates: serde_json::json!({ "attribute_invalidated": e.attribute_type }),
-                    metadata: metadata.clone(),
-                },
-            }
-        }).collect()
+        events.into_iter()
+            .map(|event| PersonEventV2::from_v1(event, metadata.clone()))
+            .collect()
     }
     
     /// Publish events to NATS
@@ -188,6 +188,22 @@ impl AsyncCommandProcessor for PersonCommandProcessor {
         command: PersonCommand,
         correlation_id: uuid::Uuid,
     ) -> DomainResult<CommandResult> {
+        self.process_with_correlation(VersionedCommand::unconditional(command), correlation_id).await
+    }
+
+    async fn process_versioned_command(&self, command: VersionedCommand) -> DomainResult<CommandResult> {
+        self.process_with_correlation(command, uuid::Uuid::now_v7()).await
+    }
+}
+
+impl PersonCommandProcessor {
+    async fn process_with_correlation(
+        &self,
+        versioned: VersionedCommand,
+        correlation_id: uuid::Uuid,
+    ) -> DomainResult<CommandResult> {
+        let versioned = self.screen_attributes(versioned)?;
+        let command = &versioned.command;
         let command_id = uuid::Uuid::now_v7();
         let mut metadata = EventMetadata::from_command(command_id);
         metadata.correlation_id = correlation_id;
@@ -213,9 +229,8 @@ impl AsyncCommandProcessor for PersonCommandProcessor {
         
         let current_version = person.version;
 
-        // Handle command using formal Aggregate trait (pure functional)
-        use cim_domain::formal_domain::Aggregate;
-        let (_person, events) = person.handle(command)?;
+        // Reject stale commands before generating any events
+        let (_person, events) = person.handle_versioned(versioned)?;
 
         // Convert to V2 events with metadata
         let v2_events = self.convert_to_v2_events(events, metadata);
@@ -232,7 +247,9 @@ impl AsyncCommandProcessor for PersonCommandProcessor {
                 .await?;
             
             // Publish to NATS
-            self.publish_events(aggregate_id, &v2_events, current_version).await?;
+            if !self.publish_via_outbox {
+                self.publish_events(aggregate_id, &v2_events, current_version).await?;
+            }
         }
         
         // Create event stream for real-time updates
@@ -311,6 +328,88 @@ impl PersonCommandProcessor {
     }
 }
 
+/// Command processor that reports queue metrics for an inner processor
+///
+/// Commands are either processed on arrival or buffered with
+/// [`enqueue`](Self::enqueue) and worked off with
+/// [`process_next`](Self::process_next); either way they count toward the
+/// queue until their result is known.
+pub struct MonitoredCommandProcessor {
+    inner: Arc<dyn AsyncCommandProcessor>,
+    monitor: QueueMonitor,
+    queue: Mutex<VecDeque<(QueueTicket, VersionedCommand)>>,
+}
+
+impl MonitoredCommandProcessor {
+    pub fn new(inner: Arc<dyn AsyncCommandProcessor>, metrics: Arc<dyn ProcessorMetrics>) -> Self {
+        Self {
+            inner,
+            monitor: QueueMonitor::new("person.commands", metrics),
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// How long a command may wait before the lag alarm fires
+    pub fn with_lag_threshold(mut self, lag_threshold: chrono::Duration) -> Self {
+        self.monitor = self.monitor.with_lag_threshold(lag_threshold);
+        self
+    }
+
+    pub fn monitor(&self) -> &QueueMonitor {
+        &self.monitor
+    }
+
+    /// Buffer a command for later processing
+    pub fn enqueue(&self, command: VersionedCommand) {
+        let ticket = self.monitor.enqueued();
+        self.queue.lock().expect("command queue poisoned").push_back((ticket, command));
+    }
+
+    /// Process the oldest buffered command, if any
+    pub async fn process_next(&self) -> Option<DomainResult<CommandResult>> {
+        let (ticket, command) = self.queue.lock().expect("command queue poisoned").pop_front()?;
+        Some(self.process_tracked(ticket, command).await)
+    }
+
+    /// Process every buffered command in arrival order
+    pub async fn drain(&self) -> Vec<DomainResult<CommandResult>> {
+        let mut results = Vec::new();
+        while let Some(result) = self.process_next().await {
+            results.push(result);
+        }
+        results
+    }
+
+    async fn process_tracked(&self, ticket: QueueTicket, command: VersionedCommand) -> DomainResult<CommandResult> {
+        let result = self.inner.process_versioned_command(command).await;
+        self.monitor.completed(ticket);
+        result
+    }
+}
+
+#[async_trait]
+impl AsyncCommandProcessor for MonitoredCommandProcessor {
+    async fn process_command(&self, command: PersonCommand) -> DomainResult<CommandResult> {
+        self.process_versioned_command(VersionedCommand::unconditional(command)).await
+    }
+
+    async fn process_command_with_correlation(
+        &self,
+        command: PersonCommand,
+        correlation_id: uuid::Uuid,
+    ) -> DomainResult<CommandResult> {
+        let ticket = self.monitor.enqueued();
+        let result = self.inner.process_command_with_correlation(command, correlation_id).await;
+        self.monitor.completed(ticket);
+        result
+    }
+
+    async fn process_versioned_command(&self, command: VersionedCommand) -> DomainResult<CommandResult> {
+        let ticket = self.monitor.enqueued();
+        self.process_tracked(ticket, command).await
+    }
+}
+
 /// Command handler trait for components
 #[async_trait]
 pub trait AsyncComponentCommandHandler: Send + Sync {
@@ -324,5 +423,60 @@ pub trait AsyncComponentCommandHandler: Send + Sync {
 
 #[cfg(test)]
 mod tests {
-    // Mock implementations would go here
+    use super::*;
+    use crate::commands::CreatePerson;
+    use crate::infrastructure::InMemoryProcessorMetrics;
+    use crate::value_objects::PersonName;
+
+    /// Accepts every command without producing events
+    struct NoopProcessor;
+
+    #[async_trait]
+    impl AsyncCommandProcessor for NoopProcessor {
+        async fn process_command(&self, command: PersonCommand) -> DomainResult<CommandResult> {
+            Ok(CommandResult {
+                aggregate_id: command.aggregate_id(),
+                version: 0,
+                events: Vec::new(),
+                event_stream: None,
+            })
+        }
+
+        async fn process_command_with_correlation(
+            &self,
+            command: PersonCommand,
+            _correlation_id: uuid::Uuid,
+        ) -> DomainResult<CommandResult> {
+            self.process_command(command).await
+        }
+    }
+
+    fn create_command() -> VersionedCommand {
+        VersionedCommand::unconditional(PersonCommand::CreatePerson(CreatePerson {
+            person_id: PersonId::new(),
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_undrained_queue_raises_oldest_item_age() {
+        let metrics = Arc::new(InMemoryProcessorMetrics::new());
+        let processor = MonitoredCommandProcessor::new(Arc::new(NoopProcessor), metrics.clone())
+            .with_lag_threshold(chrono::Duration::milliseconds(10));
+
+        processor.enqueue(create_command());
+        processor.enqueue(create_command());
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+
+        let stats = processor.monitor().report();
+        assert_eq!(stats.depth, 2);
+        assert!(stats.lagging);
+        assert!(metrics.gauge("person.commands.oldest_item_age_seconds").unwrap() > 0.01);
+        assert_eq!(metrics.counter("person.commands.lag_alarms"), 1);
+
+        assert_eq!(processor.drain().await.len(), 2);
+        assert_eq!(metrics.gauge("person.commands.queue_depth"), Some(0.0));
+        assert!(!processor.monitor().report().lagging);
+    }
 }
\ No newline at end of file
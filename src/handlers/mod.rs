@@ -6,5 +6,5 @@ mod async_command_processor;
 pub use command_handlers::{handle_create_person, handle_person_command};
 pub use async_command_processor::{
     AsyncCommandProcessor, PersonCommandProcessor, CommandResult,
-    AsyncComponentCommandHandler
+    AsyncComponentCommandHandler, MonitoredCommandProcessor,
 };
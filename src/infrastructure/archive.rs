@@ -0,0 +1,301 @@
+//! Retention and cold archival of old events
+//!
+//! [`TieredEventStore`] keeps recent events in a hot [`EventStore`] and moves
+//! older ones to an [`ArchiveStore`]. Only events already covered by a recent
+//! snapshot are archived, so loading from the latest snapshot plus the hot
+//! tail never touches the archive; only a full-history read (`get_events`,
+//! or `get_events_from_version` reaching below the hot tail) does.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use cim_domain::DomainResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::event_store::{EventEnvelope, EventStore};
+use super::persistence::{PersonSnapshot, SnapshotStore};
+use crate::aggregate::PersonId;
+use crate::events::PersonEvent;
+use crate::nats::MessageIdentity;
+
+/// Cold storage for archived events
+#[async_trait]
+pub trait ArchiveStore: Send + Sync {
+    /// Store archived events; events already archived are ignored
+    async fn archive(&self, aggregate_id: PersonId, events: Vec<EventEnvelope>) -> DomainResult<()>;
+
+    /// All archived events for an aggregate, in sequence order
+    async fn get_archived(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>>;
+}
+
+/// In-memory archive store for testing
+pub struct InMemoryArchiveStore {
+    events: Arc<RwLock<HashMap<PersonId, Vec<EventEnvelope>>>>,
+}
+
+impl Default for InMemoryArchiveStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryArchiveStore {
+    pub fn new() -> Self {
+        Self {
+            events: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl ArchiveStore for InMemoryArchiveStore {
+    async fn archive(&self, aggregate_id: PersonId, events: Vec<EventEnvelope>) -> DomainResult<()> {
+        let mut store = self.events.write().await;
+        let archived = store.entry(aggregate_id).or_default();
+        let newest = archived.last().map(|e| e.sequence).unwrap_or(0);
+        archived.extend(events.into_iter().filter(|e| e.sequence > newest));
+        Ok(())
+    }
+
+    async fn get_archived(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
+        let store = self.events.read().await;
+        Ok(store.get(&aggregate_id).cloned().unwrap_or_default())
+    }
+}
+
+/// When events move from hot storage to the archive
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+    /// Events younger than this stay hot
+    pub hot_retention: Duration,
+    /// Persons whose latest snapshot is older than this are left alone
+    pub max_snapshot_age: Duration,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            hot_retention: Duration::days(90),
+            max_snapshot_age: Duration::days(7),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    pub fn new(hot_retention: Duration) -> Self {
+        Self {
+            hot_retention,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_snapshot_age(mut self, max_snapshot_age: Duration) -> Self {
+        self.max_snapshot_age = max_snapshot_age;
+        self
+    }
+}
+
+/// Event store that splits each stream between hot storage and an archive
+pub struct TieredEventStore {
+    hot: Arc<dyn EventStore>,
+    archive: Arc<dyn ArchiveStore>,
+}
+
+impl TieredEventStore {
+    pub fn new(hot: Arc<dyn EventStore>, archive: Arc<dyn ArchiveStore>) -> Self {
+        Self { hot, archive }
+    }
+
+    /// Move eligible events of every snapshotted person to the archive,
+    /// returning how many events were archived
+    pub async fn apply_retention(
+        &self,
+        snapshots: &dyn SnapshotStore,
+        policy: &RetentionPolicy,
+    ) -> DomainResult<usize> {
+        self.apply_retention_at(snapshots, policy, Utc::now()).await
+    }
+
+    async fn apply_retention_at(
+        &self,
+        snapshots: &dyn SnapshotStore,
+        policy: &RetentionPolicy,
+        now: DateTime<Utc>,
+    ) -> DomainResult<usize> {
+        let cutoff = now - policy.hot_retention;
+        let mut archived = 0;
+
+        for aggregate_id in snapshots.list_aggregate_ids().await? {
+            let Some(snapshot) = snapshots.get_latest_snapshot(aggregate_id).await? else {
+                continue;
+            };
+            if snapshot.timestamp < now - policy.max_snapshot_age {
+                continue;
+            }
+
+            // Archive the oldest events up to the snapshot, stopping at the
+            // first one that is still recent
+            let candidates: Vec<EventEnvelope> = self.hot.get_events(aggregate_id).await?
+                .into_iter()
+                .take_while(|e| e.sequence <= snapshot.version && e.timestamp < cutoff)
+                .collect();
+            let Some(through) = candidates.last().map(|e| e.sequence) else {
+                continue;
+            };
+
+            // Copy before evicting: a crash in between leaves the events in
+            // both tiers, and the archive ignores the duplicates next time
+            self.archive.archive(aggregate_id, candidates).await?;
+            archived += self.hot.evict_events(aggregate_id, through).await?.len();
+        }
+
+        Ok(archived)
+    }
+}
+
+#[async_trait]
+impl EventStore for TieredEventStore {
+    async fn append_events(
+        &self,
+        aggregate_id: PersonId,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+    ) -> DomainResult<()> {
+        self.hot.append_events(aggregate_id, events, expected_version).await
+    }
+
+    async fn append_events_caused_by(
+        &self,
+        aggregate_id: PersonId,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+        cause: &MessageIdentity,
+    ) -> DomainResult<()> {
+        self.hot.append_events_caused_by(aggregate_id, events, expected_version, cause).await
+    }
+
+    async fn get_events(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
+        self.get_events_from_version(aggregate_id, 0).await
+    }
+
+    async fn get_events_from_version(
+        &self,
+        aggregate_id: PersonId,
+        from_version: u64,
+    ) -> DomainResult<Vec<EventEnvelope>> {
+        let hot = self.hot.get_events_from_version(aggregate_id, from_version).await?;
+        let oldest_hot = self.hot.get_events(aggregate_id).await?.first().map(|e| e.sequence);
+        if oldest_hot.is_some_and(|oldest| from_version >= oldest) {
+            return Ok(hot);
+        }
+
+        let mut events: Vec<EventEnvelope> = self.archive.get_archived(aggregate_id).await?
+            .into_iter()
+            .filter(|e| e.sequence >= from_version)
+            .filter(|e| match oldest_hot {
+                Some(oldest) => e.sequence < oldest,
+                None => true,
+            })
+            .collect();
+        events.extend(hot);
+        Ok(events)
+    }
+
+    async fn get_current_version(&self, aggregate_id: PersonId) -> DomainResult<u64> {
+        self.hot.get_current_version(aggregate_id).await
+    }
+
+    async fn latest_snapshot(&self, aggregate_id: PersonId) -> DomainResult<Option<PersonSnapshot>> {
+        self.hot.latest_snapshot(aggregate_id).await
+    }
+
+    async fn list_aggregate_ids(&self) -> DomainResult<Vec<PersonId>> {
+        // Eviction always leaves the newest event in the hot tier
+        self.hot.list_aggregate_ids().await
+    }
+
+    fn records_outbox(&self) -> bool {
+        self.hot.records_outbox()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::Person;
+    use crate::events::{PersonCreated, PersonUpdated};
+    use crate::infrastructure::{InMemoryEventStore, InMemorySnapshotStore, PersonRepository, PersonSnapshot};
+    use crate::value_objects::PersonName;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts how often the archive is read
+    #[derive(Default)]
+    struct CountingArchive {
+        inner: InMemoryArchiveStore,
+        reads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ArchiveStore for CountingArchive {
+        async fn archive(&self, aggregate_id: PersonId, events: Vec<EventEnvelope>) -> DomainResult<()> {
+            self.inner.archive(aggregate_id, events).await
+        }
+
+        async fn get_archived(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.get_archived(aggregate_id).await
+        }
+    }
+
+    fn renamed(person_id: PersonId, given: &str) -> PersonEvent {
+        PersonEvent::PersonUpdated(PersonUpdated {
+            person_id,
+            name: PersonName::new(given.to_string(), "Lovelace".to_string()),
+            updated_at: Utc::now(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_archived_events_only_load_for_full_history() {
+        let archive = Arc::new(CountingArchive::default());
+        let store = Arc::new(TieredEventStore::new(Arc::new(InMemoryEventStore::new()), archive.clone()));
+        let snapshots = Arc::new(InMemorySnapshotStore::new());
+        let repository = PersonRepository::new(store.clone(), snapshots.clone(), 100);
+
+        let person_id = PersonId::new();
+        let mut events = vec![PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        })];
+        events.extend(["Augusta", "Ada"].map(|given| renamed(person_id, given)));
+        store.append_events(person_id, events, None).await.unwrap();
+
+        let snapshot_state = repository.load(person_id).await.unwrap().unwrap();
+        snapshots.save_snapshot(PersonSnapshot {
+            aggregate_id: person_id,
+            version: 3,
+            state: snapshot_state,
+            timestamp: Utc::now(),
+        }).await.unwrap();
+        store.append_events(person_id, vec![renamed(person_id, "Countess")], Some(3)).await.unwrap();
+        let reads_before = archive.reads.load(Ordering::SeqCst);
+
+        let policy = RetentionPolicy::new(Duration::zero());
+        let archived = store.apply_retention_at(snapshots.as_ref(), &policy, Utc::now() + Duration::seconds(1)).await.unwrap();
+        assert_eq!(archived, 3);
+        assert_eq!(store.get_current_version(person_id).await.unwrap(), 4);
+
+        // Snapshot plus hot tail
+        let person: Person = repository.load(person_id).await.unwrap().unwrap();
+        assert_eq!(person.core_identity.legal_name.components.given_names, vec!["Countess".to_string()]);
+        assert_eq!(archive.reads.load(Ordering::SeqCst), reads_before);
+
+        // Full history reaches into the archive
+        let history = store.get_events(person_id).await.unwrap();
+        assert_eq!(history.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(archive.reads.load(Ordering::SeqCst), reads_before + 1);
+    }
+}
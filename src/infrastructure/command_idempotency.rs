@@ -0,0 +1,123 @@
+//! Deduplication of command submissions by idempotency key
+//!
+//! Clients resubmit commands when a reply times out, and without a key the
+//! retry runs the command again: a second `CreatePerson` is rejected and a
+//! second `AddTag` for another tag emits more events.
+//! [`IdempotencyKeys`] remembers the outcome of each keyed submission for a
+//! TTL, so a duplicate gets the original events back instead. Keys are
+//! scoped to the person the command targets; the same key may be reused for
+//! different people. Concurrent submissions with the same key wait for the
+//! first, and a failed submission is not remembered, so it can be retried.
+//! A key reused for a different command within the TTL is rejected rather
+//! than answered with the other command's outcome.
+
+use chrono::{DateTime, Duration, Utc};
+use cim_domain::{DomainError, DomainResult};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+use crate::aggregate::PersonId;
+use crate::commands::PersonCommand;
+use crate::events::PersonEvent;
+
+/// What a command did, handed to its first submission and every duplicate
+#[derive(Debug, Clone)]
+pub struct CommandOutcome {
+    pub aggregate_id: PersonId,
+    /// Aggregate version after the command's events
+    pub version: u64,
+    pub events: Vec<PersonEvent>,
+}
+
+struct SeenKey {
+    seen_at: DateTime<Utc>,
+    /// Hash of the command first submitted with the key
+    fingerprint: blake3::Hash,
+    outcome: Arc<OnceCell<CommandOutcome>>,
+}
+
+/// Hash of a command's JSON form
+fn fingerprint(command: &PersonCommand) -> DomainResult<blake3::Hash> {
+    let json = serde_json::to_vec(command)
+        .map_err(|e| DomainError::SerializationError(e.to_string()))?;
+    Ok(blake3::hash(&json))
+}
+
+/// Recently seen idempotency keys, per person
+pub struct IdempotencyKeys {
+    ttl: Duration,
+    seen: Mutex<HashMap<PersonId, HashMap<String, SeenKey>>>,
+}
+
+impl Default for IdempotencyKeys {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdempotencyKeys {
+    pub fn new() -> Self {
+        Self {
+            ttl: Duration::minutes(5),
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How long a key's outcome is remembered
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Run `execute` for `command` unless `key` was already seen for its person within the TTL
+    ///
+    /// Without a key the command always runs. Reusing a key for a different
+    /// command is a validation error.
+    pub async fn run<F, Fut>(
+        &self,
+        command: &PersonCommand,
+        key: Option<&str>,
+        execute: F,
+    ) -> DomainResult<CommandOutcome>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = DomainResult<CommandOutcome>>,
+    {
+        let Some(key) = key else {
+            return execute().await;
+        };
+        let slot = self.slot(command.aggregate_id(), key, fingerprint(command)?)?;
+        Ok(slot.get_or_try_init(execute).await?.clone())
+    }
+
+    /// The outcome slot for a key, dropping expired keys first
+    fn slot(
+        &self,
+        aggregate_id: PersonId,
+        key: &str,
+        fingerprint: blake3::Hash,
+    ) -> DomainResult<Arc<OnceCell<CommandOutcome>>> {
+        let now = Utc::now();
+        let mut seen = self.seen.lock().expect("idempotency keys poisoned");
+        seen.retain(|_, keys| {
+            keys.retain(|_, seen| now - seen.seen_at < self.ttl);
+            !keys.is_empty()
+        });
+        let seen_key = seen.entry(aggregate_id)
+            .or_default()
+            .entry(key.to_string())
+            .or_insert_with(|| SeenKey {
+                seen_at: now,
+                fingerprint,
+                outcome: Arc::new(OnceCell::new()),
+            });
+        if seen_key.fingerprint != fingerprint {
+            return Err(DomainError::ValidationError(format!(
+                "Idempotency key {key} was already used for a different command"
+            )));
+        }
+        Ok(seen_key.outcome.clone())
+    }
+}
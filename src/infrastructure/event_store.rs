@@ -1,14 +1,22 @@
 //! Event Store implementation for Person domain
+//!
+//! Long streams can be compacted: [`InMemoryEventStore::compact`] folds a
+//! prefix of a stream into a snapshot and drops those events. Loading through
+//! [`load_aggregate`] starts from the store's latest snapshot and applies only
+//! the remaining tail, so callers don't see a difference.
 
 use async_trait::async_trait;
 use cim_domain::{DomainError, DomainResult};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use tokio::sync::RwLock;
 use std::sync::Arc;
 
 use crate::aggregate::{Person, PersonId, EventSourced};
 use crate::events::PersonEvent;
+use crate::nats::MessageIdentity;
+use super::outbox::{OutboxEntry, OutboxStore};
+use super::persistence::{InMemorySnapshotStore, PersonSnapshot, SnapshotStore};
 
 /// Event wrapper with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +27,20 @@ pub struct EventEnvelope {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub correlation_id: String,
     pub causation_id: String,
+    /// Position in the backing stream, for stores that have one; set on load
+    #[serde(default)]
+    pub stream_sequence: Option<u64>,
+}
+
+/// Correlation and causation ids for an appended envelope
+///
+/// Events caused by a message share its correlation id and name it as their
+/// cause; without one each envelope gets fresh ids.
+pub(crate) fn envelope_ids(cause: Option<&MessageIdentity>) -> (String, String) {
+    match cause {
+        Some(cause) => (cause.correlation_id.to_string(), cause.message_id.to_string()),
+        None => (uuid::Uuid::now_v7().to_string(), uuid::Uuid::now_v7().to_string()),
+    }
 }
 
 /// Event Store trait for persistence
@@ -32,6 +54,20 @@ pub trait EventStore: Send + Sync {
         expected_version: Option<u64>,
     ) -> DomainResult<()>;
     
+    /// Append events caused by the message `cause`
+    ///
+    /// Stores that keep envelope ids record `cause`'s correlation id and its
+    /// message id as the causation id; others just append.
+    async fn append_events_caused_by(
+        &self,
+        aggregate_id: PersonId,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+        _cause: &MessageIdentity,
+    ) -> DomainResult<()> {
+        self.append_events(aggregate_id, events, expected_version).await
+    }
+
     /// Load all events for an aggregate
     async fn get_events(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>>;
     
@@ -44,11 +80,46 @@ pub trait EventStore: Send + Sync {
     
     /// Get current version of an aggregate
     async fn get_current_version(&self, aggregate_id: PersonId) -> DomainResult<u64>;
+
+    /// Remove events up to and including `through_sequence` from this store,
+    /// returning them so they can be archived (see [`super::archive`])
+    ///
+    /// The newest event is always kept so the stream's version stays known.
+    async fn evict_events(
+        &self,
+        _aggregate_id: PersonId,
+        _through_sequence: u64,
+    ) -> DomainResult<Vec<EventEnvelope>> {
+        Err(DomainError::generic("This event store does not support eviction"))
+    }
+
+    /// Snapshot standing in for a compacted prefix of the stream, if any
+    ///
+    /// Events up to the snapshot's version may no longer be stored, so
+    /// readers start from it and apply only the events after it.
+    async fn latest_snapshot(&self, _aggregate_id: PersonId) -> DomainResult<Option<PersonSnapshot>> {
+        Ok(None)
+    }
+
+    /// Every aggregate with at least one stored event
+    async fn list_aggregate_ids(&self) -> DomainResult<Vec<PersonId>> {
+        Err(DomainError::generic("This event store cannot list its aggregates"))
+    }
+
+    /// Whether appends also record publish intents in an outbox (see
+    /// [`super::outbox`])
+    fn records_outbox(&self) -> bool {
+        false
+    }
 }
 
 /// In-memory event store for testing
 pub struct InMemoryEventStore {
     events: Arc<RwLock<HashMap<PersonId, Vec<EventEnvelope>>>>,
+    /// Publish intents, written under the events lock (see [`super::outbox`])
+    outbox: Option<Arc<RwLock<VecDeque<OutboxEntry>>>>,
+    /// Where compacted prefixes are kept
+    snapshots: Option<Arc<InMemorySnapshotStore>>,
 }
 
 impl Default for InMemoryEventStore {
@@ -61,23 +132,95 @@ impl InMemoryEventStore {
     pub fn new() -> Self {
         Self {
             events: Arc::new(RwLock::new(HashMap::new())),
+            outbox: None,
+            snapshots: None,
         }
     }
-}
 
-#[async_trait]
-impl EventStore for InMemoryEventStore {
-    async fn append_events(
+    /// Store that also records an outbox entry for every appended event
+    pub fn with_outbox() -> Self {
+        Self {
+            events: Arc::new(RwLock::new(HashMap::new())),
+            outbox: Some(Arc::new(RwLock::new(VecDeque::new()))),
+            snapshots: None,
+        }
+    }
+
+    /// Keep compaction snapshots in `snapshots`, enabling [`Self::compact`]
+    pub fn with_snapshots(mut self, snapshots: Arc<InMemorySnapshotStore>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Fold a person's events through `up_to_version` into a snapshot and
+    /// drop them from the stream
+    ///
+    /// The events lock is held throughout, so appends issued meanwhile wait
+    /// and then run against the compacted stream; compaction never changes
+    /// the stream's version, so their version expectations still hold. The
+    /// newest event is always kept so the version stays known.
+    pub async fn compact(&self, person_id: PersonId, up_to_version: u64) -> DomainResult<PersonSnapshot> {
+        let snapshots = self.snapshots.as_ref().ok_or_else(|| {
+            DomainError::generic("Compaction needs a snapshot store; build the store with_snapshots")
+        })?;
+
+        let mut store = self.events.write().await;
+        let events = store.get_mut(&person_id)
+            .filter(|events| !events.is_empty())
+            .ok_or_else(|| DomainError::AggregateNotFound(format!("Person {person_id}")))?;
+        let current_version = events.last().map(|e| e.sequence).unwrap_or(0);
+        if up_to_version == 0 || up_to_version > current_version {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot compact person {person_id} through version {up_to_version}; stream is at {current_version}"
+            )));
+        }
+
+        let (base, base_version) = match snapshots.get_latest_snapshot(person_id).await? {
+            // Already compacted further than asked
+            Some(snapshot) if snapshot.version >= up_to_version => {
+                truncate_through(events, up_to_version);
+                return Ok(snapshot);
+            }
+            Some(snapshot) => (snapshot.state, snapshot.version),
+            None => {
+                let mut person = Person::empty();
+                person.id = person_id;
+                (person, 0)
+            }
+        };
+        if events.first().is_some_and(|e| e.sequence > base_version + 1) {
+            return Err(DomainError::ValidationError(format!(
+                "Events after version {base_version} of person {person_id} are no longer stored"
+            )));
+        }
+
+        let state = events.iter()
+            .filter(|e| e.sequence > base_version && e.sequence <= up_to_version)
+            .try_fold(base, |person, envelope| person.apply_event(&envelope.event))?;
+        let snapshot = PersonSnapshot {
+            aggregate_id: person_id,
+            version: up_to_version,
+            state,
+            timestamp: chrono::Utc::now(),
+        };
+        snapshots.save_snapshot(snapshot.clone()).await?;
+        truncate_through(events, up_to_version);
+
+        Ok(snapshot)
+    }
+
+    async fn append(
         &self,
         aggregate_id: PersonId,
         events: Vec<PersonEvent>,
         expected_version: Option<u64>,
+        cause: Option<&MessageIdentity>,
     ) -> DomainResult<()> {
         let mut store = self.events.write().await;
         let aggregate_events = store.entry(aggregate_id).or_insert_with(Vec::new);
         
         // Check expected version
-        let current_version = aggregate_events.len() as u64;
+        let current_version = aggregate_events.last().map(|e| e.sequence).unwrap_or(0);
         if let Some(expected) = expected_version {
             if expected != current_version {
                 return Err(DomainError::ConcurrencyConflict {
@@ -87,21 +230,63 @@ impl EventStore for InMemoryEventStore {
             }
         }
         
+        // Outbox entries are written while the events lock is held, so the
+        // append and its publish intents land together
+        let mut outbox = match &self.outbox {
+            Some(outbox) => Some(outbox.write().await),
+            None => None,
+        };
+
         // Append events
         for (i, event) in events.into_iter().enumerate() {
+            let (correlation_id, causation_id) = envelope_ids(cause);
             let envelope = EventEnvelope {
                 aggregate_id,
                 sequence: current_version + i as u64 + 1,
                 event,
                 timestamp: chrono::Utc::now(),
-                correlation_id: uuid::Uuid::now_v7().to_string(),
-                causation_id: uuid::Uuid::now_v7().to_string(),
+                correlation_id,
+                causation_id,
+                stream_sequence: None,
             };
+            if let Some(outbox) = outbox.as_mut() {
+                outbox.push_back(OutboxEntry::for_stored(&envelope));
+            }
             aggregate_events.push(envelope);
         }
         
         Ok(())
     }
+}
+
+/// Drop events up to and including `through`, always keeping the newest
+fn truncate_through(events: &mut Vec<EventEnvelope>, through: u64) -> Vec<EventEnvelope> {
+    let newest = events.last().map(|e| e.sequence).unwrap_or(0);
+    let through = through.min(newest.saturating_sub(1));
+    let split = events.partition_point(|e| e.sequence <= through);
+    events.drain(..split).collect()
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append_events(
+        &self,
+        aggregate_id: PersonId,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+    ) -> DomainResult<()> {
+        self.append(aggregate_id, events, expected_version, None).await
+    }
+
+    async fn append_events_caused_by(
+        &self,
+        aggregate_id: PersonId,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+        cause: &MessageIdentity,
+    ) -> DomainResult<()> {
+        self.append(aggregate_id, events, expected_version, Some(cause)).await
+    }
     
     async fn get_events(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
         let store = self.events.read().await;
@@ -120,7 +305,58 @@ impl EventStore for InMemoryEventStore {
     
     async fn get_current_version(&self, aggregate_id: PersonId) -> DomainResult<u64> {
         let store = self.events.read().await;
-        Ok(store.get(&aggregate_id).map(|e| e.len() as u64).unwrap_or(0))
+        Ok(store.get(&aggregate_id)
+            .and_then(|events| events.last())
+            .map(|e| e.sequence)
+            .unwrap_or(0))
+    }
+
+    async fn list_aggregate_ids(&self) -> DomainResult<Vec<PersonId>> {
+        let store = self.events.read().await;
+        Ok(store.iter()
+            .filter(|(_, events)| !events.is_empty())
+            .map(|(id, _)| *id)
+            .collect())
+    }
+
+    async fn evict_events(
+        &self,
+        aggregate_id: PersonId,
+        through_sequence: u64,
+    ) -> DomainResult<Vec<EventEnvelope>> {
+        let mut store = self.events.write().await;
+        let Some(events) = store.get_mut(&aggregate_id) else {
+            return Ok(Vec::new());
+        };
+        Ok(truncate_through(events, through_sequence))
+    }
+
+    async fn latest_snapshot(&self, aggregate_id: PersonId) -> DomainResult<Option<PersonSnapshot>> {
+        match &self.snapshots {
+            Some(snapshots) => snapshots.get_latest_snapshot(aggregate_id).await,
+            None => Ok(None),
+        }
+    }
+
+    fn records_outbox(&self) -> bool {
+        self.outbox.is_some()
+    }
+}
+
+#[async_trait]
+impl OutboxStore for InMemoryEventStore {
+    async fn pending(&self, limit: usize) -> DomainResult<Vec<OutboxEntry>> {
+        match &self.outbox {
+            Some(outbox) => Ok(outbox.read().await.iter().take(limit).cloned().collect()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn mark_published(&self, id: uuid::Uuid) -> DomainResult<()> {
+        if let Some(outbox) = &self.outbox {
+            outbox.write().await.retain(|entry| entry.id != id);
+        }
+        Ok(())
     }
 }
 
@@ -129,12 +365,20 @@ pub async fn load_aggregate(
     store: &dyn EventStore,
     aggregate_id: PersonId,
 ) -> DomainResult<Person> {
-    let events = store.get_events(aggregate_id).await?;
-
-    // Start with empty Person and apply all events (pure functional)
-    let mut aggregate = Person::empty();
-    aggregate.id = aggregate_id; // Set ID before applying events
+    // Start from the snapshot of any compacted prefix, else from an empty Person
+    let (aggregate, events) = match store.latest_snapshot(aggregate_id).await? {
+        Some(snapshot) => {
+            let tail = store.get_events_from_version(aggregate_id, snapshot.version + 1).await?;
+            (snapshot.state, tail)
+        }
+        None => {
+            let mut aggregate = Person::empty();
+            aggregate.id = aggregate_id; // Set ID before applying events
+            (aggregate, store.get_events(aggregate_id).await?)
+        }
+    };
 
+    // Apply the events (pure functional)
     let aggregate = events.into_iter().try_fold(aggregate, |agg, envelope| {
         agg.apply_event(&envelope.event)
     })?;
@@ -150,4 +394,56 @@ pub async fn save_aggregate_events(
     expected_version: Option<u64>,
 ) -> DomainResult<()> {
     store.append_events(aggregate_id, events, expected_version).await
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{PersonCreated, PersonUpdated};
+    use crate::value_objects::PersonName;
+    use chrono::Utc;
+
+    fn renamed(person_id: PersonId, n: usize) -> PersonEvent {
+        PersonEvent::PersonUpdated(PersonUpdated {
+            person_id,
+            name: PersonName::new(format!("Name{n}"), "Test".to_string()),
+            updated_at: Utc::now(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_compaction_is_transparent_to_loads() {
+        let store = InMemoryEventStore::new().with_snapshots(Arc::new(InMemorySnapshotStore::new()));
+        let person_id = PersonId::new();
+        let mut events = vec![PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Name0".to_string(), "Test".to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        })];
+        events.extend((1..100).map(|n| renamed(person_id, n)));
+        store.append_events(person_id, events, Some(0)).await.unwrap();
+
+        // An append racing the compaction is serialized with it either way
+        let (snapshot, appended) = tokio::join!(
+            store.compact(person_id, 90),
+            store.append_events(person_id, vec![renamed(person_id, 100)], Some(100)),
+        );
+        assert_eq!(snapshot.unwrap().version, 90);
+        appended.unwrap();
+
+        assert_eq!(store.get_events(person_id).await.unwrap().len(), 11);
+        assert_eq!(store.get_current_version(person_id).await.unwrap(), 101);
+        let person = load_aggregate(&store, person_id).await.unwrap();
+        assert_eq!(person.version, 101);
+        assert_eq!(person.core_identity.legal_name.full_name(), "Name100 Test");
+
+        // Compacting the whole stream keeps the newest event so the version is known
+        store.compact(person_id, 101).await.unwrap();
+        assert_eq!(store.get_events(person_id).await.unwrap().len(), 1);
+        assert_eq!(load_aggregate(&store, person_id).await.unwrap().version, 101);
+
+        assert!(store.append_events(person_id, vec![renamed(person_id, 0)], Some(90)).await.is_err());
+        assert!(store.compact(person_id, 500).await.is_err());
+    }
+}
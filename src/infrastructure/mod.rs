@@ -3,16 +3,33 @@
 pub mod event_store;
 pub mod persistence;
 pub mod nats_integration;
+pub mod command_idempotency;
+pub mod query_responder;
 // Component store deprecated - components belong in separate domains
 // pub mod component_store;
 pub mod streaming;
 pub mod retry;
 pub mod subscriptions;
+pub mod outbox;
+pub mod resilient_publisher;
+pub mod archive;
+pub mod processor_metrics;
 
 pub use event_store::*;
 pub use persistence::*;
 pub use nats_integration::*;
+pub use command_idempotency::{IdempotencyKeys, CommandOutcome};
+pub use query_responder::PersonQueryResponder;
 // pub use component_store::*;
 pub use streaming::{StreamingConfig, StreamingClient, EventMetadata};
-pub use retry::{RetryHandler, CircuitBreaker};
-pub use subscriptions::{SubscriptionManager, StreamingEventHandler}; 
\ No newline at end of file
+pub use retry::{RetryHandler, CircuitBreaker, CircuitStatus};
+pub use subscriptions::{
+    SubscriptionManager, StreamingEventHandler, CheckpointStore, InMemoryCheckpointStore,
+    SubscriptionCheckpoints,
+};
+pub use outbox::{OutboxEntry, OutboxStore, OutboxPublisher, OutboxRelay, JetStreamOutboxPublisher};
+pub use resilient_publisher::{ResilientEventPublisher, PublishError};
+pub use archive::{ArchiveStore, InMemoryArchiveStore, RetentionPolicy, TieredEventStore};
+pub use processor_metrics::{
+    ProcessorMetrics, InMemoryProcessorMetrics, QueueMonitor, QueueStats, QueueTicket,
+};
@@ -4,13 +4,18 @@ use async_nats::{Client, jetstream};
 use async_trait::async_trait;
 use cim_domain::{DomainError, DomainResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use futures::StreamExt;
+use tracing::Instrument;
 
 use crate::aggregate::{Person, PersonId};
 use crate::events::PersonEvent;
 use crate::commands::PersonCommand;
-use super::event_store::{EventStore, EventEnvelope};
+use crate::queries::PersonQuery;
+use crate::nats::{command_span, MessageIdentity, PersonMessageEnvelope};
+use super::event_store::{envelope_ids, EventStore, EventEnvelope};
+use super::command_idempotency::{CommandOutcome, IdempotencyKeys};
 
 /// NATS subject patterns for Person domain
 pub struct PersonSubjects;
@@ -26,6 +31,16 @@ impl PersonSubjects {
         "person.events.>"
     }
     
+    /// Queries subject pattern
+    pub fn queries() -> &'static str {
+        "person.queries.>"
+    }
+    
+    /// Query subject for a query, named after its `query_type`
+    pub fn query_for(query: &PersonQuery) -> String {
+        format!("person.queries.{}", query.query_type())
+    }
+    
     /// Command subject for specific aggregate
     pub fn command_for(aggregate_id: PersonId) -> String {
         format!("person.commands.{aggregate_id}")
@@ -35,6 +50,27 @@ impl PersonSubjects {
     pub fn event_for(aggregate_id: PersonId, event_type: &str) -> String {
         format!("person.events.{aggregate_id}.{event_type}")
     }
+
+    /// Subject token for an event's type
+    pub fn event_type(event: &PersonEvent) -> &'static str {
+        match event {
+            PersonEvent::PersonCreated(_) => "created",
+            PersonEvent::PersonUpdated(_) => "updated",
+            PersonEvent::NameUpdated(_) => "name_updated",
+            PersonEvent::BirthDateSet(_) => "birth_date_set",
+            PersonEvent::DeathRecorded(_) => "death_recorded",
+            PersonEvent::PersonDeactivated(_) => "deactivated",
+            PersonEvent::PersonReactivated(_) => "reactivated",
+            PersonEvent::PersonMergedInto(_) => "merged",
+            PersonEvent::AttributeRecorded(_) => "attribute_recorded",
+            PersonEvent::AttributeUpdated(_) => "attribute_updated",
+            PersonEvent::AttributeInvalidated(_) => "attribute_invalidated",
+            PersonEvent::LifeEventRecorded(_) => "life_event_recorded",
+            PersonEvent::ConsentRecorded(_) => "consent_recorded",
+            PersonEvent::TagAdded(_) => "tag_added",
+            PersonEvent::TagRemoved(_) => "tag_removed",
+        }
+    }
 }
 
 /// NATS-based event store implementation
@@ -96,15 +132,118 @@ impl NatsEventStore {
             stream_name,
         })
     }
+
+    /// Load a person's events from JetStream stream sequence `from_sequence` on
+    ///
+    /// Each returned envelope carries its `stream_sequence`; a consumer that
+    /// checkpoints the last one it handled resumes from that value plus one.
+    /// If the stream no longer holds messages that far back, the events in
+    /// between may be lost and the call fails with [`SequenceUnavailable`].
+    pub async fn load_events_from(
+        &self,
+        person_id: PersonId,
+        from_sequence: u64,
+    ) -> DomainResult<Vec<EventEnvelope>> {
+        let start_sequence = from_sequence.max(1);
+        let mut stream = self.jetstream.get_stream(&self.stream_name).await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to get stream: {e}"),
+            })?;
+        let first_available = stream.info().await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to get stream info: {e}"),
+            })?
+            .state
+            .first_sequence;
+        if start_sequence < first_available {
+            return Err(SequenceUnavailable { person_id, requested: from_sequence, first_available }.into());
+        }
+
+        let consumer = stream
+            .create_consumer(jetstream::consumer::pull::Config {
+                filter_subject: format!("person.events.{person_id}.>"),
+                deliver_policy: jetstream::consumer::DeliverPolicy::ByStartSequence { start_sequence },
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to create consumer: {e}"),
+            })?;
+
+        let mut events = Vec::new();
+        loop {
+            let mut batch = consumer.fetch().max_messages(REPLAY_BATCH_SIZE).messages().await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "NATS JetStream".to_string(),
+                    message: format!("Failed to fetch messages: {e}"),
+                })?;
+
+            let mut fetched = 0;
+            while let Some(msg) = batch.next().await {
+                let msg = msg.map_err(|e| DomainError::ExternalServiceError {
+                    service: "NATS JetStream".to_string(),
+                    message: format!("Failed to get message: {e}"),
+                })?;
+                let stream_sequence = msg.info()
+                    .map_err(|e| DomainError::ExternalServiceError {
+                        service: "NATS JetStream".to_string(),
+                        message: format!("Message has no JetStream info: {e}"),
+                    })?
+                    .stream_sequence;
+
+                let mut envelope: EventEnvelope = serde_json::from_slice(&msg.payload)
+                    .map_err(|e| DomainError::SerializationError(e.to_string()))?;
+                envelope.stream_sequence = Some(stream_sequence);
+                events.push(envelope);
+                fetched += 1;
+
+                msg.ack().await
+                    .map_err(|e| DomainError::ExternalServiceError {
+                        service: "NATS JetStream".to_string(),
+                        message: format!("Failed to ack message: {e}"),
+                    })?;
+            }
+
+            if fetched < REPLAY_BATCH_SIZE {
+                break;
+            }
+        }
+
+        Ok(events)
+    }
 }
 
-#[async_trait]
-impl EventStore for NatsEventStore {
-    async fn append_events(
+/// Messages fetched per round trip when replaying a subject
+const REPLAY_BATCH_SIZE: usize = 256;
+
+/// The stream no longer holds the messages a replay asked for
+///
+/// Happens when retention limits or a purge removed messages at or after the
+/// requested sequence; the caller has to rebuild from a snapshot instead.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Sequence unavailable: events for person {person_id} requested from stream sequence {requested}, but the stream starts at {first_available}")]
+pub struct SequenceUnavailable {
+    pub person_id: PersonId,
+    pub requested: u64,
+    pub first_available: u64,
+}
+
+impl From<SequenceUnavailable> for DomainError {
+    fn from(e: SequenceUnavailable) -> Self {
+        DomainError::ValidationError(e.to_string())
+    }
+}
+
+impl NatsEventStore {
+    async fn append(
         &self,
         aggregate_id: PersonId,
         events: Vec<PersonEvent>,
         expected_version: Option<u64>,
+        cause: Option<&MessageIdentity>,
     ) -> DomainResult<()> {
         // Check expected version if provided
         if let Some(expected) = expected_version {
@@ -119,30 +258,20 @@ impl EventStore for NatsEventStore {
         
         // Publish each event
         for (index, event) in events.into_iter().enumerate() {
-            let event_type = match &event {
-                PersonEvent::PersonCreated(_) => "created",
-                PersonEvent::PersonUpdated(_) => "updated",
-                PersonEvent::NameUpdated(_) => "name_updated",
-                PersonEvent::BirthDateSet(_) => "birth_date_set",
-                PersonEvent::DeathRecorded(_) => "death_recorded",
-                PersonEvent::PersonDeactivated(_) => "deactivated",
-                PersonEvent::PersonReactivated(_) => "reactivated",
-                PersonEvent::PersonMergedInto(_) => "merged",
-                PersonEvent::AttributeRecorded(_) => "attribute_recorded",
-                PersonEvent::AttributeUpdated(_) => "attribute_updated",
-                PersonEvent::AttributeInvalidated(_) => "attribute_invalidated",
-            };
+            let event_type = PersonSubjects::event_type(&event);
             
             let subject = PersonSubjects::event_for(aggregate_id, event_type);
             let sequence = self.get_current_version(aggregate_id).await? + index as u64 + 1;
+            let (correlation_id, causation_id) = envelope_ids(cause);
             
             let envelope = EventEnvelope {
                 aggregate_id,
                 sequence,
                 event,
                 timestamp: chrono::Utc::now(),
-                correlation_id: uuid::Uuid::now_v7().to_string(),
-                causation_id: uuid::Uuid::now_v7().to_string(),
+                correlation_id,
+                causation_id,
+                stream_sequence: None,
             };
             
             let payload = serde_json::to_vec(&envelope)
@@ -157,6 +286,28 @@ impl EventStore for NatsEventStore {
         
         Ok(())
     }
+}
+
+#[async_trait]
+impl EventStore for NatsEventStore {
+    async fn append_events(
+        &self,
+        aggregate_id: PersonId,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+    ) -> DomainResult<()> {
+        self.append(aggregate_id, events, expected_version, None).await
+    }
+
+    async fn append_events_caused_by(
+        &self,
+        aggregate_id: PersonId,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+        cause: &MessageIdentity,
+    ) -> DomainResult<()> {
+        self.append(aggregate_id, events, expected_version, Some(cause)).await
+    }
     
     async fn get_events(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
         self.get_events_from_version(aggregate_id, 0).await
@@ -217,17 +368,57 @@ impl EventStore for NatsEventStore {
         let events = self.get_events(aggregate_id).await?;
         Ok(events.len() as u64)
     }
+
+    async fn list_aggregate_ids(&self) -> DomainResult<Vec<PersonId>> {
+        let stream = self.jetstream.get_stream(self.stream_name.as_str()).await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to get stream: {e}"),
+            })?;
+        let mut subjects = stream.info_with_subjects("person.events.>").await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to list stream subjects: {e}"),
+            })?;
+
+        // Subjects are person.events.{person_id}.{event_type}
+        let mut ids = std::collections::HashSet::new();
+        while let Some(subject) = subjects.next().await {
+            let (subject, _count) = subject.map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to list stream subjects: {e}"),
+            })?;
+            if let Some(id) = subject.split('.').nth(2).and_then(|id| uuid::Uuid::parse_str(id).ok()) {
+                ids.insert(PersonId::from_uuid(id));
+            }
+        }
+        Ok(ids.into_iter().collect())
+    }
 }
 
+/// Header carrying a command's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
 /// Command handler service
 pub struct PersonCommandHandler {
     repository: Arc<super::persistence::PersonRepository>,
     client: Client,
+    idempotency_keys: IdempotencyKeys,
 }
 
 impl PersonCommandHandler {
     pub fn new(repository: Arc<super::persistence::PersonRepository>, client: Client) -> Self {
-        Self { repository, client }
+        Self {
+            repository,
+            client,
+            idempotency_keys: IdempotencyKeys::new(),
+        }
+    }
+
+    /// Remember idempotency keys for `ttl` instead of 5 minutes
+    pub fn with_idempotency_ttl(mut self, ttl: chrono::Duration) -> Self {
+        self.idempotency_keys = IdempotencyKeys::new().with_ttl(ttl);
+        self
     }
     
     /// Start listening for commands
@@ -243,12 +434,20 @@ impl PersonCommandHandler {
         while let Some(msg) = subscription.next().await {
             let command: PersonCommand = serde_json::from_slice(&msg.payload)
                 .map_err(|e| DomainError::SerializationError(e.to_string()))?;
-            
-            // Handle command
-            match self.handle_command(command).await {
-                Ok(response) => {
+            let headers: HashMap<String, String> = msg.headers.iter()
+                .flat_map(|headers| headers.iter())
+                .filter_map(|(name, values)| Some((name.to_string(), values.first()?.as_str().to_string())))
+                .collect();
+            let idempotency_key = headers.get(IDEMPOTENCY_KEY_HEADER).cloned();
+
+            // Handle command under the identity it was sent with
+            let identity = MessageIdentity::from_headers(&headers);
+            let mut envelope = PersonMessageEnvelope::new(command, msg.subject.to_string(), identity);
+            envelope.headers = headers;
+            match self.handle_envelope(envelope, idempotency_key).await {
+                Ok(outcome) => {
                     if let Some(reply) = msg.reply {
-                        let payload = serde_json::to_vec(&response)
+                        let payload = serde_json::to_vec(&CommandResponse::from(&outcome))
                             .map_err(|e| DomainError::SerializationError(e.to_string()))?;
                         
                         self.client.publish(reply, payload.into()).await
@@ -268,37 +467,204 @@ impl PersonCommandHandler {
     }
     
     /// Handle a single command
-    async fn handle_command(&self, command: PersonCommand) -> DomainResult<CommandResponse> {
-        let aggregate_id = command.aggregate_id();
-        
-        // Load or create aggregate
-        let person = match self.repository.load(aggregate_id).await? {
-            Some(p) => p,
-            None => {
-                if matches!(command, PersonCommand::CreatePerson(_)) {
-                    Person::empty()
-                } else {
-                    return Err(DomainError::AggregateNotFound(format!("Person {aggregate_id}")));
-                }
+    ///
+    /// A command submitted again with the same `idempotency_key` for the same
+    /// person within the TTL is not executed; the first submission's events
+    /// are returned instead. Another command under a key already used for the
+    /// person is rejected.
+    pub async fn handle(
+        &self,
+        command: PersonCommand,
+        idempotency_key: Option<String>,
+    ) -> DomainResult<CommandOutcome> {
+        let subject = PersonSubjects::command_for(command.aggregate_id());
+        let envelope = PersonMessageEnvelope::new(command, subject, MessageIdentity::new());
+        self.handle_envelope(envelope, idempotency_key).await
+    }
+
+    /// Handle a command inside a [`command_span`] built from its envelope
+    ///
+    /// Logs emitted while loading, handling and saving carry the envelope's
+    /// correlation and causation ids, and the stored events are recorded as
+    /// caused by the envelope's message.
+    pub async fn handle_envelope(
+        &self,
+        envelope: PersonMessageEnvelope<PersonCommand>,
+        idempotency_key: Option<String>,
+    ) -> DomainResult<CommandOutcome> {
+        let span = command_span(&envelope);
+        let command = envelope.payload;
+        let identity = envelope.identity;
+        async move {
+            let result = self.idempotency_keys
+                .run(&command, idempotency_key.as_deref(), || {
+                    execute_command_caused_by(&self.repository, command.clone(), &identity)
+                })
+                .await;
+            match &result {
+                Ok(outcome) => tracing::info!(version = outcome.version, events = outcome.events.len(), "Command handled"),
+                Err(e) => tracing::warn!(error = %e, "Command failed"),
             }
-        };
+            result
+        }
+        .instrument(span)
+        .await
+    }
 
-        // Handle command using formal Aggregate trait (pure functional)
-        use cim_domain::formal_domain::Aggregate;
-        let expected_version = if person.version == 0 { None } else { Some(person.version) };
-        let (person, events) = person.handle(command)?;
+    /// Handle several commands for one person as a unit
+    ///
+    /// Each command sees the state left by the ones before it, and all events
+    /// are saved in a single append. If any command fails, nothing is saved
+    /// and the error names the index of the failing command.
+    pub async fn handle_batch(
+        &self,
+        person_id: PersonId,
+        commands: Vec<PersonCommand>,
+    ) -> DomainResult<Vec<PersonEvent>> {
+        check_merge_targets(&self.repository, &commands).await?;
+        let loaded = self.repository.load(person_id).await?;
+        let expected_version = loaded.as_ref().map_or(0, |p| p.version);
+        let (person, events) = fold_batch(loaded, person_id, commands)?;
 
-        // Save events
-        self.repository.save(&person, events.clone(), expected_version).await?;
+        if !events.is_empty() {
+            carry_over_merges(&self.repository, &person, &events).await?;
+            self.repository.save_with_expected_version(&person, events.clone(), expected_version).await?;
+        }
 
-        Ok(CommandResponse {
-            aggregate_id,
-            version: person.version,
-            events_generated: events.len(),
-        })
+        Ok(events)
     }
 }
 
+/// Load the command's aggregate, handle the command and save its events
+pub(crate) async fn execute_command(
+    repository: &super::persistence::PersonRepository,
+    command: PersonCommand,
+) -> DomainResult<CommandOutcome> {
+    execute_command_caused_by(repository, command, &MessageIdentity::new()).await
+}
+
+/// [`execute_command`] for a command delivered as the message `cause`
+///
+/// The saved events carry `cause`'s correlation id and name its message id
+/// as their causation id.
+pub(crate) async fn execute_command_caused_by(
+    repository: &super::persistence::PersonRepository,
+    command: PersonCommand,
+    cause: &MessageIdentity,
+) -> DomainResult<CommandOutcome> {
+    let aggregate_id = command.aggregate_id();
+    
+    // Load or create aggregate
+    let person = match repository.load(aggregate_id).await? {
+        Some(p) => p,
+        None => {
+            if matches!(command, PersonCommand::CreatePerson(_)) {
+                Person::empty()
+            } else {
+                return Err(DomainError::AggregateNotFound(format!("Person {aggregate_id}")));
+            }
+        }
+    };
+
+    check_merge_targets(repository, std::slice::from_ref(&command)).await?;
+
+    // Handle command using formal Aggregate trait (pure functional)
+    use cim_domain::formal_domain::Aggregate;
+    let expected_version = person.version;
+    let (person, events) = person.handle(command)?;
+
+    carry_over_merges(repository, &person, &events).await?;
+
+    // Save events, failing if another handler appended since the load
+    repository.save_caused_by(&person, events.clone(), expected_version, cause).await?;
+
+    Ok(CommandOutcome {
+        aggregate_id,
+        version: person.version,
+        events,
+    })
+}
+
+/// Fail before anything is saved if a merge targets a person that does not
+/// exist or can no longer take attributes
+async fn check_merge_targets(
+    repository: &super::persistence::PersonRepository,
+    commands: &[PersonCommand],
+) -> DomainResult<()> {
+    for command in commands {
+        if let PersonCommand::MergePersons(merge) = command {
+            let target = repository.load(merge.target_person_id).await?
+                .ok_or_else(|| DomainError::AggregateNotFound(format!("Person {}", merge.target_person_id)))?;
+            if !target.is_active() {
+                return Err(DomainError::ValidationError(format!(
+                    "Cannot merge into person {}: it is not active",
+                    merge.target_person_id
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Move the attributes of every person merged by `events` onto the target
+///
+/// Runs before the source's events are saved, so a carry-over that fails
+/// leaves the source unmerged and the whole merge can be retried; a retry
+/// only carries what the target still lacks. The target's events go to its
+/// own stream.
+async fn carry_over_merges(
+    repository: &super::persistence::PersonRepository,
+    source: &Person,
+    events: &[PersonEvent],
+) -> DomainResult<()> {
+    for event in events {
+        if let PersonEvent::PersonMergedInto(merge) = event {
+            crate::services::MergeService::carry_over(repository, source, merge).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Run a batch of commands against an evolving aggregate without persisting
+///
+/// `person` is the stored aggregate, or `None` if the person does not exist
+/// yet; in that case the batch has to start with `CreatePerson`.
+fn fold_batch(
+    person: Option<Person>,
+    person_id: PersonId,
+    commands: Vec<PersonCommand>,
+) -> DomainResult<(Person, Vec<PersonEvent>)> {
+    use cim_domain::formal_domain::Aggregate;
+
+    let mut exists = person.is_some();
+    let mut person = person.unwrap_or_else(Person::empty);
+    let mut events = Vec::new();
+
+    for (index, command) in commands.into_iter().enumerate() {
+        let target = command.aggregate_id();
+        if target != person_id {
+            return Err(DomainError::ValidationError(format!(
+                "Batch command {index} targets person {target}, batch is for {person_id}"
+            )));
+        }
+        let creates = matches!(command, PersonCommand::CreatePerson(_));
+        if !exists && !creates {
+            return Err(DomainError::ValidationError(format!(
+                "Batch command {index} failed: person {person_id} does not exist"
+            )));
+        }
+
+        let (next, emitted) = person
+            .handle(command)
+            .map_err(|e| DomainError::ValidationError(format!("Batch command {index} failed: {e}")))?;
+        person = next;
+        exists |= creates;
+        events.extend(emitted);
+    }
+
+    Ok((person, events))
+}
+
 /// Response to a command
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CommandResponse {
@@ -307,6 +673,16 @@ pub struct CommandResponse {
     pub events_generated: usize,
 }
 
+impl From<&CommandOutcome> for CommandResponse {
+    fn from(outcome: &CommandOutcome) -> Self {
+        Self {
+            aggregate_id: outcome.aggregate_id,
+            version: outcome.version,
+            events_generated: outcome.events.len(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,4 +702,190 @@ mod tests {
             format!("person.events.{person_id}.created")
         );
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_envelopes_stored_before_stream_sequences_still_load() {
+        let person_id = PersonId::new();
+        let payload = serde_json::json!({
+            "aggregate_id": person_id,
+            "sequence": 1,
+            "event": {"TagAdded": {"person_id": person_id, "tag": {"category": "sales", "name": "vip"}, "added_at": chrono::Utc::now()}},
+            "timestamp": chrono::Utc::now(),
+            "correlation_id": "corr",
+            "causation_id": "cause",
+        });
+
+        let envelope: EventEnvelope = serde_json::from_value(payload).unwrap();
+        assert_eq!(envelope.stream_sequence, None);
+
+        let err: DomainError = SequenceUnavailable { person_id, requested: 3, first_available: 10 }.into();
+        assert!(err.to_string().contains("Sequence unavailable"));
+        assert!(err.to_string().contains("starts at 10"));
+    }
+
+    #[test]
+    fn test_fold_batch_is_all_or_nothing() {
+        use crate::commands::{CreatePerson, RecordDeath};
+        use crate::value_objects::PersonName;
+
+        let person_id = PersonId::new();
+        let create = PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+        });
+        let death = PersonCommand::RecordDeath(RecordDeath {
+            person_id,
+            date_of_death: chrono::NaiveDate::from_ymd_opt(1852, 11, 27).unwrap(),
+        });
+
+        let (person, events) = fold_batch(None, person_id, vec![create.clone(), death.clone()]).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(person.id, person_id);
+        assert!(!person.is_active());
+
+        // The second death is checked against the state left by the first
+        let err = fold_batch(None, person_id, vec![create, death.clone(), death.clone()]).unwrap_err();
+        assert!(err.to_string().contains("command 2"));
+
+        let err = fold_batch(None, person_id, vec![death]).unwrap_err();
+        assert!(err.to_string().contains("command 0"));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_submission_with_same_key_creates_once() {
+        use crate::commands::CreatePerson;
+        use crate::infrastructure::{InMemoryEventStore, InMemorySnapshotStore, PersonRepository};
+        use crate::value_objects::PersonName;
+
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = PersonRepository::new(store.clone(), Arc::new(InMemorySnapshotStore::new()), 100);
+        let handler = PersonCommandHandler::new(Arc::new(repository), test_client().await);
+        let person_id = PersonId::new();
+        let create = PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+        });
+
+        let mut outcomes = Vec::new();
+        for _ in 0..2 {
+            outcomes.push(handler.handle(create.clone(), Some("create-ada".to_string())).await.unwrap());
+        }
+
+        let [first, retried] = &outcomes[..] else { unreachable!() };
+        assert_eq!(first.version, 1);
+        assert_eq!(retried.version, 1);
+        assert_eq!(retried.events.len(), 1);
+        assert_eq!(retried.events[0].occurred_at(), first.events[0].occurred_at());
+
+        let stored = store.get_events(person_id).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert!(matches!(stored[0].event, PersonEvent::PersonCreated(_)));
+    }
+
+    /// Client for handlers whose tests never publish; no server is needed
+    async fn test_client() -> Client {
+        async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_key_reused_for_another_command_is_rejected() {
+        use crate::commands::{AddTag, CreatePerson};
+        use crate::infrastructure::{InMemoryEventStore, InMemorySnapshotStore, PersonRepository};
+        use crate::value_objects::{PersonName, Tag};
+
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = PersonRepository::new(store.clone(), Arc::new(InMemorySnapshotStore::new()), 100);
+        let handler = PersonCommandHandler::new(Arc::new(repository), test_client().await);
+        let person_id = PersonId::new();
+        handler.handle(PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+        }), None).await.unwrap();
+
+        let tag = |name: &str| PersonCommand::AddTag(AddTag { person_id, tag: Tag::new("segment", name).unwrap() });
+        handler.handle(tag("vip"), Some("tag-1".to_string())).await.unwrap();
+        let err = handler.handle(tag("churned"), Some("tag-1".to_string())).await.unwrap_err();
+        assert!(matches!(err, DomainError::ValidationError(_)), "{err:?}");
+
+        // Only the first tag was added, and its retry is still answered
+        let retried = handler.handle(tag("vip"), Some("tag-1".to_string())).await.unwrap();
+        assert_eq!(retried.version, 2);
+        assert_eq!(store.get_events(person_id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_merge_carries_traced_attributes_to_the_target() {
+        use crate::commands::{CreatePerson, MergePersons, MergeReason, RecordAttribute};
+        use crate::infrastructure::{InMemoryEventStore, InMemorySnapshotStore, PersonRepository};
+        use crate::services::MergeService;
+        use crate::value_objects::{
+            AttributeSource, AttributeType, AttributeValue, ConfidenceLevel, IdentifyingAttributeType,
+            PersonAttribute, PersonName, Provenance, TemporalValidity,
+        };
+
+        let repository = PersonRepository::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemorySnapshotStore::new()),
+            100,
+        );
+        let (source_id, target_id) = (PersonId::new(), PersonId::new());
+        for (person_id, family) in [(source_id, "Byron"), (target_id, "Lovelace")] {
+            execute_command(&repository, PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), family.to_string()),
+                source: "test".to_string(),
+            })).await.unwrap();
+        }
+        let birth_place = AttributeType::Identifying(IdentifyingAttributeType::BirthPlace);
+        execute_command(&repository, PersonCommand::RecordAttribute(RecordAttribute {
+            person_id: source_id,
+            attribute: PersonAttribute::new(
+                birth_place.clone(),
+                AttributeValue::LocationReference("london".to_string()),
+                TemporalValidity::of(chrono::Utc::now()),
+                Provenance::new(AttributeSource::DocumentVerified, ConfidenceLevel::Certain),
+            ),
+        })).await.unwrap();
+
+        let outcome = execute_command(&repository, PersonCommand::MergePersons(MergePersons {
+            source_person_id: source_id,
+            target_person_id: target_id,
+            merge_reason: MergeReason::DuplicateIdentity,
+        })).await.unwrap();
+        let PersonEvent::PersonMergedInto(merge) = &outcome.events[0] else {
+            panic!("expected PersonMergedInto");
+        };
+
+        let target = repository.load(target_id).await.unwrap().unwrap();
+        let carried = target.attributes.currently_valid().find_by_type(&birth_place).cloned().unwrap();
+        assert_eq!(MergeService::merge_origin(&carried), Some(source_id));
+        let trace = carried.provenance.trace.last().unwrap();
+        assert_eq!(trace.applied_at, merge.merged_at);
+        assert!(trace.transformation.starts_with(crate::services::MERGE_TRANSFORMATION));
+
+        // A merge into a person that does not exist leaves the source untouched
+        let err = execute_command(&repository, PersonCommand::MergePersons(MergePersons {
+            source_person_id: target_id,
+            target_person_id: PersonId::new(),
+            merge_reason: MergeReason::DuplicateIdentity,
+        })).await.unwrap_err();
+        assert!(matches!(err, DomainError::AggregateNotFound(_)));
+        assert!(repository.load(target_id).await.unwrap().unwrap().is_active());
+
+        // Nor does one into a person already merged away, which could not take the attributes
+        let err = execute_command(&repository, PersonCommand::MergePersons(MergePersons {
+            source_person_id: target_id,
+            target_person_id: source_id,
+            merge_reason: MergeReason::DuplicateIdentity,
+        })).await.unwrap_err();
+        assert!(matches!(err, DomainError::ValidationError(_)));
+        assert!(repository.load(target_id).await.unwrap().unwrap().is_active());
+    }
+}
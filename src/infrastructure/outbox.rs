@@ -0,0 +1,262 @@
+//! Transactional outbox for reliable event publishing
+//!
+//! An event store with an outbox records a publish intent for every event
+//! in the same write that appends it. [`OutboxRelay`] later publishes the
+//! pending intents and marks them as sent. An intent is only marked after
+//! a successful publish, so a crash at any point results in a re-publish
+//! rather than a lost event: delivery is at-least-once and consumers should
+//! de-duplicate on `(aggregate_id, sequence)`.
+//!
+//! Entries hold the [`StreamingEventEnvelope`] that subscriptions decode and
+//! are published on its subject, exactly as events published straight after
+//! an append are.
+
+use async_nats::jetstream;
+use async_trait::async_trait;
+use cim_domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::events::{EventMetadata, PersonEventV2, StreamingEventEnvelope};
+use super::event_store::EventEnvelope;
+
+/// An event waiting to be published
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub id: Uuid,
+    pub envelope: StreamingEventEnvelope,
+}
+
+impl OutboxEntry {
+    pub fn new(envelope: StreamingEventEnvelope) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            envelope,
+        }
+    }
+
+    /// Entry publishing a stored event in its streaming form
+    ///
+    /// The stored correlation and causation ids become the event's metadata
+    /// when they are UUIDs; a correlation id that is not gets a fresh one.
+    pub fn for_stored(stored: &EventEnvelope) -> Self {
+        let metadata = EventMetadata {
+            correlation_id: Uuid::parse_str(&stored.correlation_id).unwrap_or_else(|_| Uuid::now_v7()),
+            causation_id: Uuid::parse_str(&stored.causation_id).ok(),
+            timestamp: stored.timestamp,
+            ..EventMetadata::new()
+        };
+        let event = PersonEventV2::from_v1(stored.event.clone(), metadata);
+        Self::new(StreamingEventEnvelope::new(stored.aggregate_id, stored.sequence, event))
+    }
+}
+
+/// Storage side of the outbox, implemented by event stores that record
+/// publish intents alongside appended events
+#[async_trait]
+pub trait OutboxStore: Send + Sync {
+    /// Oldest unpublished entries, in append order
+    async fn pending(&self, limit: usize) -> DomainResult<Vec<OutboxEntry>>;
+
+    /// Mark an entry as published so it is not relayed again
+    async fn mark_published(&self, id: Uuid) -> DomainResult<()>;
+}
+
+/// Destination the relay publishes outbox entries to
+#[async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    async fn publish(&self, entry: &OutboxEntry) -> DomainResult<()>;
+}
+
+/// Publishes outbox entries to the streaming event subjects on JetStream
+pub struct JetStreamOutboxPublisher {
+    jetstream: jetstream::Context,
+}
+
+impl JetStreamOutboxPublisher {
+    pub fn new(jetstream: jetstream::Context) -> Self {
+        Self { jetstream }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for JetStreamOutboxPublisher {
+    async fn publish(&self, entry: &OutboxEntry) -> DomainResult<()> {
+        let envelope = &entry.envelope;
+        let subject = envelope.subject();
+        let payload = serde_json::to_vec(envelope)
+            .map_err(|e| DomainError::SerializationError(e.to_string()))?;
+
+        self.jetstream
+            .publish(subject, payload.into())
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to publish outbox entry {}: {e}", entry.id),
+            })?
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Outbox entry {} was not acknowledged: {e}", entry.id),
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Background relay moving pending outbox entries to the publisher
+pub struct OutboxRelay {
+    outbox: Arc<dyn OutboxStore>,
+    publisher: Arc<dyn OutboxPublisher>,
+    batch_size: usize,
+    poll_interval: Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(outbox: Arc<dyn OutboxStore>, publisher: Arc<dyn OutboxPublisher>) -> Self {
+        Self {
+            outbox,
+            publisher,
+            batch_size: 100,
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Publish one batch of pending entries, returning how many were sent
+    ///
+    /// Stops at the first failed publish so entries go out in append order;
+    /// the failed entry stays pending and is retried on the next pass.
+    pub async fn relay_pending(&self) -> DomainResult<usize> {
+        let mut published = 0;
+        for entry in self.outbox.pending(self.batch_size).await? {
+            self.publisher.publish(&entry).await?;
+            self.outbox.mark_published(entry.id).await?;
+            published += 1;
+        }
+        Ok(published)
+    }
+
+    /// Relay until the task is dropped or aborted
+    pub async fn run(&self) {
+        loop {
+            match self.relay_pending().await {
+                // A full batch likely means more are waiting
+                Ok(published) if published == self.batch_size => continue,
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Outbox relay pass failed, will retry: {}", e),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Run the relay on a background task
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::events::{PersonCreated, PersonEvent};
+    use crate::infrastructure::{EventStore, InMemoryEventStore};
+    use crate::value_objects::PersonName;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tokio::sync::Mutex;
+
+    /// Records published sequences; can be switched to fail every publish
+    #[derive(Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<u64>>,
+        unavailable: AtomicBool,
+    }
+
+    #[async_trait]
+    impl OutboxPublisher for RecordingPublisher {
+        async fn publish(&self, entry: &OutboxEntry) -> DomainResult<()> {
+            if self.unavailable.load(Ordering::SeqCst) {
+                return Err(DomainError::generic("broker unavailable"));
+            }
+            self.published.lock().await.push(entry.envelope.sequence);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_appended_before_a_crash_are_published_on_restart() {
+        let store = Arc::new(InMemoryEventStore::with_outbox());
+        let person_id = PersonId::new();
+        let created = PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+            created_at: chrono::Utc::now(),
+        });
+
+        // The process dies after the append, before anything is published
+        store.append_events(person_id, vec![created], None).await.unwrap();
+        let crashed = Arc::new(RecordingPublisher::default());
+        crashed.unavailable.store(true, Ordering::SeqCst);
+        let relay = OutboxRelay::new(store.clone(), crashed.clone());
+        assert!(relay.relay_pending().await.is_err());
+        drop(relay);
+        assert_eq!(store.pending(10).await.unwrap().len(), 1);
+
+        // A relay started after the restart delivers the intent exactly once
+        let publisher = Arc::new(RecordingPublisher::default());
+        let relay = OutboxRelay::new(store.clone(), publisher.clone());
+        assert_eq!(relay.relay_pending().await.unwrap(), 1);
+        assert_eq!(relay.relay_pending().await.unwrap(), 0);
+
+        assert_eq!(*publisher.published.lock().await, vec![1]);
+        assert!(store.pending(10).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_only_stores_built_with_an_outbox_record_one() {
+        assert!(InMemoryEventStore::with_outbox().records_outbox());
+        assert!(!InMemoryEventStore::new().records_outbox());
+    }
+
+    #[tokio::test]
+    async fn test_outbox_entries_decode_as_subscriptions_read_them() {
+        use crate::infrastructure::subscriptions::{decode_envelope, ProjectionHandler};
+        use crate::infrastructure::StreamingEventHandler;
+        use crate::events::PersonEventV2;
+
+        let store = InMemoryEventStore::with_outbox();
+        let person_id = PersonId::new();
+        let created = PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+            created_at: chrono::Utc::now(),
+        });
+        store.append_events(person_id, vec![created], None).await.unwrap();
+        let entry = store.pending(1).await.unwrap().remove(0);
+
+        // What the JetStream publisher sends, decoded the way SubscriptionManager does
+        let payload = serde_json::to_vec(&entry.envelope).unwrap();
+        let decoded = decode_envelope(&payload).unwrap();
+        assert_eq!(decoded.aggregate_id, person_id);
+        assert_eq!(decoded.sequence, 1);
+        assert!(matches!(decoded.event, PersonEventV2::Created { person_id: created, .. } if created == person_id));
+        assert_eq!(entry.envelope.subject(), format!("person.events.{person_id}.person.created"));
+
+        let handler = ProjectionHandler::new("outbox".to_string());
+        assert!(handler.handle_event(decoded).await.is_ok());
+    }
+}
@@ -2,7 +2,9 @@
 
 use crate::aggregate::{Person, PersonId};
 use crate::events::PersonEvent;
-use cim_domain::DomainResult;
+use crate::nats::MessageIdentity;
+use cim_domain::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -31,6 +33,9 @@ pub trait SnapshotStore: Send + Sync {
     
     /// Delete snapshots older than a certain version
     async fn delete_snapshots_before(&self, aggregate_id: PersonId, version: u64) -> DomainResult<()>;
+
+    /// List every aggregate that has at least one snapshot
+    async fn list_aggregate_ids(&self) -> DomainResult<Vec<PersonId>>;
 }
 
 /// In-memory snapshot store
@@ -74,13 +79,43 @@ impl SnapshotStore for InMemorySnapshotStore {
         }
         Ok(())
     }
+
+    async fn list_aggregate_ids(&self) -> DomainResult<Vec<PersonId>> {
+        let store = self.snapshots.read().await;
+        Ok(store.iter()
+            .filter(|(_, snapshots)| !snapshots.is_empty())
+            .map(|(id, _)| *id)
+            .collect())
+    }
+}
+
+/// When the repository snapshots an aggregate after saving
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotPolicy {
+    /// Snapshot every `interval` events; 0 never snapshots
+    pub interval: u64,
+}
+
+impl SnapshotPolicy {
+    pub fn every(interval: u64) -> Self {
+        Self { interval }
+    }
+
+    /// Whether a save taking the stream from `previous_version` to
+    /// `current_version` passed a multiple of the interval
+    ///
+    /// A save of several events can step over the multiple itself; the
+    /// snapshot is then taken at the version the save ended on.
+    pub fn should_snapshot(&self, previous_version: u64, current_version: u64) -> bool {
+        self.interval > 0 && current_version / self.interval > previous_version / self.interval
+    }
 }
 
 /// Repository for Person aggregates combining event and snapshot stores
 pub struct PersonRepository {
     event_store: Arc<dyn EventStore>,
     snapshot_store: Arc<dyn SnapshotStore>,
-    snapshot_frequency: u64, // Take snapshot every N events
+    snapshot_policy: SnapshotPolicy,
 }
 
 impl PersonRepository {
@@ -92,14 +127,26 @@ impl PersonRepository {
         Self {
             event_store,
             snapshot_store,
-            snapshot_frequency,
+            snapshot_policy: SnapshotPolicy::every(snapshot_frequency),
         }
     }
+
+    /// Replace the snapshot policy given as a frequency to [`new`](Self::new)
+    pub fn with_snapshot_policy(mut self, snapshot_policy: SnapshotPolicy) -> Self {
+        self.snapshot_policy = snapshot_policy;
+        self
+    }
     
     /// Load a person aggregate
     pub async fn load(&self, aggregate_id: PersonId) -> DomainResult<Option<Person>> {
-        // Try to load from snapshot first
-        let snapshot = self.snapshot_store.get_latest_snapshot(aggregate_id).await?;
+        // Start from the newest snapshot, ours or one left by compaction. The
+        // events between an older one and a compaction snapshot are gone.
+        let repository_snapshot = self.snapshot_store.get_latest_snapshot(aggregate_id).await?;
+        let compaction_snapshot = self.event_store.latest_snapshot(aggregate_id).await?;
+        let snapshot = match (repository_snapshot, compaction_snapshot) {
+            (Some(ours), Some(compacted)) => Some(if compacted.version > ours.version { compacted } else { ours }),
+            (ours, compacted) => ours.or(compacted),
+        };
         
         let (person, from_version) = if let Some(snapshot) = snapshot {
             (snapshot.state, snapshot.version + 1)
@@ -123,19 +170,79 @@ impl PersonRepository {
         Ok(Some(person))
     }
     
+    /// Load a person as they stood at `as_of`, `None` if they did not exist yet
+    ///
+    /// Replays the stored history rather than starting from a snapshot, which
+    /// may be newer than `as_of`. A stream whose prefix was compacted away can
+    /// only be read back to the compaction point; earlier instants fail.
+    pub async fn load_as_of(&self, aggregate_id: PersonId, as_of: DateTime<Utc>) -> DomainResult<Option<Person>> {
+        let envelopes = self.event_store.get_events(aggregate_id).await?;
+        let Some(first) = envelopes.first() else {
+            return Ok(None);
+        };
+
+        let person = if first.sequence > 1 {
+            let snapshot = self.event_store.latest_snapshot(aggregate_id).await?.ok_or_else(|| {
+                DomainError::generic(format!("Events before version {} of person {aggregate_id} are missing", first.sequence))
+            })?;
+            // The last event folded into the snapshot, or the first after it
+            let boundary = envelopes.iter()
+                .find(|e| e.sequence >= snapshot.version)
+                .unwrap_or(first);
+            if boundary.event.occurred_at() > as_of {
+                return Err(DomainError::generic(format!(
+                    "History of person {aggregate_id} before {} was compacted",
+                    boundary.event.occurred_at()
+                )));
+            }
+            let tail: Vec<PersonEvent> = envelopes.into_iter()
+                .filter(|e| e.sequence > snapshot.version)
+                .map(|e| e.event)
+                .collect();
+            Person::replay_onto(snapshot.state, &tail, as_of)
+        } else {
+            if first.event.occurred_at() > as_of {
+                return Ok(None);
+            }
+            let events: Vec<PersonEvent> = envelopes.into_iter().map(|e| e.event).collect();
+            Person::replay_until(events, as_of)
+        };
+
+        person.map(Some).map_err(DomainError::generic)
+    }
+    
     /// Save a person aggregate
+    ///
+    /// `person` must be the state after `events`, as it becomes the snapshot
+    /// when the [`SnapshotPolicy`] calls for one.
     pub async fn save(
         &self,
         person: &Person,
         events: Vec<PersonEvent>,
         expected_version: Option<u64>,
+    ) -> DomainResult<()> {
+        self.save_events(person, events, expected_version, None).await
+    }
+
+    async fn save_events(
+        &self,
+        person: &Person,
+        events: Vec<PersonEvent>,
+        expected_version: Option<u64>,
+        cause: Option<&MessageIdentity>,
     ) -> DomainResult<()> {
         // Save events
-        self.event_store.append_events(person.id, events, expected_version).await?;
+        let appended = events.len() as u64;
+        match cause {
+            Some(cause) => self.event_store.append_events_caused_by(person.id, events, expected_version, cause).await?,
+            None => self.event_store.append_events(person.id, events, expected_version).await?,
+        }
         
         // Check if we should take a snapshot
         let current_version = self.event_store.get_current_version(person.id).await?;
-        if current_version % self.snapshot_frequency == 0 {
+        let previous_version = current_version.saturating_sub(appended);
+        if self.snapshot_policy.should_snapshot(previous_version, current_version) {
+            let interval = self.snapshot_policy.interval;
             let snapshot = PersonSnapshot {
                 aggregate_id: person.id,
                 version: current_version,
@@ -145,10 +252,10 @@ impl PersonRepository {
             self.snapshot_store.save_snapshot(snapshot).await?;
             
             // Clean up old snapshots
-            if current_version > self.snapshot_frequency * 2 {
+            if current_version > interval * 2 {
                 self.snapshot_store.delete_snapshots_before(
                     person.id,
-                    current_version - self.snapshot_frequency * 2,
+                    current_version - interval * 2,
                 ).await?;
             }
         }
@@ -156,9 +263,265 @@ impl PersonRepository {
         Ok(())
     }
     
+    /// Save a person aggregate only if nobody else has appended since it was loaded
+    ///
+    /// `expected_version` is the version the aggregate had when it was loaded,
+    /// 0 for a person that must not exist yet. If the stored stream has moved
+    /// on, the save fails with `DomainError::ConcurrencyConflict` and none of
+    /// the events are written.
+    pub async fn save_with_expected_version(
+        &self,
+        person: &Person,
+        events: Vec<PersonEvent>,
+        expected_version: u64,
+    ) -> DomainResult<()> {
+        self.save(person, events, Some(expected_version)).await
+    }
+
+    /// [`PersonRepository::save_with_expected_version`] for events caused by
+    /// the message `cause`, whose correlation and message ids the stored
+    /// envelopes carry
+    pub async fn save_caused_by(
+        &self,
+        person: &Person,
+        events: Vec<PersonEvent>,
+        expected_version: u64,
+        cause: &MessageIdentity,
+    ) -> DomainResult<()> {
+        self.save_events(person, events, Some(expected_version), Some(cause)).await
+    }
+    
     /// Check if a person exists
     pub async fn exists(&self, aggregate_id: PersonId) -> DomainResult<bool> {
         let version = self.event_store.get_current_version(aggregate_id).await?;
         Ok(version > 0)
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CreatePerson, PersonCommand, SetBirthDate, UpdateName};
+    use crate::infrastructure::{EventEnvelope, InMemoryEventStore};
+    use crate::value_objects::PersonName;
+    use cim_domain::formal_domain::Aggregate;
+
+    #[tokio::test]
+    async fn test_concurrent_saves_do_not_clobber_each_other() {
+        let repository = PersonRepository::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemorySnapshotStore::new()),
+            100,
+        );
+        let person_id = PersonId::new();
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        let create = PersonCommand::CreatePerson(CreatePerson { person_id, name: name.clone(), source: "test".to_string() });
+
+        let (person, events) = Person::empty().handle(create.clone()).unwrap();
+        repository.save_with_expected_version(&person, events, 0).await.unwrap();
+
+        // A second create racing the first finds the person already there
+        let (person, events) = Person::empty().handle(create).unwrap();
+        let err = repository.save_with_expected_version(&person, events, 0).await.unwrap_err();
+        assert!(matches!(err, DomainError::ConcurrencyConflict { expected: 0, actual: 1 }));
+
+        // Two handlers load the same version; only the first to save wins
+        let first = repository.load(person_id).await.unwrap().unwrap();
+        let second = first.clone();
+        let loaded_version = first.version;
+
+        let (first, events) = first.handle(PersonCommand::SetBirthDate(SetBirthDate {
+            person_id,
+            birth_date: chrono::NaiveDate::from_ymd_opt(1815, 12, 10).unwrap(),
+        })).unwrap();
+        repository.save_with_expected_version(&first, events, loaded_version).await.unwrap();
+
+        let (second, events) = second.handle(PersonCommand::UpdateName(UpdateName {
+            person_id,
+            name: PersonName::new("Augusta".to_string(), "King".to_string()),
+            reason: None,
+        })).unwrap();
+        let err = repository.save_with_expected_version(&second, events, loaded_version).await.unwrap_err();
+        assert!(matches!(err, DomainError::ConcurrencyConflict { .. }));
+
+        let stored = repository.load(person_id).await.unwrap().unwrap();
+        assert_eq!(stored.version, loaded_version + 1);
+        assert_eq!(stored.core_identity.legal_name, name);
+        assert!(stored.core_identity.birth_date.is_some());
+    }
+
+    /// Event store counting the events handed out for replay after a snapshot
+    struct CountingEventStore {
+        inner: InMemoryEventStore,
+        tail_events: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventStore for CountingEventStore {
+        async fn append_events(
+            &self,
+            aggregate_id: PersonId,
+            events: Vec<PersonEvent>,
+            expected_version: Option<u64>,
+        ) -> DomainResult<()> {
+            self.inner.append_events(aggregate_id, events, expected_version).await
+        }
+
+        async fn get_events(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
+            self.inner.get_events(aggregate_id).await
+        }
+
+        async fn get_events_from_version(
+            &self,
+            aggregate_id: PersonId,
+            from_version: u64,
+        ) -> DomainResult<Vec<EventEnvelope>> {
+            let events = self.inner.get_events_from_version(aggregate_id, from_version).await?;
+            self.tail_events.store(events.len(), std::sync::atomic::Ordering::SeqCst);
+            Ok(events)
+        }
+
+        async fn get_current_version(&self, aggregate_id: PersonId) -> DomainResult<u64> {
+            self.inner.get_current_version(aggregate_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_replay_only_the_tail_after_the_latest_snapshot() {
+        let store = Arc::new(CountingEventStore {
+            inner: InMemoryEventStore::new(),
+            tail_events: Default::default(),
+        });
+        let snapshots = Arc::new(InMemorySnapshotStore::new());
+        let repository = PersonRepository::new(store.clone(), snapshots.clone(), 0)
+            .with_snapshot_policy(SnapshotPolicy::every(100));
+        let tail = || store.tail_events.load(std::sync::atomic::Ordering::SeqCst);
+
+        let person_id = PersonId::new();
+        let rename = |i: usize| PersonCommand::UpdateName(UpdateName {
+            person_id,
+            name: PersonName::new(format!("Ada{i}"), "Lovelace".to_string()),
+            reason: None,
+        });
+        let (mut person, events) = Person::empty().handle(PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+        })).unwrap();
+        repository.save(&person, events, Some(0)).await.unwrap();
+
+        for i in 1..1000 {
+            let (next, events) = person.handle(rename(i)).unwrap();
+            repository.save(&next, events, Some(next.version - 1)).await.unwrap();
+            person = next;
+
+            if i % 97 == 0 {
+                let loaded = repository.load(person_id).await.unwrap().unwrap();
+                assert_eq!(loaded.version, person.version);
+                assert!(tail() < 100, "replayed {} events at version {}", tail(), person.version);
+            }
+        }
+
+        let loaded = repository.load(person_id).await.unwrap().unwrap();
+        assert_eq!(loaded.version, 1000);
+        assert_eq!(loaded.core_identity.legal_name, person.core_identity.legal_name);
+        assert_eq!(snapshots.get_latest_snapshot(person_id).await.unwrap().unwrap().version, 1000);
+        assert_eq!(tail(), 0);
+
+        // A batch stepping over a multiple still snapshots, at the batch's end
+        let mut batch = Vec::new();
+        for i in 1000..1150 {
+            let (next, events) = person.handle(rename(i)).unwrap();
+            batch.extend(events);
+            person = next;
+        }
+        repository.save(&person, batch, Some(1000)).await.unwrap();
+        assert_eq!(snapshots.get_latest_snapshot(person_id).await.unwrap().unwrap().version, 1150);
+        assert_eq!(repository.load(person_id).await.unwrap().unwrap().version, 1150);
+        assert_eq!(tail(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_load_as_of_returns_the_name_held_at_that_time() {
+        let repository = PersonRepository::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemorySnapshotStore::new()),
+            100,
+        );
+        let person_id = PersonId::new();
+        let rename = |given: &str| PersonCommand::UpdateName(UpdateName {
+            person_id,
+            name: PersonName::new(given.to_string(), "Lovelace".to_string()),
+            reason: None,
+        });
+        let tick = || tokio::time::sleep(std::time::Duration::from_millis(2));
+
+        let before_creation = Utc::now();
+        tick().await;
+        let (person, events) = Person::empty().handle(PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Byron".to_string()),
+            source: "test".to_string(),
+        })).unwrap();
+        repository.save(&person, events, Some(0)).await.unwrap();
+
+        tick().await;
+        let (person, events) = person.handle(rename("Ada")).unwrap();
+        repository.save(&person, events, Some(1)).await.unwrap();
+        tick().await;
+        let between_renames = Utc::now();
+        tick().await;
+        let (person, events) = person.handle(rename("Augusta")).unwrap();
+        repository.save(&person, events, Some(2)).await.unwrap();
+
+        let then = repository.load_as_of(person_id, between_renames).await.unwrap().unwrap();
+        assert_eq!(then.core_identity.legal_name, PersonName::new("Ada".to_string(), "Lovelace".to_string()));
+        assert_eq!(then.version, 2);
+
+        let now = repository.load_as_of(person_id, Utc::now()).await.unwrap().unwrap();
+        assert_eq!(now.core_identity.legal_name, person.core_identity.legal_name);
+        assert!(repository.load_as_of(person_id, before_creation).await.unwrap().is_none());
+        assert!(repository.load_as_of(PersonId::new(), Utc::now()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_load_prefers_a_compaction_snapshot_newer_than_its_own() {
+        let store = Arc::new(InMemoryEventStore::new().with_snapshots(Arc::new(InMemorySnapshotStore::new())));
+        let snapshots = Arc::new(InMemorySnapshotStore::new());
+        let repository = PersonRepository::new(store.clone(), snapshots.clone(), 5);
+
+        let person_id = PersonId::new();
+        let (mut person, events) = Person::empty().handle(PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Byron".to_string()),
+            source: "test".to_string(),
+        })).unwrap();
+        repository.save(&person, events, Some(0)).await.unwrap();
+        for i in 2..=8 {
+            let command = if i == 6 {
+                PersonCommand::SetBirthDate(SetBirthDate {
+                    person_id,
+                    birth_date: chrono::NaiveDate::from_ymd_opt(1815, 12, 10).unwrap(),
+                })
+            } else {
+                PersonCommand::UpdateName(UpdateName {
+                    person_id,
+                    name: PersonName::new(format!("Ada{i}"), "Lovelace".to_string()),
+                    reason: None,
+                })
+            };
+            let (next, events) = person.handle(command).unwrap();
+            repository.save(&next, events, Some(next.version - 1)).await.unwrap();
+            person = next;
+        }
+        assert_eq!(snapshots.get_latest_snapshot(person_id).await.unwrap().unwrap().version, 5);
+
+        // Events 6 and 7, which the repository snapshot needs, are folded away
+        store.compact(person_id, 7).await.unwrap();
+
+        let loaded = repository.load(person_id).await.unwrap().unwrap();
+        assert_eq!(loaded.version, 8);
+        assert_eq!(loaded.core_identity.legal_name, person.core_identity.legal_name);
+        assert!(loaded.core_identity.birth_date.is_some());
+    }
+}
@@ -0,0 +1,221 @@
+//! Queue metrics and lag alarms for the async processors
+//!
+//! A [`QueueMonitor`] tracks work a processor has accepted but not finished.
+//! On every change it reports three gauges through [`ProcessorMetrics`]:
+//!
+//! - `<name>.queue_depth`: items waiting or in progress
+//! - `<name>.processing_rate`: items completed per second over the rate window
+//! - `<name>.oldest_item_age_seconds`: how long the oldest pending item has waited
+//!
+//! When the oldest item has waited longer than the lag threshold, the monitor
+//! logs a warning and increments `<name>.lag_alarms` once, then stays quiet
+//! until the backlog recovers.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// Sink for processor metrics
+pub trait ProcessorMetrics: Send + Sync {
+    /// Set a gauge to its current value
+    fn record_gauge(&self, name: &str, value: f64);
+
+    /// Add to a monotonically increasing counter
+    fn increment_counter(&self, name: &str, by: u64);
+}
+
+/// In-memory metrics for testing and local inspection
+#[derive(Default)]
+pub struct InMemoryProcessorMetrics {
+    gauges: Mutex<HashMap<String, f64>>,
+    counters: Mutex<HashMap<String, u64>>,
+}
+
+impl InMemoryProcessorMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn gauge(&self, name: &str) -> Option<f64> {
+        self.gauges.lock().expect("metrics poisoned").get(name).copied()
+    }
+
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counters.lock().expect("metrics poisoned").get(name).copied().unwrap_or(0)
+    }
+}
+
+impl ProcessorMetrics for InMemoryProcessorMetrics {
+    fn record_gauge(&self, name: &str, value: f64) {
+        self.gauges.lock().expect("metrics poisoned").insert(name.to_string(), value);
+    }
+
+    fn increment_counter(&self, name: &str, by: u64) {
+        *self.counters.lock().expect("metrics poisoned").entry(name.to_string()).or_default() += by;
+    }
+}
+
+/// Point-in-time view of a processor's queue
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueStats {
+    pub depth: usize,
+    /// Completions per second over the rate window
+    pub processing_rate: f64,
+    pub oldest_item_age: Option<Duration>,
+    pub lagging: bool,
+}
+
+/// Handle for one tracked item, returned by [`QueueMonitor::enqueued`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QueueTicket(u64);
+
+#[derive(Default)]
+struct MonitorState {
+    next_ticket: u64,
+    /// Enqueue time of each pending item; tickets increase, so the first is the oldest
+    pending: BTreeMap<QueueTicket, DateTime<Utc>>,
+    /// Completion times within the rate window
+    completions: VecDeque<DateTime<Utc>>,
+    lagging: bool,
+}
+
+/// Tracks a processor's pending work and reports it as metrics
+pub struct QueueMonitor {
+    name: String,
+    metrics: Arc<dyn ProcessorMetrics>,
+    lag_threshold: Duration,
+    rate_window: Duration,
+    state: Mutex<MonitorState>,
+}
+
+impl QueueMonitor {
+    /// Monitor reporting under `name`, alarming once items wait 30 seconds
+    pub fn new(name: impl Into<String>, metrics: Arc<dyn ProcessorMetrics>) -> Self {
+        Self {
+            name: name.into(),
+            metrics,
+            lag_threshold: Duration::seconds(30),
+            rate_window: Duration::seconds(60),
+            state: Mutex::new(MonitorState::default()),
+        }
+    }
+
+    /// How long the oldest item may wait before the lag alarm fires
+    pub fn with_lag_threshold(mut self, lag_threshold: Duration) -> Self {
+        self.lag_threshold = lag_threshold;
+        self
+    }
+
+    /// Window the processing rate is averaged over
+    pub fn with_rate_window(mut self, rate_window: Duration) -> Self {
+        self.rate_window = rate_window;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn lag_threshold(&self) -> Duration {
+        self.lag_threshold
+    }
+
+    /// Start tracking an item
+    pub fn enqueued(&self) -> QueueTicket {
+        let now = Utc::now();
+        let ticket = {
+            let mut state = self.state.lock().expect("queue monitor poisoned");
+            let ticket = QueueTicket(state.next_ticket);
+            state.next_ticket += 1;
+            state.pending.insert(ticket, now);
+            ticket
+        };
+        self.report_at(now);
+        ticket
+    }
+
+    /// Stop tracking an item, whether it succeeded or failed
+    pub fn completed(&self, ticket: QueueTicket) {
+        let now = Utc::now();
+        {
+            let mut state = self.state.lock().expect("queue monitor poisoned");
+            if state.pending.remove(&ticket).is_some() {
+                state.completions.push_back(now);
+            }
+        }
+        self.report_at(now);
+    }
+
+    /// Publish the current stats, raising the lag alarm if needed
+    pub fn report(&self) -> QueueStats {
+        self.report_at(Utc::now())
+    }
+
+    fn report_at(&self, now: DateTime<Utc>) -> QueueStats {
+        let (stats, alarm) = {
+            let mut state = self.state.lock().expect("queue monitor poisoned");
+            while state.completions.front().is_some_and(|at| *at < now - self.rate_window) {
+                state.completions.pop_front();
+            }
+
+            let oldest_item_age = state.pending.values().next().map(|enqueued_at| now - *enqueued_at);
+            let lagging = oldest_item_age.is_some_and(|age| age > self.lag_threshold);
+            let alarm = lagging && !state.lagging;
+            state.lagging = lagging;
+
+            let window_seconds = self.rate_window.num_milliseconds() as f64 / 1000.0;
+            let stats = QueueStats {
+                depth: state.pending.len(),
+                processing_rate: if window_seconds > 0.0 { state.completions.len() as f64 / window_seconds } else { 0.0 },
+                oldest_item_age,
+                lagging,
+            };
+            (stats, alarm)
+        };
+
+        let age_seconds = stats.oldest_item_age
+            .map_or(0.0, |age| age.num_milliseconds() as f64 / 1000.0);
+        self.metrics.record_gauge(&format!("{}.queue_depth", self.name), stats.depth as f64);
+        self.metrics.record_gauge(&format!("{}.processing_rate", self.name), stats.processing_rate);
+        self.metrics.record_gauge(&format!("{}.oldest_item_age_seconds", self.name), age_seconds);
+
+        if alarm {
+            warn!(
+                "{} is lagging: oldest item has waited {:.1}s (threshold {}s, depth {})",
+                self.name,
+                age_seconds,
+                self.lag_threshold.num_seconds(),
+                stats.depth,
+            );
+            self.metrics.increment_counter(&format!("{}.lag_alarms", self.name), 1);
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lag_alarm_fires_once_until_recovered() {
+        let metrics = Arc::new(InMemoryProcessorMetrics::new());
+        let monitor = QueueMonitor::new("commands", metrics.clone());
+
+        let ticket = monitor.enqueued();
+        let later = Utc::now() + Duration::minutes(1);
+        assert!(monitor.report_at(later).lagging);
+        monitor.report_at(later);
+        assert_eq!(metrics.counter("commands.lag_alarms"), 1);
+        assert!(metrics.gauge("commands.oldest_item_age_seconds").unwrap() > 30.0);
+
+        monitor.completed(ticket);
+        let stats = monitor.report();
+        assert_eq!(stats.depth, 0);
+        assert!(!stats.lagging);
+        assert!(stats.processing_rate > 0.0);
+        assert_eq!(metrics.gauge("commands.oldest_item_age_seconds"), Some(0.0));
+    }
+}
@@ -0,0 +1,179 @@
+//! NATS request/reply server for person queries
+//!
+//! Clients send a JSON [`PersonQuery`] as a request on
+//! [`PersonSubjects::query_for`] and receive a JSON [`PersonQueryResponse`]
+//! on the reply subject. Anything the responder cannot answer, such as a
+//! payload that is not a query or one sent to another query's subject, is
+//! answered with [`PersonQueryResponse::Error`] so the requester is not left
+//! waiting for a timeout. Relationship queries name the requester's
+//! [`ViewerRole`](crate::projections::ViewerRole) and only see what that role
+//! may.
+
+use async_nats::Client;
+use cim_domain::{DomainError, DomainResult};
+use futures::StreamExt;
+use std::sync::Arc;
+
+use super::nats_integration::PersonSubjects;
+use crate::queries::{PersonQuery, PersonQueryResponse, PersonQueryService};
+
+/// Answers person queries published on `person.queries.>`
+pub struct PersonQueryResponder {
+    queries: Arc<PersonQueryService>,
+    client: Client,
+}
+
+impl PersonQueryResponder {
+    pub fn new(queries: Arc<PersonQueryService>, client: Client) -> Self {
+        Self { queries, client }
+    }
+
+    /// Start answering queries until the subscription ends
+    pub async fn start(&self) -> DomainResult<()> {
+        let mut subscription = self.client
+            .subscribe(PersonSubjects::queries())
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS".to_string(),
+                message: format!("Failed to subscribe: {e}"),
+            })?;
+
+        while let Some(msg) = subscription.next().await {
+            let Some(reply) = msg.reply else {
+                tracing::warn!("Ignoring query on {} without a reply subject", msg.subject);
+                continue;
+            };
+
+            let response = answer(&self.queries, &msg.subject, &msg.payload).await;
+            let payload = match serde_json::to_vec(&response) {
+                Ok(payload) => payload,
+                Err(e) => match serde_json::to_vec(&PersonQueryResponse::Error {
+                    message: format!("Failed to serialize response: {e}"),
+                }) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        tracing::error!("Failed to serialize error reply to {}: {}", msg.subject, e);
+                        continue;
+                    }
+                },
+            };
+
+            // One requester that cannot be answered must not stop the others
+            if let Err(e) = self.client.publish(reply, payload.into()).await {
+                tracing::error!("Failed to send reply to {}: {}", msg.subject, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Response to a query request received on `subject`
+async fn answer(queries: &PersonQueryService, subject: &str, payload: &[u8]) -> PersonQueryResponse {
+    let query: PersonQuery = match serde_json::from_slice(payload) {
+        Ok(query) => query,
+        Err(e) => {
+            return PersonQueryResponse::Error { message: format!("Invalid query: {e}") };
+        }
+    };
+
+    let expected = PersonSubjects::query_for(&query);
+    if subject != expected {
+        return PersonQueryResponse::Error {
+            message: format!("{} query sent to {subject}, expected {expected}", query.query_type()),
+        };
+    }
+
+    queries.execute(&query).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::events::{PersonCreated, PersonEvent};
+    use crate::projections::*;
+    use crate::value_objects::PersonName;
+
+    #[tokio::test]
+    async fn test_answers_queries_and_reports_bad_requests() {
+        let summaries = Arc::new(PersonSummaryProjection::new());
+        let queries = PersonQueryService::new(
+            summaries.clone(),
+            Arc::new(PersonSearchProjection::new()),
+            Arc::new(PersonSkillsProjection::new()),
+            Arc::new(PersonNetworkProjection::new()),
+            Arc::new(PersonTimelineProjection::new()),
+        );
+        let person_id = PersonId::new();
+        summaries.handle_event(&PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+            created_at: chrono::Utc::now(),
+        })).await.unwrap();
+
+        let query = PersonQuery::GetSummary { person_id };
+        let payload = serde_json::to_vec(&query).unwrap();
+        let response = answer(&queries, &PersonSubjects::query_for(&query), &payload).await;
+        let PersonQueryResponse::Summary(Some(summary)) = response else {
+            panic!("expected a summary, got {response:?}");
+        };
+        assert_eq!(summary.person_id, person_id);
+
+        // The subject names the query type
+        let response = answer(&queries, "person.queries.GetAllSummaries", &payload).await;
+        assert!(matches!(response, PersonQueryResponse::Error { message } if message.contains("GetSummary")));
+
+        let response = answer(&queries, "person.queries.GetSummary", b"{\"query_type\":\"Unknown\"}").await;
+        assert!(matches!(response, PersonQueryResponse::Error { message } if message.starts_with("Invalid query")));
+    }
+
+    #[tokio::test]
+    async fn test_connections_are_redacted_for_the_requesting_role() {
+        let network = Arc::new(PersonNetworkProjection::new());
+        let queries = PersonQueryService::new(
+            Arc::new(PersonSummaryProjection::new()),
+            Arc::new(PersonSearchProjection::new()),
+            Arc::new(PersonSkillsProjection::new()),
+            network.clone(),
+            Arc::new(PersonTimelineProjection::new()),
+        );
+        let (ada, doctor, colleague) = (PersonId::new(), PersonId::new(), PersonId::new());
+        for (to_person, relationship_type) in [
+            (doctor, RelationshipType::Caregiver),
+            (colleague, RelationshipType::Colleague),
+        ] {
+            network.add_relationship(PersonRelationship {
+                from_person: ada,
+                to_person,
+                relationship_type,
+                strength: 0.8,
+                established_at: chrono::Utc::now(),
+                last_interaction: None,
+                interaction_count: 0,
+            }).await;
+        }
+
+        // A request without a role is not answered with the graph
+        let unscoped = serde_json::json!({"query_type": "GetConnections", "person_id": ada});
+        let response = answer(&queries, "person.queries.GetConnections", &serde_json::to_vec(&unscoped).unwrap()).await;
+        assert!(matches!(response, PersonQueryResponse::Error { message } if message.starts_with("Invalid query")));
+
+        let query = PersonQuery::GetConnections { person_id: ada, viewer: ViewerRole::Marketer };
+        let payload = serde_json::to_vec(&query).unwrap();
+        let response = answer(&queries, &PersonSubjects::query_for(&query), &payload).await;
+        let PersonQueryResponse::Connections(connections) = response else {
+            panic!("expected connections, got {response:?}");
+        };
+        let visible: Vec<PersonId> = connections.visible.iter().map(|r| r.to_person).collect();
+        assert_eq!(visible, vec![colleague]);
+        assert_eq!(connections.redacted_count, 1);
+
+        // Nor can a path be traced through the edges the role cannot see
+        let query = PersonQuery::FindShortestPath { from: ada, to: doctor, viewer: ViewerRole::Marketer };
+        let payload = serde_json::to_vec(&query).unwrap();
+        let response = answer(&queries, &PersonSubjects::query_for(&query), &payload).await;
+        assert!(matches!(response, PersonQueryResponse::Path(None)));
+    }
+}
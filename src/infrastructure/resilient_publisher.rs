@@ -0,0 +1,191 @@
+//! Publishing guarded by retries and a circuit breaker
+//!
+//! [`ResilientEventPublisher`] wraps another [`OutboxPublisher`], usually the
+//! [`JetStreamOutboxPublisher`](super::JetStreamOutboxPublisher). Each publish
+//! is retried under a [`RetryPolicy`]; a publish that still fails counts as
+//! one failure for the circuit breaker. Once enough consecutive publishes
+//! have failed the breaker opens and publishes fail fast with
+//! [`PublishError::CircuitOpen`] until its timeout has passed, so an
+//! [`OutboxRelay`](super::OutboxRelay) stops hammering an unavailable NATS
+//! server and simply leaves its entries pending.
+
+use async_trait::async_trait;
+use cim_domain::{DomainError, DomainResult};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::outbox::{OutboxEntry, OutboxPublisher};
+use super::retry::{retry_with_policy, CircuitBreaker, CircuitBreakerError, CircuitStatus};
+use super::streaming::RetryPolicy;
+
+/// Why a publish did not go through
+#[derive(Debug, thiserror::Error)]
+pub enum PublishError {
+    #[error("Circuit breaker is open; publishing is paused")]
+    CircuitOpen,
+
+    #[error("Publish failed: {0}")]
+    Failed(DomainError),
+}
+
+impl From<PublishError> for DomainError {
+    fn from(err: PublishError) -> Self {
+        match err {
+            PublishError::CircuitOpen => DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: err.to_string(),
+            },
+            PublishError::Failed(err) => err,
+        }
+    }
+}
+
+/// Publisher adding retries and a circuit breaker to another publisher
+pub struct ResilientEventPublisher {
+    inner: Arc<dyn OutboxPublisher>,
+    retry_policy: RetryPolicy,
+    breaker: CircuitBreaker,
+}
+
+impl ResilientEventPublisher {
+    /// Open the breaker after 5 consecutive failed publishes, for 30 seconds
+    pub fn new(inner: Arc<dyn OutboxPublisher>) -> Self {
+        Self {
+            inner,
+            retry_policy: RetryPolicy::default(),
+            breaker: CircuitBreaker::new(5, 1, Duration::from_secs(30)),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Open after `failure_threshold` consecutive failures, try again after
+    /// `cooldown`, and close after `success_threshold` successful trials
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, success_threshold: u32, cooldown: Duration) -> Self {
+        self.breaker = CircuitBreaker::new(failure_threshold, success_threshold, cooldown);
+        self
+    }
+
+    /// Breaker status, for metrics and health checks
+    pub async fn circuit_status(&self) -> CircuitStatus {
+        self.breaker.status().await
+    }
+
+    /// Consecutive failed publishes, for metrics
+    pub async fn consecutive_failures(&self) -> u32 {
+        self.breaker.failure_count().await
+    }
+
+    /// Publish an entry, retrying before counting it as failed
+    pub async fn try_publish(&self, entry: &OutboxEntry) -> Result<(), PublishError> {
+        let inner = self.inner.clone();
+        let retry_policy = self.retry_policy.clone();
+        let entry = entry.clone();
+        let context = format!("publish outbox entry {}", entry.id);
+
+        let result = self.breaker.execute(move || {
+            Box::pin(async move {
+                retry_with_policy(&retry_policy, || {
+                    let inner = inner.clone();
+                    let entry = entry.clone();
+                    Box::pin(async move { inner.publish(&entry).await })
+                }, &context).await
+            })
+        }).await;
+
+        result.map_err(|err| match err {
+            CircuitBreakerError::Open => PublishError::CircuitOpen,
+            CircuitBreakerError::OperationFailed(err) => PublishError::Failed(err),
+        })
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for ResilientEventPublisher {
+    async fn publish(&self, entry: &OutboxEntry) -> DomainResult<()> {
+        Ok(self.try_publish(entry).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::events::{PersonEvent, TagRemoved};
+    use crate::infrastructure::EventEnvelope;
+    use crate::value_objects::Tag;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct FlakyPublisher {
+        healthy: AtomicBool,
+        attempts: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl OutboxPublisher for FlakyPublisher {
+        async fn publish(&self, _entry: &OutboxEntry) -> DomainResult<()> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if self.healthy.load(Ordering::SeqCst) {
+                Ok(())
+            } else {
+                Err(DomainError::generic("NATS unavailable"))
+            }
+        }
+    }
+
+    fn entry() -> OutboxEntry {
+        let person_id = PersonId::new();
+        OutboxEntry::for_stored(&EventEnvelope {
+            aggregate_id: person_id,
+            sequence: 1,
+            event: PersonEvent::TagRemoved(TagRemoved {
+                person_id,
+                tag: Tag::new("segment", "vip").unwrap(),
+                removed_at: chrono::Utc::now(),
+            }),
+            timestamp: chrono::Utc::now(),
+            correlation_id: "corr".to_string(),
+            causation_id: "cause".to_string(),
+            stream_sequence: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_after_failures_and_half_opens_after_cooldown() {
+        let inner = Arc::new(FlakyPublisher::default());
+        let publisher = ResilientEventPublisher::new(inner.clone())
+            .with_retry_policy(RetryPolicy {
+                max_retries: 1,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+                multiplier: 1.0,
+            })
+            .with_circuit_breaker(2, 1, Duration::from_millis(50));
+        let entry = entry();
+
+        for _ in 0..2 {
+            assert!(matches!(publisher.try_publish(&entry).await, Err(PublishError::Failed(_))));
+        }
+        // Each failed publish was tried twice
+        assert_eq!(inner.attempts.load(Ordering::SeqCst), 4);
+        assert_eq!(publisher.circuit_status().await, CircuitStatus::Open);
+        assert_eq!(publisher.consecutive_failures().await, 2);
+
+        // Open: fails fast without reaching NATS
+        assert!(matches!(publisher.try_publish(&entry).await, Err(PublishError::CircuitOpen)));
+        assert_eq!(inner.attempts.load(Ordering::SeqCst), 4);
+        assert!(publisher.publish(&entry).await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(publisher.circuit_status().await, CircuitStatus::HalfOpen);
+
+        inner.healthy.store(true, Ordering::SeqCst);
+        publisher.try_publish(&entry).await.unwrap();
+        assert_eq!(publisher.circuit_status().await, CircuitStatus::Closed);
+        assert_eq!(publisher.consecutive_failures().await, 0);
+    }
+}
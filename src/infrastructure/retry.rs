@@ -43,47 +43,7 @@ impl RetryHandler {
         F: Fn() -> futures::future::BoxFuture<'static, Result<T, E>>,
         E: std::fmt::Display,
     {
-        let mut attempts = 0;
-        let mut backoff = self.policy.initial_backoff;
-        
-        loop {
-            match operation().await {
-                Ok(result) => {
-                    if attempts > 0 {
-                        info!("Operation {} succeeded after {} attempts", context, attempts + 1);
-                    }
-                    return Ok(result);
-                }
-                Err(err) => {
-                    attempts += 1;
-                    
-                    if attempts > self.policy.max_retries {
-                        error!(
-                            "Operation {} failed after {} attempts: {}",
-                            context, attempts, err
-                        );
-                        return Err(err);
-                    }
-                    
-                    warn!(
-                        "Operation {} failed (attempt {}/{}): {}, retrying in {:?}",
-                        context, attempts, self.policy.max_retries + 1, err, backoff
-                    );
-                    
-                    sleep(backoff).await;
-                    
-                    // Exponential backoff with jitter
-                    backoff = std::cmp::min(
-                        backoff.mul_f64(self.policy.multiplier),
-                        self.policy.max_backoff,
-                    );
-                    
-                    // Add jitter (±10%)
-                    let jitter = backoff.as_secs_f64() * 0.1 * (rand::random::<f64>() - 0.5);
-                    backoff += Duration::from_secs_f64(jitter);
-                }
-            }
-        }
+        retry_with_policy(&self.policy, operation, context).await
     }
     
     /// Send a failed event to the dead letter queue
@@ -162,6 +122,61 @@ impl RetryHandler {
     }
 }
 
+/// Run `operation` until it succeeds or `policy` runs out of retries
+///
+/// Backs off exponentially between attempts, with jitter, and returns the
+/// last error once the retries are used up.
+pub async fn retry_with_policy<F, T, E>(
+    policy: &RetryPolicy,
+    operation: F,
+    context: &str,
+) -> Result<T, E>
+where
+    F: Fn() -> futures::future::BoxFuture<'static, Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let mut attempts = 0;
+    let mut backoff = policy.initial_backoff;
+    
+    loop {
+        match operation().await {
+            Ok(result) => {
+                if attempts > 0 {
+                    info!("Operation {} succeeded after {} attempts", context, attempts + 1);
+                }
+                return Ok(result);
+            }
+            Err(err) => {
+                attempts += 1;
+                
+                if attempts > policy.max_retries {
+                    error!(
+                        "Operation {} failed after {} attempts: {}",
+                        context, attempts, err
+                    );
+                    return Err(err);
+                }
+                
+                warn!(
+                    "Operation {} failed (attempt {}/{}): {}, retrying in {:?}",
+                    context, attempts, policy.max_retries + 1, err, backoff
+                );
+                
+                sleep(backoff).await;
+                
+                // Exponential backoff with jitter
+                backoff = std::cmp::min(
+                    backoff.mul_f64(policy.multiplier),
+                    policy.max_backoff,
+                );
+                
+                // Add jitter (±10%)
+                backoff = backoff.mul_f64(1.0 + 0.2 * (rand::random::<f64>() - 0.5));
+            }
+        }
+    }
+}
+
 /// Failed event information for dead letter queue
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FailedEvent {
@@ -201,10 +216,14 @@ struct CircuitState {
     last_failure_time: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum CircuitStatus {
+/// Whether a circuit breaker lets operations through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitStatus {
+    /// Operations run normally
     Closed,
+    /// Operations are refused until the timeout has passed
     Open,
+    /// Trial operations run; enough successes close the circuit again
     HalfOpen,
 }
 
@@ -224,6 +243,28 @@ impl CircuitBreaker {
         }
     }
     
+    /// Current status, reporting an open circuit whose timeout has passed as half-open
+    pub async fn status(&self) -> CircuitStatus {
+        let state = self.state.read().await;
+        if state.status == CircuitStatus::Open && self.timeout_elapsed(&state) {
+            CircuitStatus::HalfOpen
+        } else {
+            state.status
+        }
+    }
+
+    /// Consecutive failures since the last success
+    pub async fn failure_count(&self) -> u32 {
+        self.state.read().await.failure_count
+    }
+
+    fn timeout_elapsed(&self, state: &CircuitState) -> bool {
+        state.last_failure_time.is_some_and(|last_failure| {
+            let elapsed = chrono::Utc::now().signed_duration_since(last_failure);
+            elapsed.to_std().unwrap_or_default() >= self.timeout
+        })
+    }
+
     /// Execute a function with circuit breaker protection
     pub async fn execute<F, T, E>(&self, operation: F) -> Result<T, CircuitBreakerError<E>>
     where
@@ -232,22 +273,14 @@ impl CircuitBreaker {
         // Check if circuit should transition from Open to HalfOpen
         {
             let mut state = self.state.write().await;
-            if state.status == CircuitStatus::Open {
-                if let Some(last_failure) = state.last_failure_time {
-                    let elapsed = chrono::Utc::now().signed_duration_since(last_failure);
-                    if elapsed.to_std().unwrap_or_default() >= self.timeout {
-                        state.status = CircuitStatus::HalfOpen;
-                        state.success_count = 0;
-                    }
-                }
+            if state.status == CircuitStatus::Open && self.timeout_elapsed(&state) {
+                state.status = CircuitStatus::HalfOpen;
+                state.success_count = 0;
             }
         }
         
         // Check current state
-        let current_status = {
-            let state = self.state.read().await;
-            state.status.clone()
-        };
+        let current_status = self.state.read().await.status;
         
         match current_status {
             CircuitStatus::Open => Err(CircuitBreakerError::Open),
@@ -283,7 +316,8 @@ impl CircuitBreaker {
         state.success_count = 0;
         state.last_failure_time = Some(chrono::Utc::now());
         
-        if state.failure_count >= self.failure_threshold {
+        // A failed trial in the half-open state reopens the circuit at once
+        if state.status == CircuitStatus::HalfOpen || state.failure_count >= self.failure_threshold {
             state.status = CircuitStatus::Open;
             warn!("Circuit breaker opened after {} failures", state.failure_count);
         }
@@ -4,10 +4,13 @@ use async_nats::{jetstream, Client};
 use async_nats::jetstream::consumer::{pull::Config as PullConfig, DeliverPolicy, AckPolicy};
 use async_nats::jetstream::stream::{Config as StreamConfig, RetentionPolicy, StorageType, DiscardPolicy};
 use cim_domain::{DomainError, DomainResult};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::events::StreamingEventEnvelope;
+
 /// Enhanced streaming configuration for Person domain
 #[derive(Debug, Clone)]
 pub struct StreamingConfig {
@@ -145,6 +148,30 @@ impl Default for DeadLetterConfig {
     }
 }
 
+/// Messages [`StreamingClient::fold_events`] pulls per round trip
+pub const FOLD_BATCH_SIZE: usize = 256;
+
+/// What [`StreamingClient::fold_events`] does with a fetched message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FoldStep {
+    /// Fold the message and keep fetching
+    Fold,
+    /// Fold the message; it was the last one in the stream when the fold began
+    FoldLast,
+    /// The message was appended after the fold began; stop without folding it
+    Stop,
+}
+
+impl FoldStep {
+    fn at(stream_sequence: u64, last_sequence: u64) -> Self {
+        match stream_sequence.cmp(&last_sequence) {
+            std::cmp::Ordering::Less => Self::Fold,
+            std::cmp::Ordering::Equal => Self::FoldLast,
+            std::cmp::Ordering::Greater => Self::Stop,
+        }
+    }
+}
+
 /// Enhanced NATS streaming client
 pub struct StreamingClient {
     client: Client,
@@ -226,6 +253,91 @@ impl StreamingClient {
         Ok(())
     }
     
+    /// Fold every event in the stream, oldest first, without loading them all
+    ///
+    /// Events are pulled through an ephemeral consumer in batches of
+    /// [`FOLD_BATCH_SIZE`], and the next batch is only requested once `f` has
+    /// seen the previous one, so a slow fold holds at most one batch in
+    /// memory instead of letting the server push the whole stream. Events
+    /// appended after the fold started are not included. Envelopes are decoded
+    /// as the [`StreamingEventEnvelope`]s that subscriptions read from the
+    /// same stream, each with its `stream_position` set; any other payload
+    /// fails the fold.
+    pub async fn fold_events<T>(
+        &self,
+        init: T,
+        mut f: impl FnMut(T, &StreamingEventEnvelope) -> T,
+    ) -> DomainResult<T> {
+        let mut stream = self.jetstream.get_stream(&self.config.stream_name).await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to get stream: {e}"),
+            })?;
+        let last_sequence = stream.info().await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to get stream info: {e}"),
+            })?
+            .state
+            .last_sequence;
+
+        let consumer = stream
+            .create_consumer(PullConfig {
+                filter_subject: "person.events.>".to_string(),
+                deliver_policy: DeliverPolicy::All,
+                ack_policy: AckPolicy::None,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "NATS JetStream".to_string(),
+                message: format!("Failed to create consumer: {e}"),
+            })?;
+
+        let mut acc = init;
+        loop {
+            let mut batch = consumer.fetch().max_messages(FOLD_BATCH_SIZE).messages().await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "NATS JetStream".to_string(),
+                    message: format!("Failed to fetch messages: {e}"),
+                })?;
+
+            let mut fetched = 0;
+            while let Some(msg) = batch.next().await {
+                let msg = msg.map_err(|e| DomainError::ExternalServiceError {
+                    service: "NATS JetStream".to_string(),
+                    message: format!("Failed to get message: {e}"),
+                })?;
+                let stream_sequence = msg.info()
+                    .map_err(|e| DomainError::ExternalServiceError {
+                        service: "NATS JetStream".to_string(),
+                        message: format!("Message has no JetStream info: {e}"),
+                    })?
+                    .stream_sequence;
+                fetched += 1;
+                let step = FoldStep::at(stream_sequence, last_sequence);
+                if step == FoldStep::Stop {
+                    return Ok(acc);
+                }
+
+                let mut envelope: StreamingEventEnvelope = serde_json::from_slice(&msg.payload)
+                    .map_err(|e| DomainError::SerializationError(e.to_string()))?;
+                envelope.stream_position = Some(stream_sequence);
+                acc = f(acc, &envelope);
+
+                if step == FoldStep::FoldLast {
+                    return Ok(acc);
+                }
+            }
+
+            // Nothing left to fetch, e.g. the last message was removed by
+            // the stream's limits while folding
+            if fetched == 0 {
+                return Ok(acc);
+            }
+        }
+    }
+    
     /// Get the underlying client
     pub fn client(&self) -> &Client {
         &self.client
@@ -256,6 +368,7 @@ pub struct EventMetadata {
     /// Actor who initiated the action
     pub actor: Option<String>,
     /// Additional context
+    #[serde(with = "crate::value_objects::json_in_binary")]
     pub context: HashMap<String, serde_json::Value>,
 }
 
@@ -341,4 +454,62 @@ mod tests {
         let metadata = EventMetadata::from_command(command_id);
         assert_eq!(metadata.causation_id, Some(command_id));
     }
+
+    #[test]
+    fn test_fold_stops_at_the_last_sequence_seen_when_it_began() {
+        assert_eq!(FoldStep::at(1, 3), FoldStep::Fold);
+        assert_eq!(FoldStep::at(3, 3), FoldStep::FoldLast);
+        assert_eq!(FoldStep::at(4, 3), FoldStep::Stop);
+        // An empty stream stops on whatever was appended since
+        assert_eq!(FoldStep::at(1, 0), FoldStep::Stop);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires NATS server"]
+    async fn test_fold_events_reads_every_batch_then_stops() {
+        use crate::aggregate::PersonId;
+        use crate::events::PersonEventV2;
+        use crate::value_objects::PersonName;
+
+        async fn positions_of(streaming: &StreamingClient, person_id: PersonId) -> Vec<(u64, u64)> {
+            streaming
+                .fold_events(Vec::new(), |mut positions, envelope| {
+                    if envelope.aggregate_id == person_id {
+                        positions.push((envelope.sequence, envelope.stream_position.unwrap()));
+                    }
+                    positions
+                })
+                .await
+                .unwrap()
+        }
+
+        let streaming = StreamingClient::new("nats://localhost:4222", StreamingConfig::default())
+            .await
+            .unwrap();
+        let person_id = PersonId::new();
+        assert!(positions_of(&streaming, person_id).await.is_empty());
+
+        // More than one batch, so the fold has to fetch again
+        let published = FOLD_BATCH_SIZE as u64 + 3;
+        for sequence in 1..=published {
+            let event = PersonEventV2::Created {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                source: "test".to_string(),
+                metadata: EventMetadata::new(),
+            };
+            let envelope = StreamingEventEnvelope::new(person_id, sequence, event);
+            streaming.jetstream()
+                .publish(envelope.subject(), serde_json::to_vec(&envelope).unwrap().into())
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+        }
+
+        let positions = positions_of(&streaming, person_id).await;
+        let sequences: Vec<u64> = positions.iter().map(|(sequence, _)| *sequence).collect();
+        assert_eq!(sequences, (1..=published).collect::<Vec<_>>());
+        assert!(positions.windows(2).all(|pair| pair[0].1 < pair[1].1));
+    }
 }
\ No newline at end of file
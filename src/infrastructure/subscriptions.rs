@@ -1,16 +1,30 @@
 //! Streaming subscription handlers for event processing
+//!
+//! Delivery is at-least-once. After a message is acknowledged its stream
+//! sequence is saved as the subscription's checkpoint; on reconnect the
+//! consumer starts after the checkpoint and skips anything at or below it,
+//! so a restart neither replays the whole stream nor loses the messages
+//! that were received but not yet acknowledged.
 
 use async_nats::jetstream;
 use async_trait::async_trait;
 use cim_domain::{DomainError, DomainResult};
 use futures::StreamExt;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{error, info, warn};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
 
 use crate::events::{PersonEventV2, StreamingEventEnvelope};
 use super::retry::{RetryHandler, FailedEvent};
 use super::streaming::StreamingClient;
 
+/// Decode a message payload as the envelope subscriptions receive
+pub(crate) fn decode_envelope(payload: &[u8]) -> DomainResult<StreamingEventEnvelope> {
+    serde_json::from_slice(payload)
+        .map_err(|e| DomainError::SerializationError(format!("Failed to deserialize event: {}", e)))
+}
+
 /// Trait for handling streaming events
 #[async_trait]
 pub trait StreamingEventHandler: Send + Sync {
@@ -21,11 +35,74 @@ pub trait StreamingEventHandler: Send + Sync {
     fn name(&self) -> &str;
 }
 
+/// Persistence for the last acknowledged stream sequence per subscription
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    async fn load_checkpoint(&self, subscription_id: &str) -> DomainResult<Option<u64>>;
+
+    async fn save_checkpoint(&self, subscription_id: &str, sequence: u64) -> DomainResult<()>;
+}
+
+/// In-memory checkpoint store; checkpoints last as long as the process
+#[derive(Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: RwLock<HashMap<String, u64>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load_checkpoint(&self, subscription_id: &str) -> DomainResult<Option<u64>> {
+        Ok(self.checkpoints.read().await.get(subscription_id).copied())
+    }
+
+    async fn save_checkpoint(&self, subscription_id: &str, sequence: u64) -> DomainResult<()> {
+        self.checkpoints.write().await.insert(subscription_id.to_string(), sequence);
+        Ok(())
+    }
+}
+
+/// Checkpoint bookkeeping on top of a [`CheckpointStore`]
+#[derive(Clone)]
+pub struct SubscriptionCheckpoints {
+    store: Arc<dyn CheckpointStore>,
+}
+
+impl SubscriptionCheckpoints {
+    pub fn new(store: Arc<dyn CheckpointStore>) -> Self {
+        Self { store }
+    }
+
+    /// Record `sequence` as acknowledged; checkpoints never move backwards
+    pub async fn checkpoint(&self, subscription_id: &str, sequence: u64) -> DomainResult<()> {
+        match self.store.load_checkpoint(subscription_id).await? {
+            Some(current) if current >= sequence => Ok(()),
+            _ => self.store.save_checkpoint(subscription_id, sequence).await,
+        }
+    }
+
+    /// First stream sequence the subscription still has to process
+    pub async fn resume_from(&self, subscription_id: &str) -> DomainResult<u64> {
+        Ok(self.store.load_checkpoint(subscription_id).await?.map_or(1, |sequence| sequence + 1))
+    }
+
+    /// Whether `sequence` was acknowledged before, e.g. a redelivery after a restart
+    pub async fn is_acknowledged(&self, subscription_id: &str, sequence: u64) -> DomainResult<bool> {
+        Ok(self.store.load_checkpoint(subscription_id).await?.is_some_and(|current| sequence <= current))
+    }
+}
+
 /// Streaming subscription manager
 pub struct SubscriptionManager {
     streaming_client: Arc<StreamingClient>,
     retry_handler: Arc<RetryHandler>,
     handlers: Vec<Box<dyn StreamingEventHandler>>,
+    checkpoints: SubscriptionCheckpoints,
 }
 
 impl SubscriptionManager {
@@ -38,6 +115,55 @@ impl SubscriptionManager {
             streaming_client,
             retry_handler,
             handlers: Vec::new(),
+            checkpoints: SubscriptionCheckpoints::new(Arc::new(InMemoryCheckpointStore::new())),
+        }
+    }
+
+    /// Keep checkpoints in `store` instead of in memory
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoints = SubscriptionCheckpoints::new(store);
+        self
+    }
+
+    /// Record that `subscription_id` has acknowledged everything up to `sequence`
+    pub async fn checkpoint(&self, subscription_id: &str, sequence: u64) -> DomainResult<()> {
+        self.checkpoints.checkpoint(subscription_id, sequence).await
+    }
+
+    /// Acknowledge a message and checkpoint its stream sequence
+    async fn acknowledge(&self, msg: &jetstream::Message, consumer_name: &str) {
+        if let Err(e) = msg.ack().await {
+            error!("Failed to acknowledge message: {}", e);
+            return;
+        }
+        match msg.info() {
+            Ok(info) => {
+                if let Err(e) = self.checkpoint(consumer_name, info.stream_sequence).await {
+                    error!("Failed to checkpoint consumer {}: {}", consumer_name, e);
+                }
+            }
+            Err(e) => warn!("Acknowledged message without stream info: {}", e),
+        }
+    }
+
+    /// Whether `msg` is at or below the consumer's checkpoint
+    ///
+    /// A checkpoint that cannot be read counts as not acknowledged: the
+    /// message is processed again rather than the subscription stopping.
+    async fn already_acknowledged(&self, msg: &jetstream::Message, consumer_name: &str) -> bool {
+        let Ok(info) = msg.info() else {
+            return false;
+        };
+        match self.checkpoints.is_acknowledged(consumer_name, info.stream_sequence).await {
+            Ok(true) => {
+                debug!("Skipping already acknowledged sequence {}", info.stream_sequence);
+                true
+            }
+            Ok(false) => false,
+            Err(e) => {
+                error!("Failed to read checkpoint of consumer {}: {}", consumer_name, e);
+                false
+            }
         }
     }
     
@@ -62,9 +188,17 @@ impl SubscriptionManager {
                 message: format!("Failed to get stream: {e}"),
             })?;
             
+        // A consumer created afresh starts after the checkpoint rather than at the beginning
+        let start_sequence = self.checkpoints.resume_from(consumer_name).await?;
+        let deliver_policy = if start_sequence > 1 {
+            jetstream::consumer::DeliverPolicy::ByStartSequence { start_sequence }
+        } else {
+            jetstream::consumer::DeliverPolicy::All
+        };
         let consumer = stream
             .get_or_create_consumer(consumer_name, jetstream::consumer::pull::Config {
                 durable_name: Some(consumer_name.to_string()),
+                deliver_policy,
                 ..Default::default()
             })
             .await
@@ -93,13 +227,19 @@ impl SubscriptionManager {
                 }
             };
             
+            // Redeliveries of messages acknowledged before a restart were already processed
+            if self.already_acknowledged(&msg, consumer_name).await {
+                if let Err(e) = msg.ack().await {
+                    error!("Failed to acknowledge message: {}", e);
+                }
+                continue;
+            }
+
             // Process the message
             match self.process_message(&msg, consumer_name).await {
                 Ok(_) => {
                     // Acknowledge successful processing
-                    if let Err(e) = msg.ack().await {
-                        error!("Failed to acknowledge message: {}", e);
-                    }
+                    self.acknowledge(&msg, consumer_name).await;
                 }
                 Err(e) => {
                     error!("Failed to process message: {}", e);
@@ -143,9 +283,7 @@ impl SubscriptionManager {
                     }
                     
                     // Acknowledge to prevent redelivery
-                    if let Err(ack_err) = msg.ack().await {
-                        error!("Failed to acknowledge DLQ message: {}", ack_err);
-                    }
+                    self.acknowledge(&msg, consumer_name).await;
                 }
             }
         }
@@ -160,8 +298,7 @@ impl SubscriptionManager {
         consumer_name: &str,
     ) -> DomainResult<()> {
         // Deserialize envelope
-        let envelope: StreamingEventEnvelope = serde_json::from_slice(&msg.payload)
-            .map_err(|e| DomainError::SerializationError(format!("Failed to deserialize event: {}", e)))?;
+        let envelope = decode_envelope(&msg.payload)?;
         
         info!(
             "Processing event {} for aggregate {} by consumer {}",
@@ -197,8 +334,7 @@ impl SubscriptionManager {
         consumer_name: &str,
         error: DomainError,
     ) -> DomainResult<()> {
-        let envelope: StreamingEventEnvelope = serde_json::from_slice(&msg.payload)
-            .map_err(|e| DomainError::SerializationError(e.to_string()))?;
+        let envelope = decode_envelope(&msg.payload)?;
         
         let failed_event = FailedEvent {
             event_id: envelope.event_id,
@@ -279,4 +415,116 @@ mod tests {
         
         assert!(handler.handle_event(envelope).await.is_ok());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_restart_resumes_after_last_acknowledged_sequence() {
+        let store: Arc<dyn CheckpointStore> = Arc::new(InMemoryCheckpointStore::new());
+        let stream: Vec<u64> = (1..=10).collect();
+
+        // First run acknowledges 1-4, then crashes while processing 5
+        let checkpoints = SubscriptionCheckpoints::new(store.clone());
+        assert_eq!(checkpoints.resume_from("projections").await.unwrap(), 1);
+        for &sequence in &stream[..4] {
+            checkpoints.checkpoint("projections", sequence).await.unwrap();
+        }
+
+        // After the restart, 5 onwards is processed; redelivered 3 and 4 are skipped
+        let restarted = SubscriptionCheckpoints::new(store.clone());
+        let start = restarted.resume_from("projections").await.unwrap();
+        assert_eq!(start, 5);
+        let mut processed = Vec::new();
+        for &sequence in stream.iter().filter(|&&sequence| sequence >= start - 2) {
+            if restarted.is_acknowledged("projections", sequence).await.unwrap() {
+                continue;
+            }
+            processed.push(sequence);
+            restarted.checkpoint("projections", sequence).await.unwrap();
+        }
+        assert_eq!(processed, (5..=10).collect::<Vec<_>>());
+
+        // A late acknowledgement does not move the checkpoint back
+        restarted.checkpoint("projections", 3).await.unwrap();
+        assert_eq!(store.load_checkpoint("projections").await.unwrap(), Some(10));
+        assert_eq!(restarted.resume_from("other").await.unwrap(), 1);
+    }
+
+    struct RecordingHandler {
+        sequences: Arc<RwLock<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl StreamingEventHandler for RecordingHandler {
+        async fn handle_event(&self, envelope: StreamingEventEnvelope) -> DomainResult<()> {
+            self.sequences.write().await.push(envelope.sequence);
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires NATS server"]
+    async fn test_manager_skips_redeliveries_at_or_below_the_checkpoint() {
+        use crate::infrastructure::streaming::{ConsumerConfig, RetryPolicy, StreamingConfig};
+        use async_nats::jetstream::consumer::{AckPolicy, DeliverPolicy};
+        use std::time::Duration;
+
+        // A consumer that replays its subjects from the start, as one recreated after a restart does
+        let person_id = PersonId::new();
+        let consumer_name = format!("restart-test-{person_id}");
+        let mut config = StreamingConfig::default();
+        config.consumers.insert(consumer_name.clone(), ConsumerConfig {
+            durable_name: consumer_name.clone(),
+            filter_subjects: vec![format!("person.events.{person_id}.>")],
+            deliver_policy: DeliverPolicy::All,
+            ack_policy: AckPolicy::Explicit,
+            max_deliver: 3,
+            ack_wait: Duration::from_secs(30),
+            description: None,
+        });
+        let streaming = StreamingClient::new("nats://localhost:4222", config).await.unwrap();
+
+        let mut stream_sequences = Vec::new();
+        for sequence in 1..=5 {
+            let event = PersonEventV2::NameUpdated {
+                person_id,
+                old_name: PersonName::new("Ada".to_string(), "Byron".to_string()),
+                new_name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                change_reason: None,
+                metadata: EventMetadata::new(),
+            };
+            let envelope = StreamingEventEnvelope::new(person_id, sequence, event);
+            let ack = streaming.jetstream()
+                .publish(envelope.subject(), serde_json::to_vec(&envelope).unwrap().into())
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+            stream_sequences.push(ack.sequence);
+        }
+
+        // The first run acknowledged the first three before it stopped
+        let store: Arc<dyn CheckpointStore> = Arc::new(InMemoryCheckpointStore::new());
+        store.save_checkpoint(&consumer_name, stream_sequences[2]).await.unwrap();
+
+        let retry_handler = RetryHandler::new(
+            streaming.client().clone(),
+            streaming.jetstream().clone(),
+            RetryPolicy::default(),
+            "person.dlq.>".to_string(),
+        );
+        let sequences = Arc::new(RwLock::new(Vec::new()));
+        let mut manager = SubscriptionManager::new(Arc::new(streaming), Arc::new(retry_handler))
+            .with_checkpoint_store(store.clone());
+        manager.register_handler(Box::new(RecordingHandler { sequences: sequences.clone() }));
+
+        // The consumer runs until stopped; give it long enough to drain the five messages
+        let run = tokio::time::timeout(Duration::from_secs(2), manager.start_consumer(&consumer_name)).await;
+        assert!(run.is_err(), "consumer stopped early: {run:?}");
+
+        assert_eq!(*sequences.read().await, vec![4, 5]);
+        assert_eq!(store.load_checkpoint(&consumer_name).await.unwrap(), Some(stream_sequences[4]));
+    }
+}
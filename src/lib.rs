@@ -31,6 +31,10 @@ pub mod workflow;
 // Category Theory traits (FRP/CT compliance)
 pub mod category_theory;
 
+// Synchronous facade for non-async callers
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
 // Re-export main types
 pub use aggregate::{Person, PersonId, PersonMarker};
 pub use commands::PersonCommand;
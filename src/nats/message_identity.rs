@@ -221,6 +221,29 @@ impl PersonActor {
     pub fn contact_system(name: &str) -> Self {
         Self::ContactSystem(name.to_string())
     }
+
+    /// Parse the `kind:value` form written by `Display`
+    pub fn parse(actor: &str) -> Self {
+        let Some((kind, value)) = actor.split_once(':') else {
+            return Self::Unknown;
+        };
+        let value = value.to_string();
+        match kind {
+            "user" => Self::User(value),
+            "system" => Self::System(value),
+            "api" => Self::ApiClient(value),
+            "job" => Self::Job(value),
+            "hr" => Self::HrSystem(value),
+            "idp" => Self::IdentityProvider(value),
+            "skills" => Self::SkillsSystem(value),
+            "network" => Self::NetworkSystem(value),
+            "employment" => Self::EmploymentSystem(value),
+            "contact" => Self::ContactSystem(value),
+            "data" => Self::DataSystem(value),
+            "privacy" => Self::PrivacySystem(value),
+            _ => Self::Unknown,
+        }
+    }
 }
 
 impl std::fmt::Display for PersonActor {
@@ -370,6 +393,43 @@ impl MessageIdentity {
     pub fn is_correlated_with(&self, other: &MessageIdentity) -> bool {
         self.correlation_id == other.correlation_id
     }
+
+    /// Convert to message headers
+    pub fn to_headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("x-message-id".to_string(), self.message_id.to_string());
+        headers.insert("x-correlation-id".to_string(), self.correlation_id.to_string());
+        headers.insert("x-causation-id".to_string(), self.causation_id.to_string());
+        headers.insert("x-actor".to_string(), self.actor.to_string());
+        headers
+    }
+
+    /// Create from message headers
+    ///
+    /// Missing or unreadable headers fall back to what [`MessageIdentity::new`]
+    /// would give, so a message sent without identity headers starts its own
+    /// correlation group.
+    pub fn from_headers(headers: &HashMap<String, String>) -> Self {
+        let message_id = headers.get("x-message-id")
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .map(MessageId::from_uuid)
+            .unwrap_or_default();
+        Self {
+            correlation_id: headers.get("x-correlation-id")
+                .map(|id| CorrelationId::from(id.as_str()))
+                .unwrap_or_else(|| CorrelationId::from(message_id.as_uuid())),
+            causation_id: headers.get("x-causation-id")
+                .map(|id| CausationId::from(id.as_str()))
+                .unwrap_or_else(|| CausationId::from(message_id.clone())),
+            actor: headers.get("x-actor")
+                .map(String::as_str)
+                .map(PersonActor::parse)
+                .unwrap_or_default(),
+            message_id,
+            timestamp: Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
 }
 
 impl Default for MessageIdentity {
@@ -627,6 +687,22 @@ mod tests {
         assert_eq!(restored.span_id, context.span_id);
     }
     
+    #[test]
+    fn test_identity_headers_round_trip() {
+        let parent = MessageIdentity::for_hr_system("workday-hr");
+        let identity = parent.create_child();
+
+        let restored = MessageIdentity::from_headers(&identity.to_headers());
+        assert_eq!(restored.message_id, identity.message_id);
+        assert!(restored.is_correlated_with(&parent));
+        assert!(restored.is_caused_by(&parent.message_id));
+        assert_eq!(restored.actor, PersonActor::hr_system("workday-hr"));
+
+        let fresh = MessageIdentity::from_headers(&HashMap::new());
+        assert_eq!(fresh.correlation_id, CorrelationId::from(fresh.message_id.as_uuid()));
+        assert_eq!(fresh.actor, PersonActor::Unknown);
+    }
+
     #[test]
     fn test_employment_workflow_tracing() {
         // Test employment workflow message tracing
@@ -4,9 +4,12 @@
 //! - Subject algebra for events, commands, and queries
 //! - Message identity and correlation
 //! - Event publishing and subscription utilities
+//! - `tracing` spans correlating command handling logs
 
 pub mod subjects;
 pub mod message_identity;
+pub mod spans;
 
 pub use subjects::*;
-pub use message_identity::*;
\ No newline at end of file
+pub use message_identity::*;
+pub use spans::command_span;
\ No newline at end of file
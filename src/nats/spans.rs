@@ -0,0 +1,96 @@
+//! `tracing` spans for person messages
+//!
+//! [`command_span`] turns a command envelope's [`MessageIdentity`] into span
+//! fields, so every log line emitted while the command is handled carries the
+//! correlation and causation ids it arrived with. If the envelope's headers
+//! hold a [`PersonTracingContext`], its trace and span ids are recorded too,
+//! linking the logs to the distributed trace.
+
+use cim_domain::formal_domain::DomainCommand;
+use tracing::{field, Span};
+
+use super::message_identity::{PersonMessageEnvelope, PersonTracingContext};
+use crate::commands::PersonCommand;
+
+/// Span covering the handling of a command envelope
+///
+/// Fields: `message_id`, `correlation_id`, `causation_id`, `person_id`,
+/// `command_type`, `actor`, and `trace_id` / `parent_span_id` when the
+/// envelope carries tracing headers.
+pub fn command_span(envelope: &PersonMessageEnvelope<PersonCommand>) -> Span {
+    let identity = &envelope.identity;
+    let span = tracing::info_span!(
+        "person_command",
+        message_id = %identity.message_id,
+        correlation_id = %identity.correlation_id,
+        causation_id = %identity.causation_id,
+        person_id = %envelope.payload.aggregate_id(),
+        command_type = envelope.payload.name(),
+        actor = %identity.actor,
+        trace_id = field::Empty,
+        parent_span_id = field::Empty,
+    );
+
+    if let Some(context) = PersonTracingContext::from_headers(&envelope.headers) {
+        span.record("trace_id", context.trace_id.as_str());
+        span.record("parent_span_id", context.span_id.as_str());
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::commands::RemoveTag;
+    use crate::nats::MessageIdentity;
+    use crate::value_objects::Tag;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_logs_inside_command_span_carry_identity_and_trace() {
+        let person_id = PersonId::new();
+        let command = PersonCommand::RemoveTag(RemoveTag {
+            person_id,
+            tag: Tag::new("segment", "vip").unwrap(),
+        });
+        let identity = MessageIdentity::for_system("crm");
+        let context = PersonTracingContext::new();
+        let mut envelope = PersonMessageEnvelope::new(command, "person.commands.test".to_string(), identity.clone());
+        envelope.headers = context.to_headers();
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let _entered = command_span(&envelope).entered();
+            tracing::info!("removing tag");
+        });
+
+        let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("removing tag"));
+        assert!(logs.contains(&format!("correlation_id={}", identity.correlation_id)));
+        assert!(logs.contains(&format!("causation_id={}", identity.causation_id)));
+        assert!(logs.contains(&format!("person_id={person_id}")));
+        assert!(logs.contains("command_type=\"RemoveTag\""));
+        assert!(logs.contains(&format!("trace_id=\"{}\"", context.trace_id)));
+    }
+}
@@ -4,6 +4,7 @@
 //! following CIM domain conventions and enabling efficient wildcard subscriptions.
 
 use std::fmt;
+use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 
 /// Root-level subject categories for Person domain
@@ -400,6 +401,129 @@ impl PersonSubject {
             entity_id: Some(person_id.to_string()),
         }
     }
+    
+    /// Parse a subject rendered by [`Display`](fmt::Display) back into its parts
+    ///
+    /// Expects `[namespace.]root.person.aggregate[.scope.id][.operation][.entity_id]`.
+    /// The operation has to be a known event, command or query type for the
+    /// root, except for the cross-domain `document.<cid>.<event>` and
+    /// `location.<id>.<event>` forms. Ids containing dots can't be told
+    /// apart from extra tokens, so subjects built from them don't parse.
+    pub fn parse(subject: &str) -> Result<Self, SubjectParseError> {
+        if subject.is_empty() {
+            return Err(SubjectParseError::Empty);
+        }
+        let tokens: Vec<&str> = subject.split('.').collect();
+        if tokens.iter().any(|token| token.is_empty()) {
+            return Err(SubjectParseError::EmptyToken(subject.to_string()));
+        }
+        if tokens.iter().any(|token| *token == "*" || *token == ">") {
+            return Err(SubjectParseError::Wildcard(subject.to_string()));
+        }
+
+        let mut tokens = tokens.into_iter().peekable();
+        let mut next = |part: &'static str| tokens.next().ok_or(SubjectParseError::Missing(part));
+
+        let first = next("root")?;
+        let (namespace, root) = match parse_root(first) {
+            Some(root) => (None, root),
+            None => {
+                let root = next("root")?;
+                let root = parse_root(root).ok_or_else(|| SubjectParseError::UnknownRoot(root.to_string()))?;
+                (Some(first.to_string()), root)
+            }
+        };
+
+        let domain = next("domain")?;
+        if domain != "person" {
+            return Err(SubjectParseError::UnknownDomain(domain.to_string()));
+        }
+        let aggregate: PersonAggregate = next("aggregate")?.parse()?;
+
+        let mut subject = Self {
+            namespace,
+            root,
+            domain: domain.to_string(),
+            aggregate,
+            scope: PersonScope::Global,
+            operation: None,
+            entity_id: None,
+        };
+
+        let scope_kind = tokens.next_if(|token| matches!(*token, "user" | "org" | "team" | "region" | "dept"));
+        if let Some(kind) = scope_kind {
+            let id = tokens.next().ok_or(SubjectParseError::Missing("scope id"))?.to_string();
+            subject.scope = match kind {
+                "user" => PersonScope::User(id),
+                "org" => PersonScope::Organization(id),
+                "team" => PersonScope::Team(id),
+                "region" => PersonScope::Region(id),
+                _ => PersonScope::Department(id),
+            };
+        }
+
+        let Some(operation) = tokens.next() else {
+            return Ok(subject);
+        };
+        subject.operation = Some(match operation {
+            "document" | "location" if subject.root == PersonSubjectRoot::Events => {
+                let related_id = tokens.next().ok_or(SubjectParseError::Missing("related id"))?;
+                let event_type = tokens.next().ok_or(SubjectParseError::Missing("related event type"))?;
+                format!("{operation}.{related_id}.{event_type}")
+            }
+            _ => {
+                match subject.root {
+                    PersonSubjectRoot::Events => operation.parse::<PersonEventType>()?.to_string(),
+                    PersonSubjectRoot::Commands => operation.parse::<PersonCommandType>()?.to_string(),
+                    PersonSubjectRoot::Queries => operation.parse::<PersonQueryType>()?.to_string(),
+                }
+            }
+        });
+
+        subject.entity_id = tokens.next().map(str::to_string);
+        let trailing: Vec<&str> = tokens.collect();
+        if !trailing.is_empty() {
+            return Err(SubjectParseError::TrailingTokens(trailing.join(".")));
+        }
+        Ok(subject)
+    }
+
+    /// Check whether a NATS subscription pattern would receive this subject
+    ///
+    /// Tokens are compared against the rendered subject: `*` matches exactly
+    /// one token and `>`, only valid as the last token, matches one or more.
+    pub fn matches(&self, pattern: &str) -> bool {
+        subject_matches(&self.to_string(), pattern)
+    }
+}
+
+fn parse_root(token: &str) -> Option<PersonSubjectRoot> {
+    match token {
+        "events" => Some(PersonSubjectRoot::Events),
+        "commands" => Some(PersonSubjectRoot::Commands),
+        "queries" => Some(PersonSubjectRoot::Queries),
+        _ => None,
+    }
+}
+
+fn subject_matches(subject: &str, pattern: &str) -> bool {
+    let mut subject_tokens = subject.split('.');
+    let mut pattern_tokens = pattern.split('.').peekable();
+
+    while let Some(token) = pattern_tokens.next() {
+        if token.is_empty() {
+            return false;
+        }
+        if token == ">" {
+            return pattern_tokens.peek().is_none() && subject_tokens.next().is_some();
+        }
+        match subject_tokens.next() {
+            Some(subject_token) if token == "*" || token == subject_token => {}
+            _ => return false,
+        }
+    }
+
+    subject_tokens.next().is_none()
 }
 
 impl Default for PersonSubject {
@@ -601,6 +725,220 @@ impl fmt::Display for PersonScope {
     }
 }
 
+impl PersonAggregate {
+    /// Every aggregate, in declaration order
+    pub const ALL: &'static [PersonAggregate] = &[
+        PersonAggregate::Person,
+        PersonAggregate::Identity,
+        PersonAggregate::Employment,
+        PersonAggregate::Skills,
+        PersonAggregate::Network,
+        PersonAggregate::Preferences,
+        PersonAggregate::Demographics,
+        PersonAggregate::Contact,
+    ];
+}
+
+impl FromStr for PersonAggregate {
+    type Err = SubjectParseError;
+
+    /// Parse a rendered subject token, e.g. `skills`
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Self::ALL.iter()
+            .find(|known| known.to_string() == token)
+            .cloned()
+            .ok_or_else(|| SubjectParseError::UnknownAggregate(token.to_string()))
+    }
+}
+
+impl PersonEventType {
+    /// Every event type, in declaration order
+    pub const ALL: &'static [PersonEventType] = &[
+        PersonEventType::Created,
+        PersonEventType::Updated,
+        PersonEventType::Archived,
+        PersonEventType::Reactivated,
+        PersonEventType::Merged,
+        PersonEventType::Split,
+        PersonEventType::NameUpdated,
+        PersonEventType::BirthDateSet,
+        PersonEventType::DeathRecorded,
+        PersonEventType::IdentifierAdded,
+        PersonEventType::IdentifierRemoved,
+        PersonEventType::EmploymentAdded,
+        PersonEventType::EmploymentUpdated,
+        PersonEventType::EmploymentEnded,
+        PersonEventType::RoleChanged,
+        PersonEventType::OrganizationChanged,
+        PersonEventType::SkillAdded,
+        PersonEventType::SkillUpdated,
+        PersonEventType::SkillRemoved,
+        PersonEventType::SkillEndorsed,
+        PersonEventType::CertificationAdded,
+        PersonEventType::CertificationExpired,
+        PersonEventType::ConnectionRequested,
+        PersonEventType::ConnectionAccepted,
+        PersonEventType::ConnectionRejected,
+        PersonEventType::ConnectionRemoved,
+        PersonEventType::NetworkUpdated,
+        PersonEventType::ContactAdded,
+        PersonEventType::ContactUpdated,
+        PersonEventType::ContactRemoved,
+        PersonEventType::ContactVerified,
+        PersonEventType::ComponentRegistered,
+        PersonEventType::ComponentUnregistered,
+        PersonEventType::ComponentDataUpdated,
+        PersonEventType::PrivacySettingsUpdated,
+        PersonEventType::ConsentGiven,
+        PersonEventType::ConsentRevoked,
+        PersonEventType::DataExportRequested,
+        PersonEventType::DataDeletionRequested,
+    ];
+}
+
+impl FromStr for PersonEventType {
+    type Err = SubjectParseError;
+
+    /// Parse a rendered subject token, e.g. `skill_added`
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Self::ALL.iter()
+            .find(|known| known.to_string() == token)
+            .cloned()
+            .ok_or_else(|| SubjectParseError::UnknownEventType(token.to_string()))
+    }
+}
+
+impl PersonCommandType {
+    /// Every command type, in declaration order
+    pub const ALL: &'static [PersonCommandType] = &[
+        PersonCommandType::CreatePerson,
+        PersonCommandType::UpdatePerson,
+        PersonCommandType::ArchivePerson,
+        PersonCommandType::ReactivatePerson,
+        PersonCommandType::MergePerson,
+        PersonCommandType::UpdateName,
+        PersonCommandType::SetBirthDate,
+        PersonCommandType::RecordDeath,
+        PersonCommandType::AddIdentifier,
+        PersonCommandType::RemoveIdentifier,
+        PersonCommandType::AddEmployment,
+        PersonCommandType::UpdateEmployment,
+        PersonCommandType::EndEmployment,
+        PersonCommandType::ChangeRole,
+        PersonCommandType::AddSkill,
+        PersonCommandType::UpdateSkill,
+        PersonCommandType::RemoveSkill,
+        PersonCommandType::EndorseSkill,
+        PersonCommandType::AddCertification,
+        PersonCommandType::RequestConnection,
+        PersonCommandType::AcceptConnection,
+        PersonCommandType::RejectConnection,
+        PersonCommandType::RemoveConnection,
+        PersonCommandType::AddContact,
+        PersonCommandType::UpdateContact,
+        PersonCommandType::RemoveContact,
+        PersonCommandType::VerifyContact,
+        PersonCommandType::RegisterComponent,
+        PersonCommandType::UnregisterComponent,
+        PersonCommandType::UpdateComponentData,
+        PersonCommandType::UpdatePrivacySettings,
+        PersonCommandType::GiveConsent,
+        PersonCommandType::RevokeConsent,
+        PersonCommandType::RequestDataExport,
+        PersonCommandType::RequestDataDeletion,
+    ];
+}
+
+impl FromStr for PersonCommandType {
+    type Err = SubjectParseError;
+
+    /// Parse a rendered subject token, e.g. `update_name`
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Self::ALL.iter()
+            .find(|known| known.to_string() == token)
+            .cloned()
+            .ok_or_else(|| SubjectParseError::UnknownCommandType(token.to_string()))
+    }
+}
+
+impl PersonQueryType {
+    /// Every query type, in declaration order
+    pub const ALL: &'static [PersonQueryType] = &[
+        PersonQueryType::GetPerson,
+        PersonQueryType::ListPersons,
+        PersonQueryType::SearchPersons,
+        PersonQueryType::GetPersonHistory,
+        PersonQueryType::FindByIdentifier,
+        PersonQueryType::GetIdentityHistory,
+        PersonQueryType::GetEmploymentHistory,
+        PersonQueryType::FindByEmployer,
+        PersonQueryType::FindByRole,
+        PersonQueryType::GetSkills,
+        PersonQueryType::SearchBySkill,
+        PersonQueryType::GetSkillEndorsements,
+        PersonQueryType::GetCertifications,
+        PersonQueryType::GetConnections,
+        PersonQueryType::GetConnectionRequests,
+        PersonQueryType::FindMutualConnections,
+        PersonQueryType::GetNetworkAnalysis,
+        PersonQueryType::GetContacts,
+        PersonQueryType::FindByContact,
+        PersonQueryType::VerifyContactReachability,
+        PersonQueryType::GetPrivacySettings,
+        PersonQueryType::GetConsentHistory,
+        PersonQueryType::GetDataExportStatus,
+    ];
+}
+
+impl FromStr for PersonQueryType {
+    type Err = SubjectParseError;
+
+    /// Parse a rendered subject token, e.g. `get_person`
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Self::ALL.iter()
+            .find(|known| known.to_string() == token)
+            .cloned()
+            .ok_or_else(|| SubjectParseError::UnknownQueryType(token.to_string()))
+    }
+}
+
+/// Why a subject string is not a valid person subject
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubjectParseError {
+    #[error("Subject is empty")]
+    Empty,
+
+    #[error("Subject {0:?} contains an empty token")]
+    EmptyToken(String),
+
+    #[error("Subject {0:?} contains a wildcard; only concrete subjects can be parsed")]
+    Wildcard(String),
+
+    #[error("Subject ends before its {0}")]
+    Missing(&'static str),
+
+    #[error("Unknown subject root {0:?}, expected events, commands or queries")]
+    UnknownRoot(String),
+
+    #[error("Subject is for domain {0:?}, not person")]
+    UnknownDomain(String),
+
+    #[error("Unknown person aggregate {0:?}")]
+    UnknownAggregate(String),
+
+    #[error("Unknown person event type {0:?}")]
+    UnknownEventType(String),
+
+    #[error("Unknown person command type {0:?}")]
+    UnknownCommandType(String),
+
+    #[error("Unknown person query type {0:?}")]
+    UnknownQueryType(String),
+
+    #[error("Unexpected tokens after the entity id: {0:?}")]
+    TrailingTokens(String),
+}
+
 /// Builder for constructing PersonSubject instances
 #[derive(Debug, Default)]
 pub struct PersonSubjectBuilder {
@@ -655,6 +993,21 @@ impl PersonSubjectBuilder {
     pub fn build(self) -> PersonSubject {
         self.subject
     }
+    
+    /// Subscription subject for every event, command and query about one person
+    ///
+    /// Root, aggregate and operation are wildcards; the namespace set on the
+    /// builder is kept. Matches globally scoped subjects with a single-token
+    /// operation that end in `person_id`.
+    pub fn wildcard_for_aggregate(self, person_id: &str) -> String {
+        let mut parts = Vec::new();
+        if let Some(namespace) = self.subject.namespace {
+            parts.push(namespace);
+        }
+        parts.extend(["*".to_string(), self.subject.domain, "*".to_string(), "*".to_string()]);
+        parts.push(person_id.to_string());
+        parts.join(".")
+    }
 }
 
 #[cfg(test)]
@@ -829,4 +1182,119 @@ mod tests {
         );
         assert_eq!(connection_accepted.to_string(), "events.person.network.connection_accepted.person123");
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_single_and_tail_wildcards() {
+        let subject = PersonSubject::event(PersonAggregate::Person, PersonEventType::Created, "person123");
+        
+        assert!(subject.matches("events.person.person.created.person123"));
+        assert!(subject.matches("events.person.person.*.*"));
+        assert!(subject.matches("*.person.person.created.person123"));
+        assert!(subject.matches("events.person.>"));
+        assert!(subject.matches(">"));
+        
+        // `*` is exactly one token; `>` needs at least one
+        assert!(!subject.matches("events.person.person.*"));
+        assert!(!subject.matches("events.person.person.created.person123.*"));
+        assert!(!subject.matches("events.person.person.created.person123.>"));
+        assert!(!subject.matches("events.person.person.created"));
+        
+        // `>` is only a wildcard at the end, and empty tokens never match
+        assert!(!subject.matches("events.>.created.person123"));
+        assert!(!subject.matches("events..person.created.person123"));
+        assert!(!subject.matches(""));
+        assert!(!subject.matches("events.person.person.create*.person123"));
+    }
+    
+    #[test]
+    fn test_wildcard_for_aggregate() {
+        let pattern = PersonSubjectBuilder::new().wildcard_for_aggregate("person123");
+        assert_eq!(pattern, "*.person.*.*.person123");
+        
+        assert!(PersonSubject::event(PersonAggregate::Skills, PersonEventType::SkillAdded, "person123").matches(&pattern));
+        assert!(PersonSubject::command(PersonAggregate::Person, PersonCommandType::UpdateName, "person123").matches(&pattern));
+        assert!(PersonSubject::query(PersonAggregate::Person, PersonQueryType::GetPerson)
+            .with_entity_id("person123".to_string())
+            .matches(&pattern));
+        assert!(!PersonSubject::event(PersonAggregate::Person, PersonEventType::Created, "person456").matches(&pattern));
+        
+        let tenant_pattern = PersonSubjectBuilder::new()
+            .namespace("tenant1".to_string())
+            .wildcard_for_aggregate("person123");
+        let subject = PersonSubject::event(PersonAggregate::Person, PersonEventType::Created, "person123");
+        assert!(subject.clone().with_namespace("tenant1".to_string()).matches(&tenant_pattern));
+        assert!(!subject.matches(&tenant_pattern));
+    }
+    
+    #[test]
+    fn test_rendered_subjects_parse_back() {
+        let subjects = vec![
+            PersonSubject::event(PersonAggregate::Person, PersonEventType::Created, "person123"),
+            PersonSubject::event(PersonAggregate::Person, PersonEventType::Created, "person123")
+                .with_namespace("tenant1".to_string()),
+            PersonSubject::user_event("user456", PersonAggregate::Skills, PersonEventType::SkillAdded, "person123"),
+            PersonSubject::org_event("org789", PersonAggregate::Employment, PersonEventType::EmploymentAdded, "person123"),
+            PersonSubject::team_event("team1", PersonAggregate::Network, PersonEventType::ConnectionAccepted, "person123"),
+            PersonSubject::command(PersonAggregate::Person, PersonCommandType::UpdateName, "person123")
+                .with_scope(PersonScope::Region("eu".to_string())),
+            PersonSubject::command(PersonAggregate::Contact, PersonCommandType::VerifyContact, "person123")
+                .with_scope(PersonScope::Department("hr".to_string())),
+            PersonSubject::query(PersonAggregate::Person, PersonQueryType::GetPerson),
+            PersonSubject::query(PersonAggregate::Skills, PersonQueryType::SearchBySkill)
+                .with_entity_id("person123".to_string()),
+            PersonSubject::person_location_event("person123", "loc42", "moved_in"),
+            PersonSubjectBuilder::new()
+                .namespace("tenant1".to_string())
+                .events()
+                .aggregate(PersonAggregate::Skills)
+                .scope(PersonScope::User("user123".to_string()))
+                .operation("skill_added".to_string())
+                .entity_id("person456".to_string())
+                .build(),
+            PersonSubjectBuilder::new().commands().aggregate(PersonAggregate::Preferences).build(),
+        ];
+
+        for subject in subjects {
+            let rendered = subject.to_string();
+            assert_eq!(PersonSubject::parse(&rendered), Ok(subject), "{rendered}");
+        }
+
+        // The parsed operation is typed by the root
+        let parsed = PersonSubject::parse("commands.person.person.create_person.p1").unwrap();
+        assert_eq!(parsed.operation.as_deref().map(str::parse::<PersonCommandType>), Some(Ok(PersonCommandType::CreatePerson)));
+        assert_eq!(PersonEventType::ALL.len(), 39);
+    }
+    
+    #[test]
+    fn test_invalid_subjects_report_the_bad_token() {
+        use SubjectParseError::*;
+        
+        assert_eq!(PersonSubject::parse(""), Err(Empty));
+        assert_eq!(PersonSubject::parse("events..person"), Err(EmptyToken("events..person".to_string())));
+        assert_eq!(PersonSubject::parse("events.person.person.*.*"), Err(Wildcard("events.person.person.*.*".to_string())));
+        assert_eq!(PersonSubject::parse("events.person"), Err(Missing("aggregate")));
+        assert_eq!(PersonSubject::parse("tenant1"), Err(Missing("root")));
+        assert_eq!(PersonSubject::parse("tenant1.replies.person.person"), Err(UnknownRoot("replies".to_string())));
+        assert_eq!(PersonSubject::parse("events.org.person.created.p1"), Err(UnknownDomain("org".to_string())));
+        assert_eq!(PersonSubject::parse("events.person.pets.created.p1"), Err(UnknownAggregate("pets".to_string())));
+        assert_eq!(PersonSubject::parse("events.person.skills.user"), Err(Missing("scope id")));
+        
+        // Operations are checked against the root's types
+        assert_eq!(
+            PersonSubject::parse("events.person.person.create_person.p1"),
+            Err(UnknownEventType("create_person".to_string())),
+        );
+        assert_eq!(
+            PersonSubject::parse("commands.person.person.created.p1"),
+            Err(UnknownCommandType("created".to_string())),
+        );
+        assert_eq!(
+            PersonSubject::parse("queries.person.person.get_everything"),
+            Err(UnknownQueryType("get_everything".to_string())),
+        );
+        assert_eq!(
+            PersonSubject::parse("events.person.person.created.p1.extra.tokens"),
+            Err(TrailingTokens("extra.tokens".to_string())),
+        );
+    }
+}
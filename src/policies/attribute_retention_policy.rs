@@ -0,0 +1,213 @@
+//! Policy for invalidating attributes past their retention period
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use cim_domain::DomainResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::aggregate::Person;
+use crate::commands::{InvalidateAttribute, PersonCommand};
+use crate::events::PersonEventV2;
+use crate::infrastructure::PersonRepository;
+use crate::nats::MessageIdentity;
+use crate::value_objects::AttributeType;
+use super::Policy;
+
+/// Reason recorded on attributes invalidated by this policy
+pub const RETENTION_EXPIRED_REASON: &str = "Retention period expired";
+
+/// Policy that invalidates attributes once they have been kept too long
+///
+/// Retention runs from when an attribute was recorded. Any event for a
+/// person triggers a scan of their current attributes; attributes whose
+/// validity has already ended are skipped, so repeated events do not
+/// invalidate the same attribute twice. Each command names the expired
+/// attribute by when it was recorded, so a newer attribute of the same type
+/// stays valid. Attribute types without a configured period are kept
+/// indefinitely.
+pub struct RetentionPolicy {
+    repository: Arc<PersonRepository>,
+    retention: HashMap<AttributeType, Duration>,
+}
+
+impl RetentionPolicy {
+    pub fn new(repository: Arc<PersonRepository>, retention: HashMap<AttributeType, Duration>) -> Self {
+        Self {
+            repository,
+            retention,
+        }
+    }
+
+    /// Invalidation commands for `person`'s attributes expired at `now`
+    pub fn expired_attributes(&self, person: &Person, now: DateTime<Utc>) -> Vec<PersonCommand> {
+        let today = now.date_naive();
+        person.attributes.attributes.iter()
+            .filter(|attr| attr.temporal.valid_until.map_or(true, |end| end > today))
+            .filter(|attr| {
+                self.retention.get(&attr.attribute_type)
+                    .is_some_and(|period| attr.temporal.recorded_at + *period <= now)
+            })
+            .map(|attr| PersonCommand::InvalidateAttribute(InvalidateAttribute {
+                person_id: person.id,
+                attribute_type: attr.attribute_type.clone(),
+                reason: Some(RETENTION_EXPIRED_REASON.to_string()),
+                attribute_recorded_at: Some(attr.temporal.recorded_at),
+            }))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Policy for RetentionPolicy {
+    async fn evaluate(&self, event: &PersonEventV2, _identity: &MessageIdentity) -> DomainResult<Vec<PersonCommand>> {
+        let Some(person) = self.repository.load(event.aggregate_id()).await? else {
+            return Ok(vec![]);
+        };
+        Ok(self.expired_attributes(&person, Utc::now()))
+    }
+
+    fn name(&self) -> &str {
+        "AttributeRetention"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cim_domain::formal_domain::Aggregate;
+    use crate::aggregate::PersonId;
+    use crate::commands::{CreatePerson, RecordAttribute};
+    use crate::events::EventMetadata;
+    use crate::infrastructure::{EventStore, InMemoryEventStore, InMemorySnapshotStore};
+    use crate::value_objects::{
+        AttributeSource, AttributeValue, ConfidenceLevel, DemographicAttributeType, PersonAttribute,
+        PersonName, Provenance, TemporalValidity,
+    };
+
+    fn demographic(
+        person_id: PersonId,
+        kind: DemographicAttributeType,
+        value: &str,
+        recorded_at: DateTime<Utc>,
+    ) -> PersonCommand {
+        PersonCommand::RecordAttribute(RecordAttribute {
+            person_id,
+            attribute: PersonAttribute::new(
+                AttributeType::Demographic(kind),
+                AttributeValue::Text(value.to_string()),
+                TemporalValidity::of(recorded_at),
+                Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+            ),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_only_attributes_past_retention_are_invalidated_once() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = Arc::new(PersonRepository::new(store.clone(), Arc::new(InMemorySnapshotStore::new()), 100));
+        let nationality = AttributeType::Demographic(DemographicAttributeType::Nationality);
+        let policy = RetentionPolicy::new(
+            repository,
+            HashMap::from([(nationality.clone(), Duration::days(365 * 2))]),
+        );
+
+        let person_id = PersonId::new();
+        let long_ago = Utc::now() - Duration::days(365 * 3);
+        let commands = vec![
+            PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                source: "test".to_string(),
+            }),
+            demographic(person_id, DemographicAttributeType::Nationality, "British", long_ago),
+            // No retention configured for the language, however old
+            demographic(person_id, DemographicAttributeType::PreferredLanguage, "en", long_ago),
+        ];
+        let mut person = Person::empty();
+        let mut events = Vec::new();
+        for command in commands {
+            let (next, emitted) = person.handle(command).unwrap();
+            person = next;
+            events.extend(emitted);
+        }
+        store.append_events(person_id, events, None).await.unwrap();
+
+        let event = PersonEventV2::BirthDateSet {
+            person_id,
+            birth_date: chrono::NaiveDate::from_ymd_opt(1815, 12, 10).unwrap(),
+            metadata: EventMetadata::new(),
+        };
+        // Two years ago it still had a year of retention left
+        assert!(policy.expired_attributes(&person, Utc::now() - Duration::days(365 * 2)).is_empty());
+
+        let commands = policy.evaluate(&event, &MessageIdentity::new()).await.unwrap();
+        assert_eq!(commands.len(), 1);
+        let PersonCommand::InvalidateAttribute(invalidate) = &commands[0] else {
+            panic!("expected InvalidateAttribute");
+        };
+        assert_eq!(invalidate.person_id, person_id);
+        assert_eq!(invalidate.attribute_type, nationality);
+
+        // Once invalidated, later events leave it alone
+        let (_, invalidated) = person.handle(commands[0].clone()).unwrap();
+        store.append_events(person_id, invalidated, None).await.unwrap();
+        assert!(policy.evaluate(&event, &MessageIdentity::new()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_only_the_expired_one_of_two_same_type_attributes_is_invalidated() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = Arc::new(PersonRepository::new(store.clone(), Arc::new(InMemorySnapshotStore::new()), 100));
+        let nationality = AttributeType::Demographic(DemographicAttributeType::Nationality);
+        let policy = RetentionPolicy::new(
+            repository,
+            HashMap::from([(nationality.clone(), Duration::days(365 * 2))]),
+        );
+
+        let person_id = PersonId::new();
+        let long_ago = Utc::now() - Duration::days(365 * 3);
+        let recently = Utc::now() - Duration::days(30);
+        let commands = vec![
+            PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                source: "test".to_string(),
+            }),
+            demographic(person_id, DemographicAttributeType::Nationality, "British", long_ago),
+            demographic(person_id, DemographicAttributeType::Nationality, "Italian", recently),
+        ];
+        let mut person = Person::empty();
+        let mut events = Vec::new();
+        for command in commands {
+            let (next, emitted) = person.handle(command).unwrap();
+            person = next;
+            events.extend(emitted);
+        }
+        store.append_events(person_id, events, None).await.unwrap();
+
+        let event = PersonEventV2::BirthDateSet {
+            person_id,
+            birth_date: chrono::NaiveDate::from_ymd_opt(1815, 12, 10).unwrap(),
+            metadata: EventMetadata::new(),
+        };
+        let commands = policy.evaluate(&event, &MessageIdentity::new()).await.unwrap();
+        assert_eq!(commands.len(), 1);
+        let PersonCommand::InvalidateAttribute(invalidate) = &commands[0] else {
+            panic!("expected InvalidateAttribute");
+        };
+        assert_eq!(invalidate.attribute_recorded_at, Some(long_ago));
+
+        let (person, invalidated) = person.handle(commands[0].clone()).unwrap();
+        let today = Utc::now().date_naive();
+        let valid: Vec<&AttributeValue> = person.attributes.attributes.iter()
+            .filter(|attr| attr.temporal.valid_until.map_or(true, |end| end > today))
+            .map(|attr| &attr.value)
+            .collect();
+        assert_eq!(valid, vec![&AttributeValue::Text("Italian".to_string())]);
+
+        // The expired one is gone, so the policy does not fire again
+        store.append_events(person_id, invalidated, None).await.unwrap();
+        assert!(policy.evaluate(&event, &MessageIdentity::new()).await.unwrap().is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use cim_domain::DomainResult;
 
 use crate::commands::{PersonCommand, ArchivePerson};
 use crate::events::PersonEventV2;
+use crate::nats::MessageIdentity;
 use super::Policy;
 
 /// Policy that archives persons after a period of inactivity
@@ -23,7 +24,7 @@ impl AutoArchiveInactivePersonsPolicy {
 
 #[async_trait]
 impl Policy for AutoArchiveInactivePersonsPolicy {
-    async fn evaluate(&self, event: &PersonEventV2) -> DomainResult<Vec<PersonCommand>> {
+    async fn evaluate(&self, event: &PersonEventV2, _identity: &MessageIdentity) -> DomainResult<Vec<PersonCommand>> {
         // This is a simplified example - in reality, you'd check activity from a projection
         if let PersonEventV2::Created { person_id, metadata, .. } = event {
             // Check if this is a reactivation after long inactivity
@@ -1,4 +1,9 @@
 //! Policy engine for event-driven business rules
+//!
+//! Commands generated by policies stay in the triggering event's NATS
+//! trace: each one is wrapped in a [`PersonMessageEnvelope`] whose identity
+//! keeps the event's correlation id and names the event's message id as its
+//! cause.
 
 use async_trait::async_trait;
 use cim_domain::DomainResult;
@@ -7,12 +12,16 @@ use tracing::{info, debug};
 
 use crate::commands::PersonCommand;
 use crate::events::PersonEventV2;
+use crate::infrastructure::PersonSubjects;
+use crate::nats::{MessageIdentity, PersonMessageEnvelope};
 
 /// Policy trait for event-driven rules
 #[async_trait]
 pub trait Policy: Send + Sync {
     /// Evaluate an event and potentially generate commands
-    async fn evaluate(&self, event: &PersonEventV2) -> DomainResult<Vec<PersonCommand>>;
+    ///
+    /// `identity` is the message identity the event was delivered with.
+    async fn evaluate(&self, event: &PersonEventV2, identity: &MessageIdentity) -> DomainResult<Vec<PersonCommand>>;
     
     /// Get the policy name
     fn name(&self) -> &str;
@@ -62,7 +71,15 @@ impl PolicyEngine {
     }
     
     /// Evaluate an event against all policies
-    pub async fn evaluate(&self, event: &PersonEventV2) -> Vec<PersonCommand> {
+    ///
+    /// Each generated command gets a child of `identity`, so it shares the
+    /// event's correlation id and is caused by the event's message. The
+    /// generating policy is recorded in the identity's `policy` metadata.
+    pub async fn evaluate(
+        &self,
+        event: &PersonEventV2,
+        identity: &MessageIdentity,
+    ) -> Vec<PersonMessageEnvelope<PersonCommand>> {
         let mut commands = Vec::new();
         
         for policy in &self.policies {
@@ -72,15 +89,20 @@ impl PolicyEngine {
             
             debug!("Evaluating policy {} for event {}", policy.name(), event.event_type());
             
-            match policy.evaluate(event).await {
+            match policy.evaluate(event, identity).await {
                 Ok(policy_commands) => {
                     if !policy_commands.is_empty() {
                         info!(
-                            "Policy {} generated {} commands",
+                            "Policy {} generated {} commands (correlation {})",
                             policy.name(),
-                            policy_commands.len()
+                            policy_commands.len(),
+                            identity.correlation_id
                         );
-                        commands.extend(policy_commands);
+                        commands.extend(policy_commands.into_iter().map(|command| {
+                            let subject = PersonSubjects::command_for(command.aggregate_id());
+                            let child = identity.create_child().with_metadata("policy", policy.name());
+                            PersonMessageEnvelope::new(command, subject, child)
+                        }));
                     }
                 }
                 Err(e) => {
@@ -97,8 +119,10 @@ impl PolicyEngine {
 // Example policies
 
 mod auto_archive_policy;
+mod attribute_retention_policy;
 
 pub use auto_archive_policy::AutoArchiveInactivePersonsPolicy;
+pub use attribute_retention_policy::{RetentionPolicy, RETENTION_EXPIRED_REASON};
 
 /// Create a default policy engine with standard policies
 pub fn create_default_policy_engine() -> PolicyEngine {
@@ -110,4 +134,79 @@ pub fn create_default_policy_engine() -> PolicyEngine {
     )));
 
     engine
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::commands::{AddTag, CreatePerson};
+    use crate::events::{EventMetadata, PersonEvent};
+    use crate::infrastructure::nats_integration::{execute_command, execute_command_caused_by};
+    use crate::infrastructure::{EventStore, InMemoryEventStore, InMemorySnapshotStore, PersonRepository};
+    use crate::value_objects::{PersonName, Tag};
+
+    /// Tags every newly created person for onboarding
+    struct TagNewcomers;
+
+    #[async_trait]
+    impl Policy for TagNewcomers {
+        async fn evaluate(&self, event: &PersonEventV2, _identity: &MessageIdentity) -> DomainResult<Vec<PersonCommand>> {
+            let PersonEventV2::Created { person_id, .. } = event else {
+                return Ok(vec![]);
+            };
+            Ok(vec![PersonCommand::AddTag(AddTag {
+                person_id: *person_id,
+                tag: Tag::new("lifecycle", "newcomer").unwrap(),
+            })])
+        }
+
+        fn name(&self) -> &str {
+            "TagNewcomers"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_correlation_survives_event_command_event_hop() {
+        let mut engine = PolicyEngine::new();
+        engine.register(Arc::new(TagNewcomers));
+
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = PersonRepository::new(store.clone(), Arc::new(InMemorySnapshotStore::new()), 100);
+        let person_id = PersonId::new();
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        execute_command(&repository, PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: name.clone(),
+            source: "test".to_string(),
+        }))
+        .await
+        .unwrap();
+        let created = PersonEventV2::Created {
+            person_id,
+            name,
+            source: "test".to_string(),
+            metadata: EventMetadata::new(),
+        };
+        let event_identity = MessageIdentity::for_system("person-service");
+
+        let commands = engine.evaluate(&created, &event_identity).await;
+        assert_eq!(commands.len(), 1);
+        let command = &commands[0];
+        assert_eq!(command.identity.correlation_id, event_identity.correlation_id);
+        assert!(command.identity.is_caused_by(&event_identity.message_id));
+        assert_eq!(command.identity.get_metadata("policy").map(String::as_str), Some("TagNewcomers"));
+        assert_eq!(command.subject, PersonSubjects::command_for(person_id));
+
+        // The handler stores the command's events as caused by the command message
+        let outcome = execute_command_caused_by(&repository, command.payload.clone(), &command.identity)
+            .await
+            .unwrap();
+        assert!(matches!(outcome.events[..], [PersonEvent::TagAdded(_)]));
+        let stored = store.get_events(person_id).await.unwrap();
+        let tagged = stored.last().unwrap();
+        assert!(matches!(tagged.event, PersonEvent::TagAdded(_)));
+        assert_eq!(tagged.correlation_id, event_identity.correlation_id.to_string());
+        assert_eq!(tagged.causation_id, command.identity.message_id.to_string());
+    }
+}
@@ -1,15 +1,16 @@
 //! Async projection update handlers
 
 use async_trait::async_trait;
-use cim_domain::DomainResult;
+use cim_domain::{DomainError, DomainResult};
+use serde::{de::DeserializeOwned, Serialize};
 use std::sync::Arc;
 use tracing::{info, debug};
 
 use crate::aggregate::PersonId;
 use crate::events::{PersonEventV2, StreamingEventEnvelope};
 use crate::infrastructure::{StreamingEventHandler, SubscriptionManager};
-use crate::projections::PersonSummary;
-use std::collections::HashMap;
+use crate::projections::{PersonSummary, SummaryStatus};
+use std::collections::{BTreeMap, HashMap};
 use chrono::{DateTime, Utc};
 
 /// Base trait for async projection handlers
@@ -66,6 +67,8 @@ impl AsyncProjectionHandler for SummaryProjectionHandler {
                     skills_count: 0,
                     component_count: 0,
                     last_updated: metadata.timestamp,
+                    merged_from: None,
+                    status: SummaryStatus::Active,
                 };
                 
                 self.storage.save(person_id, &summary).await?;
@@ -73,28 +76,31 @@ impl AsyncProjectionHandler for SummaryProjectionHandler {
             }
             
             PersonEventV2::NameUpdated { person_id, new_name, metadata, .. } => {
-                if let Some(mut summary) = self.storage.get(person_id).await? {
+                if let Some(previous) = self.storage.get(person_id).await? {
+                    let mut summary = previous.clone();
                     summary.name = new_name.full_name();
                     summary.last_updated = metadata.timestamp;
-                    self.storage.save(person_id, &summary).await?;
+                    write_changes(self.storage.as_ref(), person_id, &previous, &summary).await?;
                     debug!("Updated name in summary for person {}", person_id);
                 }
             }
             
             PersonEventV2::Suspended { person_id, metadata, .. } => {
-                if let Some(mut summary) = self.storage.get(person_id).await? {
+                if let Some(previous) = self.storage.get(person_id).await? {
                     // Mark as suspended in some way, perhaps via component_count or a custom field
+                    let mut summary = previous.clone();
                     summary.last_updated = metadata.timestamp;
-                    self.storage.save(person_id, &summary).await?;
+                    write_changes(self.storage.as_ref(), person_id, &previous, &summary).await?;
                     debug!("Suspended person {} in summary", person_id);
                 }
             }
             
             PersonEventV2::Activated { person_id, metadata, .. } => {
-                if let Some(mut summary) = self.storage.get(person_id).await? {
+                if let Some(previous) = self.storage.get(person_id).await? {
                     // Mark as active again
+                    let mut summary = previous.clone();
                     summary.last_updated = metadata.timestamp;
-                    self.storage.save(person_id, &summary).await?;
+                    write_changes(self.storage.as_ref(), person_id, &previous, &summary).await?;
                     debug!("Activated person {} in summary", person_id);
                 }
             }
@@ -187,6 +193,88 @@ pub trait ProjectionStorage<T>: Send + Sync {
     
     /// Delete a projection
     async fn delete(&self, id: &PersonId) -> DomainResult<()>;
+
+    /// Whether this store can update individual fields in place
+    fn supports_patch(&self) -> bool {
+        false
+    }
+
+    /// Apply a field-level patch to a stored projection
+    ///
+    /// Only called when `supports_patch` returns true.
+    async fn apply_patch(&self, _id: &PersonId, _patch: &ProjectionPatch) -> DomainResult<()> {
+        Err(DomainError::generic("Projection storage does not support patch updates"))
+    }
+}
+
+/// Minimal field-level delta between two versions of a projection
+///
+/// Fields are the top-level keys of the projection's serialized form, mapped
+/// to their new values.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProjectionPatch {
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+
+impl ProjectionPatch {
+    /// Compute the fields that differ between `before` and `after`
+    pub fn diff<T: Serialize>(before: &T, after: &T) -> DomainResult<Self> {
+        let before = to_object(before)?;
+        let after = to_object(after)?;
+
+        let fields = after.into_iter()
+            .filter(|(key, value)| before.get(key) != Some(value))
+            .collect();
+
+        Ok(Self { fields })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Apply this patch to a projection value, producing the patched value
+    pub fn apply_to<T: Serialize + DeserializeOwned>(&self, current: &T) -> DomainResult<T> {
+        let mut object = to_object(current)?;
+        for (key, value) in &self.fields {
+            object.insert(key.clone(), value.clone());
+        }
+        serde_json::from_value(serde_json::Value::Object(object))
+            .map_err(|e| DomainError::SerializationError(e.to_string()))
+    }
+}
+
+fn to_object<T: Serialize>(value: &T) -> DomainResult<serde_json::Map<String, serde_json::Value>> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(DomainError::SerializationError(
+            "Projection must serialize to an object to be patched".to_string(),
+        )),
+        Err(e) => Err(DomainError::SerializationError(e.to_string())),
+    }
+}
+
+/// Persist a projection change, patching when the store supports it
+///
+/// Falls back to a full write otherwise; unchanged projections are not written.
+pub async fn write_changes<T>(
+    storage: &dyn ProjectionStorage<T>,
+    id: &PersonId,
+    before: &T,
+    after: &T,
+) -> DomainResult<()>
+where
+    T: Serialize + Sync,
+{
+    if !storage.supports_patch() {
+        return storage.save(id, after).await;
+    }
+
+    let patch = ProjectionPatch::diff(before, after)?;
+    if patch.is_empty() {
+        return Ok(());
+    }
+    storage.apply_patch(id, &patch).await
 }
 
 /// Skill data structure
@@ -239,8 +327,88 @@ pub fn register_projection_handlers(
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::events::EventMetadata;
+    use crate::value_objects::PersonName;
+    use tokio::sync::Mutex;
+
+    /// Patch-capable storage that records every write it receives
+    #[derive(Default)]
+    struct RecordingStorage {
+        summaries: Mutex<HashMap<PersonId, PersonSummary>>,
+        full_writes: Mutex<usize>,
+        patches: Mutex<Vec<ProjectionPatch>>,
+    }
+
+    #[async_trait]
+    impl ProjectionStorage<PersonSummary> for RecordingStorage {
+        async fn save(&self, id: &PersonId, projection: &PersonSummary) -> DomainResult<()> {
+            *self.full_writes.lock().await += 1;
+            self.summaries.lock().await.insert(*id, projection.clone());
+            Ok(())
+        }
+
+        async fn get(&self, id: &PersonId) -> DomainResult<Option<PersonSummary>> {
+            Ok(self.summaries.lock().await.get(id).cloned())
+        }
+
+        async fn delete(&self, id: &PersonId) -> DomainResult<()> {
+            self.summaries.lock().await.remove(id);
+            Ok(())
+        }
+
+        fn supports_patch(&self) -> bool {
+            true
+        }
+
+        async fn apply_patch(&self, id: &PersonId, patch: &ProjectionPatch) -> DomainResult<()> {
+            let mut summaries = self.summaries.lock().await;
+            if let Some(current) = summaries.get(id) {
+                let patched = patch.apply_to(current)?;
+                summaries.insert(*id, patched);
+            }
+            self.patches.lock().await.push(patch.clone());
+            Ok(())
+        }
+    }
+
     #[tokio::test]
     async fn test_summary_projection_handler() {
         // Mock storage implementation would go here
     }
+
+    #[tokio::test]
+    async fn test_name_update_writes_name_patch_not_full_summary() {
+        let storage = Arc::new(RecordingStorage::default());
+        let handler = SummaryProjectionHandler::new(storage.clone());
+        let person_id = PersonId::new();
+        let old_name = PersonName::new("Ada".to_string(), "Byron".to_string());
+        let new_name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+
+        handler.handle_specific_event(&PersonEventV2::Created {
+            person_id,
+            name: old_name.clone(),
+            source: "test".to_string(),
+            metadata: EventMetadata::new(),
+        }).await.unwrap();
+        assert_eq!(*storage.full_writes.lock().await, 1);
+
+        handler.handle_specific_event(&PersonEventV2::NameUpdated {
+            person_id,
+            old_name,
+            new_name: new_name.clone(),
+            change_reason: None,
+            metadata: EventMetadata::new(),
+        }).await.unwrap();
+
+        // No second full write; one patch carrying the name (plus its timestamp)
+        assert_eq!(*storage.full_writes.lock().await, 1);
+        let patches = storage.patches.lock().await;
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].fields["name"], serde_json::json!(new_name.full_name()));
+        assert!(patches[0].fields.keys().all(|field| field == "name" || field == "last_updated"));
+
+        let stored = storage.get(&person_id).await.unwrap().unwrap();
+        assert_eq!(stored.name, new_name.full_name());
+    }
 }
\ No newline at end of file
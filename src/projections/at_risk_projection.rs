@@ -0,0 +1,239 @@
+//! At-risk persons projection
+//!
+//! Combines three signals into one composite risk score for customer
+//! success:
+//! - churn risk reported by behavioral analytics (0.0 to 1.0)
+//! - inactivity, measured from the person's last event against a window
+//! - unresolved support signals (open tickets, complaints)
+//!
+//! Churn risk and support signals come from other domains and are fed in
+//! through [`AtRiskProjection::record_churn_risk`] and the support signal
+//! methods; inactivity is tracked from person events.
+
+use super::PersonProjection;
+use crate::aggregate::PersonId;
+use crate::events::*;
+use chrono::{DateTime, Duration, Utc};
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Unresolved support signals at which the support component saturates
+const SUPPORT_SIGNAL_SATURATION: usize = 3;
+
+/// Relative weight of each risk component
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RiskWeights {
+    pub churn: f32,
+    pub inactivity: f32,
+    pub support: f32,
+}
+
+impl Default for RiskWeights {
+    fn default() -> Self {
+        Self {
+            churn: 0.5,
+            inactivity: 0.3,
+            support: 0.2,
+        }
+    }
+}
+
+/// Components of a person's risk score, each in `[0, 1]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskBreakdown {
+    pub churn_risk: f32,
+    /// Time since the last event relative to the inactivity window, capped at 1
+    pub inactivity: f32,
+    pub unresolved_support: f32,
+    /// Weighted composite score
+    pub score: f32,
+}
+
+#[derive(Debug, Clone)]
+struct RiskState {
+    last_activity: DateTime<Utc>,
+    churn_risk: f32,
+    open_support_signals: HashSet<String>,
+}
+
+/// Projection ranking persons by composite risk
+pub struct AtRiskProjection {
+    states: Arc<RwLock<HashMap<PersonId, RiskState>>>,
+    inactivity_window: Duration,
+    weights: RiskWeights,
+}
+
+impl Default for AtRiskProjection {
+    fn default() -> Self {
+        Self::new(Duration::days(30))
+    }
+}
+
+impl AtRiskProjection {
+    /// Create a projection treating `inactivity_window` without events as fully inactive
+    pub fn new(inactivity_window: Duration) -> Self {
+        Self {
+            states: Arc::new(RwLock::new(HashMap::new())),
+            inactivity_window,
+            weights: RiskWeights::default(),
+        }
+    }
+
+    pub fn with_weights(mut self, weights: RiskWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Record the latest churn risk from behavioral analytics
+    pub async fn record_churn_risk(&self, person_id: PersonId, churn_risk: f32) {
+        let mut states = self.states.write().await;
+        if let Some(state) = states.get_mut(&person_id) {
+            state.churn_risk = churn_risk.clamp(0.0, 1.0);
+        }
+    }
+
+    /// A support signal (ticket, complaint) was opened for a person
+    pub async fn open_support_signal(&self, person_id: PersonId, signal_id: impl Into<String>) {
+        let mut states = self.states.write().await;
+        if let Some(state) = states.get_mut(&person_id) {
+            state.open_support_signals.insert(signal_id.into());
+        }
+    }
+
+    /// A support signal was resolved
+    pub async fn resolve_support_signal(&self, person_id: PersonId, signal_id: &str) {
+        let mut states = self.states.write().await;
+        if let Some(state) = states.get_mut(&person_id) {
+            state.open_support_signals.remove(signal_id);
+        }
+    }
+
+    /// Persons whose composite score is at least `threshold`, riskiest first
+    pub async fn at_risk(&self, threshold: f32) -> Vec<(PersonId, RiskBreakdown)> {
+        let now = Utc::now();
+        let states = self.states.read().await;
+        let mut at_risk: Vec<_> = states.iter()
+            .map(|(person_id, state)| (*person_id, self.breakdown(state, now)))
+            .filter(|(_, breakdown)| breakdown.score >= threshold)
+            .collect();
+
+        at_risk.sort_by(|a, b| b.1.score.total_cmp(&a.1.score));
+        at_risk
+    }
+
+    /// Risk breakdown for one person
+    pub async fn risk_of(&self, person_id: &PersonId) -> Option<RiskBreakdown> {
+        let states = self.states.read().await;
+        states.get(person_id).map(|state| self.breakdown(state, Utc::now()))
+    }
+
+    fn breakdown(&self, state: &RiskState, now: DateTime<Utc>) -> RiskBreakdown {
+        let window = self.inactivity_window.num_seconds().max(1) as f32;
+        let idle = (now - state.last_activity).num_seconds().max(0) as f32;
+        let inactivity = (idle / window).min(1.0);
+        let unresolved_support =
+            (state.open_support_signals.len() as f32 / SUPPORT_SIGNAL_SATURATION as f32).min(1.0);
+
+        let total_weight = self.weights.churn + self.weights.inactivity + self.weights.support;
+        let score = if total_weight > 0.0 {
+            (self.weights.churn * state.churn_risk
+                + self.weights.inactivity * inactivity
+                + self.weights.support * unresolved_support)
+                / total_weight
+        } else {
+            0.0
+        };
+
+        RiskBreakdown {
+            churn_risk: state.churn_risk,
+            inactivity,
+            unresolved_support,
+            score,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for AtRiskProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        let mut states = self.states.write().await;
+        match event {
+            PersonEvent::PersonCreated(e) => {
+                states.insert(e.person_id, RiskState {
+                    last_activity: e.created_at,
+                    churn_risk: 0.0,
+                    open_support_signals: HashSet::new(),
+                });
+            }
+
+            // Inactive persons are no longer customers at risk
+            PersonEvent::PersonDeactivated(e) => {
+                states.remove(&e.person_id);
+            }
+            PersonEvent::DeathRecorded(e) => {
+                states.remove(&e.person_id);
+            }
+            PersonEvent::PersonMergedInto(e) => {
+                states.remove(&e.source_person_id);
+            }
+
+            other => {
+                if let Some(state) = states.get_mut(&other.person_id()) {
+                    state.last_activity = state.last_activity.max(other.occurred_at());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "AtRiskProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        self.states.write().await.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::PersonName;
+
+    fn created(person_id: PersonId, created_at: DateTime<Utc>) -> PersonEvent {
+        PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Test".to_string(), "Customer".to_string()),
+            source: "test".to_string(),
+            created_at,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_churning_inactive_person_is_at_risk() {
+        let projection = AtRiskProjection::new(Duration::days(30));
+        let churning = PersonId::new();
+        let healthy = PersonId::new();
+
+        projection.handle_event(&created(churning, Utc::now() - Duration::days(120))).await.unwrap();
+        projection.handle_event(&created(healthy, Utc::now())).await.unwrap();
+        projection.record_churn_risk(churning, 0.9).await;
+        projection.record_churn_risk(healthy, 0.1).await;
+
+        let at_risk = projection.at_risk(0.6).await;
+        assert_eq!(at_risk.len(), 1);
+
+        let (person_id, breakdown) = &at_risk[0];
+        assert_eq!(*person_id, churning);
+        assert!((breakdown.inactivity - 1.0).abs() < f32::EPSILON);
+        assert!(breakdown.score >= 0.6);
+
+        let healthy_risk = projection.risk_of(&healthy).await.unwrap();
+        assert!(healthy_risk.score < 0.6);
+    }
+}
@@ -0,0 +1,238 @@
+//! Dead letters for events projections could not apply
+//!
+//! Without a sink a failing event is logged and the projection moves on,
+//! silently diverging from the event store. With one configured through
+//! [`ProjectionManager::with_dead_letters`](super::ProjectionManager::with_dead_letters),
+//! the event is retried and then handed to the sink together with the
+//! projection name and error, so it can be inspected and replayed once the
+//! projection is fixed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::PersonProjection;
+use crate::events::PersonEvent;
+
+/// An event a projection failed to apply on every attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub projection_name: String,
+    pub event: PersonEvent,
+    /// Error of the last attempt
+    pub error: String,
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Destination for events projections gave up on
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    async fn send(&self, dead_letter: DeadLetter) -> DomainResult<()>;
+}
+
+/// Dead letter sink keeping everything in memory, oldest first
+#[derive(Default)]
+pub struct InMemoryDeadLetterSink {
+    dead_letters: RwLock<Vec<DeadLetter>>,
+}
+
+impl InMemoryDeadLetterSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.read().await.clone()
+    }
+
+    /// Dead letters of one projection
+    pub async fn for_projection(&self, projection_name: &str) -> Vec<DeadLetter> {
+        self.dead_letters.read().await.iter()
+            .filter(|dead_letter| dead_letter.projection_name == projection_name)
+            .cloned()
+            .collect()
+    }
+
+    pub async fn len(&self) -> usize {
+        self.dead_letters.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.dead_letters.read().await.is_empty()
+    }
+
+    /// Remove and return every dead letter, e.g. to replay them
+    pub async fn take(&self) -> Vec<DeadLetter> {
+        std::mem::take(&mut *self.dead_letters.write().await)
+    }
+}
+
+#[async_trait]
+impl DeadLetterSink for InMemoryDeadLetterSink {
+    async fn send(&self, dead_letter: DeadLetter) -> DomainResult<()> {
+        self.dead_letters.write().await.push(dead_letter);
+        Ok(())
+    }
+}
+
+/// Where failed events go and how often they are retried first
+#[derive(Clone)]
+pub(super) struct DeadLetterRouting {
+    pub(super) sink: Arc<dyn DeadLetterSink>,
+    pub(super) retries: u32,
+}
+
+/// Apply an event to a projection, dead-lettering it if every attempt fails
+pub(super) async fn apply_or_dead_letter(
+    projection: &dyn PersonProjection,
+    event: &PersonEvent,
+    routing: Option<&DeadLetterRouting>,
+) {
+    let attempts = 1 + routing.map_or(0, |routing| routing.retries);
+    let mut attempt = 1;
+    let error = loop {
+        match projection.handle_event(event).await {
+            Ok(()) => return,
+            Err(e) if attempt < attempts => {
+                tracing::warn!(
+                    "Projection {} failed (attempt {}/{}): {}",
+                    projection.projection_name(),
+                    attempt,
+                    attempts,
+                    e
+                );
+                attempt += 1;
+            }
+            Err(e) => break e,
+        }
+    };
+
+    tracing::error!("Error in projection {}: {}", projection.projection_name(), error);
+    // Continue processing other events even if this one failed
+    if let Some(routing) = routing {
+        let dead_letter = DeadLetter {
+            projection_name: projection.projection_name().to_string(),
+            event: event.clone(),
+            error: error.to_string(),
+            attempts,
+            failed_at: Utc::now(),
+        };
+        if let Err(e) = routing.sink.send(dead_letter).await {
+            tracing::error!("Failed to dead-letter event for {}: {}", projection.projection_name(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::events::{NameUpdated, PersonCreated};
+    use crate::projections::ProjectionManager;
+    use crate::value_objects::PersonName;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Rejects renames; fails everything else `flaky` times before applying it
+    struct Unreliable {
+        name: &'static str,
+        flaky: u32,
+        failures: AtomicU32,
+    }
+
+    impl Unreliable {
+        fn new(name: &'static str, flaky: u32) -> Arc<Self> {
+            Arc::new(Self { name, flaky, failures: AtomicU32::new(0) })
+        }
+    }
+
+    #[async_trait]
+    impl PersonProjection for Unreliable {
+        async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+            if matches!(event, PersonEvent::NameUpdated(_)) {
+                return Err(cim_domain::DomainError::generic("rename rejected"));
+            }
+            if self.failures.fetch_add(1, Ordering::SeqCst) < self.flaky {
+                return Err(cim_domain::DomainError::generic("temporarily unavailable"));
+            }
+            Ok(())
+        }
+
+        fn projection_name(&self) -> &str {
+            self.name
+        }
+
+        async fn clear(&self) -> DomainResult<()> {
+            Ok(())
+        }
+    }
+
+    fn events() -> [PersonEvent; 2] {
+        let person_id = PersonId::new();
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        [
+            PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: name.clone(),
+                source: "test".to_string(),
+                created_at: Utc::now(),
+            }),
+            PersonEvent::NameUpdated(NameUpdated {
+                person_id,
+                old_name: name,
+                new_name: PersonName::new("Augusta".to_string(), "Lovelace".to_string()),
+                reason: None,
+                updated_at: Utc::now(),
+            }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_events_failing_every_retry_are_dead_lettered() {
+        let sink = Arc::new(InMemoryDeadLetterSink::new());
+        let mut manager = ProjectionManager::new().with_dead_letters(sink.clone(), 2);
+        // Recovers from its transient failures within the retries
+        manager.register_projection(Unreliable::new("Recovers", 2));
+        manager.register_projection(Unreliable::new("Exhausted", 3));
+
+        let [created, renamed] = events();
+        manager.handle_event(&created).await.unwrap();
+        manager.handle_event(&renamed).await.unwrap();
+
+        let exhausted = sink.for_projection("Exhausted").await;
+        assert_eq!(exhausted.len(), 2);
+        assert!(matches!(exhausted[0].event, PersonEvent::PersonCreated(_)));
+        assert!(exhausted[0].error.contains("temporarily unavailable"));
+        assert_eq!(exhausted[0].attempts, 3);
+
+        let recovers = sink.for_projection("Recovers").await;
+        assert_eq!(recovers.len(), 1);
+        assert!(matches!(recovers[0].event, PersonEvent::NameUpdated(_)));
+        assert!(recovers[0].error.contains("rename rejected"));
+
+        assert_eq!(sink.take().await.len(), 3);
+        assert!(sink.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_fan_out_dead_letters_too() {
+        let sink = Arc::new(InMemoryDeadLetterSink::new());
+        let mut manager = ProjectionManager::new()
+            .with_concurrent_fan_out(4)
+            .with_dead_letters(sink.clone(), 0);
+        manager.register_projection(Unreliable::new("Worker", 0));
+
+        for event in events() {
+            manager.handle_event(&event).await.unwrap();
+        }
+        manager.flush().await;
+
+        let dead_letters = sink.dead_letters().await;
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].projection_name, "Worker");
+        assert_eq!(dead_letters[0].attempts, 1);
+    }
+}
@@ -0,0 +1,317 @@
+//! Aggregated domain statistics projection
+//!
+//! Maintains live totals for dashboards (active persons, persons per
+//! segment, average skills per person) incrementally, and lets subscribers
+//! receive debounced [`DomainStats`] snapshots whenever they change.
+//!
+//! Skills and segments are owned by other domains, so those totals are fed
+//! through [`DomainStatsProjection::skill_added`] and
+//! [`DomainStatsProjection::assign_segment`] rather than person events.
+
+use super::PersonProjection;
+use crate::aggregate::PersonId;
+use crate::events::*;
+use crate::value_objects::SegmentType;
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+
+/// Snapshot of domain-wide totals
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainStats {
+    pub active_persons: usize,
+    /// Active persons per segment
+    pub persons_by_segment: HashMap<String, usize>,
+    /// Skills per active person
+    pub average_skills_per_person: f64,
+}
+
+#[derive(Debug, Default)]
+struct PersonTally {
+    active: bool,
+    skills: usize,
+    segment: Option<String>,
+}
+
+/// Running totals; only active persons contribute
+#[derive(Debug, Default)]
+struct StatsState {
+    persons: HashMap<PersonId, PersonTally>,
+    active_persons: usize,
+    active_skills: usize,
+    persons_by_segment: HashMap<String, usize>,
+}
+
+impl StatsState {
+    fn set_active(&mut self, person_id: PersonId, active: bool) {
+        let tally = self.persons.entry(person_id).or_default();
+        if tally.active == active {
+            return;
+        }
+        tally.active = active;
+
+        if active {
+            self.active_persons += 1;
+            self.active_skills += tally.skills;
+            if let Some(segment) = &tally.segment {
+                *self.persons_by_segment.entry(segment.clone()).or_default() += 1;
+            }
+        } else {
+            self.active_persons -= 1;
+            self.active_skills -= tally.skills;
+            if let Some(segment) = &tally.segment {
+                decrement(&mut self.persons_by_segment, segment);
+            }
+        }
+    }
+
+    fn add_skill(&mut self, person_id: PersonId) {
+        let tally = self.persons.entry(person_id).or_default();
+        tally.skills += 1;
+        if tally.active {
+            self.active_skills += 1;
+        }
+    }
+
+    fn remove_skill(&mut self, person_id: PersonId) {
+        let tally = self.persons.entry(person_id).or_default();
+        if tally.skills == 0 {
+            return;
+        }
+        tally.skills -= 1;
+        if tally.active {
+            self.active_skills -= 1;
+        }
+    }
+
+    fn set_segment(&mut self, person_id: PersonId, segment: Option<String>) {
+        let tally = self.persons.entry(person_id).or_default();
+        if tally.active {
+            if let Some(old) = &tally.segment {
+                decrement(&mut self.persons_by_segment, old);
+            }
+            if let Some(new) = &segment {
+                *self.persons_by_segment.entry(new.clone()).or_default() += 1;
+            }
+        }
+        tally.segment = segment;
+    }
+
+    fn snapshot(&self) -> DomainStats {
+        let average_skills_per_person = if self.active_persons == 0 {
+            0.0
+        } else {
+            self.active_skills as f64 / self.active_persons as f64
+        };
+
+        DomainStats {
+            active_persons: self.active_persons,
+            persons_by_segment: self.persons_by_segment.clone(),
+            average_skills_per_person,
+        }
+    }
+}
+
+fn decrement(counts: &mut HashMap<String, usize>, key: &str) {
+    if let Some(count) = counts.get_mut(key) {
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            counts.remove(key);
+        }
+    }
+}
+
+fn segment_key(segment: &SegmentType) -> String {
+    match segment {
+        SegmentType::Custom(name) => name.clone(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Projection maintaining [`DomainStats`]
+pub struct DomainStatsProjection {
+    state: Arc<RwLock<StatsState>>,
+    sender: watch::Sender<DomainStats>,
+}
+
+impl Default for DomainStatsProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DomainStatsProjection {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(DomainStats::default());
+        Self {
+            state: Arc::new(RwLock::new(StatsState::default())),
+            sender,
+        }
+    }
+
+    /// Current totals
+    pub async fn stats(&self) -> DomainStats {
+        self.state.read().await.snapshot()
+    }
+
+    /// Subscribe to changes, coalescing bursts that arrive within `debounce`
+    pub fn subscribe(&self, debounce: Duration) -> DomainStatsSubscription {
+        DomainStatsSubscription {
+            receiver: self.sender.subscribe(),
+            debounce,
+        }
+    }
+
+    /// A skill was added to a person in the skills domain
+    pub async fn skill_added(&self, person_id: PersonId) {
+        let mut state = self.state.write().await;
+        state.add_skill(person_id);
+        self.publish(&state);
+    }
+
+    /// A skill was removed from a person in the skills domain
+    pub async fn skill_removed(&self, person_id: PersonId) {
+        let mut state = self.state.write().await;
+        state.remove_skill(person_id);
+        self.publish(&state);
+    }
+
+    /// Set (or clear) the segment a person belongs to
+    pub async fn assign_segment(&self, person_id: PersonId, segment: Option<&SegmentType>) {
+        let mut state = self.state.write().await;
+        state.set_segment(person_id, segment.map(segment_key));
+        self.publish(&state);
+    }
+
+    fn publish(&self, state: &StatsState) {
+        let next = state.snapshot();
+        self.sender.send_if_modified(|current| {
+            if *current == next {
+                false
+            } else {
+                *current = next;
+                true
+            }
+        });
+    }
+}
+
+/// Debounced stream of [`DomainStats`] snapshots
+pub struct DomainStatsSubscription {
+    receiver: watch::Receiver<DomainStats>,
+    debounce: Duration,
+}
+
+impl DomainStatsSubscription {
+    /// Wait for the next change and return the stats once they have settled
+    ///
+    /// Returns `None` once the projection has been dropped.
+    pub async fn next(&mut self) -> Option<DomainStats> {
+        self.receiver.changed().await.ok()?;
+
+        // Keep waiting while further changes arrive inside the debounce window
+        while let Ok(Ok(())) = tokio::time::timeout(self.debounce, self.receiver.changed()).await {}
+
+        Some(self.receiver.borrow_and_update().clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for DomainStatsProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        let change = match event {
+            PersonEvent::PersonCreated(e) => Some((e.person_id, true)),
+            PersonEvent::PersonReactivated(e) => Some((e.person_id, true)),
+            PersonEvent::PersonDeactivated(e) => Some((e.person_id, false)),
+            PersonEvent::DeathRecorded(e) => Some((e.person_id, false)),
+            PersonEvent::PersonMergedInto(e) => Some((e.source_person_id, false)),
+            _ => None,
+        };
+
+        if let Some((person_id, active)) = change {
+            let mut state = self.state.write().await;
+            state.set_active(person_id, active);
+            self.publish(&state);
+        }
+
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "DomainStatsProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        let mut state = self.state.write().await;
+        *state = StatsState::default();
+        self.publish(&state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::PersonName;
+    use chrono::Utc;
+
+    fn created(person_id: PersonId) -> PersonEvent {
+        PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Test".to_string(), "Person".to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        })
+    }
+
+    fn deactivated(person_id: PersonId) -> PersonEvent {
+        PersonEvent::PersonDeactivated(PersonDeactivated {
+            person_id,
+            reason: "archived".to_string(),
+            deactivated_at: Utc::now(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_active_count_and_skill_average() {
+        let projection = DomainStatsProjection::new();
+        let (alice, bob, carol) = (PersonId::new(), PersonId::new(), PersonId::new());
+
+        for person in [alice, bob, carol] {
+            projection.handle_event(&created(person)).await.unwrap();
+        }
+        assert_eq!(projection.stats().await.active_persons, 3);
+
+        projection.skill_added(alice).await;
+        projection.skill_added(alice).await;
+        projection.skill_added(bob).await;
+        projection.skill_added(carol).await;
+        assert!((projection.stats().await.average_skills_per_person - 4.0 / 3.0).abs() < 1e-9);
+
+        projection.assign_segment(alice, Some(&SegmentType::VIP)).await;
+        projection.assign_segment(carol, Some(&SegmentType::VIP)).await;
+
+        // Archiving removes the person and their skills from the totals
+        projection.handle_event(&deactivated(carol)).await.unwrap();
+        let stats = projection.stats().await;
+        assert_eq!(stats.active_persons, 2);
+        assert!((stats.average_skills_per_person - 1.5).abs() < 1e-9);
+        assert_eq!(stats.persons_by_segment.get("VIP"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn test_subscription_debounces_bursts() {
+        let projection = DomainStatsProjection::new();
+        let mut subscription = projection.subscribe(Duration::from_millis(20));
+
+        for _ in 0..3 {
+            projection.handle_event(&created(PersonId::new())).await.unwrap();
+        }
+
+        let stats = subscription.next().await.unwrap();
+        assert_eq!(stats.active_persons, 3);
+    }
+}
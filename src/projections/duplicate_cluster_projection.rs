@@ -0,0 +1,311 @@
+//! Clusters of likely-duplicate persons
+//!
+//! Scoring every pair of persons does not scale, so candidates are first
+//! grouped by a blocking key: the Soundex code of the family name plus the
+//! birth year. Only persons in the same block (a missing birth year blocks
+//! with every year) are scored with the [`IdentityMatcher`]. Clusters are
+//! the connected components of pairs scoring at least the requested minimum,
+//! so A~B and B~C put A, B and C together even if A and C score lower.
+//!
+//! Scores are kept up to date incrementally: creating a person or changing
+//! their name or birth date only rescores that person against their block.
+//! Deactivated persons leave their block until they are reactivated.
+
+use super::name_normalizer::{NameNormalizer, UnicodeFoldingNormalizer};
+use super::phonetic::soundex;
+use super::PersonProjection;
+use crate::aggregate::{EventSourced, Person, PersonId};
+use crate::events::PersonEvent;
+use crate::services::identity_matching::{birth_date_of, IdentityMatcher};
+use chrono::Datelike;
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// A group of persons that likely describe the same human
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cluster {
+    pub members: Vec<PersonId>,
+    /// Mean score of the qualifying links inside the cluster
+    pub mean_score: f64,
+}
+
+#[derive(Default)]
+struct ClusterState {
+    persons: HashMap<PersonId, Person>,
+    /// Persons by family-name Soundex code
+    blocks: HashMap<String, HashSet<PersonId>>,
+    /// Symmetric match scores between persons in the same block
+    links: HashMap<PersonId, HashMap<PersonId, f64>>,
+    /// Deactivated persons, kept out of every block until reactivated
+    deactivated: HashMap<PersonId, Person>,
+}
+
+impl ClusterState {
+    fn remove(&mut self, person_id: &PersonId) {
+        if let Some(person) = self.persons.remove(person_id) {
+            if let Some(block) = self.blocks.get_mut(&phonetic_key(&person)) {
+                block.remove(person_id);
+            }
+        }
+        for other in self.links.remove(person_id).unwrap_or_default().keys() {
+            if let Some(links) = self.links.get_mut(other) {
+                links.remove(person_id);
+            }
+        }
+    }
+
+    fn insert(&mut self, person: Person, matcher: &IdentityMatcher) {
+        let person_id = person.id;
+        self.remove(&person_id);
+
+        let key = phonetic_key(&person);
+        let year = birth_date_of(&person).map(|date| date.year());
+        let candidates: Vec<PersonId> = self.blocks.get(&key).into_iter().flatten().copied().collect();
+
+        for other_id in candidates {
+            let other = &self.persons[&other_id];
+            let other_year = birth_date_of(other).map(|date| date.year());
+            if year.is_some() && other_year.is_some() && year != other_year {
+                continue;
+            }
+            let score = matcher.score(&person, other).score;
+            self.links.entry(person_id).or_default().insert(other_id, score);
+            self.links.entry(other_id).or_default().insert(person_id, score);
+        }
+
+        self.blocks.entry(key).or_default().insert(person_id);
+        self.persons.insert(person_id, person);
+    }
+}
+
+/// Projection grouping likely-duplicate persons into clusters
+pub struct DuplicateClusterProjection {
+    state: Arc<RwLock<ClusterState>>,
+    matcher: IdentityMatcher,
+}
+
+impl Default for DuplicateClusterProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DuplicateClusterProjection {
+    pub fn new() -> Self {
+        Self::with_matcher(IdentityMatcher::default())
+    }
+
+    pub fn with_matcher(matcher: IdentityMatcher) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ClusterState::default())),
+            matcher,
+        }
+    }
+
+    /// Clusters of two or more persons linked by scores of at least `min_score`
+    ///
+    /// Larger clusters come first.
+    pub async fn clusters(&self, min_score: f64) -> Vec<Cluster> {
+        let state = self.state.read().await;
+        let mut visited = HashSet::new();
+        let mut clusters = Vec::new();
+
+        for start in state.links.keys() {
+            if !visited.insert(*start) {
+                continue;
+            }
+            let mut members = vec![*start];
+            let mut scores = Vec::new();
+            let mut frontier = vec![*start];
+            while let Some(person_id) = frontier.pop() {
+                for (other, score) in &state.links[&person_id] {
+                    if *score < min_score {
+                        continue;
+                    }
+                    scores.push(*score);
+                    if visited.insert(*other) {
+                        members.push(*other);
+                        frontier.push(*other);
+                    }
+                }
+            }
+
+            if members.len() > 1 {
+                clusters.push(Cluster {
+                    members,
+                    // Each link was seen from both ends
+                    mean_score: scores.iter().sum::<f64>() / scores.len() as f64,
+                });
+            }
+        }
+
+        clusters.sort_by(|a, b| b.members.len().cmp(&a.members.len()));
+        clusters
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for DuplicateClusterProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        let mut state = self.state.write().await;
+
+        match event {
+            PersonEvent::PersonMergedInto(e) => {
+                state.remove(&e.source_person_id);
+                state.deactivated.remove(&e.source_person_id);
+            }
+            PersonEvent::PersonDeactivated(e) => {
+                if let Some(person) = state.persons.get(&e.person_id).cloned() {
+                    state.remove(&e.person_id);
+                    state.deactivated.insert(e.person_id, person.apply_event(event)?);
+                }
+            }
+            PersonEvent::PersonReactivated(e) => {
+                if let Some(person) = state.deactivated.remove(&e.person_id) {
+                    let reactivated = person.apply_event(event)?;
+                    state.insert(reactivated, &self.matcher);
+                }
+            }
+
+            PersonEvent::PersonCreated(_)
+            | PersonEvent::PersonUpdated(_)
+            | PersonEvent::NameUpdated(_)
+            | PersonEvent::BirthDateSet(_)
+            | PersonEvent::AttributeRecorded(_)
+            | PersonEvent::AttributeUpdated(_)
+            | PersonEvent::AttributeInvalidated(_) => {
+                let person_id = event.person_id();
+                if let Some(parked) = state.deactivated.remove(&person_id) {
+                    // Rescored when the person is reactivated
+                    state.deactivated.insert(person_id, parked.apply_event(event)?);
+                    return Ok(());
+                }
+                let current = match (event, state.persons.get(&person_id)) {
+                    (PersonEvent::PersonCreated(_), _) => {
+                        let mut person = Person::empty();
+                        person.id = person_id;
+                        person
+                    }
+                    (_, Some(person)) => person.clone(),
+                    // Persons created before this projection started are picked up on replay
+                    (_, None) => return Ok(()),
+                };
+                let updated = current.apply_event(event)?;
+                state.insert(updated, &self.matcher);
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "DuplicateClusterProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        *self.state.write().await = ClusterState::default();
+        Ok(())
+    }
+}
+
+/// Soundex code of a person's family name(s)
+fn phonetic_key(person: &Person) -> String {
+    let family = person.core_identity.legal_name.components.family_names.join(" ");
+    soundex(&UnicodeFoldingNormalizer.normalize(&family))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{BirthDateSet, NameUpdated, PersonCreated, PersonDeactivated, PersonReactivated};
+    use crate::value_objects::PersonName;
+    use chrono::{NaiveDate, Utc};
+
+    async fn add_person(
+        projection: &DuplicateClusterProjection,
+        given: &str,
+        family: &str,
+        born: (i32, u32, u32),
+    ) -> PersonId {
+        let person_id = PersonId::new();
+        projection.handle_event(&PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new(given.to_string(), family.to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        })).await.unwrap();
+        projection.handle_event(&PersonEvent::BirthDateSet(BirthDateSet {
+            person_id,
+            birth_date: NaiveDate::from_ymd_opt(born.0, born.1, born.2).unwrap(),
+            set_at: Utc::now(),
+        })).await.unwrap();
+        person_id
+    }
+
+    #[tokio::test]
+    async fn test_similar_persons_form_one_cluster() {
+        let projection = DuplicateClusterProjection::new();
+        let a = add_person(&projection, "Maria", "Garcia", (1980, 5, 1)).await;
+        let b = add_person(&projection, "Maria", "Garcia", (1980, 5, 2)).await;
+        let c = add_person(&projection, "Maria", "Garcia", (1980, 5, 3)).await;
+        // Same block as the others, but the names differ
+        let garsia = add_person(&projection, "Maria", "Garsia", (1980, 5, 1)).await;
+        let smith = add_person(&projection, "John", "Smith", (1980, 5, 1)).await;
+
+        let clusters = projection.clusters(0.8).await;
+        assert_eq!(clusters.len(), 1);
+        let members: HashSet<PersonId> = clusters[0].members.iter().copied().collect();
+        assert_eq!(members, HashSet::from([a, b, c]));
+        assert!(!members.contains(&garsia) && !members.contains(&smith));
+        assert!(clusters[0].mean_score >= 0.8);
+    }
+
+    fn members(clusters: &[Cluster]) -> Vec<HashSet<PersonId>> {
+        clusters.iter().map(|c| c.members.iter().copied().collect()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_name_update_rescores_the_person() {
+        let projection = DuplicateClusterProjection::new();
+        let a = add_person(&projection, "Maria", "Garcia", (1980, 5, 1)).await;
+        let b = add_person(&projection, "Mary", "Garcia", (1980, 5, 1)).await;
+        let before = projection.clusters(0.8).await;
+        assert!(before.is_empty());
+
+        projection.handle_event(&PersonEvent::NameUpdated(NameUpdated {
+            person_id: b,
+            old_name: PersonName::new("Mary".to_string(), "Garcia".to_string()),
+            new_name: PersonName::new("Maria".to_string(), "Garcia".to_string()),
+            reason: None,
+            updated_at: Utc::now(),
+        })).await.unwrap();
+
+        assert_eq!(members(&projection.clusters(0.8).await), vec![HashSet::from([a, b])]);
+    }
+
+    #[tokio::test]
+    async fn test_reactivation_rescores_the_person() {
+        let projection = DuplicateClusterProjection::new();
+        let a = add_person(&projection, "Maria", "Garcia", (1980, 5, 1)).await;
+        let b = add_person(&projection, "Maria", "Garcia", (1980, 5, 2)).await;
+
+        projection.handle_event(&PersonEvent::PersonDeactivated(PersonDeactivated {
+            person_id: b,
+            reason: "Duplicate suspected".to_string(),
+            deactivated_at: Utc::now(),
+        })).await.unwrap();
+        assert!(projection.clusters(0.8).await.is_empty());
+
+        projection.handle_event(&PersonEvent::PersonReactivated(PersonReactivated {
+            person_id: b,
+            reason: "Confirmed distinct record".to_string(),
+            reactivated_at: Utc::now(),
+        })).await.unwrap();
+        assert_eq!(members(&projection.clusters(0.8).await), vec![HashSet::from([a, b])]);
+    }
+}
@@ -0,0 +1,156 @@
+//! Concurrent fan-out of events to projections
+//!
+//! Each projection gets its own worker task fed by a bounded queue. Events
+//! reach every queue in the order they were handled, and each worker applies
+//! them one at a time, so per-person ordering holds within every
+//! projection while a slow projection only delays itself. When a queue is
+//! full, [`ProjectionManager::handle_event`](super::ProjectionManager::handle_event)
+//! waits for room, which pushes backpressure up to the event source.
+
+use super::dead_letter::{apply_or_dead_letter, DeadLetterRouting};
+use super::PersonProjection;
+use crate::events::PersonEvent;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+
+enum WorkerMessage {
+    Event(PersonEvent),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Handle to the task applying events to one projection
+pub(super) struct ProjectionWorker {
+    name: String,
+    sender: mpsc::Sender<WorkerMessage>,
+}
+
+impl ProjectionWorker {
+    /// Spawn a worker on the current Tokio runtime
+    pub(super) fn spawn(
+        projection: Arc<dyn PersonProjection>,
+        queue_bound: usize,
+        dead_letters: Option<DeadLetterRouting>,
+    ) -> Self {
+        let name = projection.projection_name().to_string();
+        let (sender, mut receiver) = mpsc::channel(queue_bound.max(1));
+
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                match message {
+                    WorkerMessage::Event(event) => {
+                        apply_or_dead_letter(projection.as_ref(), &event, dead_letters.as_ref()).await;
+                    }
+                    WorkerMessage::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+
+        Self { name, sender }
+    }
+
+    /// Queue an event, waiting while the queue is full
+    pub(super) async fn send(&self, event: PersonEvent) {
+        if self.sender.send(WorkerMessage::Event(event)).await.is_err() {
+            tracing::error!("Projection worker {} has stopped", self.name);
+        }
+    }
+
+    /// Wait until every event queued so far has been applied
+    pub(super) async fn flush(&self) {
+        let (done, applied) = oneshot::channel();
+        if self.sender.send(WorkerMessage::Flush(done)).await.is_ok() {
+            let _ = applied.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::PersonId;
+    use crate::events::{PersonCreated, PersonEvent};
+    use crate::projections::{PersonProjection, ProjectionManager};
+    use crate::value_objects::PersonName;
+    use cim_domain::DomainResult;
+    use chrono::Utc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Semaphore;
+
+    /// Counts events; optionally waits for a permit before each one
+    struct CountingProjection {
+        applied: AtomicUsize,
+        gate: Option<Semaphore>,
+    }
+
+    impl CountingProjection {
+        fn new(gate: Option<Semaphore>) -> Arc<Self> {
+            Arc::new(Self { applied: AtomicUsize::new(0), gate })
+        }
+
+        fn applied(&self) -> usize {
+            self.applied.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PersonProjection for CountingProjection {
+        async fn handle_event(&self, _event: &PersonEvent) -> DomainResult<()> {
+            if let Some(gate) = &self.gate {
+                gate.acquire().await.expect("gate closed").forget();
+            }
+            self.applied.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn projection_name(&self) -> &str {
+            "CountingProjection"
+        }
+
+        async fn clear(&self) -> DomainResult<()> {
+            self.applied.store(0, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn created() -> PersonEvent {
+        PersonEvent::PersonCreated(PersonCreated {
+            person_id: PersonId::new(),
+            name: PersonName::new("Test".to_string(), "Person".to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_slow_projection_does_not_delay_fast_ones() {
+        let slow = CountingProjection::new(Some(Semaphore::new(0)));
+        let fast = [CountingProjection::new(None), CountingProjection::new(None)];
+
+        let mut manager = ProjectionManager::new().with_concurrent_fan_out(8);
+        manager.register_projection(slow.clone());
+        for projection in &fast {
+            manager.register_projection(projection.clone());
+        }
+
+        for _ in 0..3 {
+            manager.handle_event(&created()).await.unwrap();
+        }
+
+        // The fast projections catch up while the slow one is still blocked
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while fast.iter().any(|projection| projection.applied() < 3) {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("fast projections were delayed");
+        assert_eq!(slow.applied(), 0);
+
+        slow.gate.as_ref().unwrap().add_permits(3);
+        manager.flush().await;
+        assert_eq!(slow.applied(), 3);
+    }
+}
@@ -14,27 +14,59 @@ pub mod person_search_projection;
 pub mod person_skills_projection;
 pub mod person_network_projection;
 pub mod person_timeline_projection;
+pub mod person_attribute_index_projection;
+pub mod person_consent_projection;
+pub mod person_tag_projection;
+pub mod person_merge_redirect_projection;
+pub mod person_onboarding_projection;
+pub mod domain_stats_projection;
+pub mod person_attribute_stats_projection;
+pub mod name_normalizer;
+pub mod swappable_projection;
+pub mod at_risk_projection;
+pub mod skill_taxonomy;
+pub mod duplicate_cluster_projection;
+mod fan_out;
+mod phonetic;
+pub mod dead_letter;
 
 pub use person_summary_projection::*;
 pub use person_search_projection::*;
 pub use person_skills_projection::*;
 pub use person_network_projection::*;
 pub use person_timeline_projection::*;
+pub use person_attribute_index_projection::*;
+pub use person_consent_projection::*;
+pub use person_tag_projection::PersonTagProjection;
+pub use person_merge_redirect_projection::PersonMergeRedirectProjection;
+pub use person_onboarding_projection::{OnboardingProgress, PersonOnboardingProjection};
+pub use domain_stats_projection::*;
+pub use person_attribute_stats_projection::{AttributeStats, PersonAttributeStatsProjection};
+pub use swappable_projection::SwappableProjection;
+pub use at_risk_projection::{AtRiskProjection, RiskBreakdown, RiskWeights};
+pub use skill_taxonomy::SkillTaxonomy;
+pub use duplicate_cluster_projection::{Cluster, DuplicateClusterProjection};
+pub use name_normalizer::{NameNormalizer, UnicodeFoldingNormalizer, GermanTransliterationNormalizer};
+pub use dead_letter::{DeadLetter, DeadLetterSink, InMemoryDeadLetterSink};
 
 // Pure functional projections (FRP/CT compliant)
 pub mod pure_projections;
 pub use pure_projections::{
-    project_person_summary, project_person_search, project_timeline_entry
+    project_person_summary, project_person_search, project_timeline_entry,
+    summary_from_person,
 };
 
 mod async_handlers;
 pub use async_handlers::{
     AsyncProjectionHandler, SummaryProjectionHandler, SkillsProjectionHandler,
-    ProjectionStorage, register_projection_handlers
+    ProjectionStorage, ProjectionPatch, write_changes, register_projection_handlers
 };
 
 use crate::aggregate::PersonId;
 use crate::events::PersonEvent;
+use crate::infrastructure::{EventStore, PersonSnapshot, SnapshotStore};
+use fan_out::ProjectionWorker;
+use dead_letter::{apply_or_dead_letter, DeadLetterRouting};
 
 /// Trait for projections that process person events
 #[async_trait::async_trait]
@@ -47,11 +79,87 @@ pub trait PersonProjection: Send + Sync {
     
     /// Clear all data in the projection
     async fn clear(&self) -> DomainResult<()>;
+
+    /// Seed this projection's state for one person from an aggregate snapshot
+    ///
+    /// Returns `false` when the projection cannot be derived from aggregate
+    /// state; bootstrap then replays that person's full history instead.
+    async fn seed_from_snapshot(&self, _snapshot: &PersonSnapshot) -> DomainResult<bool> {
+        Ok(false)
+    }
+
+    /// Bootstrap every person in `then_tail_from` from their latest snapshot,
+    /// then tail the events recorded after each snapshot's version
+    ///
+    /// Persons without a snapshot, or whose snapshot this projection cannot
+    /// seed from, are replayed from their first event. Seeding overwrites
+    /// per-person state, so re-running bootstrap on a seedable projection is
+    /// idempotent. The returned checkpoint can be fed to
+    /// [`PersonProjection::catch_up`] to resume tailing later.
+    async fn bootstrap_from_snapshots(
+        &self,
+        snapshot_store: &dyn SnapshotStore,
+        then_tail_from: &dyn EventStore,
+    ) -> DomainResult<ProjectionCheckpoint> {
+        let mut checkpoint = ProjectionCheckpoint::default();
+
+        for person_id in then_tail_from.list_aggregate_ids().await? {
+            let position = match snapshot_store.get_latest_snapshot(person_id).await? {
+                Some(snapshot) if self.seed_from_snapshot(&snapshot).await? => snapshot.version,
+                _ => 0,
+            };
+            checkpoint.positions.insert(person_id, position);
+        }
+
+        self.catch_up(&mut checkpoint, then_tail_from).await?;
+        Ok(checkpoint)
+    }
+
+    /// Apply every event after the checkpointed positions, advancing them
+    async fn catch_up(
+        &self,
+        checkpoint: &mut ProjectionCheckpoint,
+        event_store: &dyn EventStore,
+    ) -> DomainResult<()> {
+        for (person_id, position) in checkpoint.positions.iter_mut() {
+            let events = event_store.get_events_from_version(*person_id, *position + 1).await?;
+            for envelope in events {
+                self.handle_event(&envelope.event).await?;
+                *position = envelope.sequence;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Last event sequence applied to a projection, per person
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectionCheckpoint {
+    pub positions: HashMap<PersonId, u64>,
+}
+
+/// Outcome of [`ProjectionManager::rebuild_from`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RebuildReport {
+    /// Stored events replayed through the projections
+    pub events_replayed: usize,
+    /// Failed clears and events per projection name; clean projections are absent
+    pub errors: HashMap<String, usize>,
+}
+
+impl RebuildReport {
+    pub fn error_count(&self, projection_name: &str) -> usize {
+        self.errors.get(projection_name).copied().unwrap_or(0)
+    }
 }
 
 /// Manager for coordinating multiple projections
 pub struct ProjectionManager {
     projections: Vec<Arc<dyn PersonProjection>>,
+    /// Queue bound per projection when fanning out concurrently
+    fan_out_bound: Option<usize>,
+    workers: Vec<ProjectionWorker>,
+    dead_letters: Option<DeadLetterRouting>,
 }
 
 impl Default for ProjectionManager {
@@ -64,36 +172,201 @@ impl ProjectionManager {
     pub fn new() -> Self {
         Self {
             projections: Vec::new(),
+            fan_out_bound: None,
+            workers: Vec::new(),
+            dead_letters: None,
+        }
+    }
+
+    /// Retry failed events `retries` times, then hand them to `sink`
+    ///
+    /// Applies to live events through [`ProjectionManager::handle_event`];
+    /// rebuilds report their failures instead.
+    pub fn with_dead_letters(mut self, sink: Arc<dyn DeadLetterSink>, retries: u32) -> Self {
+        self.dead_letters = Some(DeadLetterRouting { sink, retries });
+        if let Some(queue_bound) = self.fan_out_bound {
+            // Workers carry the routing, so replace any spawned without it
+            self.workers = self.spawn_workers(queue_bound);
         }
+        self
+    }
+
+    fn spawn_workers(&self, queue_bound: usize) -> Vec<ProjectionWorker> {
+        self.projections.iter()
+            .map(|projection| ProjectionWorker::spawn(projection.clone(), queue_bound, self.dead_letters.clone()))
+            .collect()
+    }
+
+    /// Apply events to each projection on its own task
+    ///
+    /// Every projection queues up to `queue_bound` events; `handle_event`
+    /// returns once the event is queued everywhere rather than applied. Use
+    /// [`ProjectionManager::flush`] to wait for queued events. Projections
+    /// must be registered from within a Tokio runtime.
+    pub fn with_concurrent_fan_out(mut self, queue_bound: usize) -> Self {
+        self.fan_out_bound = Some(queue_bound);
+        self.workers = self.spawn_workers(queue_bound);
+        self
     }
     
     /// Register a projection with the manager
     pub fn register_projection(&mut self, projection: Arc<dyn PersonProjection>) {
+        if let Some(queue_bound) = self.fan_out_bound {
+            self.workers.push(ProjectionWorker::spawn(projection.clone(), queue_bound, self.dead_letters.clone()));
+        }
         self.projections.push(projection);
     }
     
     /// Process an event through all registered projections
     pub async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        if self.fan_out_bound.is_some() {
+            // Queue concurrently so one full queue doesn't hold up the others
+            futures::future::join_all(self.workers.iter().map(|worker| worker.send(event.clone()))).await;
+            return Ok(());
+        }
+
         for projection in &self.projections {
-            if let Err(e) = projection.handle_event(event).await {
-                tracing::error!(
-                    "Error in projection {}: {}",
-                    projection.projection_name(),
-                    e
-                );
-                // Continue processing other projections even if one fails
-            }
+            // Continue processing other projections even if one fails
+            apply_or_dead_letter(projection.as_ref(), event, self.dead_letters.as_ref()).await;
         }
         Ok(())
     }
+
+    /// Wait until every projection has applied the events handled so far
+    ///
+    /// Returns immediately unless concurrent fan-out is enabled.
+    pub async fn flush(&self) {
+        futures::future::join_all(self.workers.iter().map(ProjectionWorker::flush)).await;
+    }
     
     /// Clear all projections
     pub async fn clear_all(&self) -> DomainResult<()> {
+        // Don't let queued events land after the clear
+        self.flush().await;
         for projection in &self.projections {
             projection.clear().await?;
         }
         Ok(())
     }
+
+    /// Clear every projection and rebuild it from the stored events of `person_ids`
+    ///
+    /// Each person's events are replayed in sequence order. As in
+    /// [`ProjectionManager::handle_event`], a projection that fails is logged
+    /// and counted in the report while the others carry on; only a failure to
+    /// read from the store aborts the rebuild. Events are applied directly, so
+    /// with concurrent fan-out the live stream should be paused meanwhile.
+    pub async fn rebuild_from(
+        &self,
+        store: &dyn EventStore,
+        person_ids: &[PersonId],
+    ) -> DomainResult<RebuildReport> {
+        self.flush().await;
+        let mut report = RebuildReport::default();
+        let mut record_error = |projection: &Arc<dyn PersonProjection>, e: cim_domain::DomainError| {
+            tracing::error!("Error rebuilding projection {}: {}", projection.projection_name(), e);
+            *report.errors.entry(projection.projection_name().to_string()).or_insert(0) += 1;
+        };
+
+        for projection in &self.projections {
+            if let Err(e) = projection.clear().await {
+                record_error(projection, e);
+            }
+        }
+
+        let mut events_replayed = 0;
+        for person_id in person_ids {
+            for envelope in store.get_events(*person_id).await? {
+                for projection in &self.projections {
+                    if let Err(e) = projection.handle_event(&envelope.event).await {
+                        record_error(projection, e);
+                    }
+                }
+                events_replayed += 1;
+            }
+        }
+
+        report.events_replayed = events_replayed;
+        tracing::info!(
+            "Rebuilt {} projections from {} events",
+            self.projections.len(),
+            events_replayed
+        );
+        Ok(report)
+    }
+
+    /// Rebuild a projection into a fresh shadow instance, then swap it in
+    ///
+    /// The live instance keeps serving queries (and receiving events) until
+    /// the shadow has replayed every person in `person_ids`, so readers never
+    /// see a partially rebuilt projection. The shadow replays the events
+    /// stored when the rebuild starts; live events delivered to `slot` from
+    /// then on are buffered and applied to the shadow under the same lock
+    /// that holds back further deliveries until the swap. An event appended
+    /// to the store just before the rebuild starts but delivered just after
+    /// reaches the shadow twice. Returns the instance that was replaced.
+    pub async fn rebuild_and_swap<P, F>(
+        &self,
+        slot: &SwappableProjection<P>,
+        factory: F,
+        event_store: &dyn EventStore,
+        person_ids: &[PersonId],
+    ) -> DomainResult<Arc<P>>
+    where
+        P: PersonProjection + 'static,
+        F: FnOnce() -> P,
+    {
+        let shadow = factory();
+
+        let targets = {
+            let mut pending = slot.pending.lock().await;
+            *pending = Some(Vec::new());
+            let mut targets = HashMap::new();
+            for person_id in person_ids {
+                match event_store.get_current_version(*person_id).await {
+                    Ok(version) => targets.insert(*person_id, version),
+                    Err(e) => {
+                        *pending = None;
+                        return Err(e);
+                    }
+                };
+            }
+            targets
+        };
+
+        let replayed = replay_up_to(&shadow, event_store, &targets).await;
+
+        let mut pending = slot.pending.lock().await;
+        let buffered = pending.take().unwrap_or_default();
+        replayed?;
+        for event in &buffered {
+            shadow.handle_event(event).await?;
+        }
+
+        tracing::info!(
+            "Swapping in rebuilt projection {} with {} live events applied",
+            shadow.projection_name(),
+            buffered.len()
+        );
+        Ok(slot.swap(Arc::new(shadow)))
+    }
+}
+
+/// Apply each person's stored events up to and including `targets[person]`
+async fn replay_up_to(
+    projection: &dyn PersonProjection,
+    event_store: &dyn EventStore,
+    targets: &HashMap<PersonId, u64>,
+) -> DomainResult<()> {
+    for (person_id, target) in targets {
+        for envelope in event_store.get_events_from_version(*person_id, 1).await? {
+            if envelope.sequence > *target {
+                break;
+            }
+            projection.handle_event(&envelope.event).await?;
+        }
+    }
+    Ok(())
 }
 
 /// Common data structures used across projections
@@ -110,6 +383,23 @@ pub struct PersonSummary {
     pub skills_count: usize,
     pub component_count: usize,
     pub last_updated: DateTime<Utc>,
+    /// The merged-away id this summary was requested under, if any
+    #[serde(default)]
+    pub merged_from: Option<PersonId>,
+    #[serde(default)]
+    pub status: SummaryStatus,
+}
+
+/// Whether the person a summary describes is visible to queries
+///
+/// Deactivated summaries are kept so that a reactivation can restore them,
+/// and merged ones so the merged-away record can still be audited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SummaryStatus {
+    #[default]
+    Active,
+    Deactivated,
+    Merged { into: PersonId },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +417,8 @@ pub struct SkillSummary {
     pub skill_name: String,
     pub category: String,
     pub proficiency: String,
+    /// Proficiency weight from 0 to 1 after decay for disuse
+    pub effective_proficiency: f32,
     pub years_experience: Option<f32>,
     pub last_used: Option<DateTime<Utc>>,
     pub endorsement_count: usize,
@@ -140,3 +432,105 @@ pub struct TimelineEntry {
     pub description: String,
     pub metadata: HashMap<String, serde_json::Value>,
 }
+
+/// What produced an entry in a person's unified profile history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryEntryKind {
+    /// Identity or lifecycle change (creation, name change, deactivation, ...)
+    DomainEvent,
+    /// Attribute recorded, updated or invalidated
+    AttributeChange,
+    /// Life event annotated by the user
+    LifeEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub kind: HistoryEntryKind,
+    pub event_type: String,
+    pub title: String,
+    pub description: String,
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// A piece of profile data a data steward may require
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProfileField {
+    /// An email address indexed for search (fed by the contacts domain)
+    Email,
+    /// An employer indexed for search, or a valid employment attribute
+    Employer,
+    /// A location indexed for search (fed by the location domain)
+    Location,
+    /// Core birth date, or any valid birth date/time/year attribute
+    BirthDate,
+    /// A currently-valid attribute of this exact type
+    Attribute(crate::value_objects::AttributeType),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{NameUpdated, PersonCreated};
+    use crate::infrastructure::InMemoryEventStore;
+    use crate::value_objects::PersonName;
+
+    /// Projection rejecting every rename
+    struct RejectsRenames;
+
+    #[async_trait::async_trait]
+    impl PersonProjection for RejectsRenames {
+        async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+            match event {
+                PersonEvent::NameUpdated(_) => Err(cim_domain::DomainError::generic("rename rejected")),
+                _ => Ok(()),
+            }
+        }
+
+        fn projection_name(&self) -> &str {
+            "RejectsRenames"
+        }
+
+        async fn clear(&self) -> DomainResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_isolates_failing_projections() {
+        let person_id = PersonId::new();
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        let store = InMemoryEventStore::new();
+        store.append_events(person_id, vec![
+            PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: name.clone(),
+                source: "test".to_string(),
+                created_at: Utc::now(),
+            }),
+            PersonEvent::NameUpdated(NameUpdated {
+                person_id,
+                old_name: name,
+                new_name: PersonName::new("Augusta".to_string(), "Lovelace".to_string()),
+                reason: None,
+                updated_at: Utc::now(),
+            }),
+        ], None).await.unwrap();
+
+        let summaries = Arc::new(PersonSummaryProjection::new());
+        let mut manager = ProjectionManager::new();
+        manager.register_projection(Arc::new(RejectsRenames));
+        manager.register_projection(summaries.clone());
+
+        let report = manager.rebuild_from(&store, &[person_id]).await.unwrap();
+        assert_eq!(report.events_replayed, 2);
+        assert_eq!(report.error_count("RejectsRenames"), 1);
+        assert_eq!(report.error_count(summaries.projection_name()), 0);
+        assert_eq!(summaries.get_summary(&person_id).await.unwrap().name, "Augusta");
+
+        // Rebuilding again starts from a clean slate rather than doubling up
+        manager.rebuild_from(&store, &[person_id]).await.unwrap();
+        assert_eq!(summaries.get_all_summaries().await.len(), 1);
+    }
+}
@@ -0,0 +1,105 @@
+//! Name normalization for search
+//!
+//! Search indexes and queries both pass through a [`NameNormalizer`] so that
+//! differences in casing, diacritics and transliteration ("Zoë" / "Zoe",
+//! "Müller" / "Mueller") don't prevent a match.
+
+/// Normalizes text before it is indexed or matched
+pub trait NameNormalizer: Send + Sync {
+    fn normalize(&self, text: &str) -> String;
+}
+
+/// Lowercases and folds Latin diacritics to their base letters
+///
+/// "Zoë" → "zoe", "Müller" → "muller", "Łukasz" → "lukasz".
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnicodeFoldingNormalizer;
+
+impl NameNormalizer for UnicodeFoldingNormalizer {
+    fn normalize(&self, text: &str) -> String {
+        let mut folded = String::with_capacity(text.len());
+        for c in text.chars().flat_map(char::to_lowercase) {
+            match fold_char(c) {
+                Some(replacement) => folded.push_str(replacement),
+                None if is_combining_mark(c) => {}
+                None => folded.push(c),
+            }
+        }
+        folded
+    }
+}
+
+/// German transliteration: umlauts become two-letter forms before folding
+///
+/// "Müller" → "mueller", "Größe" → "groesse", so "Müller" and "Mueller" match.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GermanTransliterationNormalizer;
+
+impl NameNormalizer for GermanTransliterationNormalizer {
+    fn normalize(&self, text: &str) -> String {
+        let transliterated: String = text.chars()
+            .flat_map(char::to_lowercase)
+            .map(|c| match c {
+                'ä' => "ae".to_string(),
+                'ö' => "oe".to_string(),
+                'ü' => "ue".to_string(),
+                'ß' => "ss".to_string(),
+                other => other.to_string(),
+            })
+            .collect();
+        UnicodeFoldingNormalizer.normalize(&transliterated)
+    }
+}
+
+fn fold_char(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+        'ł' | 'ľ' | 'ĺ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' | 'ŕ' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ß' => "ss",
+        'ť' | 'ţ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// Combining diacritical marks left over from decomposed input
+fn is_combining_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diacritics_fold_to_base_letters() {
+        let normalizer = UnicodeFoldingNormalizer;
+        assert_eq!(normalizer.normalize("Zoë"), "zoe");
+        assert_eq!(normalizer.normalize("José"), "jose");
+        // Decomposed "e" + combining diaeresis
+        assert_eq!(normalizer.normalize("Zoe\u{0308}"), "zoe");
+    }
+
+    #[test]
+    fn test_german_umlauts_transliterate() {
+        let normalizer = GermanTransliterationNormalizer;
+        assert_eq!(normalizer.normalize("Müller"), "mueller");
+        assert_eq!(normalizer.normalize("Mueller"), "mueller");
+        assert_eq!(normalizer.normalize("Strauß"), "strauss");
+    }
+}
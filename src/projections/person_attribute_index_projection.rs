@@ -0,0 +1,525 @@
+//! Attribute-value index projection
+//!
+//! Indexes person attributes by type so that "who shares this value?"
+//! questions (e.g. all O-negative donors) can be answered without loading
+//! aggregates. Only currently-valid attributes are returned, and healthcare
+//! attributes are only visible when the projection was built with
+//! healthcare access.
+//!
+//! Persons are tracked from creation so that queries can tell a withheld
+//! value ([`AttributeStatus::NotProvided`]) from one never recorded
+//! ([`AttributeStatus::Missing`]).
+//!
+//! Core birth and death dates are kept alongside the attributes so ages can
+//! be derived without replaying events.
+
+use super::PersonProjection;
+use crate::aggregate::PersonId;
+use crate::events::*;
+use crate::value_objects::{
+    age_on, AttributeStatus, AttributeType, AttributeValue, AttributeValueRange, ConfidenceLevel,
+    DatePrecision, IdentifyingAttributeType, PersonAttribute,
+};
+use chrono::NaiveDate;
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Who may read healthcare attributes through the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthcareAccess {
+    /// Healthcare attributes are hidden from queries
+    Denied,
+    /// Caller is authorized for healthcare data (e.g. emergency/medical use)
+    Granted,
+}
+
+/// A person's age on a given date
+///
+/// With an approximate birth date the age is only known to a range;
+/// `years` is counted from the recorded date and lies within it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgeInfo {
+    /// Completed years, counted from the recorded birth date
+    pub years: u32,
+    /// Youngest the person can be, given the birth date's precision
+    pub years_min: u32,
+    /// Oldest the person can be, given the birth date's precision
+    pub years_max: u32,
+    /// Died on or before the as-of date; the age stops at the date of death
+    pub is_deceased: bool,
+    /// How precisely the birth date is known
+    pub precision: DatePrecision,
+    /// Confidence in `years`, following the precision
+    pub confidence: ConfidenceLevel,
+}
+
+/// Birth attribute types, most precise first
+const BIRTH_ATTRIBUTE_TYPES: [IdentifyingAttributeType; 4] = [
+    IdentifyingAttributeType::BirthDateTime,
+    IdentifyingAttributeType::BirthDate,
+    IdentifyingAttributeType::ApproximateBirthDate,
+    IdentifyingAttributeType::BirthYear,
+];
+
+fn confidence_of(precision: DatePrecision) -> ConfidenceLevel {
+    match precision {
+        DatePrecision::Exact => ConfidenceLevel::Certain,
+        DatePrecision::Month => ConfidenceLevel::Likely,
+        DatePrecision::Year => ConfidenceLevel::Possible,
+        DatePrecision::Decade | DatePrecision::Century => ConfidenceLevel::Uncertain,
+    }
+}
+
+/// Projection indexing attributes by type, then by person
+pub struct PersonAttributeIndexProjection {
+    index: Arc<RwLock<HashMap<AttributeType, HashMap<PersonId, PersonAttribute>>>>,
+    persons: Arc<RwLock<HashSet<PersonId>>>,
+    /// Core birth dates set via `BirthDateSet`
+    birth_dates: Arc<RwLock<HashMap<PersonId, NaiveDate>>>,
+    /// Dates of death recorded via `DeathRecorded`
+    death_dates: Arc<RwLock<HashMap<PersonId, NaiveDate>>>,
+    healthcare_access: HealthcareAccess,
+}
+
+impl Default for PersonAttributeIndexProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersonAttributeIndexProjection {
+    /// Create an index that hides healthcare attributes
+    pub fn new() -> Self {
+        Self::with_healthcare_access(HealthcareAccess::Denied)
+    }
+
+    pub fn with_healthcare_access(healthcare_access: HealthcareAccess) -> Self {
+        Self {
+            index: Arc::new(RwLock::new(HashMap::new())),
+            persons: Arc::new(RwLock::new(HashSet::new())),
+            birth_dates: Arc::new(RwLock::new(HashMap::new())),
+            death_dates: Arc::new(RwLock::new(HashMap::new())),
+            healthcare_access,
+        }
+    }
+
+    /// Find all people whose currently-valid attribute of `attr_type` equals `value`
+    pub async fn find_by_attribute_value(
+        &self,
+        attr_type: &AttributeType,
+        value: &AttributeValue,
+    ) -> Vec<PersonId> {
+        if !self.may_read(attr_type) {
+            return Vec::new();
+        }
+
+        let index = self.index.read().await;
+        index.get(attr_type)
+            .map(|people| {
+                people.iter()
+                    .filter(|(_, attr)| &attr.value == value && attr.is_currently_valid())
+                    .map(|(person_id, _)| *person_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Find all people whose attribute of `attr_type` valid on `as_of` lies in `range`
+    ///
+    /// Only the current value of each person is indexed, so `as_of` checks
+    /// that value's validity period; superseded values are not searched.
+    pub async fn find_by_attribute_range(
+        &self,
+        attr_type: &AttributeType,
+        range: &AttributeValueRange,
+        as_of: NaiveDate,
+    ) -> Vec<PersonId> {
+        if !self.may_read(attr_type) {
+            return Vec::new();
+        }
+
+        let index = self.index.read().await;
+        index.get(attr_type)
+            .map(|people| {
+                people.iter()
+                    .filter(|(_, attr)| attr.is_valid_on(as_of) && range.contains(attr))
+                    .map(|(person_id, _)| *person_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Find all known people whose `attr_type` has the given status
+    ///
+    /// `Missing` covers created persons with no currently-valid attribute of
+    /// that type; `NotProvided` matches the recorded reason exactly.
+    pub async fn find_by_status(
+        &self,
+        attr_type: &AttributeType,
+        status: AttributeStatus,
+    ) -> Vec<PersonId> {
+        if !self.may_read(attr_type) {
+            return Vec::new();
+        }
+
+        let persons = self.persons.read().await;
+        let index = self.index.read().await;
+        let people = index.get(attr_type);
+
+        persons.iter()
+            .filter(|person_id| {
+                let current = people
+                    .and_then(|people| people.get(*person_id))
+                    .filter(|attr| attr.is_currently_valid());
+                current.map_or(AttributeStatus::Missing, PersonAttribute::status) == status
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Types of a person's currently-valid attributes that hold a value
+    ///
+    /// Withheld (`NotProvided`) values are left out, as are healthcare
+    /// attributes when the index has no healthcare access.
+    pub async fn provided_attribute_types(&self, person_id: &PersonId) -> HashSet<AttributeType> {
+        let index = self.index.read().await;
+        index.iter()
+            .filter(|(attr_type, _)| {
+                !matches!(attr_type, AttributeType::Healthcare(_))
+                    || self.healthcare_access == HealthcareAccess::Granted
+            })
+            .filter_map(|(attr_type, people)| {
+                let attr = people.get(person_id)?;
+                (attr.is_currently_valid() && attr.status() == AttributeStatus::Provided)
+                    .then(|| attr_type.clone())
+            })
+            .collect()
+    }
+
+    /// Whether the person's core birth date has been set
+    pub async fn has_birth_date(&self, person_id: &PersonId) -> bool {
+        self.birth_dates.read().await.contains_key(person_id)
+    }
+
+    /// A person's age on `as_of`, `None` if no birth date is known or the
+    /// person was not yet born
+    ///
+    /// The most precise currently-valid birth attribute is used, falling
+    /// back to the core birth date.
+    pub async fn get_age_on(&self, person_id: &PersonId, as_of: NaiveDate) -> Option<AgeInfo> {
+        let (birth_date, precision) = self.birth_date_of(person_id).await?;
+        let death_date = self.death_dates.read().await.get(person_id).copied();
+        let is_deceased = death_date.is_some_and(|died| died <= as_of);
+        let on = death_date.map_or(as_of, |died| died.min(as_of));
+
+        let (earliest, latest) = precision.bounds(birth_date);
+        let years_max = age_on(earliest, on)?;
+        let years_min = age_on(latest, on).unwrap_or(0);
+        let years = age_on(birth_date, on).unwrap_or(years_min);
+
+        Some(AgeInfo {
+            years,
+            years_min,
+            years_max,
+            is_deceased,
+            precision,
+            confidence: confidence_of(precision),
+        })
+    }
+
+    async fn birth_date_of(&self, person_id: &PersonId) -> Option<(NaiveDate, DatePrecision)> {
+        let recorded = {
+            let index = self.index.read().await;
+            BIRTH_ATTRIBUTE_TYPES.into_iter().find_map(|attr_type| {
+                index.get(&AttributeType::Identifying(attr_type))?
+                    .get(person_id)
+                    .filter(|attr| attr.is_currently_valid())?
+                    .date_with_precision()
+            })
+        };
+        match recorded {
+            Some(dated) => Some(dated),
+            None => {
+                let birth_date = *self.birth_dates.read().await.get(person_id)?;
+                Some((birth_date, DatePrecision::Exact))
+            }
+        }
+    }
+
+    fn may_read(&self, attr_type: &AttributeType) -> bool {
+        if matches!(attr_type, AttributeType::Healthcare(_))
+            && self.healthcare_access == HealthcareAccess::Denied
+        {
+            tracing::warn!("Healthcare attribute lookup denied for {:?}", attr_type);
+            return false;
+        }
+        true
+    }
+
+    async fn remove_person(&self, person_id: &PersonId) {
+        self.persons.write().await.remove(person_id);
+        self.birth_dates.write().await.remove(person_id);
+        self.death_dates.write().await.remove(person_id);
+        let mut index = self.index.write().await;
+        for people in index.values_mut() {
+            people.remove(person_id);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for PersonAttributeIndexProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        match event {
+            PersonEvent::PersonCreated(e) => {
+                self.persons.write().await.insert(e.person_id);
+            }
+
+            PersonEvent::AttributeRecorded(e) => {
+                self.persons.write().await.insert(e.person_id);
+                let mut index = self.index.write().await;
+                index.entry(e.attribute.attribute_type.clone())
+                    .or_default()
+                    .insert(e.person_id, e.attribute.clone());
+            }
+
+            PersonEvent::AttributeUpdated(e) => {
+                let mut index = self.index.write().await;
+                if let Some(people) = index.get_mut(&e.attribute_type) {
+                    people.remove(&e.person_id);
+                }
+                index.entry(e.new_attribute.attribute_type.clone())
+                    .or_default()
+                    .insert(e.person_id, e.new_attribute.clone());
+            }
+
+            PersonEvent::AttributeInvalidated(e) => {
+                let mut index = self.index.write().await;
+                if let Some(people) = index.get_mut(&e.attribute_type) {
+                    people.remove(&e.person_id);
+                }
+            }
+
+            PersonEvent::BirthDateSet(e) => {
+                self.birth_dates.write().await.insert(e.person_id, e.birth_date);
+            }
+
+            PersonEvent::DeathRecorded(e) => {
+                self.death_dates.write().await.insert(e.person_id, e.date_of_death);
+            }
+
+            PersonEvent::PersonMergedInto(e) => {
+                self.remove_person(&e.source_person_id).await;
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "PersonAttributeIndexProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        self.index.write().await.clear();
+        self.persons.write().await.clear();
+        self.birth_dates.write().await.clear();
+        self.death_dates.write().await.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::{
+        AttributeSource, BloodTypeValue, ConfidenceLevel, DemographicAttributeType,
+        HealthcareAttributeType, NotProvidedReason, PersonName, PhysicalAttributeType, Provenance,
+        TemporalValidity,
+    };
+    use chrono::Utc;
+
+    fn blood_type_recorded(person_id: PersonId, blood_type: BloodTypeValue) -> PersonEvent {
+        PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id,
+            attribute: PersonAttribute::new(
+                AttributeType::Healthcare(HealthcareAttributeType::BloodType),
+                AttributeValue::BloodType(blood_type),
+                TemporalValidity::of(Utc::now()),
+                Provenance::new(AttributeSource::DocumentVerified, ConfidenceLevel::Certain),
+            ),
+            recorded_at: Utc::now(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_find_o_negative_donors() {
+        let projection = PersonAttributeIndexProjection::with_healthcare_access(HealthcareAccess::Granted);
+        let donor_a = PersonId::new();
+        let donor_b = PersonId::new();
+        let a_positive = PersonId::new();
+        let o_positive = PersonId::new();
+
+        for (person, blood_type) in [
+            (donor_a, BloodTypeValue::ONegative),
+            (donor_b, BloodTypeValue::ONegative),
+            (a_positive, BloodTypeValue::APositive),
+            (o_positive, BloodTypeValue::OPositive),
+        ] {
+            projection.handle_event(&blood_type_recorded(person, blood_type)).await.unwrap();
+        }
+
+        let blood_type = AttributeType::Healthcare(HealthcareAttributeType::BloodType);
+        let mut donors = projection
+            .find_by_attribute_value(&blood_type, &AttributeValue::BloodType(BloodTypeValue::ONegative))
+            .await;
+        donors.sort_by_key(|id| id.to_string());
+        let mut expected = vec![donor_a, donor_b];
+        expected.sort_by_key(|id| id.to_string());
+        assert_eq!(donors, expected);
+
+        // Invalidated attributes drop out of the index
+        projection.handle_event(&PersonEvent::AttributeInvalidated(AttributeInvalidated {
+            person_id: donor_b,
+            attribute_type: blood_type.clone(),
+            invalidated_at: Utc::now(),
+            reason: Some("lab error".to_string()),
+            attribute_recorded_at: None,
+        })).await.unwrap();
+
+        let donors = projection
+            .find_by_attribute_value(&blood_type, &AttributeValue::BloodType(BloodTypeValue::ONegative))
+            .await;
+        assert_eq!(donors, vec![donor_a]);
+    }
+
+    #[tokio::test]
+    async fn test_healthcare_values_hidden_without_access() {
+        let projection = PersonAttributeIndexProjection::new();
+        let person = PersonId::new();
+        projection.handle_event(&blood_type_recorded(person, BloodTypeValue::ONegative)).await.unwrap();
+
+        let donors = projection
+            .find_by_attribute_value(
+                &AttributeType::Healthcare(HealthcareAttributeType::BloodType),
+                &AttributeValue::BloodType(BloodTypeValue::ONegative),
+            )
+            .await;
+        assert!(donors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_declined_is_distinct_from_unrecorded() {
+        let projection = PersonAttributeIndexProjection::new();
+        let declined = PersonId::new();
+        let never_asked = PersonId::new();
+
+        for person_id in [declined, never_asked] {
+            projection.handle_event(&PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: PersonName::new("Test".to_string(), "Person".to_string()),
+                source: "test".to_string(),
+                created_at: Utc::now(),
+            })).await.unwrap();
+        }
+
+        let ethnicity = AttributeType::Demographic(DemographicAttributeType::Ethnicity);
+        projection.handle_event(&PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id: declined,
+            attribute: PersonAttribute::new(
+                ethnicity.clone(),
+                AttributeValue::NotProvided { reason: NotProvidedReason::Declined },
+                TemporalValidity::of(Utc::now()),
+                Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+            ),
+            recorded_at: Utc::now(),
+        })).await.unwrap();
+
+        let declined_status = AttributeStatus::NotProvided(NotProvidedReason::Declined);
+        assert_eq!(projection.find_by_status(&ethnicity, declined_status).await, vec![declined]);
+        assert_eq!(projection.find_by_status(&ethnicity, AttributeStatus::Missing).await, vec![never_asked]);
+        assert!(projection.find_by_status(&ethnicity, AttributeStatus::Provided).await.is_empty());
+    }
+
+    fn attribute_with_validity(
+        person_id: PersonId,
+        attribute_type: AttributeType,
+        value: AttributeValue,
+        valid_until: Option<NaiveDate>,
+    ) -> PersonEvent {
+        PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id,
+            attribute: PersonAttribute::new(
+                attribute_type,
+                value,
+                TemporalValidity::new(Utc::now(), None, valid_until),
+                Provenance::new(AttributeSource::Measured, ConfidenceLevel::Certain),
+            ),
+            recorded_at: Utc::now(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_find_heights_in_range_valid_today() {
+        let projection = PersonAttributeIndexProjection::new();
+        let height = AttributeType::Physical(PhysicalAttributeType::Height);
+        let today = Utc::now().date_naive();
+        let [tall, in_range, outdated, wrong_kind] = [PersonId::new(), PersonId::new(), PersonId::new(), PersonId::new()];
+
+        for event in [
+            attribute_with_validity(tall, height.clone(), AttributeValue::Length(1.85), None),
+            attribute_with_validity(in_range, height.clone(), AttributeValue::Length(1.75), None),
+            // Was in range, but no longer valid
+            attribute_with_validity(outdated, height.clone(), AttributeValue::Length(1.72), Some(today - chrono::Duration::days(30))),
+            attribute_with_validity(wrong_kind, height.clone(), AttributeValue::Number(1.75), None),
+        ] {
+            projection.handle_event(&event).await.unwrap();
+        }
+
+        let range = AttributeValueRange::Length { min: 1.7, max: 1.8 };
+        assert_eq!(projection.find_by_attribute_range(&height, &range, today).await, vec![in_range]);
+
+        let mut back_then = projection.find_by_attribute_range(&height, &range, today - chrono::Duration::days(60)).await;
+        back_then.sort_by_key(|id| id.to_string());
+        let mut expected = vec![in_range, outdated];
+        expected.sort_by_key(|id| id.to_string());
+        assert_eq!(back_then, expected);
+
+        let numbers = AttributeValueRange::Number { min: 1.7, max: 1.8 };
+        assert_eq!(projection.find_by_attribute_range(&height, &numbers, today).await, vec![wrong_kind]);
+    }
+
+    #[tokio::test]
+    async fn test_date_ranges_respect_precision() {
+        let projection = PersonAttributeIndexProjection::new();
+        let birth = AttributeType::Identifying(IdentifyingAttributeType::BirthDate);
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let [exact, born_1980, eighties, seventies] = [PersonId::new(), PersonId::new(), PersonId::new(), PersonId::new()];
+
+        for (person_id, value) in [
+            (exact, AttributeValue::Date(date(1982, 6, 15))),
+            (born_1980, AttributeValue::Year(1980)),
+            // Could be anywhere from 1980 to 1989
+            (eighties, AttributeValue::ApproximateDate { date: date(1985, 1, 1), precision: DatePrecision::Decade }),
+            (seventies, AttributeValue::YearMonth(1979, 12)),
+        ] {
+            projection.handle_event(&attribute_with_validity(person_id, birth.clone(), value, None)).await.unwrap();
+        }
+
+        let today = Utc::now().date_naive();
+        let early_eighties = AttributeValueRange::Date { from: date(1980, 1, 1), to: date(1984, 12, 31) };
+        let mut found = projection.find_by_attribute_range(&birth, &early_eighties, today).await;
+        found.sort_by_key(|id| id.to_string());
+        let mut expected = vec![exact, born_1980];
+        expected.sort_by_key(|id| id.to_string());
+        assert_eq!(found, expected);
+
+        let whole_decade = AttributeValueRange::Date { from: date(1980, 1, 1), to: date(1989, 12, 31) };
+        assert_eq!(projection.find_by_attribute_range(&birth, &whole_decade, today).await.len(), 3);
+    }
+}
@@ -0,0 +1,258 @@
+//! Attribute data-quality statistics projection
+//!
+//! Counts persons' current attributes by type and confidence, and how many
+//! were imported from each external system, so dashboards can spot e.g. a
+//! pile of `Uncertain` birth dates. Each person holds at most one current
+//! attribute per type, as in the attribute index: a later recording of the
+//! same type replaces the earlier one.
+
+use super::PersonProjection;
+use crate::aggregate::PersonId;
+use crate::events::PersonEvent;
+use crate::value_objects::{AttributeSource, AttributeType, ConfidenceLevel, PersonAttribute};
+use cim_domain::DomainResult;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Snapshot of attribute counts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttributeStats {
+    /// Current attributes across all persons
+    pub total: usize,
+    /// Current attributes per type, then per confidence
+    pub by_type: HashMap<AttributeType, HashMap<ConfidenceLevel, usize>>,
+    /// Current attributes per importing system (`AttributeSource::Imported`)
+    pub imported_by_system: HashMap<String, usize>,
+}
+
+impl AttributeStats {
+    /// Current attributes of `attr_type` held with `confidence`
+    pub fn count(&self, attr_type: &AttributeType, confidence: ConfidenceLevel) -> usize {
+        self.by_type
+            .get(attr_type)
+            .and_then(|by_confidence| by_confidence.get(&confidence))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// What a counted attribute contributes
+#[derive(Debug, Clone)]
+struct Counted {
+    confidence: ConfidenceLevel,
+    imported_from: Option<String>,
+}
+
+impl From<&PersonAttribute> for Counted {
+    fn from(attribute: &PersonAttribute) -> Self {
+        Self {
+            confidence: attribute.provenance.confidence,
+            imported_from: match &attribute.provenance.source {
+                AttributeSource::Imported { system } => Some(system.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct StatsState {
+    current: HashMap<PersonId, HashMap<AttributeType, Counted>>,
+    stats: AttributeStats,
+}
+
+impl StatsState {
+    fn insert(&mut self, person_id: PersonId, attribute: &PersonAttribute) {
+        self.remove(&person_id, &attribute.attribute_type);
+
+        let counted = Counted::from(attribute);
+        let stats = &mut self.stats;
+        stats.total += 1;
+        *stats.by_type
+            .entry(attribute.attribute_type.clone())
+            .or_default()
+            .entry(counted.confidence)
+            .or_default() += 1;
+        if let Some(system) = &counted.imported_from {
+            *stats.imported_by_system.entry(system.clone()).or_default() += 1;
+        }
+
+        self.current
+            .entry(person_id)
+            .or_default()
+            .insert(attribute.attribute_type.clone(), counted);
+    }
+
+    fn remove(&mut self, person_id: &PersonId, attr_type: &AttributeType) {
+        let Some(counted) = self.current.get_mut(person_id).and_then(|held| held.remove(attr_type)) else {
+            return;
+        };
+        if self.current.get(person_id).is_some_and(HashMap::is_empty) {
+            self.current.remove(person_id);
+        }
+
+        let stats = &mut self.stats;
+        stats.total -= 1;
+        if let Some(by_confidence) = stats.by_type.get_mut(attr_type) {
+            decrement(by_confidence, &counted.confidence);
+            if by_confidence.is_empty() {
+                stats.by_type.remove(attr_type);
+            }
+        }
+        if let Some(system) = &counted.imported_from {
+            decrement(&mut stats.imported_by_system, system);
+        }
+    }
+
+    fn remove_person(&mut self, person_id: &PersonId) {
+        let held: Vec<AttributeType> = self.current
+            .get(person_id)
+            .map(|held| held.keys().cloned().collect())
+            .unwrap_or_default();
+        for attr_type in held {
+            self.remove(person_id, &attr_type);
+        }
+    }
+}
+
+fn decrement<K: Eq + Hash>(counts: &mut HashMap<K, usize>, key: &K) {
+    if let Some(count) = counts.get_mut(key) {
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            counts.remove(key);
+        }
+    }
+}
+
+/// Projection counting attributes by type, confidence and importing system
+pub struct PersonAttributeStatsProjection {
+    state: Arc<RwLock<StatsState>>,
+}
+
+impl Default for PersonAttributeStatsProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersonAttributeStatsProjection {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(RwLock::new(StatsState::default())),
+        }
+    }
+
+    /// Current counts
+    pub async fn get_stats(&self) -> AttributeStats {
+        self.state.read().await.stats.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for PersonAttributeStatsProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        let mut state = self.state.write().await;
+
+        match event {
+            PersonEvent::AttributeRecorded(e) => state.insert(e.person_id, &e.attribute),
+            PersonEvent::AttributeUpdated(e) => {
+                state.remove(&e.person_id, &e.attribute_type);
+                state.insert(e.person_id, &e.new_attribute);
+            }
+            PersonEvent::AttributeInvalidated(e) => state.remove(&e.person_id, &e.attribute_type),
+            PersonEvent::PersonMergedInto(e) => state.remove_person(&e.source_person_id),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "PersonAttributeStatsProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        *self.state.write().await = StatsState::default();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{AttributeInvalidated, AttributeRecorded, AttributeUpdated};
+    use crate::value_objects::{AttributeValue, IdentifyingAttributeType, Provenance, TemporalValidity};
+    use chrono::{NaiveDate, Utc};
+
+    fn birth_date(source: AttributeSource, confidence: ConfidenceLevel) -> PersonAttribute {
+        PersonAttribute::new(
+            AttributeType::Identifying(IdentifyingAttributeType::BirthDate),
+            AttributeValue::Date(NaiveDate::from_ymd_opt(1990, 6, 15).unwrap()),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(source, confidence),
+        )
+    }
+
+    fn imported(system: &str) -> AttributeSource {
+        AttributeSource::Imported { system: system.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_counts_follow_record_update_and_invalidate() {
+        let projection = PersonAttributeStatsProjection::new();
+        let birth = AttributeType::Identifying(IdentifyingAttributeType::BirthDate);
+        let [a, b, c] = [PersonId::new(), PersonId::new(), PersonId::new()];
+
+        for (person_id, attribute) in [
+            (a, birth_date(imported("legacy_crm"), ConfidenceLevel::Uncertain)),
+            (b, birth_date(imported("legacy_crm"), ConfidenceLevel::Uncertain)),
+            (c, birth_date(AttributeSource::DocumentVerified, ConfidenceLevel::Certain)),
+        ] {
+            projection.handle_event(&PersonEvent::AttributeRecorded(AttributeRecorded {
+                person_id,
+                attribute,
+                recorded_at: Utc::now(),
+            })).await.unwrap();
+        }
+
+        let stats = projection.get_stats().await;
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.count(&birth, ConfidenceLevel::Uncertain), 2);
+        assert_eq!(stats.count(&birth, ConfidenceLevel::Certain), 1);
+        assert_eq!(stats.imported_by_system["legacy_crm"], 2);
+
+        // Verifying an imported birth date moves it between buckets
+        projection.handle_event(&PersonEvent::AttributeUpdated(AttributeUpdated {
+            person_id: a,
+            attribute_type: birth.clone(),
+            old_attribute: birth_date(imported("legacy_crm"), ConfidenceLevel::Uncertain),
+            new_attribute: birth_date(AttributeSource::DocumentVerified, ConfidenceLevel::Certain),
+            updated_at: Utc::now(),
+        })).await.unwrap();
+        projection.handle_event(&PersonEvent::AttributeInvalidated(AttributeInvalidated {
+            person_id: b,
+            attribute_type: birth.clone(),
+            invalidated_at: Utc::now(),
+            reason: None,
+            attribute_recorded_at: None,
+        })).await.unwrap();
+
+        let stats = projection.get_stats().await;
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.count(&birth, ConfidenceLevel::Uncertain), 0);
+        assert_eq!(stats.count(&birth, ConfidenceLevel::Certain), 2);
+        assert!(stats.imported_by_system.is_empty());
+
+        // Recording the same type again replaces rather than adds
+        projection.handle_event(&PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id: c,
+            attribute: birth_date(AttributeSource::SelfReported, ConfidenceLevel::Likely),
+            recorded_at: Utc::now(),
+        })).await.unwrap();
+        let stats = projection.get_stats().await;
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.count(&birth, ConfidenceLevel::Likely), 1);
+    }
+}
@@ -0,0 +1,229 @@
+//! Consent history projection
+//!
+//! Keeps every grant and revocation per person and consent type so audits
+//! can show when consent changed, not just what it is now, and which policy
+//! version a decision in force at a given moment was made under.
+
+use super::PersonProjection;
+use crate::aggregate::PersonId;
+use crate::events::*;
+use crate::value_objects::{ConsentStatus, ConsentType};
+use chrono::{DateTime, Utc};
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// One recorded consent change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsentRecord {
+    pub status: ConsentStatus,
+    pub effective_at: DateTime<Utc>,
+    /// Position in this consent type's history, starting at 1
+    pub version: u64,
+    /// Consent policy version the decision was made under, if recorded
+    pub policy_version: Option<String>,
+}
+
+/// Current consent for one purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsentState {
+    /// No consent decision has been recorded
+    NotRecorded,
+    Granted { effective_at: DateTime<Utc> },
+    Revoked { effective_at: DateTime<Utc> },
+}
+
+impl From<&ConsentRecord> for ConsentState {
+    fn from(record: &ConsentRecord) -> Self {
+        match record.status {
+            ConsentStatus::Granted => ConsentState::Granted { effective_at: record.effective_at },
+            ConsentStatus::Revoked => ConsentState::Revoked { effective_at: record.effective_at },
+        }
+    }
+}
+
+/// Projection recording consent history per person and consent type
+pub struct ConsentProjection {
+    history: Arc<RwLock<HashMap<PersonId, HashMap<ConsentType, Vec<ConsentRecord>>>>>,
+}
+
+impl Default for ConsentProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConsentProjection {
+    pub fn new() -> Self {
+        Self {
+            history: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Current consent state and the date it took effect
+    pub async fn consent_state(&self, person_id: &PersonId, consent_type: &ConsentType) -> ConsentState {
+        let history = self.history.read().await;
+        history.get(person_id)
+            .and_then(|by_type| by_type.get(consent_type))
+            .and_then(|records| records.last())
+            .map(ConsentState::from)
+            .unwrap_or(ConsentState::NotRecorded)
+    }
+
+    /// Every recorded change for one consent type, oldest first
+    pub async fn consent_history(&self, person_id: &PersonId, consent_type: &ConsentType) -> Vec<ConsentRecord> {
+        let history = self.history.read().await;
+        history.get(person_id)
+            .and_then(|by_type| by_type.get(consent_type))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The decision in force at `at`: the latest one that took effect by then
+    ///
+    /// `None` when no decision had been recorded yet at that moment.
+    pub async fn consent_status_on(
+        &self,
+        person_id: &PersonId,
+        consent_type: &ConsentType,
+        at: DateTime<Utc>,
+    ) -> Option<ConsentRecord> {
+        let history = self.history.read().await;
+        history.get(person_id)?
+            .get(consent_type)?
+            .iter()
+            .rev()
+            .find(|record| record.effective_at <= at)
+            .cloned()
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for ConsentProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        if let PersonEvent::ConsentRecorded(e) = event {
+            let mut history = self.history.write().await;
+            let records = history.entry(e.person_id)
+                .or_default()
+                .entry(e.consent_type.clone())
+                .or_default();
+            let version = records.len() as u64 + 1;
+            records.push(ConsentRecord {
+                status: e.status,
+                effective_at: e.recorded_at,
+                version,
+                policy_version: e.policy_version.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "ConsentProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        self.history.write().await.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn consent_recorded(
+        person_id: PersonId,
+        status: ConsentStatus,
+        recorded_at: DateTime<Utc>,
+    ) -> PersonEvent {
+        consent_under_policy(person_id, status, recorded_at, None)
+    }
+
+    fn consent_under_policy(
+        person_id: PersonId,
+        status: ConsentStatus,
+        recorded_at: DateTime<Utc>,
+        policy_version: Option<&str>,
+    ) -> PersonEvent {
+        PersonEvent::ConsentRecorded(ConsentRecorded {
+            person_id,
+            consent_type: ConsentType::Marketing,
+            status,
+            recorded_at,
+            policy_version: policy_version.map(str::to_string),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_grant_then_revoke_keeps_full_history() {
+        let projection = ConsentProjection::new();
+        let person_id = PersonId::new();
+        let granted_at = Utc::now() - Duration::days(30);
+        let revoked_at = Utc::now();
+
+        assert_eq!(
+            projection.consent_state(&person_id, &ConsentType::Marketing).await,
+            ConsentState::NotRecorded,
+        );
+
+        projection.handle_event(&consent_recorded(person_id, ConsentStatus::Granted, granted_at)).await.unwrap();
+        projection.handle_event(&consent_recorded(person_id, ConsentStatus::Revoked, revoked_at)).await.unwrap();
+
+        assert_eq!(
+            projection.consent_state(&person_id, &ConsentType::Marketing).await,
+            ConsentState::Revoked { effective_at: revoked_at },
+        );
+
+        let history = projection.consent_history(&person_id, &ConsentType::Marketing).await;
+        assert_eq!(history, vec![
+            ConsentRecord { status: ConsentStatus::Granted, effective_at: granted_at, version: 1, policy_version: None },
+            ConsentRecord { status: ConsentStatus::Revoked, effective_at: revoked_at, version: 2, policy_version: None },
+        ]);
+
+        // Other purposes are tracked independently
+        assert_eq!(
+            projection.consent_state(&person_id, &ConsentType::Research).await,
+            ConsentState::NotRecorded,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_on_returns_decision_in_force_with_its_policy_version() {
+        let projection = ConsentProjection::new();
+        let person_id = PersonId::new();
+        let granted_at = Utc::now() - Duration::days(60);
+        let revoked_at = Utc::now() - Duration::days(20);
+        let regranted_at = Utc::now() - Duration::days(5);
+        for event in [
+            consent_under_policy(person_id, ConsentStatus::Granted, granted_at, Some("2024-01")),
+            consent_under_policy(person_id, ConsentStatus::Revoked, revoked_at, Some("2024-01")),
+            consent_under_policy(person_id, ConsentStatus::Granted, regranted_at, Some("2024-06")),
+        ] {
+            projection.handle_event(&event).await.unwrap();
+        }
+        let marketing = ConsentType::Marketing;
+
+        assert_eq!(projection.consent_status_on(&person_id, &marketing, granted_at - Duration::days(1)).await, None);
+
+        let first = projection.consent_status_on(&person_id, &marketing, granted_at + Duration::days(10)).await.unwrap();
+        assert_eq!(first.status, ConsentStatus::Granted);
+        assert_eq!(first.policy_version.as_deref(), Some("2024-01"));
+
+        // A decision is in force from the instant it was recorded
+        assert_eq!(projection.consent_status_on(&person_id, &marketing, revoked_at).await.unwrap().status, ConsentStatus::Revoked);
+
+        let latest = projection.consent_status_on(&person_id, &marketing, Utc::now()).await.unwrap();
+        assert_eq!(latest.version, 3);
+        assert_eq!(latest.policy_version.as_deref(), Some("2024-06"));
+
+        assert_eq!(
+            projection.consent_status_on(&person_id, &ConsentType::Research, Utc::now()).await,
+            None,
+        );
+    }
+}
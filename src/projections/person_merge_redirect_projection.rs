@@ -0,0 +1,65 @@
+//! Merge redirect projection
+//!
+//! Records where each merged-away person went, from `PersonMergedInto`
+//! events, so queries against a source id can be answered from the
+//! surviving record. Chains of merges resolve to the final survivor.
+
+use super::PersonProjection;
+use crate::aggregate::PersonId;
+use crate::events::PersonEvent;
+use cim_domain::DomainResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Projection mapping merged person ids to the person they merged into
+pub struct PersonMergeRedirectProjection {
+    merged_into: Arc<RwLock<HashMap<PersonId, PersonId>>>,
+}
+
+impl Default for PersonMergeRedirectProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersonMergeRedirectProjection {
+    pub fn new() -> Self {
+        Self {
+            merged_into: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// The surviving person a merged id now lives on, `None` if never merged
+    pub async fn resolve(&self, person_id: &PersonId) -> Option<PersonId> {
+        let merged_into = self.merged_into.read().await;
+        let mut current = *merged_into.get(person_id)?;
+        // A well-formed history has no cycles; bound the walk in case one does
+        for _ in 0..merged_into.len() {
+            match merged_into.get(&current) {
+                Some(next) if next != person_id => current = *next,
+                _ => break,
+            }
+        }
+        Some(current)
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for PersonMergeRedirectProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        if let PersonEvent::PersonMergedInto(e) = event {
+            self.merged_into.write().await.insert(e.source_person_id, e.merged_into_id);
+        }
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "PersonMergeRedirectProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        self.merged_into.write().await.clear();
+        Ok(())
+    }
+}
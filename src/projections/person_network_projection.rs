@@ -3,6 +3,7 @@
 use super::PersonProjection;
 use crate::aggregate::PersonId;
 use crate::events::*;
+use crate::value_objects::CommunicationChannel;
 use cim_domain::DomainResult;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -21,9 +22,71 @@ pub enum RelationshipType {
     Mentor,
     Mentee,
     BusinessPartner,
+    /// Someone to contact in an emergency
+    Emergency,
+    /// Provides care for the person (clinical or informal)
+    Caregiver,
     Other(String),
 }
 
+/// How sensitive the existence of a relationship is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RelationshipSensitivity {
+    /// Professional ties, visible to any viewer
+    Standard,
+    /// Private life (family, friends, unclassified)
+    Personal,
+    /// Care and emergency arrangements
+    Care,
+}
+
+impl RelationshipType {
+    pub fn sensitivity(&self) -> RelationshipSensitivity {
+        match self {
+            RelationshipType::Colleague
+            | RelationshipType::Manager
+            | RelationshipType::Report
+            | RelationshipType::Mentor
+            | RelationshipType::Mentee
+            | RelationshipType::BusinessPartner => RelationshipSensitivity::Standard,
+            RelationshipType::Family
+            | RelationshipType::Friend
+            | RelationshipType::Other(_) => RelationshipSensitivity::Personal,
+            RelationshipType::Emergency
+            | RelationshipType::Caregiver => RelationshipSensitivity::Care,
+        }
+    }
+}
+
+/// Role of whoever is reading the relationship graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ViewerRole {
+    /// Sees every relationship
+    Administrator,
+    /// Sees care and emergency arrangements only
+    Clinician,
+    /// Sees professional ties only
+    Marketer,
+}
+
+impl ViewerRole {
+    pub fn can_view(&self, sensitivity: RelationshipSensitivity) -> bool {
+        match self {
+            ViewerRole::Administrator => true,
+            ViewerRole::Clinician => sensitivity == RelationshipSensitivity::Care,
+            ViewerRole::Marketer => sensitivity == RelationshipSensitivity::Standard,
+        }
+    }
+}
+
+/// Connections a viewer is allowed to see
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactedConnections {
+    pub visible: Vec<PersonRelationship>,
+    /// Edges withheld from this viewer; their endpoints are not disclosed
+    pub redacted_count: usize,
+}
+
 /// A relationship between two people
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonRelationship {
@@ -36,6 +99,17 @@ pub struct PersonRelationship {
     pub interaction_count: usize,
 }
 
+/// An emergency contact, in the order they should be tried
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmergencyContact {
+    pub contact_id: PersonId,
+    /// Lower values are contacted first
+    pub priority: u32,
+    /// Channels the contact can be reached on
+    pub channels: Vec<CommunicationChannel>,
+    pub established_at: DateTime<Utc>,
+}
+
 /// Network statistics for a person
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkStats {
@@ -52,6 +126,7 @@ pub struct PersonNetworkProjection {
     relationships: Arc<RwLock<HashMap<(PersonId, PersonId), PersonRelationship>>>,
     adjacency_list: Arc<RwLock<HashMap<PersonId, HashSet<PersonId>>>>,
     reverse_adjacency: Arc<RwLock<HashMap<PersonId, HashSet<PersonId>>>>,
+    emergency_contacts: Arc<RwLock<HashMap<(PersonId, PersonId), EmergencyContact>>>,
 }
 
 impl Default for PersonNetworkProjection {
@@ -66,12 +141,54 @@ impl PersonNetworkProjection {
             relationships: Arc::new(RwLock::new(HashMap::new())),
             adjacency_list: Arc::new(RwLock::new(HashMap::new())),
             reverse_adjacency: Arc::new(RwLock::new(HashMap::new())),
+            emergency_contacts: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
-    /// Add or update a relationship
-    #[allow(dead_code)] // Reserved for relationship management (belongs in Relationships domain)
-    async fn add_relationship(&self, relationship: PersonRelationship) {
+    /// Add or update an emergency contact for a person
+    pub async fn add_emergency_contact(
+        &self,
+        person_id: PersonId,
+        contact_id: PersonId,
+        priority: u32,
+        channels: Vec<CommunicationChannel>,
+    ) {
+        let established_at = Utc::now();
+        self.add_relationship(PersonRelationship {
+            from_person: person_id,
+            to_person: contact_id,
+            relationship_type: RelationshipType::Emergency,
+            strength: 1.0,
+            established_at,
+            last_interaction: None,
+            interaction_count: 0,
+        }).await;
+
+        let mut emergency_contacts = self.emergency_contacts.write().await;
+        emergency_contacts.insert((person_id, contact_id), EmergencyContact {
+            contact_id,
+            priority,
+            channels,
+            established_at,
+        });
+    }
+
+    /// Emergency contacts for a person, highest priority first
+    pub async fn emergency_contacts(&self, person_id: &PersonId) -> Vec<EmergencyContact> {
+        let connections = self.get_connections(person_id).await;
+        let emergency_contacts = self.emergency_contacts.read().await;
+        let mut contacts: Vec<_> = connections
+            .into_iter()
+            .filter(|rel| rel.relationship_type == RelationshipType::Emergency)
+            .filter_map(|rel| emergency_contacts.get(&(*person_id, rel.to_person)).cloned())
+            .collect();
+
+        contacts.sort_by_key(|c| (c.priority, c.established_at));
+        contacts
+    }
+
+    /// Add or update a relationship reported by the Relationships domain
+    pub async fn add_relationship(&self, relationship: PersonRelationship) {
         let mut relationships = self.relationships.write().await;
         let mut adjacency = self.adjacency_list.write().await;
         let mut reverse = self.reverse_adjacency.write().await;
@@ -89,6 +206,11 @@ impl PersonNetworkProjection {
             .insert(relationship.from_person);
     }
     
+    /// Every relationship in the network, for whole-graph analysis
+    pub async fn relationships(&self) -> Vec<PersonRelationship> {
+        self.relationships.read().await.values().cloned().collect()
+    }
+
     /// Get direct connections for a person
     pub async fn get_connections(&self, person_id: &PersonId) -> Vec<PersonRelationship> {
         let relationships = self.relationships.read().await;
@@ -105,6 +227,18 @@ impl PersonNetworkProjection {
         }
     }
     
+    /// Direct connections filtered to what `viewer` may see
+    pub async fn get_connections_for(&self, person_id: &PersonId, viewer: ViewerRole) -> RedactedConnections {
+        let (visible, redacted): (Vec<_>, Vec<_>) = self.get_connections(person_id).await
+            .into_iter()
+            .partition(|rel| viewer.can_view(rel.relationship_type.sensitivity()));
+
+        RedactedConnections {
+            visible,
+            redacted_count: redacted.len(),
+        }
+    }
+
     /// Get people who have connections to this person
     pub async fn get_incoming_connections(&self, person_id: &PersonId) -> Vec<PersonRelationship> {
         let relationships = self.relationships.read().await;
@@ -256,7 +390,35 @@ impl PersonNetworkProjection {
         to: &PersonId
     ) -> Option<Vec<PersonId>> {
         let adjacency = self.adjacency_list.read().await;
-        
+        Self::shortest_path(&adjacency, from, to)
+    }
+
+    /// Shortest path using only edges `viewer` may see
+    ///
+    /// Hidden edges are not traversed, so the result never reveals a
+    /// connection the viewer could not query directly.
+    pub async fn find_shortest_path_for(
+        &self,
+        from: &PersonId,
+        to: &PersonId,
+        viewer: ViewerRole,
+    ) -> Option<Vec<PersonId>> {
+        let relationships = self.relationships.read().await;
+        let mut visible: HashMap<PersonId, HashSet<PersonId>> = HashMap::new();
+        for ((from_person, to_person), rel) in relationships.iter() {
+            if viewer.can_view(rel.relationship_type.sensitivity()) {
+                visible.entry(*from_person).or_default().insert(*to_person);
+            }
+        }
+
+        Self::shortest_path(&visible, from, to)
+    }
+
+    fn shortest_path(
+        adjacency: &HashMap<PersonId, HashSet<PersonId>>,
+        from: &PersonId,
+        to: &PersonId,
+    ) -> Option<Vec<PersonId>> {
         // BFS to find shortest path
         let mut queue = std::collections::VecDeque::new();
         let mut visited = HashSet::new();
@@ -321,6 +483,11 @@ impl PersonProjection for PersonNetworkProjection {
                 for connections in reverse.values_mut() {
                     connections.remove(&e.person_id);
                 }
+
+                let mut emergency_contacts = self.emergency_contacts.write().await;
+                emergency_contacts.retain(|(from, to), _| {
+                    from != &e.person_id && to != &e.person_id
+                });
             }
             
             PersonEvent::PersonMergedInto(e) => {
@@ -364,6 +531,20 @@ impl PersonProjection for PersonNetworkProjection {
                         .or_insert_with(HashSet::new)
                         .extend(connections);
                 }
+
+                // Emergency contacts follow their relationships
+                let mut emergency_contacts = self.emergency_contacts.write().await;
+                let moved: Vec<_> = emergency_contacts.keys()
+                    .filter(|(from, to)| from == &e.source_person_id || to == &e.source_person_id)
+                    .copied()
+                    .collect();
+                for (from, to) in moved {
+                    if let Some(mut contact) = emergency_contacts.remove(&(from, to)) {
+                        let remap = |id: PersonId| if id == e.source_person_id { e.merged_into_id } else { id };
+                        contact.contact_id = remap(to);
+                        emergency_contacts.insert((remap(from), remap(to)), contact);
+                    }
+                }
             }
             
             _ => {} // Other events don't affect network
@@ -384,7 +565,84 @@ impl PersonProjection for PersonNetworkProjection {
         relationships.clear();
         adjacency.clear();
         reverse.clear();
+        self.emergency_contacts.write().await.clear();
         
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_emergency_contacts_ordered_by_priority() {
+        let projection = PersonNetworkProjection::new();
+        let person = PersonId::new();
+        let sibling = PersonId::new();
+        let partner = PersonId::new();
+
+        projection.add_emergency_contact(person, sibling, 2, vec![CommunicationChannel::Email]).await;
+        projection.add_emergency_contact(
+            person,
+            partner,
+            1,
+            vec![CommunicationChannel::Phone, CommunicationChannel::SMS],
+        ).await;
+
+        let contacts = projection.emergency_contacts(&person).await;
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[0].contact_id, partner);
+        assert_eq!(contacts[0].channels, vec![CommunicationChannel::Phone, CommunicationChannel::SMS]);
+        assert_eq!(contacts[1].contact_id, sibling);
+        assert_eq!(contacts[1].channels, vec![CommunicationChannel::Email]);
+    }
+
+    #[tokio::test]
+    async fn test_connections_redacted_by_viewer_role() {
+        let projection = PersonNetworkProjection::new();
+        let patient = PersonId::new();
+        let coworker = PersonId::new();
+        let nurse = PersonId::new();
+        let friend = PersonId::new();
+
+        for (to_person, relationship_type) in [
+            (coworker, RelationshipType::Colleague),
+            (nurse, RelationshipType::Caregiver),
+            (friend, RelationshipType::Friend),
+        ] {
+            projection.add_relationship(PersonRelationship {
+                from_person: patient,
+                to_person,
+                relationship_type,
+                strength: 0.8,
+                established_at: Utc::now(),
+                last_interaction: None,
+                interaction_count: 0,
+            }).await;
+        }
+
+        let admin_view = projection.get_connections_for(&patient, ViewerRole::Administrator).await;
+        assert_eq!(admin_view.visible.len(), 3);
+        assert_eq!(admin_view.redacted_count, 0);
+
+        // Clinicians see care arrangements, not work or private ties
+        let clinician_view = projection.get_connections_for(&patient, ViewerRole::Clinician).await;
+        assert_eq!(clinician_view.visible.len(), 1);
+        assert_eq!(clinician_view.visible[0].to_person, nurse);
+        assert_eq!(clinician_view.redacted_count, 2);
+
+        let marketer_view = projection.get_connections_for(&patient, ViewerRole::Marketer).await;
+        assert_eq!(marketer_view.visible.len(), 1);
+        assert_eq!(marketer_view.visible[0].to_person, coworker);
+        assert_eq!(marketer_view.redacted_count, 2);
+
+        // Paths don't leak hidden edges either
+        assert_eq!(
+            projection.find_shortest_path_for(&patient, &nurse, ViewerRole::Clinician).await,
+            Some(vec![patient, nurse])
+        );
+        assert_eq!(projection.find_shortest_path_for(&patient, &nurse, ViewerRole::Marketer).await, None);
+        assert_eq!(projection.find_shortest_path_for(&patient, &coworker, ViewerRole::Clinician).await, None);
+    }
+}
@@ -0,0 +1,109 @@
+//! Onboarding progress projection
+//!
+//! Follows each person's onboarding through the state transitions that
+//! [`PersonOnboarding`](crate::aggregate::PersonOnboarding) records as
+//! `onboarding_state` updates. A failed onboarding keeps the steps that were
+//! completed before it failed.
+
+use super::AsyncProjectionHandler;
+use crate::aggregate::{OnboardingState, OnboardingStep, PersonId};
+use crate::events::{PersonEventV2, StreamingEventEnvelope};
+use crate::infrastructure::StreamingEventHandler;
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// How far a person's onboarding has got
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnboardingProgress {
+    pub person_id: PersonId,
+    pub state: OnboardingState,
+    /// Steps taken so far, in order
+    pub completed_steps: Vec<OnboardingStep>,
+    /// `None` once onboarding has completed or failed
+    pub next_step: Option<OnboardingStep>,
+}
+
+impl OnboardingProgress {
+    fn new(person_id: PersonId) -> Self {
+        let state = OnboardingState::Started;
+        Self {
+            person_id,
+            next_step: state.next_step(),
+            state,
+            completed_steps: Vec::new(),
+        }
+    }
+
+    /// Share of the required steps completed, from 0.0 to 1.0
+    pub fn fraction_complete(&self) -> f64 {
+        self.completed_steps.len() as f64 / OnboardingStep::ALL.len() as f64
+    }
+
+    fn advance(&mut self, state: OnboardingState) {
+        if !matches!(state, OnboardingState::Failed { .. }) {
+            let next_step = state.next_step();
+            self.completed_steps = OnboardingStep::ALL.iter()
+                .copied()
+                .take_while(|step| Some(*step) != next_step)
+                .collect();
+        }
+        self.next_step = state.next_step();
+        self.state = state;
+    }
+}
+
+/// Projection of onboarding progress per person
+pub struct PersonOnboardingProjection {
+    progress: Arc<RwLock<HashMap<PersonId, OnboardingProgress>>>,
+}
+
+impl Default for PersonOnboardingProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersonOnboardingProjection {
+    pub fn new() -> Self {
+        Self {
+            progress: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Progress of a person's onboarding, `None` if it never started
+    pub async fn get_progress(&self, person_id: &PersonId) -> Option<OnboardingProgress> {
+        self.progress.read().await.get(person_id).cloned()
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingEventHandler for PersonOnboardingProjection {
+    async fn handle_event(&self, envelope: StreamingEventEnvelope) -> DomainResult<()> {
+        self.handle_specific_event(&envelope.event).await
+    }
+
+    fn name(&self) -> &str {
+        "onboarding-projection"
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncProjectionHandler for PersonOnboardingProjection {
+    fn projection_name(&self) -> &str {
+        "PersonOnboarding"
+    }
+
+    async fn handle_specific_event(&self, event: &PersonEventV2) -> DomainResult<()> {
+        if let Some(state) = OnboardingState::from_event(event) {
+            let person_id = event.aggregate_id();
+            self.progress.write().await
+                .entry(person_id)
+                .or_insert_with(|| OnboardingProgress::new(person_id))
+                .advance(state);
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,31 @@
 //! Person search projection for full-text and faceted search
+//!
+//! Names, employers and roles go into an inverted index of folded tokens,
+//! so queries match regardless of word order, case or diacritics ("smith
+//! garcia" finds "García Smith"). A query token matches an indexed token
+//! exactly or as its prefix, the latter for half the score. Each match
+//! scores the field's weight per occurrence of the token, scaled up for
+//! tokens few people share, and emails, skills and tags add substring
+//! matches on top.
+//!
+//! For misspelled names, [`PersonSearchProjection::search_phonetic`] looks
+//! names up by the Metaphone key of each token, which matches spellings
+//! that sound alike ("Katherine" / "Catherine", "Smith" / "Smyth").
+//!
+//! [`PersonSearchProjection::search_filter`] selects people by a
+//! [`PersonFilter`] expression instead, e.g. "(Rust OR Go) AND NOT at
+//! CompetitorCorp".
 
-use super::{PersonProjection, PersonSearchResult};
+use super::phonetic::metaphone;
+use super::{NameNormalizer, PersonProjection, PersonSearchResult, UnicodeFoldingNormalizer};
 use crate::aggregate::PersonId;
 use crate::events::*;
 use cim_domain::DomainResult;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 /// Search index entry for a person
 #[derive(Debug, Clone)]
@@ -27,11 +45,10 @@ struct SearchEntry {
 }
 
 impl SearchEntry {
-    fn new(person_id: PersonId, name: String, created_at: DateTime<Utc>) -> Self {
-        let name_tokens = tokenize(&name);
+    fn new(person_id: PersonId, name: String, name_tokens: Vec<String>, created_at: DateTime<Utc>) -> Self {
         Self {
             person_id,
-            name: name.clone(),
+            name,
             name_tokens,
             emails: Vec::new(),
             phones: Vec::new(),
@@ -44,51 +61,44 @@ impl SearchEntry {
         }
     }
     
-    fn calculate_relevance(&self, query: &str) -> f32 {
-        let query_tokens = tokenize(query);
-        let mut score = 0.0;
-        
-        // Name matching (highest weight)
-        for token in &query_tokens {
-            if self.name_tokens.iter().any(|t| t.contains(token)) {
-                score += 10.0;
+    /// Tokens to index with their weighted term frequency
+    fn indexed_terms(&self, normalizer: &dyn NameNormalizer) -> HashMap<String, f32> {
+        let mut terms = HashMap::new();
+        let fields = [
+            (self.name_tokens.clone(), NAME_TOKEN_WEIGHT),
+            (self.employer.as_deref().map(|e| tokenize(e, normalizer)).unwrap_or_default(), EMPLOYER_TOKEN_WEIGHT),
+            (self.role.as_deref().map(|r| tokenize(r, normalizer)).unwrap_or_default(), ROLE_TOKEN_WEIGHT),
+        ];
+        for (tokens, weight) in fields {
+            for token in tokens {
+                *terms.entry(token).or_insert(0.0) += weight;
             }
         }
+        terms
+    }
+
+    /// `token_score` from the token index plus substring matches on the
+    /// fields it does not cover
+    fn calculate_relevance(&self, query_tokens: &[String], token_score: f32, normalizer: &dyn NameNormalizer) -> f32 {
+        let mut score = token_score;
         
         // Email matching
-        for token in &query_tokens {
+        for token in query_tokens {
             if self.emails.iter().any(|e| e.contains(token)) {
                 score += 5.0;
             }
         }
         
-        // Employer/role matching
-        if let Some(emp) = &self.employer {
-            for token in &query_tokens {
-                if emp.to_lowercase().contains(token) {
-                    score += 3.0;
-                }
-            }
-        }
-        
-        if let Some(role) = &self.role {
-            for token in &query_tokens {
-                if role.to_lowercase().contains(token) {
-                    score += 3.0;
-                }
-            }
-        }
-        
         // Skills matching
-        for token in &query_tokens {
-            if self.skills.iter().any(|s| s.to_lowercase().contains(token)) {
+        for token in query_tokens {
+            if self.skills.iter().any(|s| normalizer.normalize(s).contains(token)) {
                 score += 2.0;
             }
         }
         
         // Tag matching
-        for token in &query_tokens {
-            if self.tags.iter().any(|t| t.to_lowercase().contains(token)) {
+        for token in query_tokens {
+            if self.tags.iter().any(|t| normalizer.normalize(t).contains(token)) {
                 score += 1.0;
             }
         }
@@ -97,17 +107,191 @@ impl SearchEntry {
     }
 }
 
+/// Composable condition on a person's indexed skills, employer and location
+///
+/// Names are compared after folding with the projection's normalizer, so
+/// case and diacritics do not matter. An empty `And` matches everyone, an
+/// empty `Or` no one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PersonFilter {
+    /// Holds a skill of exactly this name
+    HasSkill(String),
+    /// Currently employed by exactly this employer
+    EmployedBy(String),
+    /// Location contains this text, so "Berlin" matches "Berlin, Germany"
+    InLocation(String),
+    And(Vec<PersonFilter>),
+    Or(Vec<PersonFilter>),
+    Not(Box<PersonFilter>),
+}
+
+impl PersonFilter {
+    pub fn has_skill(skill: impl Into<String>) -> Self {
+        Self::HasSkill(skill.into())
+    }
+
+    pub fn employed_by(employer: impl Into<String>) -> Self {
+        Self::EmployedBy(employer.into())
+    }
+
+    pub fn in_location(location: impl Into<String>) -> Self {
+        Self::InLocation(location.into())
+    }
+
+    /// Both this and `other`, flattening nested `And`s
+    pub fn and(self, other: PersonFilter) -> Self {
+        match self {
+            Self::And(mut filters) => {
+                filters.push(other);
+                Self::And(filters)
+            }
+            filter => Self::And(vec![filter, other]),
+        }
+    }
+
+    /// Either this or `other`, flattening nested `Or`s
+    pub fn or(self, other: PersonFilter) -> Self {
+        match self {
+            Self::Or(mut filters) => {
+                filters.push(other);
+                Self::Or(filters)
+            }
+            filter => Self::Or(vec![filter, other]),
+        }
+    }
+
+    fn matches(&self, entry: &SearchEntry, normalizer: &dyn NameNormalizer) -> bool {
+        let fold = |text: &str| normalizer.normalize(text.trim());
+        match self {
+            Self::HasSkill(skill) => {
+                let skill = fold(skill);
+                entry.skills.iter().any(|held| fold(held) == skill)
+            }
+            Self::EmployedBy(employer) => entry.employer.as_deref().is_some_and(|e| fold(e) == fold(employer)),
+            Self::InLocation(location) => entry.location.as_deref().is_some_and(|l| fold(l).contains(&fold(location))),
+            Self::And(filters) => filters.iter().all(|filter| filter.matches(entry, normalizer)),
+            Self::Or(filters) => filters.iter().any(|filter| filter.matches(entry, normalizer)),
+            Self::Not(filter) => !filter.matches(entry, normalizer),
+        }
+    }
+}
+
+impl std::ops::Not for PersonFilter {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+/// Score per occurrence of a token in each indexed field
+const NAME_TOKEN_WEIGHT: f32 = 10.0;
+const EMPLOYER_TOKEN_WEIGHT: f32 = 3.0;
+const ROLE_TOKEN_WEIGHT: f32 = 3.0;
+
+/// Share of the score earned by a query token that is only a prefix
+const PREFIX_MATCH_FACTOR: f32 = 0.5;
+
+/// Inverted index from folded tokens to the people holding them
+#[derive(Debug, Default)]
+struct TokenIndex {
+    /// Weighted term frequency per person, by token
+    postings: BTreeMap<String, HashMap<PersonId, f32>>,
+    /// Indexed tokens per person, to unindex them
+    terms: HashMap<PersonId, HashMap<String, f32>>,
+}
+
+impl TokenIndex {
+    fn replace(&mut self, person_id: PersonId, terms: HashMap<String, f32>) {
+        self.remove(&person_id);
+        for (token, weight) in &terms {
+            self.postings.entry(token.clone()).or_default().insert(person_id, *weight);
+        }
+        if !terms.is_empty() {
+            self.terms.insert(person_id, terms);
+        }
+    }
+
+    fn remove(&mut self, person_id: &PersonId) {
+        let Some(terms) = self.terms.remove(person_id) else {
+            return;
+        };
+        for token in terms.keys() {
+            if let Some(postings) = self.postings.get_mut(token) {
+                postings.remove(person_id);
+                if postings.is_empty() {
+                    self.postings.remove(token);
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.postings.clear();
+        self.terms.clear();
+    }
+
+    /// Relevance of every person matching at least one query token
+    ///
+    /// Each query token counts once per person, through its best match.
+    fn scores(&self, query_tokens: &[String]) -> HashMap<PersonId, f32> {
+        let people = self.terms.len().max(1) as f32;
+        let mut scores: HashMap<PersonId, f32> = HashMap::new();
+
+        for token in query_tokens {
+            let mut best: HashMap<PersonId, f32> = HashMap::new();
+            let matches = self.postings.range(token.clone()..)
+                .take_while(|(indexed, _)| indexed.starts_with(token.as_str()));
+            for (indexed, postings) in matches {
+                let factor = if indexed == token { 1.0 } else { PREFIX_MATCH_FACTOR };
+                let rarity = 1.0 + (people / postings.len() as f32).ln();
+                for (person_id, weight) in postings {
+                    let score = weight * factor * rarity;
+                    let best = best.entry(*person_id).or_insert(0.0);
+                    *best = best.max(score);
+                }
+            }
+            for (person_id, score) in best {
+                *scores.entry(person_id).or_insert(0.0) += score;
+            }
+        }
+
+        scores
+    }
+}
+
 /// Tokenize a string for search
-fn tokenize(text: &str) -> Vec<String> {
-    text.to_lowercase()
+fn tokenize(text: &str, normalizer: &dyn NameNormalizer) -> Vec<String> {
+    normalizer.normalize(text)
         .split_whitespace()
         .map(|s| s.to_string())
         .collect()
 }
 
+/// Metaphone keys of a name's tokens
+fn phonetic_keys(name_tokens: &[String]) -> HashSet<String> {
+    name_tokens.iter()
+        .map(|token| metaphone(token))
+        .filter(|key| !key.is_empty())
+        .collect()
+}
+
+/// Lowercased domain of an email address, if it has one
+fn email_domain(email: &str) -> Option<String> {
+    let (_, domain) = email.trim().rsplit_once('@')?;
+    (!domain.is_empty()).then(|| domain.to_lowercase())
+}
+
 /// Projection that maintains a searchable index of persons
 pub struct PersonSearchProjection {
     index: Arc<RwLock<HashMap<PersonId, SearchEntry>>>,
+    /// Persons by lowercased email domain
+    email_domains: Arc<RwLock<HashMap<String, HashSet<PersonId>>>>,
+    /// Name, employer and role tokens
+    tokens: Arc<RwLock<TokenIndex>>,
+    /// Persons by Metaphone key of each name token
+    phonetic: Arc<RwLock<HashMap<String, HashSet<PersonId>>>>,
+    normalizer: Arc<dyn NameNormalizer>,
 }
 
 impl Default for PersonSearchProjection {
@@ -117,19 +301,174 @@ impl Default for PersonSearchProjection {
 }
 
 impl PersonSearchProjection {
+    /// Create a projection that folds case and diacritics
     pub fn new() -> Self {
+        Self::with_normalizer(Arc::new(UnicodeFoldingNormalizer))
+    }
+
+    /// Create a projection using `normalizer` for both indexing and queries
+    pub fn with_normalizer(normalizer: Arc<dyn NameNormalizer>) -> Self {
         Self {
             index: Arc::new(RwLock::new(HashMap::new())),
+            email_domains: Arc::new(RwLock::new(HashMap::new())),
+            tokens: Arc::new(RwLock::new(TokenIndex::default())),
+            phonetic: Arc::new(RwLock::new(HashMap::new())),
+            normalizer,
+        }
+    }
+
+    /// Index an email address for a person
+    ///
+    /// Email addresses are owned by the contacts domain; its integration
+    /// feeds them here. Addresses for persons not in the index are ignored.
+    pub async fn index_email(&self, person_id: PersonId, email: &str) {
+        let mut index = self.index.write().await;
+        let Some(entry) = index.get_mut(&person_id) else {
+            return;
+        };
+        if !entry.emails.iter().any(|e| e.eq_ignore_ascii_case(email)) {
+            entry.emails.push(email.to_string());
+        }
+        if let Some(domain) = email_domain(email) {
+            self.email_domains.write().await.entry(domain).or_default().insert(person_id);
         }
     }
+
+    /// Index the employer and role a person works as
+    ///
+    /// Employment is owned by the organization domain; its integration feeds
+    /// it here. Persons not in the index are ignored.
+    pub async fn index_employment(&self, person_id: PersonId, employer: &str, role: Option<&str>) {
+        let mut index = self.index.write().await;
+        let Some(entry) = index.get_mut(&person_id) else {
+            return;
+        };
+        entry.employer = Some(employer.to_string());
+        entry.role = role.map(str::to_string);
+        self.reindex_tokens(entry).await;
+    }
+
+    /// Index a skill a person holds
+    ///
+    /// Skills are fed by the same integration as [`PersonSkillsProjection`](super::PersonSkillsProjection).
+    /// Persons not in the index are ignored.
+    pub async fn index_skill(&self, person_id: PersonId, skill: &str) {
+        if let Some(entry) = self.index.write().await.get_mut(&person_id) {
+            entry.skills.insert(skill.to_string());
+        }
+    }
+
+    /// Index where a person is located
+    ///
+    /// Addresses are owned by the location domain; its integration feeds
+    /// them here. Persons not in the index are ignored.
+    pub async fn index_location(&self, person_id: PersonId, location: &str) {
+        if let Some(entry) = self.index.write().await.get_mut(&person_id) {
+            entry.location = Some(location.to_string());
+        }
+    }
+
+    /// Whether any email address is indexed for a person
+    pub async fn has_email(&self, person_id: &PersonId) -> bool {
+        self.index.read().await.get(person_id).is_some_and(|entry| !entry.emails.is_empty())
+    }
+
+    /// Whether a location is indexed for a person
+    pub async fn has_location(&self, person_id: &PersonId) -> bool {
+        self.index.read().await.get(person_id).is_some_and(|entry| entry.location.is_some())
+    }
+
+    /// Whether an employer is indexed for a person
+    pub async fn has_employer(&self, person_id: &PersonId) -> bool {
+        self.index.read().await.get(person_id).is_some_and(|entry| entry.employer.is_some())
+    }
+
+    /// Remove an email address from a person's index entry
+    pub async fn remove_email(&self, person_id: PersonId, email: &str) {
+        let mut index = self.index.write().await;
+        let Some(entry) = index.get_mut(&person_id) else {
+            return;
+        };
+        entry.emails.retain(|e| !e.eq_ignore_ascii_case(email));
+
+        let Some(domain) = email_domain(email) else {
+            return;
+        };
+        let still_held = entry.emails.iter().any(|e| email_domain(e).as_deref() == Some(domain.as_str()));
+        if !still_held {
+            self.unindex_domain(&domain, &person_id).await;
+        }
+    }
+
+    /// Everyone with an email address at `domain`, compared case-insensitively
+    ///
+    /// Matches the whole domain only: "acme.com" does not match "mail.acme.com".
+    pub async fn find_by_email_domain(&self, domain: &str) -> Vec<PersonId> {
+        let domain = domain.trim().trim_start_matches('@').to_lowercase();
+        self.email_domains.read().await
+            .get(&domain)
+            .map(|persons| persons.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    async fn unindex_domain(&self, domain: &str, person_id: &PersonId) {
+        let mut domains = self.email_domains.write().await;
+        if let Some(persons) = domains.get_mut(domain) {
+            persons.remove(person_id);
+            if persons.is_empty() {
+                domains.remove(domain);
+            }
+        }
+    }
+
+    /// Move a person from the phonetic keys of `old_tokens` to those of `new_tokens`
+    async fn reindex_phonetic(&self, person_id: PersonId, old_tokens: &[String], new_tokens: &[String]) {
+        let mut phonetic = self.phonetic.write().await;
+        for key in phonetic_keys(old_tokens) {
+            if let Some(persons) = phonetic.get_mut(&key) {
+                persons.remove(&person_id);
+                if persons.is_empty() {
+                    phonetic.remove(&key);
+                }
+            }
+        }
+        for key in phonetic_keys(new_tokens) {
+            phonetic.entry(key).or_default().insert(person_id);
+        }
+    }
+
+    async fn reindex_tokens(&self, entry: &SearchEntry) {
+        let terms = entry.indexed_terms(self.normalizer.as_ref());
+        self.tokens.write().await.replace(entry.person_id, terms);
+    }
+
+    /// Drop a person from the index, including their email domains
+    async fn remove_person(&self, person_id: &PersonId) {
+        let Some(entry) = self.index.write().await.remove(person_id) else {
+            return;
+        };
+        self.tokens.write().await.remove(person_id);
+        self.reindex_phonetic(*person_id, &entry.name_tokens, &[]).await;
+        for domain in entry.emails.iter().filter_map(|e| email_domain(e)) {
+            self.unindex_domain(&domain, person_id).await;
+        }
+    }
+
+    /// Tokens indexed for a name: every part of the full name
+    fn name_tokens(&self, name: &crate::value_objects::PersonName) -> Vec<String> {
+        tokenize(&name.full_name(), self.normalizer.as_ref())
+    }
     
     /// Search for persons using a query string
     pub async fn search(&self, query: &str, limit: usize) -> Vec<PersonSearchResult> {
         let index = self.index.read().await;
+        let query_tokens = tokenize(query, self.normalizer.as_ref());
+        let token_scores = self.tokens.read().await.scores(&query_tokens);
         
         let mut results: Vec<_> = index.values()
             .map(|entry| {
-                let relevance = entry.calculate_relevance(query);
+                let token_score = token_scores.get(&entry.person_id).copied().unwrap_or(0.0);
+                let relevance = entry.calculate_relevance(&query_tokens, token_score, self.normalizer.as_ref());
                 (entry, relevance)
             })
             .filter(|(_, relevance)| *relevance > 0.0)
@@ -152,6 +491,47 @@ impl PersonSearchProjection {
             .collect()
     }
     
+    /// Search for persons whose names sound like the query
+    ///
+    /// Relevance is the share of the query's tokens whose Metaphone key one
+    /// of the person's name tokens shares, so spelling does not matter as
+    /// long as the pronunciation does not change.
+    pub async fn search_phonetic(&self, query: &str, limit: usize) -> Vec<PersonSearchResult> {
+        let query_keys = phonetic_keys(&tokenize(query, self.normalizer.as_ref()));
+        if query_keys.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matched: HashMap<PersonId, usize> = HashMap::new();
+        {
+            let phonetic = self.phonetic.read().await;
+            for persons in query_keys.iter().filter_map(|key| phonetic.get(key)) {
+                for person_id in persons {
+                    *matched.entry(*person_id).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let index = self.index.read().await;
+        let mut results: Vec<PersonSearchResult> = matched.into_iter()
+            .filter_map(|(person_id, count)| {
+                let entry = index.get(&person_id)?;
+                Some(PersonSearchResult {
+                    person_id,
+                    name: entry.name.clone(),
+                    email: entry.emails.first().cloned(),
+                    employer: entry.employer.clone(),
+                    role: entry.role.clone(),
+                    relevance_score: count as f32 / query_keys.len() as f32,
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.relevance_score.partial_cmp(&a.relevance_score).unwrap());
+        results.truncate(limit);
+        results
+    }
+    
     /// Search with filters
     pub async fn search_with_filters(
         &self,
@@ -162,6 +542,8 @@ impl PersonSearchProjection {
         limit: usize,
     ) -> Vec<PersonSearchResult> {
         let index = self.index.read().await;
+        let query_tokens = query.map(|q| tokenize(q, self.normalizer.as_ref())).unwrap_or_default();
+        let token_scores = self.tokens.read().await.scores(&query_tokens);
         
         let mut results: Vec<_> = index.values()
             .filter(|entry| {
@@ -187,7 +569,12 @@ impl PersonSearchProjection {
                 true
             })
             .map(|entry| {
-                let relevance = query.map(|q| entry.calculate_relevance(q)).unwrap_or(1.0);
+                let token_score = token_scores.get(&entry.person_id).copied().unwrap_or(0.0);
+                let relevance = if query.is_some() {
+                    entry.calculate_relevance(&query_tokens, token_score, self.normalizer.as_ref())
+                } else {
+                    1.0
+                };
                 (entry, relevance)
             })
             .filter(|(_, relevance)| query.is_none() || *relevance > 0.0)
@@ -210,6 +597,29 @@ impl PersonSearchProjection {
             .collect()
     }
     
+    /// Everyone matching `filter`, ordered by name
+    ///
+    /// Every match has a relevance of 1.0; the filter selects, it does not rank.
+    pub async fn search_filter(&self, filter: &PersonFilter, limit: usize) -> Vec<PersonSearchResult> {
+        let index = self.index.read().await;
+        let mut matches: Vec<&SearchEntry> = index.values()
+            .filter(|entry| filter.matches(entry, self.normalizer.as_ref()))
+            .collect();
+        matches.sort_by(|a, b| a.name.cmp(&b.name));
+
+        matches.into_iter()
+            .take(limit)
+            .map(|entry| PersonSearchResult {
+                person_id: entry.person_id,
+                name: entry.name.clone(),
+                email: entry.emails.first().cloned(),
+                employer: entry.employer.clone(),
+                role: entry.role.clone(),
+                relevance_score: 1.0,
+            })
+            .collect()
+    }
+    
     /// Get all unique employers
     pub async fn get_employers(&self) -> Vec<String> {
         let index = self.index.read().await;
@@ -248,7 +658,14 @@ impl PersonProjection for PersonSearchProjection {
     async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
         match event {
             PersonEvent::PersonCreated(e) => {
-                let entry = SearchEntry::new(e.person_id, e.name.display_name(), e.created_at);
+                let entry = SearchEntry::new(
+                    e.person_id,
+                    e.name.display_name(),
+                    self.name_tokens(&e.name),
+                    e.created_at,
+                );
+                self.reindex_tokens(&entry).await;
+                self.reindex_phonetic(e.person_id, &[], &entry.name_tokens).await;
                 let mut index = self.index.write().await;
                 index.insert(e.person_id, entry);
             }
@@ -256,23 +673,33 @@ impl PersonProjection for PersonSearchProjection {
             PersonEvent::NameUpdated(e) => {
                 let mut index = self.index.write().await;
                 if let Some(entry) = index.get_mut(&e.person_id) {
+                    let old_tokens = std::mem::replace(&mut entry.name_tokens, self.name_tokens(&e.new_name));
                     entry.name = e.new_name.display_name();
-                    entry.name_tokens = tokenize(&entry.name);
                     entry.last_updated = e.updated_at;
+                    self.reindex_tokens(entry).await;
+                    self.reindex_phonetic(e.person_id, &old_tokens, &entry.name_tokens).await;
                 }
             }
 
             // ComponentDataUpdated removed - components belong in separate domains
 
-            PersonEvent::PersonDeactivated(e) => {
-                let mut index = self.index.write().await;
-                index.remove(&e.person_id);
+            PersonEvent::TagAdded(e) => {
+                if let Some(entry) = self.index.write().await.get_mut(&e.person_id) {
+                    entry.tags.insert(e.tag.to_string());
+                    entry.last_updated = e.added_at;
+                }
             }
-            
-            PersonEvent::PersonMergedInto(e) => {
-                let mut index = self.index.write().await;
-                index.remove(&e.source_person_id);
+
+            PersonEvent::TagRemoved(e) => {
+                if let Some(entry) = self.index.write().await.get_mut(&e.person_id) {
+                    entry.tags.remove(&e.tag.to_string());
+                    entry.last_updated = e.removed_at;
+                }
             }
+
+            PersonEvent::PersonDeactivated(e) => self.remove_person(&e.person_id).await,
+
+            PersonEvent::PersonMergedInto(e) => self.remove_person(&e.source_person_id).await,
             
             _ => {} // Other events don't affect search index
         }
@@ -285,8 +712,209 @@ impl PersonProjection for PersonSearchProjection {
     }
     
     async fn clear(&self) -> DomainResult<()> {
-        let mut index = self.index.write().await;
-        index.clear();
+        self.index.write().await.clear();
+        self.email_domains.write().await.clear();
+        self.tokens.write().await.clear();
+        self.phonetic.write().await.clear();
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projections::GermanTransliterationNormalizer;
+    use crate::value_objects::PersonName;
+
+    async fn index_person(projection: &PersonSearchProjection, given: &str, family: &str) -> PersonId {
+        let person_id = PersonId::new();
+        projection.handle_event(&PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new(given.to_string(), family.to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        })).await.unwrap();
+        person_id
+    }
+
+    #[tokio::test]
+    async fn test_diacritics_are_folded_when_searching() {
+        let projection = PersonSearchProjection::new();
+        let zoe = index_person(&projection, "Zoë", "Saldana").await;
+
+        let results = projection.search("zoe", 10).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].person_id, zoe);
+    }
+
+    #[tokio::test]
+    async fn test_german_transliteration_matches_umlauts() {
+        let projection = PersonSearchProjection::with_normalizer(Arc::new(GermanTransliterationNormalizer));
+        let mueller = index_person(&projection, "Thomas", "Müller").await;
+
+        for query in ["Mueller", "Müller"] {
+            let results = projection.search(query, 10).await;
+            assert_eq!(results.len(), 1, "query {query}");
+            assert_eq!(results[0].person_id, mueller);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_by_email_domain_ignores_case() {
+        let projection = PersonSearchProjection::new();
+        let ada = index_person(&projection, "Ada", "Lovelace").await;
+        let grace = index_person(&projection, "Grace", "Hopper").await;
+        let alan = index_person(&projection, "Alan", "Turing").await;
+
+        projection.index_email(ada, "ada@acme.com").await;
+        projection.index_email(grace, "Grace.Hopper@ACME.COM").await;
+        projection.index_email(grace, "grace@navy.mil").await;
+        projection.index_email(alan, "alan@mail.acme.com").await;
+
+        let holders: HashSet<PersonId> = projection.find_by_email_domain("Acme.Com").await.into_iter().collect();
+        assert_eq!(holders, HashSet::from([ada, grace]));
+        assert_eq!(projection.find_by_email_domain("@navy.mil").await, vec![grace]);
+
+        projection.remove_email(grace, "grace.hopper@acme.com").await;
+        assert_eq!(projection.find_by_email_domain("acme.com").await, vec![ada]);
+        assert_eq!(projection.find_by_email_domain("navy.mil").await, vec![grace]);
+    }
+
+    #[tokio::test]
+    async fn test_word_order_does_not_matter() {
+        let projection = PersonSearchProjection::new();
+        let jane_smith = index_person(&projection, "Jane", "Smith").await;
+        let john_smith = index_person(&projection, "John", "Smith").await;
+        index_person(&projection, "Jane", "Doe").await;
+        index_person(&projection, "Alan", "Turing").await;
+
+        for query in ["smith jane", "Jane Smith", "SMITH  JANE"] {
+            let results = projection.search(query, 10).await;
+            assert_eq!(results.len(), 3, "query {query}");
+            assert_eq!(results[0].person_id, jane_smith, "query {query}");
+            assert!(results[0].relevance_score > results[1].relevance_score);
+        }
+
+        // A prefix still matches, for less than the whole token
+        let results = projection.search("smi", 10).await;
+        assert_eq!(results.len(), 2);
+        let exact = projection.search("smith", 10).await;
+        assert!(results[0].relevance_score < exact[0].relevance_score);
+
+        // Renames are reindexed
+        projection.handle_event(&PersonEvent::NameUpdated(NameUpdated {
+            person_id: john_smith,
+            old_name: PersonName::new("John".to_string(), "Smith".to_string()),
+            new_name: PersonName::new("John".to_string(), "Doe".to_string()),
+            reason: None,
+            updated_at: Utc::now(),
+        })).await.unwrap();
+        let results = projection.search("smith", 10).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].person_id, jane_smith);
+    }
+
+    #[tokio::test]
+    async fn test_accents_are_folded_in_names_and_employers() {
+        let projection = PersonSearchProjection::new();
+        let garcia = index_person(&projection, "José", "García").await;
+        let other = index_person(&projection, "Ana", "Gomez").await;
+        projection.index_employment(other, "Société Générale", Some("Analyste")).await;
+
+        for query in ["garcia jose", "GARCÍA", "josé"] {
+            let results = projection.search(query, 10).await;
+            assert_eq!(results.len(), 1, "query {query}");
+            assert_eq!(results[0].person_id, garcia);
+        }
+
+        let results = projection.search("societe analyste", 10).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].person_id, other);
+        assert_eq!(results[0].employer.as_deref(), Some("Société Générale"));
+
+        // Name matches outweigh employer matches
+        projection.index_employment(garcia, "Gomez Partners", None).await;
+        let results = projection.search("gomez", 10).await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].person_id, other);
+    }
+
+    #[tokio::test]
+    async fn test_names_that_sound_alike_match_phonetically() {
+        let projection = PersonSearchProjection::new();
+        let katherine = index_person(&projection, "Katherine", "Smyth").await;
+        let philip = index_person(&projection, "Philip", "Knight").await;
+        let caroline = index_person(&projection, "Caroline", "Jones").await;
+
+        // The spelling differs, so plain search finds nothing
+        assert!(projection.search("catherine smith", 10).await.is_empty());
+
+        let results = projection.search_phonetic("Catherine Smith", 10).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].person_id, katherine);
+        assert_eq!(results[0].relevance_score, 1.0);
+
+        let results = projection.search_phonetic("filip nite", 10).await;
+        assert_eq!(results[0].person_id, philip);
+        assert_eq!(results[0].relevance_score, 1.0);
+
+        assert_eq!(projection.search_phonetic("Karolyne", 10).await[0].person_id, caroline);
+
+        // Renames move the phonetic keys too
+        projection.handle_event(&PersonEvent::NameUpdated(NameUpdated {
+            person_id: katherine,
+            old_name: PersonName::new("Katherine".to_string(), "Smyth".to_string()),
+            new_name: PersonName::new("Katherine".to_string(), "Brown".to_string()),
+            reason: None,
+            updated_at: Utc::now(),
+        })).await.unwrap();
+        let results = projection.search_phonetic("Catherine Smith", 10).await;
+        assert_eq!(results[0].relevance_score, 0.5);
+        assert!(projection.search_phonetic("smith", 10).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_nested_filters() {
+        let projection = PersonSearchProjection::new();
+        let ada = index_person(&projection, "Ada", "Lovelace").await;
+        let grace = index_person(&projection, "Grace", "Hopper").await;
+        let alan = index_person(&projection, "Alan", "Turing").await;
+        let edsger = index_person(&projection, "Edsger", "Dijkstra").await;
+
+        projection.index_skill(ada, "Rust").await;
+        projection.index_employment(ada, "Analytical Engines", None).await;
+        projection.index_location(ada, "London, UK").await;
+        projection.index_skill(grace, "Go").await;
+        projection.index_employment(grace, "CompetitorCorp", None).await;
+        projection.index_skill(alan, "go").await;
+        projection.index_location(alan, "Manchester, UK").await;
+        projection.index_skill(edsger, "Pascal").await;
+
+        let ids = |results: Vec<PersonSearchResult>| -> Vec<PersonId> {
+            results.into_iter().map(|result| result.person_id).collect()
+        };
+
+        // (Rust OR Go) AND NOT at CompetitorCorp, ordered by name
+        let filter = PersonFilter::has_skill("Rust")
+            .or(PersonFilter::has_skill("Go"))
+            .and(!PersonFilter::employed_by("competitorcorp"));
+        assert_eq!(ids(projection.search_filter(&filter, 10).await), vec![ada, alan]);
+        assert_eq!(ids(projection.search_filter(&filter, 1).await), vec![ada]);
+
+        // In the UK, and either not a Go programmer or in Manchester
+        let filter = PersonFilter::And(vec![
+            PersonFilter::in_location("uk"),
+            PersonFilter::Or(vec![
+                !PersonFilter::has_skill("Go"),
+                PersonFilter::in_location("Manchester"),
+            ]),
+        ]);
+        assert_eq!(ids(projection.search_filter(&filter, 10).await), vec![ada, alan]);
+
+        let not_uk = !PersonFilter::in_location("UK");
+        assert_eq!(ids(projection.search_filter(&not_uk, 10).await), vec![edsger, grace]);
+
+        assert_eq!(projection.search_filter(&PersonFilter::And(vec![]), 10).await.len(), 4);
+        assert!(projection.search_filter(&PersonFilter::Or(vec![]), 10).await.is_empty());
+    }
+}
@@ -3,10 +3,11 @@
 //! NOTE: This projection should eventually move to a separate Skills domain.
 //! Skills are not core to Person identity.
 
-use super::{PersonProjection, SkillSummary};
+use super::{PersonProjection, SkillSummary, SkillTaxonomy};
 use crate::aggregate::PersonId;
 use crate::events::*;
-use cim_domain::DomainResult;
+use crate::value_objects::ProficiencyLevel;
+use cim_domain::{DomainError, DomainResult};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -18,10 +19,49 @@ use chrono::{DateTime, Utc};
 struct Skill {
     name: String,
     category: String,
-    proficiency: String,
+    proficiency: ProficiencyLevel,
     years_experience: Option<f32>,
     last_used: Option<DateTime<Utc>>,
-    endorsement_count: u32,
+    endorsements: Vec<Endorsement>,
+}
+
+/// An endorsement of one person's skill by another person
+///
+/// The endorsement does not store a weight. Its weight is the endorser's own
+/// proficiency in the same skill, resolved from this projection at ranking
+/// time, so an endorser who later levels up lends more weight retroactively.
+#[derive(Debug, Clone)]
+pub struct Endorsement {
+    pub endorser_id: PersonId,
+    pub comment: Option<String>,
+    pub endorsed_at: DateTime<Utc>,
+}
+
+impl Endorsement {
+    pub fn new(endorser_id: PersonId) -> Self {
+        Self {
+            endorser_id,
+            comment: None,
+            endorsed_at: Utc::now(),
+        }
+    }
+
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
+/// Weight lent by an endorser who does not hold the endorsed skill themselves
+const UNQUALIFIED_ENDORSER_WEIGHT: f32 = 0.1;
+
+fn proficiency_weight(level: &ProficiencyLevel) -> f32 {
+    match level {
+        ProficiencyLevel::Beginner => 0.25,
+        ProficiencyLevel::Intermediate => 0.5,
+        ProficiencyLevel::Advanced => 0.75,
+        ProficiencyLevel::Expert => 1.0,
+    }
 }
 
 /// Skill profile for a person
@@ -38,12 +78,30 @@ struct PersonSkillProfile {
 #[derive(Debug, Clone)]
 struct SkillInfo {
     skill: Skill,
+    /// Proficiency weight after decay; the declared weight until decayed
+    effective_proficiency: f32,
     #[allow(dead_code)]
     added_at: DateTime<Utc>,
     #[allow(dead_code)]
     sources: HashSet<String>, // Where the skill came from (manual, git, etc.)
 }
 
+impl PersonSkillProfile {
+    fn find_skill(&self, skill_name: &str) -> Option<&SkillInfo> {
+        let skill_lower = skill_name.to_lowercase();
+        self.skills.iter()
+            .find(|(name, _)| name.to_lowercase() == skill_lower)
+            .map(|(_, info)| info)
+    }
+
+    fn find_skill_mut(&mut self, skill_name: &str) -> Option<&mut SkillInfo> {
+        let skill_lower = skill_name.to_lowercase();
+        self.skills.iter_mut()
+            .find(|(name, _)| name.to_lowercase() == skill_lower)
+            .map(|(_, info)| info)
+    }
+}
+
 /// Global skill statistics
 #[derive(Debug, Clone, Default)]
 struct SkillStatistics {
@@ -56,6 +114,7 @@ struct SkillStatistics {
 pub struct PersonSkillsProjection {
     profiles: Arc<RwLock<HashMap<PersonId, PersonSkillProfile>>>,
     statistics: Arc<RwLock<SkillStatistics>>,
+    taxonomy: SkillTaxonomy,
 }
 
 impl Default for PersonSkillsProjection {
@@ -69,8 +128,15 @@ impl PersonSkillsProjection {
         Self {
             profiles: Arc::new(RwLock::new(HashMap::new())),
             statistics: Arc::new(RwLock::new(SkillStatistics::default())),
+            taxonomy: SkillTaxonomy::new(),
         }
     }
+
+    /// Resolve broader/narrower skills with `taxonomy`
+    pub fn with_taxonomy(mut self, taxonomy: SkillTaxonomy) -> Self {
+        self.taxonomy = taxonomy;
+        self
+    }
     
     /// Get skills for a person
     pub async fn get_person_skills(&self, person_id: &PersonId) -> Vec<SkillSummary> {
@@ -81,10 +147,11 @@ impl PersonSkillsProjection {
                 .map(|info| SkillSummary {
                     skill_name: info.skill.name.clone(),
                     category: info.skill.category.clone(),
-                    proficiency: info.skill.proficiency.clone(),
+                    proficiency: format!("{:?}", info.skill.proficiency),
+                    effective_proficiency: info.effective_proficiency,
                     years_experience: info.skill.years_experience,
                     last_used: info.skill.last_used,
-                    endorsement_count: info.skill.endorsement_count as usize,
+                    endorsement_count: info.skill.endorsements.len(),
                 })
                 .collect()
         } else {
@@ -92,19 +159,206 @@ impl PersonSkillsProjection {
         }
     }
     
+    /// Record (or re-rate) a skill held by a person
+    pub async fn record_skill(
+        &self,
+        person_id: PersonId,
+        skill_name: &str,
+        category: &str,
+        proficiency: ProficiencyLevel,
+    ) {
+        let mut profiles = self.profiles.write().await;
+        let mut statistics = self.statistics.write().await;
+        let now = Utc::now();
+
+        let profile = profiles.entry(person_id).or_insert_with(|| PersonSkillProfile {
+            person_id,
+            skills: HashMap::new(),
+            skill_categories: HashMap::new(),
+            last_updated: now,
+        });
+        profile.last_updated = now;
+
+        if let Some(info) = profile.find_skill_mut(skill_name) {
+            info.effective_proficiency = proficiency_weight(&proficiency);
+            info.skill.proficiency = proficiency;
+            return;
+        }
+
+        let effective_proficiency = proficiency_weight(&proficiency);
+        profile.skills.insert(skill_name.to_string(), SkillInfo {
+            skill: Skill {
+                name: skill_name.to_string(),
+                category: category.to_string(),
+                proficiency,
+                years_experience: None,
+                last_used: None,
+                endorsements: Vec::new(),
+            },
+            effective_proficiency,
+            added_at: now,
+            sources: HashSet::new(),
+        });
+        profile.skill_categories
+            .entry(category.to_string())
+            .or_default()
+            .insert(skill_name.to_string());
+
+        *statistics.skill_counts.entry(skill_name.to_string()).or_insert(0) += 1;
+        statistics.skill_categories
+            .entry(category.to_string())
+            .or_default()
+            .insert(skill_name.to_string());
+    }
+
+    /// Record that a person last used a skill at `used_at`
+    pub async fn mark_skill_used(
+        &self,
+        person_id: &PersonId,
+        skill_name: &str,
+        used_at: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        let mut profiles = self.profiles.write().await;
+        let info = profiles
+            .get_mut(person_id)
+            .and_then(|profile| profile.find_skill_mut(skill_name))
+            .ok_or_else(|| DomainError::ValidationError(format!(
+                "Person {person_id} has no skill '{skill_name}'"
+            )))?;
+
+        info.skill.last_used = Some(used_at);
+        Ok(())
+    }
+
+    /// Recompute effective proficiencies as of `now`
+    ///
+    /// A skill's effective proficiency is its declared weight halved for
+    /// every `half_life` since it was last used. Scores are recomputed from
+    /// the declared weight, so repeated calls do not compound. Skills never
+    /// marked used keep their declared weight, as do all skills when
+    /// `half_life` is not positive.
+    pub async fn apply_decay(&self, now: DateTime<Utc>, half_life: chrono::Duration) {
+        if half_life <= chrono::Duration::zero() {
+            return;
+        }
+        let half_life_ms = half_life.num_milliseconds() as f32;
+        let mut profiles = self.profiles.write().await;
+
+        for info in profiles.values_mut().flat_map(|profile| profile.skills.values_mut()) {
+            let declared = proficiency_weight(&info.skill.proficiency);
+            info.effective_proficiency = match info.skill.last_used {
+                Some(last_used) => {
+                    let idle_ms = (now - last_used).num_milliseconds().max(0);
+                    declared * 0.5f32.powf(idle_ms as f32 / half_life_ms)
+                }
+                None => declared,
+            };
+        }
+    }
+
+    /// Endorse a skill that a person already holds
+    ///
+    /// Self-endorsements are rejected.
+    pub async fn endorse_skill(
+        &self,
+        person_id: &PersonId,
+        skill_name: &str,
+        endorsement: Endorsement,
+    ) -> DomainResult<()> {
+        if endorsement.endorser_id == *person_id {
+            return Err(DomainError::ValidationError(
+                "A person cannot endorse their own skill".to_string(),
+            ));
+        }
+
+        let mut profiles = self.profiles.write().await;
+        let info = profiles
+            .get_mut(person_id)
+            .and_then(|profile| profile.find_skill_mut(skill_name))
+            .ok_or_else(|| DomainError::ValidationError(format!(
+                "Person {person_id} has no skill '{skill_name}' to endorse"
+            )))?;
+
+        // One endorsement per endorser; a repeat replaces the earlier one
+        info.skill.endorsements.retain(|e| e.endorser_id != endorsement.endorser_id);
+        info.skill.endorsements.push(endorsement);
+        Ok(())
+    }
+
+    /// Rank people holding a skill, strongest first
+    ///
+    /// A person's score is their own proficiency weight plus, for every
+    /// endorsement, the endorser's proficiency weight in the same skill.
+    pub async fn rank_people_for_skill(&self, skill_name: &str) -> Vec<(PersonId, f32)> {
+        let profiles = self.profiles.read().await;
+
+        let endorser_weight = |endorser_id: &PersonId| {
+            profiles.get(endorser_id)
+                .and_then(|profile| profile.find_skill(skill_name))
+                .map(|info| proficiency_weight(&info.skill.proficiency))
+                .unwrap_or(UNQUALIFIED_ENDORSER_WEIGHT)
+        };
+
+        let mut ranking: Vec<(PersonId, f32)> = profiles.values()
+            .filter_map(|profile| {
+                let info = profile.find_skill(skill_name)?;
+                let endorsement_score: f32 = info.skill.endorsements.iter()
+                    .filter(|e| e.endorser_id != profile.person_id)
+                    .map(|e| endorser_weight(&e.endorser_id))
+                    .sum();
+                Some((
+                    profile.person_id,
+                    proficiency_weight(&info.skill.proficiency) + endorsement_score,
+                ))
+            })
+            .collect();
+
+        ranking.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranking
+    }
+
     /// Find people with a specific skill
-    pub async fn find_people_with_skill(&self, skill_name: &str) -> Vec<PersonId> {
+    ///
+    /// With `min_effective_proficiency`, only holders whose effective
+    /// proficiency (see [`Self::apply_decay`]) reaches it are returned.
+    pub async fn find_people_with_skill(
+        &self,
+        skill_name: &str,
+        min_effective_proficiency: Option<f32>,
+    ) -> Vec<PersonId> {
         let profiles = self.profiles.read().await;
-        let skill_lower = skill_name.to_lowercase();
         
         profiles.values()
             .filter(|profile| {
-                profile.skills.keys().any(|s| s.to_lowercase() == skill_lower)
+                profile.find_skill(skill_name).is_some_and(|info| {
+                    min_effective_proficiency.map_or(true, |min| info.effective_proficiency >= min)
+                })
             })
             .map(|profile| profile.person_id)
             .collect()
     }
     
+    /// Find people with a skill, optionally counting narrower skills
+    ///
+    /// With `include_descendants`, holders of any skill beneath `skill_name`
+    /// in the taxonomy (e.g. "React" for "JavaScript") are included.
+    pub async fn find_people_with_skill_matching(
+        &self,
+        skill_name: &str,
+        include_descendants: bool,
+    ) -> Vec<PersonId> {
+        if !include_descendants {
+            return self.find_people_with_skill(skill_name, None).await;
+        }
+
+        let wanted = self.taxonomy.descendants(skill_name);
+        let profiles = self.profiles.read().await;
+        profiles.values()
+            .filter(|profile| profile.skills.keys().any(|s| wanted.contains(&s.to_lowercase())))
+            .map(|profile| profile.person_id)
+            .collect()
+    }
+
     /// Find people with multiple skills
     pub async fn find_people_with_skills(&self, required_skills: &[String]) -> Vec<PersonId> {
         let profiles = self.profiles.read().await;
@@ -123,6 +377,46 @@ impl PersonSkillsProjection {
             .collect()
     }
     
+    /// Rank people against a requisition, including partial matches
+    ///
+    /// `required` lists each skill with the level asked for and its weight.
+    /// A held skill earns its weight scaled by how the holder's effective
+    /// proficiency compares with the asked-for level: proportionally less
+    /// when below it, with a bonus for the margin when above it. Scores are
+    /// divided by the total weight, so 1.0 means every skill at exactly the
+    /// asked-for level. People holding none of the skills are left out.
+    pub async fn rank_candidates(
+        &self,
+        required: &[(String, ProficiencyLevel, f32)],
+        limit: usize,
+    ) -> Vec<(PersonId, f32)> {
+        let total_weight: f32 = required.iter().map(|(_, _, weight)| weight.max(0.0)).sum();
+        if total_weight <= 0.0 {
+            return Vec::new();
+        }
+        let profiles = self.profiles.read().await;
+
+        let mut ranking: Vec<(PersonId, f32)> = profiles.values()
+            .filter_map(|profile| {
+                let mut matched = false;
+                let score: f32 = required.iter()
+                    .filter_map(|(skill_name, level, weight)| {
+                        let held = profile.find_skill(skill_name)?.effective_proficiency;
+                        matched = true;
+                        let asked = proficiency_weight(level);
+                        let factor = if held < asked { held / asked } else { 1.0 + (held - asked) };
+                        Some(weight.max(0.0) * factor)
+                    })
+                    .sum();
+                matched.then_some((profile.person_id, score / total_weight))
+            })
+            .collect();
+
+        ranking.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranking.truncate(limit);
+        ranking
+    }
+
     /// Get skill recommendations based on existing skills
     pub async fn get_skill_recommendations(&self, person_id: &PersonId, limit: usize) -> Vec<String> {
         let profiles = self.profiles.read().await;
@@ -161,6 +455,29 @@ impl PersonSkillsProjection {
         statistics.skill_counts.clone()
     }
     
+    /// People per skill, rolled up the taxonomy
+    ///
+    /// Each skill counts the distinct people holding it or any skill beneath
+    /// it, so "Programming" covers everyone with "Rust" or "React". Keys are
+    /// display names; skills outside the taxonomy count only their holders.
+    pub async fn get_rolled_up_statistics(&self) -> HashMap<String, usize> {
+        let profiles = self.profiles.read().await;
+        let mut holders: HashMap<String, HashSet<PersonId>> = HashMap::new();
+
+        for profile in profiles.values() {
+            for skill_name in profile.skills.keys() {
+                for ancestor in self.taxonomy.ancestors(skill_name) {
+                    let name = self.taxonomy.display_name(&ancestor)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| skill_name.clone());
+                    holders.entry(name).or_default().insert(profile.person_id);
+                }
+            }
+        }
+
+        holders.into_iter().map(|(skill, people)| (skill, people.len())).collect()
+    }
+
     /// Get skills by category
     pub async fn get_skills_by_category(&self, category: &str) -> Vec<String> {
         let statistics = self.statistics.read().await;
@@ -272,4 +589,134 @@ impl PersonProjection for PersonSkillsProjection {
         *statistics = SkillStatistics::default();
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_expert_endorsement_outweighs_beginner_endorsement() {
+        let projection = PersonSkillsProjection::new();
+        let alice = PersonId::new();
+        let bob = PersonId::new();
+        let expert = PersonId::new();
+        let novice = PersonId::new();
+
+        projection.record_skill(alice, "Rust", "Programming", ProficiencyLevel::Intermediate).await;
+        projection.record_skill(bob, "Rust", "Programming", ProficiencyLevel::Intermediate).await;
+        projection.record_skill(expert, "Rust", "Programming", ProficiencyLevel::Expert).await;
+        projection.record_skill(novice, "Rust", "Programming", ProficiencyLevel::Beginner).await;
+
+        projection.endorse_skill(&alice, "Rust", Endorsement::new(expert)).await.unwrap();
+        projection.endorse_skill(&bob, "rust", Endorsement::new(novice)).await.unwrap();
+
+        let ranking = projection.rank_people_for_skill("Rust").await;
+        let score = |id: PersonId| ranking.iter().find(|(p, _)| *p == id).unwrap().1;
+
+        assert!(score(alice) > score(bob));
+        let alice_pos = ranking.iter().position(|(p, _)| *p == alice).unwrap();
+        let bob_pos = ranking.iter().position(|(p, _)| *p == bob).unwrap();
+        assert!(alice_pos < bob_pos);
+    }
+
+    #[tokio::test]
+    async fn test_descendant_skills_match_broader_queries() {
+        let projection = PersonSkillsProjection::new().with_taxonomy(SkillTaxonomy::default_taxonomy());
+        let react_dev = PersonId::new();
+        let rustacean = PersonId::new();
+        projection.record_skill(react_dev, "React", "Programming", ProficiencyLevel::Advanced).await;
+        projection.record_skill(rustacean, "Rust", "Programming", ProficiencyLevel::Expert).await;
+
+        assert!(projection.find_people_with_skill_matching("JavaScript", false).await.is_empty());
+        assert_eq!(projection.find_people_with_skill_matching("JavaScript", true).await, vec![react_dev]);
+
+        let rolled_up = projection.get_rolled_up_statistics().await;
+        assert_eq!(rolled_up["Programming"], 2);
+        assert_eq!(rolled_up["JavaScript"], 1);
+        assert_eq!(rolled_up["React"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_self_endorsement_is_rejected() {
+        let projection = PersonSkillsProjection::new();
+        let person = PersonId::new();
+        projection.record_skill(person, "Rust", "Programming", ProficiencyLevel::Beginner).await;
+
+        let result = projection.endorse_skill(&person, "Rust", Endorsement::new(person)).await;
+        assert!(result.is_err());
+
+        let skills = projection.get_person_skills(&person).await;
+        assert_eq!(skills[0].endorsement_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_proficiency_halves_after_the_half_life() {
+        let projection = PersonSkillsProjection::new();
+        let idle = PersonId::new();
+        let active = PersonId::new();
+        let undated = PersonId::new();
+        for person in [idle, active, undated] {
+            projection.record_skill(person, "Rust", "Programming", ProficiencyLevel::Expert).await;
+        }
+
+        let now = Utc::now();
+        let half_life = chrono::Duration::days(180);
+        projection.mark_skill_used(&idle, "rust", now - half_life).await.unwrap();
+        projection.mark_skill_used(&active, "Rust", now).await.unwrap();
+        assert!(projection.mark_skill_used(&idle, "Go", now).await.is_err());
+
+        // Nothing decays until asked to
+        assert_eq!(projection.find_people_with_skill("Rust", Some(0.9)).await.len(), 3);
+
+        projection.apply_decay(now, half_life).await;
+        let skills = projection.get_person_skills(&idle).await;
+        assert_eq!(skills[0].proficiency, "Expert");
+        assert!((skills[0].effective_proficiency - 0.5).abs() < 1e-3);
+        assert_eq!(projection.get_person_skills(&active).await[0].effective_proficiency, 1.0);
+        assert_eq!(projection.get_person_skills(&undated).await[0].effective_proficiency, 1.0);
+
+        let mut proficient = projection.find_people_with_skill("Rust", Some(0.9)).await;
+        proficient.sort_by_key(|id| id.to_string());
+        let mut expected = vec![active, undated];
+        expected.sort_by_key(|id| id.to_string());
+        assert_eq!(proficient, expected);
+        assert_eq!(projection.find_people_with_skill("Rust", None).await.len(), 3);
+
+        // Decay is recomputed from the declared level, not compounded
+        projection.apply_decay(now, half_life).await;
+        assert!((projection.get_person_skills(&idle).await[0].effective_proficiency - 0.5).abs() < 1e-3);
+    }
+
+    #[tokio::test]
+    async fn test_partial_matches_rank_by_weighted_score() {
+        let projection = PersonSkillsProjection::new();
+        let full = PersonId::new();
+        let overqualified = PersonId::new();
+        let partial = PersonId::new();
+        let unrelated = PersonId::new();
+
+        projection.record_skill(full, "Rust", "Programming", ProficiencyLevel::Advanced).await;
+        projection.record_skill(full, "SQL", "Data", ProficiencyLevel::Intermediate).await;
+        projection.record_skill(overqualified, "Rust", "Programming", ProficiencyLevel::Expert).await;
+        projection.record_skill(overqualified, "SQL", "Data", ProficiencyLevel::Advanced).await;
+        // Only the heavily weighted skill, below the asked-for level
+        projection.record_skill(partial, "Rust", "Programming", ProficiencyLevel::Intermediate).await;
+        projection.record_skill(unrelated, "Go", "Programming", ProficiencyLevel::Expert).await;
+
+        let required = [
+            ("rust".to_string(), ProficiencyLevel::Advanced, 3.0),
+            ("SQL".to_string(), ProficiencyLevel::Intermediate, 1.0),
+        ];
+        let ranking = projection.rank_candidates(&required, 10).await;
+        let ids: Vec<PersonId> = ranking.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![overqualified, full, partial]);
+
+        assert!((ranking[1].1 - 1.0).abs() < 1e-6);
+        // 3 * (0.5 / 0.75) of 4
+        assert!((ranking[2].1 - 0.5).abs() < 1e-6);
+
+        assert_eq!(projection.rank_candidates(&required, 1).await[0].0, overqualified);
+        assert!(projection.rank_candidates(&[], 10).await.is_empty());
+    }
+}
@@ -1,8 +1,9 @@
 //! Person summary projection providing a quick overview of person data
 
-use super::{PersonProjection, PersonSummary};
+use super::{PersonProjection, PersonSummary, SummaryStatus};
 use crate::aggregate::PersonId;
 use crate::events::*;
+use crate::infrastructure::PersonSnapshot;
 use cim_domain::DomainResult;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -10,22 +11,37 @@ use tokio::sync::RwLock;
 
 /// Extract person ID from any PersonEvent variant
 fn extract_person_id(event: &PersonEvent) -> PersonId {
-    match event {
-        PersonEvent::PersonCreated(e) => e.person_id,
-        PersonEvent::PersonUpdated(e) => e.person_id,
-        PersonEvent::NameUpdated(e) => e.person_id,
-        PersonEvent::BirthDateSet(e) => e.person_id,
-        PersonEvent::DeathRecorded(e) => e.person_id,
-        PersonEvent::PersonDeactivated(e) => e.person_id,
-        PersonEvent::PersonReactivated(e) => e.person_id,
-        PersonEvent::PersonMergedInto(e) => e.source_person_id,
-        PersonEvent::AttributeRecorded(e) => e.person_id,
-        PersonEvent::AttributeUpdated(e) => e.person_id,
-        PersonEvent::AttributeInvalidated(e) => e.person_id,
-    }
+    event.person_id()
+}
+
+/// One page of [`PersonSummaryProjection::get_summaries_after`]
+#[derive(Debug, Clone)]
+pub struct SummaryPage {
+    pub summaries: Vec<PersonSummary>,
+    /// Last person id on this page, `None` when no summaries follow it
+    pub next_cursor: Option<PersonId>,
+    /// Visible summaries across all pages
+    pub total: usize,
+}
+
+fn is_visible(summary: &PersonSummary) -> bool {
+    summary.status == SummaryStatus::Active
+}
+
+/// Ids of the visible summaries, in paging order
+fn visible_ids(summaries: &HashMap<PersonId, PersonSummary>) -> Vec<&PersonId> {
+    let mut ids: Vec<&PersonId> = summaries.iter()
+        .filter(|(_, s)| is_visible(s))
+        .map(|(id, _)| id)
+        .collect();
+    ids.sort_by_key(|id| *id.as_uuid());
+    ids
 }
 
 /// Projection that maintains person summaries for quick access
+///
+/// Summaries of deactivated and merged persons are kept but are only
+/// returned by [`PersonSummaryProjection::get_summary_raw`].
 pub struct PersonSummaryProjection {
     summaries: Arc<RwLock<HashMap<PersonId, PersonSummary>>>,
 }
@@ -46,20 +62,69 @@ impl PersonSummaryProjection {
     /// Get a person's summary
     pub async fn get_summary(&self, person_id: &PersonId) -> Option<PersonSummary> {
         let summaries = self.summaries.read().await;
-        summaries.get(person_id).cloned()
+        summaries.get(person_id).filter(|s| is_visible(s)).cloned()
     }
     
+    /// The summary stored under `person_id`, whatever the person's status
+    pub async fn get_summary_raw(&self, person_id: &PersonId) -> Option<PersonSummary> {
+        self.summaries.read().await.get(person_id).cloned()
+    }
+
     /// Get all summaries
     pub async fn get_all_summaries(&self) -> Vec<PersonSummary> {
         let summaries = self.summaries.read().await;
-        summaries.values().cloned().collect()
+        summaries.values().filter(|s| is_visible(s)).cloned().collect()
     }
     
+    /// Up to `limit` summaries ordered by person id, skipping the first
+    /// `offset`, and the number of summaries across all pages
+    ///
+    /// The order only changes when persons are added or removed; readers
+    /// paging while that happens should use [`Self::get_summaries_after`].
+    pub async fn get_summaries_paged(&self, offset: usize, limit: usize) -> (Vec<PersonSummary>, usize) {
+        let summaries = self.summaries.read().await;
+        let ids = visible_ids(&summaries);
+        let page = ids.iter()
+            .skip(offset)
+            .take(limit)
+            .map(|id| summaries[*id].clone())
+            .collect();
+        (page, ids.len())
+    }
+
+    /// Up to `limit` summaries ordered by person id, starting after `after`
+    ///
+    /// Pass the page's `next_cursor` as `after` to get the next page. The
+    /// cursor is a person id rather than a position, so persons added or
+    /// removed while paging never shift a record past the reader or show it
+    /// twice.
+    pub async fn get_summaries_after(&self, after: Option<PersonId>, limit: usize) -> SummaryPage {
+        let summaries = self.summaries.read().await;
+        let ids = visible_ids(&summaries);
+
+        let total = ids.len();
+        let start = match after {
+            Some(after) => ids.partition_point(|id| id.as_uuid() <= after.as_uuid()),
+            None => 0,
+        };
+        let page: Vec<PersonSummary> = ids[start..].iter()
+            .take(limit)
+            .map(|id| summaries[*id].clone())
+            .collect();
+        let next_cursor = if start + page.len() < total {
+            page.last().map(|summary| summary.person_id)
+        } else {
+            None
+        };
+
+        SummaryPage { summaries: page, next_cursor, total }
+    }
+
     /// Get summaries for multiple persons
     pub async fn get_summaries(&self, person_ids: &[PersonId]) -> Vec<PersonSummary> {
         let summaries = self.summaries.read().await;
         person_ids.iter()
-            .filter_map(|id| summaries.get(id).cloned())
+            .filter_map(|id| summaries.get(id).filter(|s| is_visible(s)).cloned())
             .collect()
     }
     
@@ -69,7 +134,7 @@ impl PersonSummaryProjection {
         let query_lower = query.to_lowercase();
         
         summaries.values()
-            .filter(|s| s.name.to_lowercase().contains(&query_lower))
+            .filter(|s| is_visible(s) && s.name.to_lowercase().contains(&query_lower))
             .cloned()
             .collect()
     }
@@ -81,7 +146,7 @@ impl PersonSummaryProjection {
         
         summaries.values()
             .filter(|s| {
-                s.current_employer
+                is_visible(s) && s.current_employer
                     .as_ref()
                     .map(|e| e.to_lowercase().contains(&employer_lower))
                     .unwrap_or(false)
@@ -131,4 +196,164 @@ impl PersonProjection for PersonSummaryProjection {
         summaries.clear();
         Ok(())
     }
-} 
\ No newline at end of file
+
+    async fn seed_from_snapshot(&self, snapshot: &PersonSnapshot) -> DomainResult<bool> {
+        let mut summaries = self.summaries.write().await;
+        match super::pure_projections::summary_from_person(&snapshot.state) {
+            Some(summary) => {
+                summaries.insert(snapshot.aggregate_id, summary);
+            }
+            None => {
+                summaries.remove(&snapshot.aggregate_id);
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::Person;
+    use crate::infrastructure::{
+        load_aggregate, EventStore, InMemoryEventStore, InMemorySnapshotStore, SnapshotStore,
+    };
+    use crate::value_objects::PersonName;
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_bootstrap_from_snapshots_matches_full_replay() {
+        let event_store = InMemoryEventStore::new();
+        let snapshot_store = InMemorySnapshotStore::new();
+        let mut all_events = Vec::new();
+
+        for (given, family, renamed) in [("Ada", "Lovelace", "Augusta"), ("Alan", "Turing", "Alan M.")] {
+            let person_id = PersonId::new();
+            let name = PersonName::new(given.to_string(), family.to_string());
+            let created = PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: name.clone(),
+                source: "test".to_string(),
+                created_at: Utc::now(),
+            });
+            event_store.append_events(person_id, vec![created.clone()], None).await.unwrap();
+
+            // Snapshot after creation, then keep writing events past it
+            let person: Person = load_aggregate(&event_store, person_id).await.unwrap();
+            snapshot_store.save_snapshot(PersonSnapshot {
+                aggregate_id: person_id,
+                version: 1,
+                state: person,
+                timestamp: Utc::now(),
+            }).await.unwrap();
+
+            let renamed = PersonEvent::NameUpdated(NameUpdated {
+                person_id,
+                old_name: name,
+                new_name: PersonName::new(renamed.to_string(), family.to_string()),
+                reason: None,
+                updated_at: Utc::now(),
+            });
+            event_store.append_events(person_id, vec![renamed.clone()], Some(1)).await.unwrap();
+
+            all_events.push(created);
+            all_events.push(renamed);
+        }
+
+        let replayed = PersonSummaryProjection::new();
+        for event in &all_events {
+            replayed.handle_event(event).await.unwrap();
+        }
+
+        let bootstrapped = PersonSummaryProjection::new();
+        let checkpoint = bootstrapped
+            .bootstrap_from_snapshots(&snapshot_store, &event_store)
+            .await
+            .unwrap();
+        assert!(checkpoint.positions.values().all(|position| *position == 2));
+
+        let mut expected = replayed.get_all_summaries().await;
+        let mut actual = bootstrapped.get_all_summaries().await;
+        expected.sort_by_key(|s| s.name.clone());
+        actual.sort_by_key(|s| s.name.clone());
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert_eq!(e.person_id, a.person_id);
+            assert_eq!(e.name, a.name);
+            assert_eq!(e.last_updated, a.last_updated);
+        }
+
+        // Re-running bootstrap is idempotent
+        bootstrapped.bootstrap_from_snapshots(&snapshot_store, &event_store).await.unwrap();
+        assert_eq!(bootstrapped.get_all_summaries().await.len(), expected.len());
+    }
+
+    #[tokio::test]
+    async fn test_paging_visits_every_summary_once() {
+        let projection = PersonSummaryProjection::new();
+        let create = |i: usize| PersonEvent::PersonCreated(PersonCreated {
+            person_id: PersonId::new(),
+            name: PersonName::new(format!("P{i}"), "Test".to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        });
+        for i in 0..7 {
+            projection.handle_event(&create(i)).await.unwrap();
+        }
+        let mut expected: Vec<PersonId> = projection.get_all_summaries().await.iter().map(|s| s.person_id).collect();
+        expected.sort_by_key(|id| *id.as_uuid());
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = projection.get_summaries_after(cursor, 3).await;
+            seen.extend(page.summaries.iter().map(|s| s.person_id));
+            if seen.len() == 3 {
+                // A person added behind the cursor doesn't shift later pages
+                let mut earliest = create(99);
+                if let PersonEvent::PersonCreated(created) = &mut earliest {
+                    created.person_id = PersonId::from_uuid(uuid::Uuid::nil());
+                }
+                projection.handle_event(&earliest).await.unwrap();
+            }
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(seen, expected);
+        let last = projection.get_summaries_after(expected.last().copied(), 3).await;
+        assert!(last.summaries.is_empty());
+        assert_eq!(last.next_cursor, None);
+        assert_eq!(last.total, 8);
+    }
+
+    #[tokio::test]
+    async fn test_offset_pages_are_ordered_by_person_id() {
+        let projection = PersonSummaryProjection::new();
+        for i in 0..7 {
+            projection.handle_event(&PersonEvent::PersonCreated(PersonCreated {
+                person_id: PersonId::new(),
+                name: PersonName::new(format!("P{i}"), "Test".to_string()),
+                source: "test".to_string(),
+                created_at: Utc::now(),
+            })).await.unwrap();
+        }
+        let mut expected: Vec<PersonId> = projection.get_all_summaries().await.iter().map(|s| s.person_id).collect();
+        expected.sort_by_key(|id| *id.as_uuid());
+
+        let mut seen = Vec::new();
+        for offset in (0..7).step_by(3) {
+            let (page, total) = projection.get_summaries_paged(offset, 3).await;
+            assert_eq!(total, 7);
+            assert!(page.len() <= 3);
+            seen.extend(page.iter().map(|s| s.person_id));
+        }
+        assert_eq!(seen, expected);
+
+        let (past_end, total) = projection.get_summaries_paged(7, 3).await;
+        assert!(past_end.is_empty());
+        assert_eq!(total, 7);
+    }
+}
@@ -0,0 +1,183 @@
+//! Tag membership projection
+//!
+//! Answers "who has this tag" and "which tags does this person have" from
+//! `TagAdded`/`TagRemoved` events. Both directions are kept in one state
+//! under one lock so they never disagree. Merged-away persons drop out; their
+//! tags are expected to be applied to the surviving record.
+
+use super::PersonProjection;
+use crate::aggregate::PersonId;
+use crate::events::PersonEvent;
+use crate::value_objects::Tag;
+use cim_domain::DomainResult;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct TagState {
+    persons_by_tag: HashMap<Tag, HashSet<PersonId>>,
+    tags_by_person: HashMap<PersonId, BTreeSet<Tag>>,
+}
+
+impl TagState {
+    fn add(&mut self, person_id: PersonId, tag: &Tag) {
+        self.persons_by_tag.entry(tag.clone()).or_default().insert(person_id);
+        self.tags_by_person.entry(person_id).or_default().insert(tag.clone());
+    }
+
+    fn remove(&mut self, person_id: &PersonId, tag: &Tag) {
+        if let Some(persons) = self.persons_by_tag.get_mut(tag) {
+            persons.remove(person_id);
+            if persons.is_empty() {
+                self.persons_by_tag.remove(tag);
+            }
+        }
+        if let Some(tags) = self.tags_by_person.get_mut(person_id) {
+            tags.remove(tag);
+            if tags.is_empty() {
+                self.tags_by_person.remove(person_id);
+            }
+        }
+    }
+
+    fn remove_person(&mut self, person_id: &PersonId) {
+        for tag in self.tags_by_person.get(person_id).cloned().unwrap_or_default() {
+            self.remove(person_id, &tag);
+        }
+    }
+}
+
+/// Projection indexing persons by tag
+pub struct PersonTagProjection {
+    state: Arc<RwLock<TagState>>,
+}
+
+impl Default for PersonTagProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersonTagProjection {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(RwLock::new(TagState::default())),
+        }
+    }
+
+    /// Persons carrying `tag`
+    pub async fn persons_with_tag(&self, tag: &Tag) -> HashSet<PersonId> {
+        self.state.read().await.persons_by_tag.get(tag).cloned().unwrap_or_default()
+    }
+
+    /// Tags on a person, ordered by category then name
+    pub async fn tags_for(&self, person_id: &PersonId) -> Vec<Tag> {
+        self.state.read().await
+            .tags_by_person
+            .get(person_id)
+            .map(|tags| tags.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Tags in use within one category
+    pub async fn tags_in_category(&self, category: &str) -> Vec<Tag> {
+        let category = category.trim().to_lowercase();
+        let mut tags: Vec<Tag> = self.state.read().await
+            .persons_by_tag
+            .keys()
+            .filter(|tag| tag.category == category)
+            .cloned()
+            .collect();
+        tags.sort();
+        tags
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonProjection for PersonTagProjection {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        let mut state = self.state.write().await;
+
+        match event {
+            PersonEvent::TagAdded(e) => state.add(e.person_id, &e.tag),
+            PersonEvent::TagRemoved(e) => state.remove(&e.person_id, &e.tag),
+            PersonEvent::PersonMergedInto(e) => state.remove_person(&e.source_person_id),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn projection_name(&self) -> &str {
+        "PersonTagProjection"
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        *self.state.write().await = TagState::default();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::Person;
+    use crate::commands::{PersonCommand, TagMany, UntagMany};
+    use crate::value_objects::PersonName;
+    use cim_domain::formal_domain::MealyStateMachine;
+
+    /// Run batch commands through each person's aggregate and the projection
+    async fn execute(
+        persons: &mut HashMap<PersonId, Person>,
+        projection: &PersonTagProjection,
+        commands: Vec<PersonCommand>,
+    ) -> Vec<PersonEvent> {
+        let mut emitted = Vec::new();
+        for command in commands {
+            let person = persons.remove(&command.aggregate_id()).unwrap();
+            let events = MealyStateMachine::output(&person, person.lifecycle.clone().into(), command);
+            let person = events.iter().try_fold(person, |p, e| p.apply_event_pure(e)).unwrap();
+            for event in &events {
+                projection.handle_event(event).await.unwrap();
+            }
+            persons.insert(person.id, person);
+            emitted.extend(events);
+        }
+        emitted
+    }
+
+    #[tokio::test]
+    async fn test_tag_many_is_idempotent() {
+        let projection = PersonTagProjection::new();
+        let mut persons: HashMap<PersonId, Person> = (0..3)
+            .map(|i| {
+                let person = Person::new(PersonId::new(), PersonName::new(format!("P{i}"), "Test".to_string()));
+                (person.id, person)
+            })
+            .collect();
+        let ids: Vec<PersonId> = persons.keys().copied().collect();
+        let vip = Tag::new("Sales", "VIP").unwrap();
+        let support_vip = Tag::new("support", "vip").unwrap();
+
+        // A repeated id within the batch is tagged once
+        let mut batch = ids.clone();
+        batch.push(ids[0]);
+        let events = execute(&mut persons, &projection, TagMany { person_ids: batch, tag: vip.clone() }.into_commands()).await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(projection.persons_with_tag(&vip).await, ids.iter().copied().collect::<HashSet<_>>());
+        assert!(projection.persons_with_tag(&support_vip).await.is_empty());
+
+        // Re-tagging emits nothing and leaves the projection unchanged
+        let events = execute(&mut persons, &projection, TagMany { person_ids: ids.clone(), tag: vip.clone() }.into_commands()).await;
+        assert!(events.is_empty());
+        assert_eq!(projection.persons_with_tag(&vip).await.len(), 3);
+        assert_eq!(projection.tags_for(&ids[0]).await, vec![vip.clone()]);
+
+        let events = execute(&mut persons, &projection, UntagMany { person_ids: vec![ids[1]], tag: vip.clone() }.into_commands()).await;
+        assert_eq!(events.len(), 1);
+        assert!(!projection.persons_with_tag(&vip).await.contains(&ids[1]));
+        assert!(!persons[&ids[1]].tags.contains(&vip));
+        assert_eq!(projection.tags_in_category("sales").await, vec![vip]);
+    }
+}
@@ -1,17 +1,53 @@
 //! Person timeline projection for activity history
 
-use super::{PersonProjection, TimelineEntry};
+use super::{HistoryEntry, HistoryEntryKind, PersonProjection, TimelineEntry};
 use crate::aggregate::PersonId;
 use crate::events::*;
+use crate::value_objects::AttributeType;
 use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::RangeBounds;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
 
+/// Timeline entry type for user-recorded life events
+pub const LIFE_EVENT_ENTRY_TYPE: &str = "life_event";
+
+/// Position in a person's timeline that a page of older entries ends at
+///
+/// Entries sharing a timestamp are told apart by `seq`, their position among
+/// those entries in recording order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineCursor {
+    pub timestamp: DateTime<Utc>,
+    pub seq: usize,
+}
+
+impl TimelineCursor {
+    /// Cursor for paging through the entries strictly older than `timestamp`
+    pub fn before(timestamp: DateTime<Utc>) -> Self {
+        Self { timestamp, seq: 0 }
+    }
+}
+
+/// One page of [`PersonTimelineProjection::get_timeline_before`]
+#[derive(Debug, Clone)]
+pub struct TimelinePage {
+    /// Newest first
+    pub entries: Vec<TimelineEntry>,
+    /// Cursor for the next, older page; `None` once the oldest entry is reached
+    pub next_cursor: Option<TimelineCursor>,
+}
+
 /// Projection that maintains activity timelines for persons
+///
+/// Attribute changes are kept apart from the timeline and only appear in
+/// [`PersonTimelineProjection::unified_history`].
 pub struct PersonTimelineProjection {
     timelines: Arc<RwLock<HashMap<PersonId, Vec<TimelineEntry>>>>,
+    attribute_changes: Arc<RwLock<HashMap<PersonId, Vec<TimelineEntry>>>>,
 }
 
 impl Default for PersonTimelineProjection {
@@ -24,6 +60,7 @@ impl PersonTimelineProjection {
     pub fn new() -> Self {
         Self {
             timelines: Arc::new(RwLock::new(HashMap::new())),
+            attribute_changes: Arc::new(RwLock::new(HashMap::new())),
         }
     }
     
@@ -45,6 +82,38 @@ impl PersonTimelineProjection {
         }
     }
     
+    /// Up to `limit` entries before `cursor`, newest first
+    ///
+    /// Pass the page's `next_cursor` to get the next, older page. Entries
+    /// sharing a timestamp keep the order they were recorded in, and the
+    /// cursor records how far into such a group the page reached, so a page
+    /// holds exactly `limit` entries while paging never skips or repeats one.
+    pub async fn get_timeline_before(
+        &self,
+        person_id: &PersonId,
+        cursor: TimelineCursor,
+        limit: usize,
+    ) -> TimelinePage {
+        let timelines = self.timelines.read().await;
+        let Some(timeline) = timelines.get(person_id) else {
+            return TimelinePage { entries: Vec::new(), next_cursor: None };
+        };
+
+        let group_start = |timestamp: DateTime<Utc>| timeline.partition_point(|entry| entry.timestamp < timestamp);
+        let group_end = timeline.partition_point(|entry| entry.timestamp <= cursor.timestamp);
+        let end = (group_start(cursor.timestamp) + cursor.seq).min(group_end);
+        let start = end.saturating_sub(limit);
+
+        let next_cursor = (start > 0).then(|| {
+            let timestamp = timeline[start].timestamp;
+            TimelineCursor { timestamp, seq: start - group_start(timestamp) }
+        });
+        TimelinePage {
+            entries: timeline[start..end].iter().rev().cloned().collect(),
+            next_cursor,
+        }
+    }
+
     /// Get timeline entries within a date range
     pub async fn get_timeline_range(
         &self,
@@ -82,18 +151,67 @@ impl PersonTimelineProjection {
         }
     }
     
+    /// Timeline entries and attribute changes interleaved chronologically
+    pub async fn unified_history(
+        &self,
+        person_id: &PersonId,
+        range: impl RangeBounds<DateTime<Utc>>,
+    ) -> Vec<HistoryEntry> {
+        let timelines = self.timelines.read().await;
+        let attribute_changes = self.attribute_changes.read().await;
+
+        let timeline = timelines.get(person_id).into_iter().flatten().map(|entry| {
+            let kind = if entry.event_type == LIFE_EVENT_ENTRY_TYPE {
+                HistoryEntryKind::LifeEvent
+            } else {
+                HistoryEntryKind::DomainEvent
+            };
+            (kind, entry)
+        });
+        let attributes = attribute_changes.get(person_id).into_iter().flatten()
+            .map(|entry| (HistoryEntryKind::AttributeChange, entry));
+
+        let mut history: Vec<HistoryEntry> = timeline.chain(attributes)
+            .filter(|(_, entry)| range.contains(&entry.timestamp))
+            .map(|(kind, entry)| HistoryEntry {
+                timestamp: entry.timestamp,
+                kind,
+                event_type: entry.event_type.clone(),
+                title: entry.title.clone(),
+                description: entry.description.clone(),
+                metadata: entry.metadata.clone(),
+            })
+            .collect();
+
+        // Stable, so entries with equal timestamps keep timeline-first order
+        history.sort_by_key(|entry| entry.timestamp);
+        history
+    }
+
     /// Add an entry to the timeline
     async fn add_timeline_entry(&self, person_id: PersonId, entry: TimelineEntry) {
         let mut timelines = self.timelines.write().await;
-        let timeline = timelines.entry(person_id).or_insert_with(Vec::new);
-        
-        // Insert in chronological order
-        match timeline.binary_search_by_key(&entry.timestamp, |e| e.timestamp) {
-            Ok(pos) | Err(pos) => timeline.insert(pos, entry),
-        }
+        insert_chronologically(timelines.entry(person_id).or_default(), entry);
+    }
+
+    async fn add_attribute_change(&self, person_id: PersonId, entry: TimelineEntry) {
+        let mut attribute_changes = self.attribute_changes.write().await;
+        insert_chronologically(attribute_changes.entry(person_id).or_default(), entry);
     }
 }
 
+/// Insert after any entries with the same timestamp, keeping ties in recording order
+fn insert_chronologically(entries: &mut Vec<TimelineEntry>, entry: TimelineEntry) {
+    let pos = entries.partition_point(|e| e.timestamp <= entry.timestamp);
+    entries.insert(pos, entry);
+}
+
+fn attribute_metadata(attribute_type: &AttributeType) -> HashMap<String, serde_json::Value> {
+    let mut metadata = HashMap::new();
+    metadata.insert("attribute_type".to_string(), serde_json::to_value(attribute_type).unwrap());
+    metadata
+}
+
 #[async_trait::async_trait]
 impl PersonProjection for PersonTimelineProjection {
     async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
@@ -201,6 +319,74 @@ impl PersonProjection for PersonTimelineProjection {
                 self.add_timeline_entry(e.source_person_id, entry).await;
             }
             
+            PersonEvent::LifeEventRecorded(e) => {
+                let mut metadata = HashMap::new();
+                metadata.insert("kind".to_string(), serde_json::to_value(e.kind.to_string()).unwrap());
+                metadata.insert("date".to_string(), serde_json::to_value(e.date).unwrap());
+                if let Some(note) = &e.note {
+                    metadata.insert("note".to_string(), serde_json::to_value(note).unwrap());
+                }
+
+                // Placed on the timeline at the date it happened, not when it was recorded
+                let entry = TimelineEntry {
+                    timestamp: e.date.and_time(chrono::NaiveTime::MIN).and_utc(),
+                    event_type: LIFE_EVENT_ENTRY_TYPE.to_string(),
+                    title: format!("Life Event: {}", e.kind),
+                    description: e.note.clone().unwrap_or_else(|| e.kind.to_string()),
+                    metadata,
+                };
+
+                self.add_timeline_entry(e.person_id, entry).await;
+            }
+
+            PersonEvent::AttributeRecorded(e) => {
+                let mut metadata = attribute_metadata(&e.attribute.attribute_type);
+                metadata.insert("value".to_string(), serde_json::to_value(&e.attribute.value).unwrap());
+
+                let entry = TimelineEntry {
+                    timestamp: e.recorded_at,
+                    event_type: "attribute_recorded".to_string(),
+                    title: "Attribute Recorded".to_string(),
+                    description: format!("Recorded {:?}", e.attribute.attribute_type),
+                    metadata,
+                };
+
+                self.add_attribute_change(e.person_id, entry).await;
+            }
+
+            PersonEvent::AttributeUpdated(e) => {
+                let mut metadata = attribute_metadata(&e.attribute_type);
+                metadata.insert("old_value".to_string(), serde_json::to_value(&e.old_attribute.value).unwrap());
+                metadata.insert("new_value".to_string(), serde_json::to_value(&e.new_attribute.value).unwrap());
+
+                let entry = TimelineEntry {
+                    timestamp: e.updated_at,
+                    event_type: "attribute_updated".to_string(),
+                    title: "Attribute Updated".to_string(),
+                    description: format!("Updated {:?}", e.attribute_type),
+                    metadata,
+                };
+
+                self.add_attribute_change(e.person_id, entry).await;
+            }
+
+            PersonEvent::AttributeInvalidated(e) => {
+                let mut metadata = attribute_metadata(&e.attribute_type);
+                if let Some(reason) = &e.reason {
+                    metadata.insert("reason".to_string(), serde_json::to_value(reason).unwrap());
+                }
+
+                let entry = TimelineEntry {
+                    timestamp: e.invalidated_at,
+                    event_type: "attribute_invalidated".to_string(),
+                    title: "Attribute Invalidated".to_string(),
+                    description: format!("Invalidated {:?}", e.attribute_type),
+                    metadata,
+                };
+
+                self.add_attribute_change(e.person_id, entry).await;
+            }
+
             _ => {} // Other events handled above
         }
         
@@ -212,8 +398,138 @@ impl PersonProjection for PersonTimelineProjection {
     }
     
     async fn clear(&self) -> DomainResult<()> {
-        let mut timelines = self.timelines.write().await;
-        timelines.clear();
+        self.timelines.write().await.clear();
+        self.attribute_changes.write().await.clear();
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::{
+        AttributeSource, AttributeValue, ConfidenceLevel, IdentifyingAttributeType, LifeEventKind,
+        PersonAttribute, PersonName, Provenance, TemporalValidity,
+    };
+    use chrono::{Duration, NaiveDate};
+
+    #[tokio::test]
+    async fn test_life_event_is_retrievable_by_type() {
+        let projection = PersonTimelineProjection::new();
+        let person_id = PersonId::new();
+        let graduation = NaiveDate::from_ymd_opt(2015, 6, 12).unwrap();
+
+        projection.handle_event(&PersonEvent::LifeEventRecorded(LifeEventRecorded {
+            person_id,
+            kind: LifeEventKind::Graduated,
+            date: graduation,
+            note: Some("BSc Computer Science".to_string()),
+            recorded_at: Utc::now(),
+        })).await.unwrap();
+
+        let entries = projection.get_timeline_by_type(&person_id, LIFE_EVENT_ENTRY_TYPE).await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].metadata["kind"], serde_json::json!("graduated"));
+        assert_eq!(entries[0].timestamp.date_naive(), graduation);
+        assert_eq!(entries[0].description, "BSc Computer Science");
+
+        assert!(projection.get_timeline_by_type(&person_id, "person_created").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unified_history_interleaves_chronologically() {
+        let projection = PersonTimelineProjection::new();
+        let person_id = PersonId::new();
+        let start = Utc::now() - Duration::days(10);
+
+        let attribute = |offset_days| PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id,
+            attribute: PersonAttribute::new(
+                AttributeType::Identifying(IdentifyingAttributeType::BirthPlace),
+                AttributeValue::LocationReference("berlin".to_string()),
+                TemporalValidity::of(start + Duration::days(offset_days)),
+                Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Likely),
+            ),
+            recorded_at: start + Duration::days(offset_days),
+        });
+
+        // Handled out of order on purpose
+        projection.handle_event(&attribute(3)).await.unwrap();
+        projection.handle_event(&PersonEvent::NameUpdated(NameUpdated {
+            person_id,
+            old_name: PersonName::new("Ada".to_string(), "Byron".to_string()),
+            new_name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            reason: Some("Marriage".to_string()),
+            updated_at: start + Duration::days(2),
+        })).await.unwrap();
+        projection.handle_event(&attribute(1)).await.unwrap();
+
+        let history = projection.unified_history(&person_id, ..).await;
+        let kinds: Vec<_> = history.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, vec![
+            HistoryEntryKind::AttributeChange,
+            HistoryEntryKind::DomainEvent,
+            HistoryEntryKind::AttributeChange,
+        ]);
+        assert_eq!(history[1].event_type, "name_updated");
+        assert!(history.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+
+        // The range bounds the result
+        let recent = projection.unified_history(&person_id, start + Duration::days(2)..).await;
+        assert_eq!(recent.len(), 2);
+
+        // Attribute changes do not leak into the plain timeline
+        assert_eq!(projection.get_timeline(&person_id, None).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_timeline_pages_keep_timestamp_ties_together() {
+        let projection = PersonTimelineProjection::new();
+        let person_id = PersonId::new();
+        let start = Utc::now() - Duration::days(10);
+        let name = |family: &str| PersonName::new("Ada".to_string(), family.to_string());
+
+        projection.handle_event(&PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: name("Byron"),
+            source: "test".to_string(),
+            created_at: start,
+        })).await.unwrap();
+        for (family, day) in [("King", 1), ("Lovelace", 1), ("Noel", 1), ("Byron", 2)] {
+            projection.handle_event(&PersonEvent::NameUpdated(NameUpdated {
+                person_id,
+                old_name: name("Byron"),
+                new_name: name(family),
+                reason: None,
+                updated_at: start + Duration::days(day),
+            })).await.unwrap();
+        }
+        let new_family = |entry: &TimelineEntry| {
+            entry.metadata.get("new_name").map(|n| n["components"]["family_names"][0].as_str().unwrap().to_string())
+        };
+
+        // The first page ends inside the day-1 group and the second resumes there
+        let first = projection.get_timeline_before(&person_id, TimelineCursor::before(Utc::now()), 2).await;
+        let families: Vec<_> = first.entries.iter().map(new_family).collect();
+        assert_eq!(families, vec![Some("Byron".to_string()), Some("Noel".to_string())]);
+
+        let second = projection.get_timeline_before(&person_id, first.next_cursor.unwrap(), 2).await;
+        let families: Vec<_> = second.entries.iter().map(new_family).collect();
+        assert_eq!(families, vec![Some("Lovelace".to_string()), Some("King".to_string())]);
+
+        // Another entry for day 1 lands after the cursor and doesn't shift the next page
+        projection.handle_event(&PersonEvent::NameUpdated(NameUpdated {
+            person_id,
+            old_name: name("Byron"),
+            new_name: name("Milbanke"),
+            reason: None,
+            updated_at: start + Duration::days(1),
+        })).await.unwrap();
+
+        let third = projection.get_timeline_before(&person_id, second.next_cursor.unwrap(), 2).await;
+        assert_eq!(third.entries.len(), 1);
+        assert_eq!(third.entries[0].event_type, "person_created");
+        assert_eq!(third.next_cursor, None);
+        assert!(projection.get_timeline_before(&person_id, TimelineCursor::before(start), 2).await.entries.is_empty());
+    }
+}
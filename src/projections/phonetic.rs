@@ -0,0 +1,218 @@
+//! Phonetic keys for matching names that sound alike
+//!
+//! Both functions expect text already folded by a [`NameNormalizer`]
+//! (lowercase, no diacritics) and ignore anything but ASCII letters.
+//!
+//! [`soundex`] keeps the first letter, so it suits blocking on family names
+//! where the initial is rarely misspelled. [`metaphone`] encodes how the
+//! whole word is pronounced, so "Catherine" and "Katherine" share a key
+//! that Soundex would split (C365 / K365).
+//!
+//! [`NameNormalizer`]: super::NameNormalizer
+
+/// American Soundex: first letter plus three digits, e.g. "Garcia" → "G620"
+pub(crate) fn soundex(name: &str) -> String {
+    fn code(c: char) -> Option<char> {
+        match c {
+            'b' | 'f' | 'p' | 'v' => Some('1'),
+            'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+            'd' | 't' => Some('3'),
+            'l' => Some('4'),
+            'm' | 'n' => Some('5'),
+            'r' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let letters: Vec<char> = name.chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+
+    let mut key = first.to_ascii_uppercase().to_string();
+    let mut previous = code(first);
+    for &c in &letters[1..] {
+        let current = code(c);
+        if current.is_some() && current != previous {
+            key.extend(current);
+            if key.len() == 4 {
+                break;
+            }
+        }
+        // 'h' and 'w' don't separate letters with the same code; vowels do
+        if c != 'h' && c != 'w' {
+            previous = current;
+        }
+    }
+    format!("{key:0<4}")
+}
+
+/// Original Metaphone (Lawrence Philips, 1990), e.g. "Katherine" → "K0RN"
+///
+/// '0' stands for "th" and 'X' for "sh". Keys are not truncated.
+pub(crate) fn metaphone(word: &str) -> String {
+    let mut letters: Vec<char> = word.chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    // Silent or altered initial letters
+    match (letters.first().copied(), letters.get(1).copied()) {
+        (Some('A'), Some('E')) | (Some('G' | 'K' | 'P'), Some('N')) | (Some('W'), Some('R')) => {
+            letters.remove(0);
+        }
+        (Some('W'), Some('H')) => {
+            letters.remove(1);
+        }
+        (Some('X'), _) => letters[0] = 'S',
+        _ => {}
+    }
+
+    let is_vowel = |c: Option<&char>| matches!(c, Some('A' | 'E' | 'I' | 'O' | 'U'));
+    let is_front_vowel = |c: Option<&char>| matches!(c, Some('E' | 'I' | 'Y'));
+    let mut key = String::new();
+    let mut i = 0;
+
+    while i < letters.len() {
+        let c = letters[i];
+        let prev = i.checked_sub(1).map(|p| letters[p]);
+        let next = letters.get(i + 1);
+        let after_next = letters.get(i + 2);
+        // Doubled letters sound once, except 'cc' as in "accent"
+        if prev == Some(c) && c != 'C' {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == 0 {
+                    key.push(c);
+                }
+            }
+            'B' => {
+                // Silent in a final "mb", as in "Plumb"
+                if !(prev == Some('M') && next.is_none()) {
+                    key.push('B');
+                }
+            }
+            'C' => {
+                if next == Some(&'I') && after_next == Some(&'A') {
+                    key.push('X');
+                } else if next == Some(&'H') {
+                    key.push(if prev == Some('S') { 'K' } else { 'X' });
+                    i += 1;
+                } else if is_front_vowel(next) {
+                    if prev != Some('S') {
+                        key.push('S');
+                    }
+                } else {
+                    key.push('K');
+                }
+            }
+            'D' => {
+                if next == Some(&'G') && is_front_vowel(after_next) {
+                    key.push('J');
+                    i += 1;
+                } else {
+                    key.push('T');
+                }
+            }
+            'G' => {
+                let silent_gh = next == Some(&'H') && !is_vowel(after_next);
+                let silent_gn = next == Some(&'N')
+                    && (after_next.is_none() || (after_next == Some(&'E') && letters.get(i + 3) == Some(&'D')));
+                if silent_gh || silent_gn {
+                    // Silent, as in "Knight" or "Sign"
+                } else if is_front_vowel(next) {
+                    key.push('J');
+                } else {
+                    key.push('K');
+                }
+            }
+            'H' => {
+                let after_modifier = matches!(prev, Some('C' | 'G' | 'P' | 'S' | 'T'));
+                let between_vowel_and_consonant = is_vowel(prev.as_ref()) && !is_vowel(next);
+                if !after_modifier && !between_vowel_and_consonant {
+                    key.push('H');
+                }
+            }
+            'K' => {
+                if prev != Some('C') {
+                    key.push('K');
+                }
+            }
+            'P' => {
+                if next == Some(&'H') {
+                    key.push('F');
+                    i += 1;
+                } else {
+                    key.push('P');
+                }
+            }
+            'Q' => key.push('K'),
+            'S' => {
+                if next == Some(&'H') {
+                    key.push('X');
+                    i += 1;
+                } else if next == Some(&'I') && matches!(after_next, Some('O' | 'A')) {
+                    key.push('X');
+                } else {
+                    key.push('S');
+                }
+            }
+            'T' => {
+                if next == Some(&'I') && matches!(after_next, Some('O' | 'A')) {
+                    key.push('X');
+                } else if next == Some(&'H') {
+                    key.push('0');
+                    i += 1;
+                } else if !(next == Some(&'C') && after_next == Some(&'H')) {
+                    key.push('T');
+                }
+            }
+            'V' => key.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(next) {
+                    key.push(c);
+                }
+            }
+            'X' => key.push_str("KS"),
+            'Z' => key.push('S'),
+            other => key.push(other),
+        }
+        i += 1;
+    }
+
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("garcia"), "G620");
+        assert_eq!(soundex("robert"), "R163");
+        assert_eq!(soundex("rupert"), "R163");
+        assert_eq!(soundex("ashcraft"), "A261");
+        assert_eq!(soundex("lee"), "L000");
+    }
+
+    #[test]
+    fn test_metaphone() {
+        assert_eq!(metaphone("katherine"), "K0RN");
+        assert_eq!(metaphone("catherine"), "K0RN");
+        assert_eq!(metaphone("knight"), "NT");
+        assert_eq!(metaphone("philip"), "FLP");
+        assert_eq!(metaphone("smith"), "SM0");
+        assert_eq!(metaphone("smyth"), "SM0");
+        assert_eq!(metaphone("xavier"), "SFR");
+        assert_eq!(metaphone(""), "");
+        assert_ne!(metaphone("catherine"), metaphone("caroline"));
+    }
+}
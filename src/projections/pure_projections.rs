@@ -13,8 +13,37 @@
 //! - Calling these pure functions
 //! - Persisting the resulting state
 
+use crate::aggregate::{Person, PersonLifecycle};
 use crate::events::PersonEvent;
-use crate::projections::{PersonSummary, PersonSearchResult, TimelineEntry};
+use crate::projections::{PersonSummary, PersonSearchResult, SummaryStatus, TimelineEntry};
+use crate::value_objects::ConsentStatus;
+
+/// Derive PersonSummary state directly from aggregate state
+///
+/// Agrees with folding `project_person_summary` over the aggregate's history:
+/// deactivated and merged persons keep their summary, marked with a
+/// [`SummaryStatus`] that hides it from queries.
+pub fn summary_from_person(person: &Person) -> Option<PersonSummary> {
+    let status = match person.lifecycle {
+        PersonLifecycle::MergedInto { target_id, .. } => SummaryStatus::Merged { into: target_id },
+        PersonLifecycle::Deactivated { .. } => SummaryStatus::Deactivated,
+        _ => SummaryStatus::Active,
+    };
+    Some(PersonSummary {
+        person_id: person.id,
+        name: person.core_identity.legal_name.display_name(),
+        primary_email: None,
+        primary_phone: None,
+        current_employer: None,
+        current_role: None,
+        location: None,
+        skills_count: 0,
+        component_count: 0,
+        last_updated: person.core_identity.updated_at,
+        merged_from: None,
+        status,
+    })
+}
 
 /// Project a PersonEvent into PersonSummary state
 ///
@@ -42,6 +71,8 @@ pub fn project_person_summary(
                 skills_count: 0,
                 component_count: 0,
                 last_updated: e.created_at,
+                merged_from: None,
+                status: SummaryStatus::Active,
             })
         }
 
@@ -54,19 +85,28 @@ pub fn project_person_summary(
             })
         }
 
-        PersonEvent::PersonDeactivated(_) => {
-            // Remove summary when person is deactivated
-            None
+        PersonEvent::PersonDeactivated(e) => {
+            // Keep the summary out of sight so reactivation can restore it
+            current.map(|mut summary| {
+                summary.status = SummaryStatus::Deactivated;
+                summary.last_updated = e.deactivated_at;
+                summary
+            })
         }
 
-        PersonEvent::PersonMergedInto(_) => {
-            // Remove summary when person is merged
-            None
+        PersonEvent::PersonMergedInto(e) => {
+            // Keep the merged-away record's summary for audits
+            current.map(|mut summary| {
+                summary.status = SummaryStatus::Merged { into: e.merged_into_id };
+                summary.last_updated = e.merged_at;
+                summary
+            })
         }
 
         PersonEvent::PersonReactivated(e) => {
-            // Restore or update summary on reactivation
+            // Restore the summary kept through deactivation
             current.map(|mut summary| {
+                summary.status = SummaryStatus::Active;
                 summary.last_updated = e.reactivated_at;
                 summary
             })
@@ -116,6 +156,34 @@ pub fn project_person_summary(
                 summary
             })
         }
+
+        PersonEvent::LifeEventRecorded(e) => {
+            current.map(|mut summary| {
+                summary.last_updated = e.recorded_at;
+                summary
+            })
+        }
+
+        PersonEvent::ConsentRecorded(e) => {
+            current.map(|mut summary| {
+                summary.last_updated = e.recorded_at;
+                summary
+            })
+        }
+
+        PersonEvent::TagAdded(e) => {
+            current.map(|mut summary| {
+                summary.last_updated = e.added_at;
+                summary
+            })
+        }
+
+        PersonEvent::TagRemoved(e) => {
+            current.map(|mut summary| {
+                summary.last_updated = e.removed_at;
+                summary
+            })
+        }
     }
 }
 
@@ -321,6 +389,62 @@ pub fn project_timeline_entry(event: &PersonEvent) -> Option<TimelineEntry> {
                 map
             },
         }),
+
+        PersonEvent::LifeEventRecorded(e) => Some(TimelineEntry {
+            timestamp: e.date.and_time(chrono::NaiveTime::MIN).and_utc(),
+            event_type: "LifeEventRecorded".to_string(),
+            title: format!("Life Event: {}", e.kind),
+            description: e.note.clone().unwrap_or_else(|| e.kind.to_string()),
+            metadata: {
+                let mut map = std::collections::HashMap::new();
+                map.insert("person_id".to_string(), serde_json::json!(e.person_id.to_string()));
+                map.insert("kind".to_string(), serde_json::json!(e.kind.to_string()));
+                map.insert("date".to_string(), serde_json::json!(e.date.to_string()));
+                map
+            },
+        }),
+
+        PersonEvent::ConsentRecorded(e) => Some(TimelineEntry {
+            timestamp: e.recorded_at,
+            event_type: "ConsentRecorded".to_string(),
+            title: match e.status {
+                ConsentStatus::Granted => "Consent Granted".to_string(),
+                ConsentStatus::Revoked => "Consent Revoked".to_string(),
+            },
+            description: format!("Consent for {}", e.consent_type),
+            metadata: {
+                let mut map = std::collections::HashMap::new();
+                map.insert("person_id".to_string(), serde_json::json!(e.person_id.to_string()));
+                map.insert("consent_type".to_string(), serde_json::json!(e.consent_type.to_string()));
+                map
+            },
+        }),
+
+        PersonEvent::TagAdded(e) => Some(TimelineEntry {
+            timestamp: e.added_at,
+            event_type: "TagAdded".to_string(),
+            title: "Tag Added".to_string(),
+            description: format!("Tagged {}", e.tag),
+            metadata: {
+                let mut map = std::collections::HashMap::new();
+                map.insert("person_id".to_string(), serde_json::json!(e.person_id.to_string()));
+                map.insert("tag".to_string(), serde_json::json!(e.tag.to_string()));
+                map
+            },
+        }),
+
+        PersonEvent::TagRemoved(e) => Some(TimelineEntry {
+            timestamp: e.removed_at,
+            event_type: "TagRemoved".to_string(),
+            title: "Tag Removed".to_string(),
+            description: format!("Untagged {}", e.tag),
+            metadata: {
+                let mut map = std::collections::HashMap::new();
+                map.insert("person_id".to_string(), serde_json::json!(e.person_id.to_string()));
+                map.insert("tag".to_string(), serde_json::json!(e.tag.to_string()));
+                map
+            },
+        }),
     }
 }
 
@@ -369,6 +493,8 @@ mod tests {
             skills_count: 0,
             component_count: 0,
             last_updated: Utc::now(),
+            merged_from: None,
+            status: SummaryStatus::Active,
         };
 
         let event = PersonEvent::NameUpdated(NameUpdated {
@@ -400,6 +526,8 @@ mod tests {
             skills_count: 0,
             component_count: 0,
             last_updated: Utc::now(),
+            merged_from: None,
+            status: SummaryStatus::Active,
         };
 
         let event = PersonEvent::PersonDeactivated(PersonDeactivated {
@@ -410,8 +538,8 @@ mod tests {
 
         let result = project_person_summary(Some(current), &event);
 
-        // Deactivation should remove the summary
-        assert!(result.is_none());
+        // Deactivation hides the summary but keeps it for reactivation
+        assert_eq!(result.unwrap().status, SummaryStatus::Deactivated);
     }
 
     #[test]
@@ -0,0 +1,129 @@
+//! Hierarchical skill taxonomy
+//!
+//! Skills are recorded as flat names, but many imply broader ones: "React"
+//! is a kind of "JavaScript", which is a kind of "Programming". A
+//! [`SkillTaxonomy`] is a parent/child graph over skill names (compared
+//! case-insensitively) that lets skill queries include holders of narrower
+//! skills and lets statistics roll up to broader ones. A skill may have more
+//! than one parent ("TypeScript" under both "JavaScript" and "Static Typing").
+
+use cim_domain::{DomainError, DomainResult};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Parent/child graph of skills
+#[derive(Debug, Clone, Default)]
+pub struct SkillTaxonomy {
+    /// Display name for each normalized skill
+    names: HashMap<String, String>,
+    children: HashMap<String, HashSet<String>>,
+    parents: HashMap<String, HashSet<String>>,
+}
+
+fn normalize(skill: &str) -> String {
+    skill.trim().to_lowercase()
+}
+
+impl SkillTaxonomy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Built-in taxonomy of common technical and business skills
+    pub fn default_taxonomy() -> Self {
+        Self::from_edges(&[
+            ("JavaScript", "Programming"),
+            ("TypeScript", "JavaScript"),
+            ("React", "JavaScript"),
+            ("Vue", "JavaScript"),
+            ("Node.js", "JavaScript"),
+            ("Rust", "Programming"),
+            ("Python", "Programming"),
+            ("Django", "Python"),
+            ("Haskell", "Programming"),
+            ("SQL", "Data"),
+            ("Statistics", "Data"),
+            ("Machine Learning", "Data"),
+            ("Kubernetes", "Operations"),
+            ("Docker", "Operations"),
+            ("Negotiation", "Business"),
+            ("Public Speaking", "Communication"),
+        ])
+        .expect("built-in skill taxonomy is acyclic")
+    }
+
+    /// Build a taxonomy from `(child, parent)` pairs
+    pub fn from_edges(edges: &[(&str, &str)]) -> DomainResult<Self> {
+        edges.iter().try_fold(Self::new(), |mut taxonomy, (child, parent)| {
+            taxonomy.add_child(parent, child)?;
+            Ok(taxonomy)
+        })
+    }
+
+    /// Place `child` under `parent`, rejecting edges that would form a cycle
+    pub fn add_child(&mut self, parent: &str, child: &str) -> DomainResult<()> {
+        let (parent_key, child_key) = (normalize(parent), normalize(child));
+        if parent_key == child_key || self.descendants(child).contains(&parent_key) {
+            return Err(DomainError::ValidationError(format!(
+                "Placing '{child}' under '{parent}' would create a cycle"
+            )));
+        }
+
+        self.names.entry(parent_key.clone()).or_insert_with(|| parent.trim().to_string());
+        self.names.entry(child_key.clone()).or_insert_with(|| child.trim().to_string());
+        self.children.entry(parent_key.clone()).or_default().insert(child_key.clone());
+        self.parents.entry(child_key).or_default().insert(parent_key);
+        Ok(())
+    }
+
+    pub fn contains(&self, skill: &str) -> bool {
+        self.names.contains_key(&normalize(skill))
+    }
+
+    /// Display name of a skill known to the taxonomy
+    pub fn display_name(&self, skill: &str) -> Option<&str> {
+        self.names.get(&normalize(skill)).map(String::as_str)
+    }
+
+    /// Normalized names of `skill` and every skill beneath it
+    pub fn descendants(&self, skill: &str) -> HashSet<String> {
+        Self::reachable(&self.children, normalize(skill))
+    }
+
+    /// Normalized names of `skill` and every skill above it
+    pub fn ancestors(&self, skill: &str) -> HashSet<String> {
+        Self::reachable(&self.parents, normalize(skill))
+    }
+
+    fn reachable(edges: &HashMap<String, HashSet<String>>, start: String) -> HashSet<String> {
+        let mut seen = HashSet::from([start.clone()]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(skill) = queue.pop_front() {
+            for next in edges.get(&skill).into_iter().flatten() {
+                if seen.insert(next.clone()) {
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descendants_and_cycles() {
+        let mut taxonomy = SkillTaxonomy::default_taxonomy();
+
+        let under_javascript = taxonomy.descendants("javascript");
+        assert!(under_javascript.contains("react"));
+        assert!(under_javascript.contains("javascript"));
+        assert!(!under_javascript.contains("rust"));
+        assert!(taxonomy.ancestors("React").contains("programming"));
+
+        assert!(taxonomy.add_child("React", "Programming").is_err());
+        assert!(taxonomy.add_child("Static Typing", "TypeScript").is_ok());
+        assert!(taxonomy.ancestors("TypeScript").contains("static typing"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Atomically swappable projection handle
+//!
+//! Queries read through a [`SwappableProjection`] and always see one
+//! complete projection instance. Rebuilds happen on a shadow instance that
+//! replaces the live one in a single swap once it has caught up (see
+//! [`ProjectionManager::rebuild_and_swap`](super::ProjectionManager::rebuild_and_swap)).
+
+use super::PersonProjection;
+use crate::events::PersonEvent;
+use cim_domain::DomainResult;
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
+
+/// Live projection instance that can be replaced without readers noticing
+pub struct SwappableProjection<P> {
+    name: String,
+    current: RwLock<Arc<P>>,
+    /// Held while an event is delivered; `Some` while a rebuild is running,
+    /// collecting the live events the shadow instance must also apply
+    pub(super) pending: Mutex<Option<Vec<PersonEvent>>>,
+}
+
+impl<P: PersonProjection> SwappableProjection<P> {
+    pub fn new(initial: P) -> Self {
+        Self {
+            name: initial.projection_name().to_string(),
+            current: RwLock::new(Arc::new(initial)),
+            pending: Mutex::new(None),
+        }
+    }
+
+    /// The instance queries should read from
+    ///
+    /// Hold on to the returned `Arc` for the duration of a query to get a
+    /// consistent view even if a swap happens concurrently.
+    pub fn current(&self) -> Arc<P> {
+        self.current.read().expect("projection slot lock poisoned").clone()
+    }
+
+    /// Replace the live instance, returning the previous one
+    pub fn swap(&self, next: Arc<P>) -> Arc<P> {
+        let mut current = self.current.write().expect("projection slot lock poisoned");
+        std::mem::replace(&mut *current, next)
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: PersonProjection + 'static> PersonProjection for SwappableProjection<P> {
+    async fn handle_event(&self, event: &PersonEvent) -> DomainResult<()> {
+        let mut pending = self.pending.lock().await;
+        if let Some(buffered) = pending.as_mut() {
+            buffered.push(event.clone());
+        }
+        self.current().handle_event(event).await
+    }
+
+    fn projection_name(&self) -> &str {
+        &self.name
+    }
+
+    async fn clear(&self) -> DomainResult<()> {
+        self.current().clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::events::{NameUpdated, PersonCreated};
+    use crate::infrastructure::{EventEnvelope, EventStore, InMemoryEventStore};
+    use crate::projections::{PersonSummaryProjection, ProjectionManager};
+    use crate::value_objects::PersonName;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use tokio::sync::Semaphore;
+
+    /// Event store whose reads block until the test opens the gate
+    struct GatedEventStore {
+        inner: InMemoryEventStore,
+        gate: Semaphore,
+    }
+
+    #[async_trait]
+    impl EventStore for GatedEventStore {
+        async fn append_events(
+            &self,
+            aggregate_id: PersonId,
+            events: Vec<PersonEvent>,
+            expected_version: Option<u64>,
+        ) -> DomainResult<()> {
+            self.inner.append_events(aggregate_id, events, expected_version).await
+        }
+
+        async fn get_events(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
+            self.inner.get_events(aggregate_id).await
+        }
+
+        async fn get_events_from_version(
+            &self,
+            aggregate_id: PersonId,
+            from_version: u64,
+        ) -> DomainResult<Vec<EventEnvelope>> {
+            let _permit = self.gate.acquire().await.expect("gate closed");
+            self.inner.get_events_from_version(aggregate_id, from_version).await
+        }
+
+        async fn get_current_version(&self, aggregate_id: PersonId) -> DomainResult<u64> {
+            self.inner.get_current_version(aggregate_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queries_see_old_state_until_swap() {
+        let person_id = PersonId::new();
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        let created = PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: name.clone(),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        });
+        let renamed = PersonEvent::NameUpdated(NameUpdated {
+            person_id,
+            old_name: name,
+            new_name: PersonName::new("Augusta".to_string(), "Lovelace".to_string()),
+            reason: None,
+            updated_at: Utc::now(),
+        });
+
+        let store = GatedEventStore {
+            inner: InMemoryEventStore::new(),
+            gate: Semaphore::new(0),
+        };
+        store.append_events(person_id, vec![created.clone(), renamed], None).await.unwrap();
+
+        // The live projection was built before the rename was recorded
+        let live = PersonSummaryProjection::new();
+        live.handle_event(&created).await.unwrap();
+        let slot = SwappableProjection::new(live);
+        let manager = ProjectionManager::new();
+
+        let rebuild = manager.rebuild_and_swap(&slot, PersonSummaryProjection::new, &store, &[person_id]);
+        let query_during_rebuild = async {
+            tokio::task::yield_now().await;
+            let name = slot.current().get_summary(&person_id).await.unwrap().name;
+            store.gate.add_permits(1_000);
+            name
+        };
+        let (rebuilt, name_during_rebuild) = tokio::join!(rebuild, query_during_rebuild);
+        rebuilt.unwrap();
+
+        assert_eq!(name_during_rebuild, "Ada");
+        assert_eq!(slot.current().get_summary(&person_id).await.unwrap().name, "Augusta");
+    }
+
+    #[tokio::test]
+    async fn test_events_delivered_during_rebuild_reach_the_new_instance() {
+        let person_id = PersonId::new();
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        let created = PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: name.clone(),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        });
+        let renamed = PersonEvent::NameUpdated(NameUpdated {
+            person_id,
+            old_name: name,
+            new_name: PersonName::new("Augusta".to_string(), "Lovelace".to_string()),
+            reason: None,
+            updated_at: Utc::now(),
+        });
+
+        let store = GatedEventStore {
+            inner: InMemoryEventStore::new(),
+            gate: Semaphore::new(0),
+        };
+        store.append_events(person_id, vec![created.clone()], None).await.unwrap();
+
+        let live = PersonSummaryProjection::new();
+        live.handle_event(&created).await.unwrap();
+        let slot = SwappableProjection::new(live);
+        let manager = ProjectionManager::new();
+
+        let rebuild = manager.rebuild_and_swap(&slot, PersonSummaryProjection::new, &store, &[person_id]);
+        let deliver_during_rebuild = async {
+            tokio::task::yield_now().await;
+            // Delivered live while the shadow is still reading the store
+            slot.handle_event(&renamed).await.unwrap();
+            store.gate.add_permits(1_000);
+        };
+        let (replaced, ()) = tokio::join!(rebuild, deliver_during_rebuild);
+
+        assert_eq!(replaced.unwrap().get_summary(&person_id).await.unwrap().name, "Augusta");
+        assert_eq!(slot.current().get_summary(&person_id).await.unwrap().name, "Augusta");
+        assert!(slot.pending.lock().await.is_none());
+    }
+}
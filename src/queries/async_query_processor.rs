@@ -9,6 +9,7 @@ use tokio::sync::mpsc;
 use tracing::{info, debug};
 
 use crate::aggregate::PersonId;
+use crate::infrastructure::{ProcessorMetrics, QueueMonitor};
 use crate::projections::*;
 
 /// Person network view
@@ -250,6 +251,79 @@ impl AsyncQueryProcessor for PersonQueryProcessor {
     }
 }
 
+/// Query processor that reports in-flight queries as queue metrics
+///
+/// Each query counts toward the queue from the call until its result is
+/// known, so a slow projection store shows up as a growing oldest-item age.
+pub struct MonitoredQueryProcessor {
+    inner: Arc<dyn AsyncQueryProcessor>,
+    monitor: QueueMonitor,
+}
+
+impl MonitoredQueryProcessor {
+    pub fn new(inner: Arc<dyn AsyncQueryProcessor>, metrics: Arc<dyn ProcessorMetrics>) -> Self {
+        Self {
+            inner,
+            monitor: QueueMonitor::new("person.queries", metrics),
+        }
+    }
+
+    /// How long a query may run before the lag alarm fires
+    pub fn with_lag_threshold(mut self, lag_threshold: chrono::Duration) -> Self {
+        self.monitor = self.monitor.with_lag_threshold(lag_threshold);
+        self
+    }
+
+    pub fn monitor(&self) -> &QueueMonitor {
+        &self.monitor
+    }
+
+    async fn tracked<T>(&self, query: impl std::future::Future<Output = T>) -> T {
+        let ticket = self.monitor.enqueued();
+        let result = query.await;
+        self.monitor.completed(ticket);
+        result
+    }
+}
+
+#[async_trait]
+impl AsyncQueryProcessor for MonitoredQueryProcessor {
+    async fn get_person(&self, person_id: PersonId) -> DomainResult<Option<PersonSummary>> {
+        self.tracked(self.inner.get_person(person_id)).await
+    }
+
+    async fn search_persons(
+        &self,
+        criteria: SearchCriteria,
+    ) -> DomainResult<QueryResult<PersonSearchResult>> {
+        self.tracked(self.inner.search_persons(criteria)).await
+    }
+
+    async fn get_person_network(
+        &self,
+        person_id: PersonId,
+        depth: usize,
+    ) -> DomainResult<Option<PersonNetworkView>> {
+        self.tracked(self.inner.get_person_network(person_id, depth)).await
+    }
+
+    async fn get_person_timeline(
+        &self,
+        person_id: PersonId,
+        from: Option<chrono::DateTime<chrono::Utc>>,
+        to: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> DomainResult<QueryResult<TimelineEvent>> {
+        self.tracked(self.inner.get_person_timeline(person_id, from, to)).await
+    }
+
+    async fn subscribe_to_updates(
+        &self,
+        person_id: PersonId,
+    ) -> DomainResult<Pin<Box<dyn Stream<Item = PersonUpdate> + Send>>> {
+        self.tracked(self.inner.subscribe_to_updates(person_id)).await
+    }
+}
+
 /// Helper to consume query results
 pub async fn consume_query_result<T>(result: QueryResult<T>) -> Vec<T> 
 where
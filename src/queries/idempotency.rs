@@ -0,0 +1,179 @@
+//! Idempotent query execution keyed by request id
+//!
+//! Some queries have side effects outside the read model, such as the
+//! compliance access log. Clients retry query requests on timeouts, so
+//! [`IdempotentQueryService`] remembers the response for each request id for
+//! a TTL: a repeated id gets the cached response and the side effects are not
+//! run again. Concurrent requests with the same id wait for the first one. A
+//! request id reused for a different query within the TTL is rejected.
+
+use super::{PersonQuery, PersonQueryResponse, PersonQueryService};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use cim_domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OnceCell, RwLock};
+
+/// One recorded access to person data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessLogEntry {
+    pub request_id: String,
+    pub query: PersonQuery,
+    pub accessed_at: DateTime<Utc>,
+}
+
+/// Compliance log of who queried person data
+#[async_trait]
+pub trait QueryAccessLog: Send + Sync {
+    async fn record_access(&self, entry: AccessLogEntry) -> DomainResult<()>;
+}
+
+/// In-memory access log for testing
+#[derive(Default)]
+pub struct InMemoryQueryAccessLog {
+    entries: RwLock<Vec<AccessLogEntry>>,
+}
+
+impl InMemoryQueryAccessLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn entries(&self) -> Vec<AccessLogEntry> {
+        self.entries.read().await.clone()
+    }
+}
+
+#[async_trait]
+impl QueryAccessLog for InMemoryQueryAccessLog {
+    async fn record_access(&self, entry: AccessLogEntry) -> DomainResult<()> {
+        self.entries.write().await.push(entry);
+        Ok(())
+    }
+}
+
+struct CachedResult {
+    created_at: DateTime<Utc>,
+    query: PersonQuery,
+    response: Arc<OnceCell<PersonQueryResponse>>,
+}
+
+/// Query service wrapper that runs each request id's side effects once
+pub struct IdempotentQueryService {
+    queries: Arc<PersonQueryService>,
+    access_log: Arc<dyn QueryAccessLog>,
+    ttl: Duration,
+    results: Mutex<HashMap<String, CachedResult>>,
+}
+
+impl IdempotentQueryService {
+    pub fn new(queries: Arc<PersonQueryService>, access_log: Arc<dyn QueryAccessLog>) -> Self {
+        Self {
+            queries,
+            access_log,
+            ttl: Duration::minutes(5),
+            results: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How long a request id's response is remembered
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Answer a query, logging the access unless `request_id` was seen within the TTL
+    ///
+    /// A failed access-log write fails the request without caching it, so a
+    /// retry runs the query again. Reusing a request id for a different query
+    /// is a validation error.
+    pub async fn handle(&self, request_id: &str, query: PersonQuery) -> DomainResult<PersonQueryResponse> {
+        let slot = self.cached_slot(request_id, &query)?;
+
+        let response = slot
+            .get_or_try_init(|| async {
+                self.access_log.record_access(AccessLogEntry {
+                    request_id: request_id.to_string(),
+                    query: query.clone(),
+                    accessed_at: Utc::now(),
+                }).await?;
+                Ok::<_, DomainError>(self.queries.execute(&query).await)
+            })
+            .await?;
+
+        Ok(response.clone())
+    }
+
+    /// The response slot for a request id, dropping expired entries first
+    fn cached_slot(&self, request_id: &str, query: &PersonQuery) -> DomainResult<Arc<OnceCell<PersonQueryResponse>>> {
+        let now = Utc::now();
+        let mut results = self.results.lock().expect("idempotency cache poisoned");
+        results.retain(|_, cached| now - cached.created_at < self.ttl);
+        let cached = results.entry(request_id.to_string())
+            .or_insert_with(|| CachedResult {
+                created_at: now,
+                query: query.clone(),
+                response: Arc::new(OnceCell::new()),
+            });
+        if cached.query != *query {
+            return Err(DomainError::ValidationError(format!(
+                "Request id {request_id} was already used for a {} query",
+                cached.query.query_type()
+            )));
+        }
+        Ok(cached.response.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::projections::*;
+
+    fn service(access_log: Arc<InMemoryQueryAccessLog>) -> IdempotentQueryService {
+        let queries = Arc::new(PersonQueryService::new(
+            Arc::new(PersonSummaryProjection::new()),
+            Arc::new(PersonSearchProjection::new()),
+            Arc::new(PersonSkillsProjection::new()),
+            Arc::new(PersonNetworkProjection::new()),
+            Arc::new(PersonTimelineProjection::new()),
+        ));
+        IdempotentQueryService::new(queries, access_log)
+    }
+
+    #[tokio::test]
+    async fn test_repeated_request_id_logs_access_once() {
+        let access_log = Arc::new(InMemoryQueryAccessLog::new());
+        let service = service(access_log.clone());
+        let query = PersonQuery::GetSummary { person_id: PersonId::new() };
+
+        let first = service.handle("req-1", query.clone()).await.unwrap();
+        let retried = service.handle("req-1", query.clone()).await.unwrap();
+        assert!(matches!(first, PersonQueryResponse::Summary(None)));
+        assert!(matches!(retried, PersonQueryResponse::Summary(None)));
+        assert_eq!(access_log.entries().await.len(), 1);
+
+        service.handle("req-2", query).await.unwrap();
+        assert_eq!(access_log.entries().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_reused_for_another_query_is_rejected() {
+        let access_log = Arc::new(InMemoryQueryAccessLog::new());
+        let service = service(access_log.clone());
+        let first = PersonQuery::GetSummary { person_id: PersonId::new() };
+        let other = PersonQuery::GetSummary { person_id: PersonId::new() };
+
+        service.handle("req-1", first.clone()).await.unwrap();
+        let err = service.handle("req-1", other).await.unwrap_err();
+        assert!(matches!(err, DomainError::ValidationError(_)), "{err:?}");
+        assert_eq!(access_log.entries().await.len(), 1);
+
+        // The original query is still answered from the cache
+        service.handle("req-1", first).await.unwrap();
+        assert_eq!(access_log.entries().await.len(), 1);
+    }
+}
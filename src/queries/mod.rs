@@ -10,11 +10,13 @@
 
 use crate::aggregate::PersonId;
 use crate::projections::*;
+use crate::value_objects::{AttributeType, AttributeValueRange};
 use std::sync::Arc;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 mod async_query_processor;
 pub mod specifications;
+pub mod idempotency;
 
 pub use specifications::{
     PersonSummaryQuery, PersonSearchQuery, SkillsQuery,
@@ -22,9 +24,10 @@ pub use specifications::{
 };
 pub use async_query_processor::{
     AsyncQueryProcessor, PersonQueryProcessor, QueryResult,
-    SearchCriteria, TimelineEvent, PersonUpdate,
+    SearchCriteria, TimelineEvent, PersonUpdate, MonitoredQueryProcessor,
     consume_query_result
 };
+pub use idempotency::{AccessLogEntry, IdempotentQueryService, InMemoryQueryAccessLog, QueryAccessLog};
 
 /// Query service that coordinates access to projections
 pub struct PersonQueryService {
@@ -33,6 +36,9 @@ pub struct PersonQueryService {
     skills_projection: Arc<PersonSkillsProjection>,
     network_projection: Arc<PersonNetworkProjection>,
     timeline_projection: Arc<PersonTimelineProjection>,
+    attribute_index: Option<Arc<PersonAttributeIndexProjection>>,
+    merge_redirects: Option<Arc<PersonMergeRedirectProjection>>,
+    onboarding: Option<Arc<PersonOnboardingProjection>>,
 }
 
 impl PersonQueryService {
@@ -49,14 +55,57 @@ impl PersonQueryService {
             skills_projection,
             network_projection,
             timeline_projection,
+            attribute_index: None,
+            merge_redirects: None,
+            onboarding: None,
         }
     }
+
+    /// Back attribute-based profile fields with the attribute index
+    pub fn with_attribute_index(mut self, attribute_index: Arc<PersonAttributeIndexProjection>) -> Self {
+        self.attribute_index = Some(attribute_index);
+        self
+    }
+
+    /// Answer summary queries for merged-away ids from the surviving record
+    pub fn with_merge_redirects(mut self, merge_redirects: Arc<PersonMergeRedirectProjection>) -> Self {
+        self.merge_redirects = Some(merge_redirects);
+        self
+    }
+
+    /// Answer onboarding progress queries from the onboarding projection
+    pub fn with_onboarding(mut self, onboarding: Arc<PersonOnboardingProjection>) -> Self {
+        self.onboarding = Some(onboarding);
+        self
+    }
     
     // Summary queries
     
     /// Get a person's summary
+    ///
+    /// With merge redirects, a merged-away id yields the surviving person's
+    /// summary with `merged_from` set to the requested id.
     pub async fn get_person_summary(&self, person_id: &PersonId) -> Option<PersonSummary> {
-        self.summary_projection.get_summary(person_id).await
+        let target = match &self.merge_redirects {
+            Some(redirects) => redirects.resolve(person_id).await,
+            None => None,
+        };
+        match target {
+            Some(target) => {
+                let mut summary = self.summary_projection.get_summary(&target).await?;
+                summary.merged_from = Some(*person_id);
+                Some(summary)
+            }
+            None => self.summary_projection.get_summary(person_id).await,
+        }
+    }
+
+    /// Get the summary stored under `person_id` itself, without following merges
+    ///
+    /// For auditing a merged record: the merged-away id's own summary, with
+    /// its status recording the merge.
+    pub async fn get_person_summary_raw(&self, person_id: &PersonId) -> Option<PersonSummary> {
+        self.summary_projection.get_summary_raw(person_id).await
     }
     
     /// Get all person summaries
@@ -64,6 +113,16 @@ impl PersonQueryService {
         self.summary_projection.get_all_summaries().await
     }
     
+    /// Get up to `limit` summaries ordered by person id, skipping `offset`, with the total count
+    pub async fn get_summaries_paged(&self, offset: usize, limit: usize) -> (Vec<PersonSummary>, usize) {
+        self.summary_projection.get_summaries_paged(offset, limit).await
+    }
+
+    /// Get up to `limit` summaries ordered by person id, starting after `after`
+    pub async fn get_summaries_after(&self, after: Option<PersonId>, limit: usize) -> SummaryPage {
+        self.summary_projection.get_summaries_after(after, limit).await
+    }
+
     /// Get summaries by employer
     pub async fn get_summaries_by_employer(&self, employer: &str) -> Vec<PersonSummary> {
         self.summary_projection.get_by_employer(employer).await
@@ -106,9 +165,26 @@ impl PersonQueryService {
         self.skills_projection.get_person_skills(person_id).await
     }
     
-    /// Find people with a specific skill
-    pub async fn find_people_with_skill(&self, skill_name: &str) -> Vec<PersonId> {
-        self.skills_projection.find_people_with_skill(skill_name).await
+    /// Find people with a specific skill, optionally at a minimum effective proficiency
+    pub async fn find_people_with_skill(
+        &self,
+        skill_name: &str,
+        min_effective_proficiency: Option<f32>,
+    ) -> Vec<PersonId> {
+        self.skills_projection
+            .find_people_with_skill(skill_name, min_effective_proficiency)
+            .await
+    }
+
+    /// Find people with a skill, optionally including narrower skills
+    pub async fn find_people_with_skill_matching(
+        &self,
+        skill_name: &str,
+        include_descendants: bool,
+    ) -> Vec<PersonId> {
+        self.skills_projection
+            .find_people_with_skill_matching(skill_name, include_descendants)
+            .await
     }
     
     /// Find people with multiple skills
@@ -132,6 +208,15 @@ impl PersonQueryService {
     pub async fn get_person_connections(&self, person_id: &PersonId) -> Vec<PersonRelationship> {
         self.network_projection.get_connections(person_id).await
     }
+
+    /// Get a person's connections as a given viewer may see them
+    pub async fn get_person_connections_for(
+        &self,
+        person_id: &PersonId,
+        viewer: ViewerRole,
+    ) -> RedactedConnections {
+        self.network_projection.get_connections_for(person_id, viewer).await
+    }
     
     /// Get incoming connections
     pub async fn get_incoming_connections(&self, person_id: &PersonId) -> Vec<PersonRelationship> {
@@ -147,6 +232,16 @@ impl PersonQueryService {
     pub async fn find_shortest_path(&self, from: &PersonId, to: &PersonId) -> Option<Vec<PersonId>> {
         self.network_projection.find_shortest_path(from, to).await
     }
+
+    /// Find shortest path over the edges a given viewer may see
+    pub async fn find_shortest_path_for(
+        &self,
+        from: &PersonId,
+        to: &PersonId,
+        viewer: ViewerRole,
+    ) -> Option<Vec<PersonId>> {
+        self.network_projection.find_shortest_path_for(from, to, viewer).await
+    }
     
     // Timeline queries
     
@@ -155,6 +250,16 @@ impl PersonQueryService {
         self.timeline_projection.get_timeline(person_id, limit).await
     }
     
+    /// Get up to `limit` timeline entries before `cursor`, newest first
+    pub async fn get_timeline_before(
+        &self,
+        person_id: &PersonId,
+        cursor: TimelineCursor,
+        limit: usize,
+    ) -> TimelinePage {
+        self.timeline_projection.get_timeline_before(person_id, cursor, limit).await
+    }
+
     /// Get timeline within date range
     pub async fn get_timeline_range(
         &self,
@@ -173,10 +278,185 @@ impl PersonQueryService {
     ) -> Vec<TimelineEntry> {
         self.timeline_projection.get_timeline_by_type(person_id, event_type).await
     }
+
+    /// Get timeline entries and attribute changes as one chronological history
+    pub async fn get_unified_history(
+        &self,
+        person_id: &PersonId,
+        range: impl std::ops::RangeBounds<DateTime<Utc>>,
+    ) -> Vec<HistoryEntry> {
+        self.timeline_projection.unified_history(person_id, range).await
+    }
+
+    /// Answer a [`PersonQuery`] request
+    pub async fn execute(&self, query: &PersonQuery) -> PersonQueryResponse {
+        match query {
+            PersonQuery::GetSummary { person_id } => {
+                PersonQueryResponse::Summary(self.get_person_summary(person_id).await)
+            }
+            PersonQuery::GetAllSummaries => {
+                PersonQueryResponse::Summaries(self.get_all_summaries().await)
+            }
+            PersonQuery::GetByEmployer { employer } => {
+                PersonQueryResponse::Summaries(self.get_summaries_by_employer(employer).await)
+            }
+            PersonQuery::Search { query, limit } => {
+                PersonQueryResponse::SearchResults(self.search_persons(query, *limit).await)
+            }
+            PersonQuery::SearchWithFilters { query, employer_filter, skill_filter, location_filter, limit } => {
+                PersonQueryResponse::SearchResults(self.search_with_filters(
+                    query.as_deref(),
+                    employer_filter.as_deref(),
+                    skill_filter.as_deref(),
+                    location_filter.as_deref(),
+                    *limit,
+                ).await)
+            }
+            PersonQuery::GetSkills { person_id } => {
+                PersonQueryResponse::Skills(self.get_person_skills(person_id).await)
+            }
+            PersonQuery::FindPeopleWithSkill { skill_name, include_descendants } => {
+                PersonQueryResponse::PersonIds(
+                    self.find_people_with_skill_matching(skill_name, *include_descendants).await,
+                )
+            }
+            PersonQuery::FindPeopleWithSkills { required_skills } => {
+                PersonQueryResponse::PersonIds(self.find_people_with_skills(required_skills).await)
+            }
+            PersonQuery::GetSkillRecommendations { person_id, limit } => {
+                PersonQueryResponse::SkillRecommendations(self.get_skill_recommendations(person_id, *limit).await)
+            }
+            PersonQuery::GetConnections { person_id, viewer } => {
+                PersonQueryResponse::Connections(self.get_person_connections_for(person_id, *viewer).await)
+            }
+            PersonQuery::GetNetworkStats { person_id } => {
+                PersonQueryResponse::NetworkStats(self.get_network_stats(person_id).await)
+            }
+            PersonQuery::FindShortestPath { from, to, viewer } => {
+                PersonQueryResponse::Path(self.find_shortest_path_for(from, to, *viewer).await)
+            }
+            PersonQuery::GetTimeline { person_id, limit } => {
+                PersonQueryResponse::Timeline(self.get_person_timeline(person_id, *limit).await)
+            }
+            PersonQuery::GetTimelineRange { person_id, start, end } => {
+                PersonQueryResponse::Timeline(self.get_timeline_range(person_id, *start, *end).await)
+            }
+        }
+    }
+
+    // Lifecycle queries
+
+    /// How far a person's onboarding has got
+    ///
+    /// `None` without an onboarding projection or if the person's onboarding
+    /// never started.
+    pub async fn get_onboarding_progress(&self, person_id: &PersonId) -> Option<OnboardingProgress> {
+        self.onboarding.as_ref()?.get_progress(person_id).await
+    }
+
+    /// A person's age on `as_of`, derived from the attribute index
+    ///
+    /// `None` without an attribute index, without a known birth date, or
+    /// before the person was born.
+    pub async fn get_age_on(&self, person_id: &PersonId, as_of: NaiveDate) -> Option<AgeInfo> {
+        self.attribute_index.as_ref()?.get_age_on(person_id, as_of).await
+    }
+
+    /// People whose attribute of `attr_type` valid on `as_of` lies in `range`
+    ///
+    /// Empty without an attribute index.
+    pub async fn find_by_attribute_range(
+        &self,
+        attr_type: &AttributeType,
+        range: &AttributeValueRange,
+        as_of: NaiveDate,
+    ) -> Vec<PersonId> {
+        match &self.attribute_index {
+            Some(index) => index.find_by_attribute_range(attr_type, range, as_of).await,
+            None => Vec::new(),
+        }
+    }
+
+    // Data quality queries
+
+    /// Persons missing any of the `required` fields, with the fields they lack
+    ///
+    /// Least recently updated profiles come first. Email, employer and
+    /// location are whatever the search projection has indexed. Without an
+    /// attribute index, fields that need it (birth date, attributes) count as
+    /// missing.
+    pub async fn find_incomplete(
+        &self,
+        required: &[ProfileField],
+        limit: usize,
+    ) -> Vec<(PersonId, Vec<ProfileField>)> {
+        let mut summaries = self.summary_projection.get_all_summaries().await;
+        summaries.sort_by_key(|summary| summary.last_updated);
+
+        let mut incomplete = Vec::new();
+        for summary in summaries {
+            if incomplete.len() >= limit {
+                break;
+            }
+            let missing = self.missing_fields(&summary, required).await;
+            if !missing.is_empty() {
+                incomplete.push((summary.person_id, missing));
+            }
+        }
+        incomplete
+    }
+
+    async fn missing_fields(&self, summary: &PersonSummary, required: &[ProfileField]) -> Vec<ProfileField> {
+        use crate::value_objects::IdentifyingAttributeType;
+
+        let (provided, has_birth_date) = match &self.attribute_index {
+            Some(index) => (
+                index.provided_attribute_types(&summary.person_id).await,
+                index.has_birth_date(&summary.person_id).await,
+            ),
+            None => Default::default(),
+        };
+        let person_id = &summary.person_id;
+        let has_email = self.search_projection.has_email(person_id).await;
+        let has_location = self.search_projection.has_location(person_id).await;
+        let has_employer = self.search_projection.has_employer(person_id).await;
+
+        required.iter()
+            .filter(|field| {
+                let present = match field {
+                    ProfileField::Email => has_email,
+                    ProfileField::Location => has_location,
+                    ProfileField::Employer => {
+                        has_employer
+                            || provided.iter().any(|attr_type| matches!(
+                                attr_type,
+                                AttributeType::Custom(custom)
+                                    if custom.category == crate::cross_domain::person_organization::EMPLOYMENT_ATTRIBUTE_CATEGORY
+                            ))
+                    }
+                    ProfileField::BirthDate => {
+                        has_birth_date
+                            || provided.iter().any(|attr_type| matches!(
+                                attr_type,
+                                AttributeType::Identifying(
+                                    IdentifyingAttributeType::BirthDateTime
+                                        | IdentifyingAttributeType::BirthDate
+                                        | IdentifyingAttributeType::BirthYear
+                                        | IdentifyingAttributeType::ApproximateBirthDate
+                                )
+                            ))
+                    }
+                    ProfileField::Attribute(attr_type) => provided.contains(attr_type),
+                };
+                !present
+            })
+            .cloned()
+            .collect()
+    }
 }
 
 /// Query request types for NATS integration
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "query_type")]
 pub enum PersonQuery {
     GetSummary { person_id: PersonId },
@@ -191,16 +471,45 @@ pub enum PersonQuery {
         limit: usize,
     },
     GetSkills { person_id: PersonId },
-    FindPeopleWithSkill { skill_name: String },
+    FindPeopleWithSkill {
+        skill_name: String,
+        /// Also match holders of narrower skills in the taxonomy
+        #[serde(default)]
+        include_descendants: bool,
+    },
     FindPeopleWithSkills { required_skills: Vec<String> },
     GetSkillRecommendations { person_id: PersonId, limit: usize },
-    GetConnections { person_id: PersonId },
+    /// Only the connections `viewer` may see are returned
+    GetConnections { person_id: PersonId, viewer: ViewerRole },
     GetNetworkStats { person_id: PersonId },
-    FindShortestPath { from: PersonId, to: PersonId },
+    /// Searches only the edges `viewer` may see
+    FindShortestPath { from: PersonId, to: PersonId, viewer: ViewerRole },
     GetTimeline { person_id: PersonId, limit: Option<usize> },
     GetTimelineRange { person_id: PersonId, start: DateTime<Utc>, end: DateTime<Utc> },
 }
 
+impl PersonQuery {
+    /// The `query_type` tag the query is serialized with
+    pub fn query_type(&self) -> &'static str {
+        match self {
+            PersonQuery::GetSummary { .. } => "GetSummary",
+            PersonQuery::GetAllSummaries => "GetAllSummaries",
+            PersonQuery::GetByEmployer { .. } => "GetByEmployer",
+            PersonQuery::Search { .. } => "Search",
+            PersonQuery::SearchWithFilters { .. } => "SearchWithFilters",
+            PersonQuery::GetSkills { .. } => "GetSkills",
+            PersonQuery::FindPeopleWithSkill { .. } => "FindPeopleWithSkill",
+            PersonQuery::FindPeopleWithSkills { .. } => "FindPeopleWithSkills",
+            PersonQuery::GetSkillRecommendations { .. } => "GetSkillRecommendations",
+            PersonQuery::GetConnections { .. } => "GetConnections",
+            PersonQuery::GetNetworkStats { .. } => "GetNetworkStats",
+            PersonQuery::FindShortestPath { .. } => "FindShortestPath",
+            PersonQuery::GetTimeline { .. } => "GetTimeline",
+            PersonQuery::GetTimelineRange { .. } => "GetTimelineRange",
+        }
+    }
+}
+
 /// Query response types
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "response_type")]
@@ -211,7 +520,7 @@ pub enum PersonQueryResponse {
     Skills(Vec<SkillSummary>),
     PersonIds(Vec<PersonId>),
     SkillRecommendations(Vec<String>),
-    Connections(Vec<PersonRelationship>),
+    Connections(RedactedConnections),
     NetworkStats(NetworkStats),
     Path(Option<Vec<PersonId>>),
     Timeline(Vec<TimelineEntry>),
@@ -219,3 +528,279 @@ pub enum PersonQueryResponse {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::MergeReason;
+    use crate::events::{AttributeRecorded, BirthDateSet, DeathRecorded, PersonCreated, PersonEvent, PersonMergedInto};
+    use crate::value_objects::{
+        AttributeSource, AttributeType, AttributeValue, ConfidenceLevel, CustomAttributeType,
+        DatePrecision, IdentifyingAttributeType, PersonAttribute, PersonName, Provenance, TemporalValidity,
+    };
+    use chrono::Duration;
+
+    fn query_service(
+        summaries: Arc<PersonSummaryProjection>,
+        index: Arc<PersonAttributeIndexProjection>,
+    ) -> PersonQueryService {
+        PersonQueryService::new(
+            summaries,
+            Arc::new(PersonSearchProjection::new()),
+            Arc::new(PersonSkillsProjection::new()),
+            Arc::new(PersonNetworkProjection::new()),
+            Arc::new(PersonTimelineProjection::new()),
+        )
+        .with_attribute_index(index)
+    }
+
+    fn recorded(person_id: PersonId, attribute_type: AttributeType, value: AttributeValue) -> PersonEvent {
+        PersonEvent::AttributeRecorded(AttributeRecorded {
+            person_id,
+            attribute: PersonAttribute::new(
+                attribute_type,
+                value,
+                TemporalValidity::of(Utc::now()),
+                Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Likely),
+            ),
+            recorded_at: Utc::now(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_find_incomplete_lists_missing_fields() {
+        let summaries = Arc::new(PersonSummaryProjection::new());
+        let index = Arc::new(PersonAttributeIndexProjection::new());
+        let queries = PersonQueryService::new(
+            summaries.clone(),
+            Arc::new(PersonSearchProjection::new()),
+            Arc::new(PersonSkillsProjection::new()),
+            Arc::new(PersonNetworkProjection::new()),
+            Arc::new(PersonTimelineProjection::new()),
+        )
+        .with_attribute_index(index.clone());
+
+        let [complete, no_employer, bare] = [PersonId::new(), PersonId::new(), PersonId::new()];
+        let employment = AttributeType::Custom(CustomAttributeType {
+            organization: "acme".to_string(),
+            attribute_name: "role".to_string(),
+            category: crate::cross_domain::person_organization::EMPLOYMENT_ATTRIBUTE_CATEGORY.to_string(),
+        });
+        let birth_place = AttributeType::Identifying(IdentifyingAttributeType::BirthPlace);
+
+        let mut events = Vec::new();
+        for (offset, person_id) in [complete, no_employer, bare].into_iter().enumerate() {
+            events.push(PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: PersonName::new("Test".to_string(), "Person".to_string()),
+                source: "test".to_string(),
+                created_at: Utc::now() + Duration::seconds(offset as i64),
+            }));
+        }
+        events.push(recorded(complete, employment, AttributeValue::Text("Engineer".to_string())));
+        events.push(recorded(complete, birth_place.clone(), AttributeValue::LocationReference("london".to_string())));
+        events.push(PersonEvent::BirthDateSet(BirthDateSet {
+            person_id: complete,
+            birth_date: NaiveDate::from_ymd_opt(1815, 12, 10).unwrap(),
+            set_at: Utc::now(),
+        }));
+        events.push(recorded(
+            no_employer,
+            AttributeType::Identifying(IdentifyingAttributeType::BirthYear),
+            AttributeValue::Year(1906),
+        ));
+        for event in &events {
+            summaries.handle_event(event).await.unwrap();
+            index.handle_event(event).await.unwrap();
+        }
+
+        let required = [ProfileField::BirthDate, ProfileField::Employer, ProfileField::Attribute(birth_place)];
+        let incomplete = queries.find_incomplete(&required, 10).await;
+        assert_eq!(incomplete, vec![
+            (no_employer, vec![ProfileField::Employer, required[2].clone()]),
+            (bare, required.to_vec()),
+        ]);
+
+        assert_eq!(queries.find_incomplete(&required, 1).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_incomplete_uses_indexed_contact_details() {
+        let summaries = Arc::new(PersonSummaryProjection::new());
+        let search = Arc::new(PersonSearchProjection::new());
+        let queries = PersonQueryService::new(
+            summaries.clone(),
+            search.clone(),
+            Arc::new(PersonSkillsProjection::new()),
+            Arc::new(PersonNetworkProjection::new()),
+            Arc::new(PersonTimelineProjection::new()),
+        );
+
+        let [emailed, located] = [PersonId::new(), PersonId::new()];
+        for (offset, person_id) in [emailed, located].into_iter().enumerate() {
+            let created = PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: PersonName::new("Test".to_string(), "Person".to_string()),
+                source: "test".to_string(),
+                created_at: Utc::now() + Duration::seconds(offset as i64),
+            });
+            summaries.handle_event(&created).await.unwrap();
+            search.handle_event(&created).await.unwrap();
+        }
+        search.index_email(emailed, "ada@example.com").await;
+        search.index_location(located, "London").await;
+
+        let required = [ProfileField::Email, ProfileField::Location];
+        assert_eq!(queries.find_incomplete(&required, 10).await, vec![
+            (emailed, vec![ProfileField::Location]),
+            (located, vec![ProfileField::Email]),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_age_on_uses_precision_and_stops_at_death() {
+        let index = Arc::new(PersonAttributeIndexProjection::new());
+        let queries = query_service(Arc::new(PersonSummaryProjection::new()), index.clone());
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let [exact, approximate, unknown] = [PersonId::new(), PersonId::new(), PersonId::new()];
+
+        let events = [
+            PersonEvent::BirthDateSet(BirthDateSet { person_id: exact, birth_date: date(1815, 12, 10), set_at: Utc::now() }),
+            PersonEvent::DeathRecorded(DeathRecorded { person_id: exact, date_of_death: date(1852, 11, 27), recorded_at: Utc::now() }),
+            recorded(
+                approximate,
+                AttributeType::Identifying(IdentifyingAttributeType::ApproximateBirthDate),
+                AttributeValue::ApproximateDate { date: date(1990, 6, 15), precision: DatePrecision::Year },
+            ),
+        ];
+        for event in &events {
+            index.handle_event(event).await.unwrap();
+        }
+
+        let alive = queries.get_age_on(&exact, date(1850, 1, 1)).await.unwrap();
+        assert_eq!((alive.years, alive.years_min, alive.years_max), (34, 34, 34));
+        assert!(!alive.is_deceased);
+        assert_eq!(alive.confidence, ConfidenceLevel::Certain);
+
+        // Ages stop at the date of death
+        let dead = queries.get_age_on(&exact, date(1900, 1, 1)).await.unwrap();
+        assert_eq!(dead.years, 36);
+        assert!(dead.is_deceased);
+
+        // Born some time in 1990: 33 or 34 on 2024-03-01
+        let ranged = queries.get_age_on(&approximate, date(2024, 3, 1)).await.unwrap();
+        assert_eq!((ranged.years, ranged.years_min, ranged.years_max), (33, 33, 34));
+        assert_eq!(ranged.precision, DatePrecision::Year);
+        assert_eq!(ranged.confidence, ConfidenceLevel::Possible);
+
+        assert!(queries.get_age_on(&approximate, date(1989, 1, 1)).await.is_none());
+        assert!(queries.get_age_on(&unknown, date(2024, 3, 1)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_summary_of_merged_person_redirects_to_survivor() {
+        let summaries = Arc::new(PersonSummaryProjection::new());
+        let redirects = Arc::new(PersonMergeRedirectProjection::new());
+        let queries = query_service(summaries.clone(), Arc::new(PersonAttributeIndexProjection::new()))
+            .with_merge_redirects(redirects.clone());
+        let [first, second, survivor] = [PersonId::new(), PersonId::new(), PersonId::new()];
+
+        let mut events: Vec<PersonEvent> = [first, second, survivor].into_iter()
+            .map(|person_id| PersonEvent::PersonCreated(PersonCreated {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+                source: "test".to_string(),
+                created_at: Utc::now(),
+            }))
+            .collect();
+        for (source, target) in [(first, second), (second, survivor)] {
+            events.push(PersonEvent::PersonMergedInto(PersonMergedInto {
+                source_person_id: source,
+                merged_into_id: target,
+                merge_reason: MergeReason::DuplicateIdentity,
+                merged_at: Utc::now(),
+            }));
+        }
+        for event in &events {
+            summaries.handle_event(event).await.unwrap();
+            redirects.handle_event(event).await.unwrap();
+        }
+
+        // A chain of merges resolves to the final survivor
+        let summary = queries.get_person_summary(&first).await.unwrap();
+        assert_eq!(summary.person_id, survivor);
+        assert_eq!(summary.merged_from, Some(first));
+
+        let direct = queries.get_person_summary(&survivor).await.unwrap();
+        assert_eq!(direct.merged_from, None);
+
+        // The raw lookup does not follow the merge
+        let raw = queries.get_person_summary_raw(&first).await.unwrap();
+        assert_eq!(raw.person_id, first);
+        assert_eq!(raw.status, SummaryStatus::Merged { into: second });
+        assert_eq!(raw.merged_from, None);
+        assert_eq!(queries.get_person_summary_raw(&survivor).await.unwrap().person_id, survivor);
+    }
+
+    #[tokio::test]
+    async fn test_find_by_attribute_range_delegates_to_the_index() {
+        let index = Arc::new(PersonAttributeIndexProjection::new());
+        let queries = query_service(Arc::new(PersonSummaryProjection::new()), index.clone());
+        let weight = AttributeType::Physical(crate::value_objects::PhysicalAttributeType::Weight);
+        let [light, heavy] = [PersonId::new(), PersonId::new()];
+        index.handle_event(&recorded(light, weight.clone(), AttributeValue::Mass(62.5))).await.unwrap();
+        index.handle_event(&recorded(heavy, weight.clone(), AttributeValue::Mass(95.0))).await.unwrap();
+
+        let today = Utc::now().date_naive();
+        let range = AttributeValueRange::Mass { min: 50.0, max: 80.0 };
+        assert_eq!(queries.find_by_attribute_range(&weight, &range, today).await, vec![light]);
+
+        let without_index = PersonQueryService::new(
+            Arc::new(PersonSummaryProjection::new()),
+            Arc::new(PersonSearchProjection::new()),
+            Arc::new(PersonSkillsProjection::new()),
+            Arc::new(PersonNetworkProjection::new()),
+            Arc::new(PersonTimelineProjection::new()),
+        );
+        assert!(without_index.find_by_attribute_range(&weight, &range, today).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_onboarding_progress_follows_onboarding_commands() {
+        use crate::aggregate::{OnboardingCommand, OnboardingState, OnboardingStep, PersonOnboarding};
+
+        let onboarding_projection = Arc::new(PersonOnboardingProjection::new());
+        let queries = query_service(
+            Arc::new(PersonSummaryProjection::new()),
+            Arc::new(PersonAttributeIndexProjection::new()),
+        )
+        .with_onboarding(onboarding_projection.clone());
+        let person_id = PersonId::new();
+        let mut onboarding = PersonOnboarding::new(person_id);
+        assert!(queries.get_onboarding_progress(&person_id).await.is_none());
+
+        for command in [
+            OnboardingCommand::StartOnboarding,
+            OnboardingCommand::VerifyIdentity { identity_id: uuid::Uuid::now_v7() },
+        ] {
+            for event in onboarding.handle_command(command).unwrap() {
+                onboarding_projection.handle_specific_event(&event).await.unwrap();
+            }
+        }
+
+        let progress = queries.get_onboarding_progress(&person_id).await.unwrap();
+        assert_eq!(progress.state, OnboardingState::CollectingBasicInfo);
+        assert_eq!(progress.completed_steps, vec![OnboardingStep::StartOnboarding, OnboardingStep::VerifyIdentity]);
+        assert_eq!(progress.next_step, Some(OnboardingStep::ProvideBasicInfo));
+        assert!((progress.fraction_complete() - 2.0 / 6.0).abs() < f64::EPSILON);
+
+        // Failing keeps what was done but leaves nothing to do next
+        for event in onboarding.handle_command(OnboardingCommand::FailOnboarding { reason: "no show".to_string() }).unwrap() {
+            onboarding_projection.handle_specific_event(&event).await.unwrap();
+        }
+        let progress = queries.get_onboarding_progress(&person_id).await.unwrap();
+        assert!(matches!(progress.state, OnboardingState::Failed { .. }));
+        assert_eq!(progress.completed_steps.len(), 2);
+        assert_eq!(progress.next_step, None);
+    }
+}
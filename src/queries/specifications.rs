@@ -163,6 +163,10 @@ pub struct SkillsQuery {
 
     /// Filter by skill category
     pub category: Option<String>,
+
+    /// Also match narrower skills in the skill taxonomy
+    #[serde(default)]
+    pub include_descendants: bool,
 }
 
 impl SkillsQuery {
@@ -173,6 +177,7 @@ impl SkillsQuery {
             skill_name: None,
             min_proficiency: None,
             category: None,
+            include_descendants: false,
         }
     }
 
@@ -183,6 +188,7 @@ impl SkillsQuery {
             skill_name: Some(skill_name),
             min_proficiency: None,
             category: None,
+            include_descendants: false,
         }
     }
 
@@ -197,6 +203,12 @@ impl SkillsQuery {
         self.category = Some(category);
         self
     }
+
+    /// Include holders of narrower skills (e.g. "React" for "JavaScript")
+    pub fn include_descendants(mut self) -> Self {
+        self.include_descendants = true;
+        self
+    }
 }
 
 /// Specification for querying person network/connections
@@ -0,0 +1,259 @@
+//! Seedable synthetic datasets for load testing
+//!
+//! [`DatasetGenerator::generate`] builds persons, relationships and skills
+//! from a seed. The same seed, count and config always produce the same
+//! dataset (ids and timestamps included) with this crate's version of
+//! `rand`, so load-test runs are comparable.
+//!
+//! The relationship graph is connected: every person after the first is
+//! attached to a random earlier person, then extra edges are added up to
+//! the configured density.
+
+use crate::aggregate::PersonId;
+use crate::commands::{CreatePerson, PersonCommand};
+use crate::projections::{PersonNetworkProjection, PersonRelationship, PersonSkillsProjection, RelationshipType};
+use crate::value_objects::{PersonName, ProficiencyLevel};
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const GIVEN_NAMES: &[&str] = &[
+    "Ada", "Alan", "Grace", "Edsger", "Barbara", "Donald", "Frances", "John",
+    "Margaret", "Dennis", "Radia", "Ken", "Hedy", "Niklaus", "Karen", "Tim",
+];
+
+const FAMILY_NAMES: &[&str] = &[
+    "Lovelace", "Turing", "Hopper", "Dijkstra", "Liskov", "Knuth", "Allen", "McCarthy",
+    "Hamilton", "Ritchie", "Perlman", "Thompson", "Lamarr", "Wirth", "Jones", "Berners-Lee",
+];
+
+const RELATIONSHIP_TYPES: &[RelationshipType] = &[
+    RelationshipType::Colleague,
+    RelationshipType::Friend,
+    RelationshipType::Family,
+    RelationshipType::Manager,
+    RelationshipType::Mentor,
+    RelationshipType::BusinessPartner,
+];
+
+/// Shape of a generated dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetConfig {
+    /// Relationships per person on top of the spanning tree
+    pub relationship_density: f32,
+    /// Skills per person, drawn uniformly from `min..=max`
+    pub min_skills_per_person: usize,
+    pub max_skills_per_person: usize,
+    /// `(name, category)` pairs; earlier entries are picked more often
+    pub skill_catalog: Vec<(String, String)>,
+}
+
+impl Default for DatasetConfig {
+    fn default() -> Self {
+        let skill_catalog = [
+            ("Rust", "programming"),
+            ("SQL", "data"),
+            ("Python", "programming"),
+            ("Negotiation", "business"),
+            ("Kubernetes", "operations"),
+            ("Public Speaking", "communication"),
+            ("Statistics", "data"),
+            ("Haskell", "programming"),
+        ]
+        .into_iter()
+        .map(|(name, category)| (name.to_string(), category.to_string()))
+        .collect();
+
+        Self {
+            relationship_density: 1.5,
+            min_skills_per_person: 1,
+            max_skills_per_person: 4,
+            skill_catalog,
+        }
+    }
+}
+
+/// A skill held by a generated person
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedSkill {
+    pub person_id: PersonId,
+    pub skill_name: String,
+    pub category: String,
+    pub proficiency: ProficiencyLevel,
+}
+
+/// Generated persons, relationships and skills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dataset {
+    /// One `CreatePerson` per person, in generation order
+    pub commands: Vec<PersonCommand>,
+    pub relationships: Vec<PersonRelationship>,
+    pub skills: Vec<GeneratedSkill>,
+}
+
+impl Dataset {
+    pub fn person_ids(&self) -> Vec<PersonId> {
+        self.commands.iter().map(PersonCommand::aggregate_id).collect()
+    }
+
+    /// Feed relationships and skills into the projections that hold them
+    ///
+    /// Persons themselves are created by running [`Dataset::commands`]
+    /// through a command processor.
+    pub async fn load_into(&self, network: &PersonNetworkProjection, skills: &PersonSkillsProjection) {
+        for relationship in &self.relationships {
+            network.add_relationship(relationship.clone()).await;
+        }
+        for skill in &self.skills {
+            skills
+                .record_skill(skill.person_id, &skill.skill_name, &skill.category, skill.proficiency.clone())
+                .await;
+        }
+    }
+}
+
+/// Deterministic generator of [`Dataset`]s
+pub struct DatasetGenerator;
+
+impl DatasetGenerator {
+    pub fn generate(seed: u64, count: usize, config: &DatasetConfig) -> Dataset {
+        let mut rng = StdRng::seed_from_u64(seed);
+        // Fixed epoch so timestamps don't depend on when the generator runs
+        let epoch = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).expect("valid epoch");
+
+        let person_ids: Vec<PersonId> = (0..count)
+            .map(|_| PersonId::from_uuid(uuid::Builder::from_random_bytes(rng.gen()).into_uuid()))
+            .collect();
+
+        let commands = person_ids.iter()
+            .map(|person_id| {
+                let given = GIVEN_NAMES[rng.gen_range(0..GIVEN_NAMES.len())];
+                let family = FAMILY_NAMES[rng.gen_range(0..FAMILY_NAMES.len())];
+                PersonCommand::CreatePerson(CreatePerson {
+                    person_id: *person_id,
+                    name: PersonName::new(given.to_string(), family.to_string()),
+                    source: "dataset_generator".to_string(),
+                })
+            })
+            .collect();
+
+        let mut edges: Vec<(usize, usize)> = (1..count).map(|i| (i, rng.gen_range(0..i))).collect();
+        let mut seen: HashSet<(usize, usize)> = edges.iter().map(|&(a, b)| (a.min(b), a.max(b))).collect();
+        let extra = (config.relationship_density.max(0.0) * count as f32) as usize;
+        let max_edges = count * count.saturating_sub(1) / 2;
+        let target = (edges.len() + extra).min(max_edges);
+        while edges.len() < target {
+            let (a, b) = (rng.gen_range(0..count), rng.gen_range(0..count));
+            if a != b && seen.insert((a.min(b), a.max(b))) {
+                edges.push((a, b));
+            }
+        }
+
+        let relationships = edges.into_iter()
+            .map(|(from, to)| PersonRelationship {
+                from_person: person_ids[from],
+                to_person: person_ids[to],
+                relationship_type: RELATIONSHIP_TYPES[rng.gen_range(0..RELATIONSHIP_TYPES.len())].clone(),
+                strength: rng.gen_range(0.1..=1.0),
+                established_at: epoch + Duration::days(rng.gen_range(0..3650)),
+                last_interaction: None,
+                interaction_count: rng.gen_range(0..50),
+            })
+            .collect();
+
+        let skills = Self::generate_skills(&mut rng, &person_ids, config);
+
+        Dataset {
+            commands,
+            relationships,
+            skills,
+        }
+    }
+
+    fn generate_skills(rng: &mut StdRng, person_ids: &[PersonId], config: &DatasetConfig) -> Vec<GeneratedSkill> {
+        let catalog = &config.skill_catalog;
+        if catalog.is_empty() {
+            return Vec::new();
+        }
+        let max = config.max_skills_per_person.max(config.min_skills_per_person).min(catalog.len());
+        let min = config.min_skills_per_person.min(max);
+
+        let mut skills = Vec::new();
+        for person_id in person_ids {
+            let wanted = rng.gen_range(min..=max);
+            let mut held = HashSet::new();
+            while held.len() < wanted {
+                // Squaring a uniform sample skews picks toward the front of the catalog
+                let index = ((rng.gen::<f64>().powi(2) * catalog.len() as f64) as usize).min(catalog.len() - 1);
+                if held.insert(index) {
+                    let (skill_name, category) = &catalog[index];
+                    let proficiency = match rng.gen_range(0..4) {
+                        0 => ProficiencyLevel::Beginner,
+                        1 => ProficiencyLevel::Intermediate,
+                        2 => ProficiencyLevel::Advanced,
+                        _ => ProficiencyLevel::Expert,
+                    };
+                    skills.push(GeneratedSkill {
+                        person_id: *person_id,
+                        skill_name: skill_name.clone(),
+                        category: category.clone(),
+                        proficiency,
+                    });
+                }
+            }
+        }
+        skills
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_same_seed_yields_identical_dataset() {
+        let config = DatasetConfig::default();
+        let first = DatasetGenerator::generate(42, 25, &config);
+        let second = DatasetGenerator::generate(42, 25, &config);
+        let other = DatasetGenerator::generate(43, 25, &config);
+
+        let json = |dataset: &Dataset| serde_json::to_value(dataset).unwrap();
+        assert_eq!(json(&first), json(&second));
+        assert_ne!(json(&first), json(&other));
+
+        assert_eq!(first.commands.len(), 25);
+        // Spanning tree plus density extras
+        assert_eq!(first.relationships.len(), 24 + 37);
+        let skills_per_person = first.skills.iter().fold(HashMap::new(), |mut counts, skill| {
+            *counts.entry(skill.person_id).or_insert(0) += 1;
+            counts
+        });
+        assert!(skills_per_person.values().all(|n| (1..=4).contains(n)));
+    }
+
+    #[test]
+    fn test_relationship_graph_is_connected() {
+        let dataset = DatasetGenerator::generate(7, 30, &DatasetConfig::default());
+        let person_ids = dataset.person_ids();
+
+        let mut neighbours: HashMap<PersonId, Vec<PersonId>> = HashMap::new();
+        for rel in &dataset.relationships {
+            neighbours.entry(rel.from_person).or_default().push(rel.to_person);
+            neighbours.entry(rel.to_person).or_default().push(rel.from_person);
+        }
+
+        let mut reached = HashSet::from([person_ids[0]]);
+        let mut frontier = vec![person_ids[0]];
+        while let Some(person) = frontier.pop() {
+            for next in neighbours.get(&person).into_iter().flatten() {
+                if reached.insert(*next) {
+                    frontier.push(*next);
+                }
+            }
+        }
+        assert_eq!(reached.len(), person_ids.len());
+    }
+}
@@ -0,0 +1,131 @@
+//! Person data export service
+//!
+//! Builds exports of a person's data, e.g. for GDPR subject access requests.
+//! Attributes that only make sense inside the organization (fraud flags,
+//! computed confidence scores) are kept out of the subject's own export but
+//! remain available to administrators.
+
+use chrono::{DateTime, Utc};
+use cim_domain::DomainResult;
+use serde::{Deserialize, Serialize};
+
+use crate::aggregate::Person;
+use crate::value_objects::{AttributeSource, AttributeType, PersonAttribute};
+use super::views::{PersonIdentityView, PersonViewService};
+
+/// Custom attribute category marking organization-internal attributes
+pub const INTERNAL_ATTRIBUTE_CATEGORY: &str = "internal";
+
+/// Who the export is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportVisibility {
+    /// Export handed to the person themselves; internal-only attributes are excluded
+    SubjectAccess,
+    /// Full export for administrators
+    Admin,
+}
+
+impl ExportVisibility {
+    /// Whether an attribute may appear in an export with this visibility
+    pub fn includes(&self, attribute: &PersonAttribute) -> bool {
+        match self {
+            ExportVisibility::Admin => true,
+            ExportVisibility::SubjectAccess => !is_internal_only(attribute),
+        }
+    }
+}
+
+/// Internal-only attributes are computed by us or explicitly categorized as internal
+pub fn is_internal_only(attribute: &PersonAttribute) -> bool {
+    let computed = matches!(attribute.provenance.source, AttributeSource::Computed);
+    let internal_category = matches!(
+        &attribute.attribute_type,
+        AttributeType::Custom(custom) if custom.category == INTERNAL_ATTRIBUTE_CATEGORY
+    );
+    computed || internal_category
+}
+
+/// Exported person data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonExport {
+    pub identity: PersonIdentityView,
+    /// All attributes visible to this export, including historical values
+    pub attributes: Vec<PersonAttribute>,
+    pub visibility: ExportVisibility,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// Service for exporting person data
+pub struct ExportService;
+
+impl Default for ExportService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExportService {
+    /// Create a new export service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Export a person's data filtered by `visibility`
+    pub fn export_person(&self, person: &Person, visibility: ExportVisibility) -> DomainResult<PersonExport> {
+        let identity = PersonViewService::create_identity_view(person)?;
+        let attributes = person.attributes.attributes.iter()
+            .filter(|attr| visibility.includes(attr))
+            .cloned()
+            .collect();
+
+        Ok(PersonExport {
+            identity,
+            attributes,
+            visibility,
+            exported_at: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+    use crate::value_objects::{
+        AttributeValue, ConfidenceLevel, CustomAttributeType, DemographicAttributeType,
+        PersonName, Provenance, TemporalValidity,
+    };
+
+    #[test]
+    fn test_subject_export_excludes_internal_attributes() {
+        let mut person = Person::new(
+            PersonId::new(),
+            PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+        );
+        let language = PersonAttribute::new(
+            AttributeType::Demographic(DemographicAttributeType::PreferredLanguage),
+            AttributeValue::Text("en".to_string()),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+        );
+        let fraud_flag = PersonAttribute::new(
+            AttributeType::Custom(CustomAttributeType {
+                organization: "acme".to_string(),
+                attribute_name: "fraud_flag".to_string(),
+                category: INTERNAL_ATTRIBUTE_CATEGORY.to_string(),
+            }),
+            AttributeValue::Boolean(true),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::Imported { system: "risk".to_string() }, ConfidenceLevel::Likely),
+        );
+        person.attributes.attributes = vec![language.clone(), fraud_flag.clone()];
+
+        let service = ExportService::new();
+        let subject = service.export_person(&person, ExportVisibility::SubjectAccess).unwrap();
+        let admin = service.export_person(&person, ExportVisibility::Admin).unwrap();
+
+        assert_eq!(subject.attributes, vec![language.clone()]);
+        assert_eq!(admin.attributes, vec![language, fraud_flag]);
+        assert_eq!(subject.identity.family_name, "Lovelace");
+    }
+}
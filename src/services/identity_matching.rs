@@ -0,0 +1,235 @@
+//! Identity matching for deduplication
+//!
+//! Scores how likely two person records describe the same human. Evidence is
+//! graded rather than binary: a birth date that is off by a day (a typical
+//! data entry error) still counts as strong evidence, and agreeing only on
+//! the birth year counts a little. Other identifying attributes (national
+//! id, birth place, ...) are weighed by
+//! [`PersonAttributeSet::match_score`](crate::value_objects::PersonAttributeSet::match_score).
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::aggregate::Person;
+use crate::value_objects::{AttributeType, AttributeValue, IdentifyingAttributeType, PersonName};
+
+/// Maximum distance (in days) for birth dates to count as a near miss
+const NEAR_MISS_DAYS: i64 = 2;
+
+/// How strongly two birth dates support a match
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BirthDateEvidence {
+    /// Same date
+    Exact,
+    /// Within a couple of days of each other
+    NearMiss { days_apart: i64 },
+    /// Only the year agrees
+    SameYear,
+    /// Dates disagree
+    Divergent,
+    /// At least one record has no birth date
+    Unknown,
+}
+
+impl BirthDateEvidence {
+    pub fn compare(a: Option<NaiveDate>, b: Option<NaiveDate>) -> Self {
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return Self::Unknown,
+        };
+
+        let days_apart = (a - b).num_days().abs();
+        if days_apart == 0 {
+            Self::Exact
+        } else if days_apart <= NEAR_MISS_DAYS {
+            Self::NearMiss { days_apart }
+        } else if a.year() == b.year() {
+            Self::SameYear
+        } else {
+            Self::Divergent
+        }
+    }
+
+    /// Strength of the evidence in `[0, 1]`
+    pub fn strength(&self) -> f64 {
+        match self {
+            Self::Exact => 1.0,
+            Self::NearMiss { .. } => 0.75,
+            Self::SameYear => 0.3,
+            Self::Divergent | Self::Unknown => 0.0,
+        }
+    }
+}
+
+/// Breakdown of an identity match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdentityMatchScore {
+    pub name_score: f64,
+    pub birth_date: BirthDateEvidence,
+    /// Identifying attribute evidence in `[0, 1]`, birth date included
+    pub identifying_score: f64,
+    /// Weighted overall score in `[0, 1]`
+    pub score: f64,
+}
+
+/// Weighted identity matcher
+#[derive(Debug, Clone)]
+pub struct IdentityMatcher {
+    name_weight: f64,
+    identifying_weight: f64,
+}
+
+impl Default for IdentityMatcher {
+    fn default() -> Self {
+        // Identifying attributes carry 40% of the weight
+        Self::new(0.6, 0.4)
+    }
+}
+
+impl IdentityMatcher {
+    pub fn new(name_weight: f64, identifying_weight: f64) -> Self {
+        Self {
+            name_weight,
+            identifying_weight,
+        }
+    }
+
+    /// Score two persons
+    pub fn score(&self, a: &Person, b: &Person) -> IdentityMatchScore {
+        let name_score = name_similarity(&a.core_identity.legal_name, &b.core_identity.legal_name);
+        let birth_date = BirthDateEvidence::compare(birth_date_of(a), birth_date_of(b));
+        let birth_date_similarity = (birth_date != BirthDateEvidence::Unknown).then_some(birth_date.strength());
+        let identifying_score = a.attributes.currently_valid()
+            .match_score_with_birth_date(&b.attributes.currently_valid(), birth_date_similarity);
+
+        let total_weight = self.name_weight + self.identifying_weight;
+        let score = if total_weight > 0.0 {
+            (self.name_weight * name_score + self.identifying_weight * identifying_score) / total_weight
+        } else {
+            0.0
+        };
+
+        IdentityMatchScore {
+            name_score,
+            birth_date,
+            identifying_score,
+            score,
+        }
+    }
+}
+
+/// Birth date from core identity, falling back to a recorded birth date attribute
+pub fn birth_date_of(person: &Person) -> Option<NaiveDate> {
+    person.core_identity.birth_date.or_else(|| {
+        person.attributes
+            .currently_valid()
+            .find_by_type(&AttributeType::Identifying(IdentifyingAttributeType::BirthDate))
+            .and_then(|attr| match &attr.value {
+                AttributeValue::Date(date) => Some(*date),
+                AttributeValue::ApproximateDate { date, .. } => Some(*date),
+                _ => None,
+            })
+    })
+}
+
+fn name_similarity(a: &PersonName, b: &PersonName) -> f64 {
+    let normalize = |parts: &[String]| -> Vec<String> {
+        parts.iter().map(|p| p.trim().to_lowercase()).collect()
+    };
+
+    let (a_given, b_given) = (normalize(&a.components.given_names), normalize(&b.components.given_names));
+    let (a_family, b_family) = (normalize(&a.components.family_names), normalize(&b.components.family_names));
+
+    match (a_family == b_family, a_given == b_given, a_given.first() == b_given.first()) {
+        (true, true, _) => 1.0,
+        (true, false, true) => 0.8,
+        (true, false, false) => 0.4,
+        (false, _, _) => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::PersonId;
+
+    fn person_born(birth_date: Option<NaiveDate>) -> Person {
+        let mut person = Person::new(
+            PersonId::new(),
+            PersonName::new("Maria".to_string(), "Garcia".to_string()),
+        );
+        person.core_identity.birth_date = birth_date;
+        person
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    #[test]
+    fn test_exact_birth_date_is_full_evidence() {
+        let matcher = IdentityMatcher::default();
+        let result = matcher.score(&person_born(date(1980, 5, 17)), &person_born(date(1980, 5, 17)));
+
+        assert_eq!(result.birth_date, BirthDateEvidence::Exact);
+        assert!((result.score - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_off_by_one_day_is_strong_partial_evidence() {
+        let matcher = IdentityMatcher::default();
+        let exact = matcher.score(&person_born(date(1980, 5, 17)), &person_born(date(1980, 5, 17)));
+        let near = matcher.score(&person_born(date(1980, 5, 17)), &person_born(date(1980, 5, 18)));
+
+        assert_eq!(near.birth_date, BirthDateEvidence::NearMiss { days_apart: 1 });
+        assert!(near.score < exact.score);
+        assert!(near.score > 0.8);
+    }
+
+    #[test]
+    fn test_same_year_only_is_weak_partial_evidence() {
+        let matcher = IdentityMatcher::default();
+        let near = matcher.score(&person_born(date(1980, 5, 17)), &person_born(date(1980, 5, 18)));
+        let same_year = matcher.score(&person_born(date(1980, 5, 17)), &person_born(date(1980, 11, 2)));
+
+        assert_eq!(same_year.birth_date, BirthDateEvidence::SameYear);
+        assert!(same_year.score < near.score);
+        assert!(same_year.score > 0.6);
+    }
+
+    #[test]
+    fn test_divergent_birth_dates_contribute_nothing() {
+        let matcher = IdentityMatcher::default();
+        let divergent = matcher.score(&person_born(date(1980, 5, 17)), &person_born(date(1992, 1, 3)));
+
+        assert_eq!(divergent.birth_date, BirthDateEvidence::Divergent);
+        assert!((divergent.score - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_identifying_attributes_count_alongside_the_birth_date() {
+        use crate::value_objects::{
+            AttributeSource, ConfidenceLevel, PersonAttribute, PersonAttributeSet, Provenance, TemporalValidity,
+        };
+        let with_national_id = |id: &str| {
+            let mut person = person_born(date(1980, 5, 17));
+            person.attributes = PersonAttributeSet::of(PersonAttribute::new(
+                AttributeType::Identifying(IdentifyingAttributeType::NationalId),
+                AttributeValue::Text(id.to_string()),
+                TemporalValidity::of(chrono::Utc::now()),
+                Provenance::new(AttributeSource::DocumentVerified, ConfidenceLevel::Certain),
+            ));
+            person
+        };
+        let matcher = IdentityMatcher::default();
+
+        let agreeing = matcher.score(&with_national_id("123-45-6789"), &with_national_id("123456789"));
+        let conflicting = matcher.score(&with_national_id("123-45-6789"), &with_national_id("987-65-4321"));
+
+        assert!((agreeing.score - 1.0).abs() < 1e-9);
+        assert!(conflicting.score < agreeing.score);
+        // Same name and birth date, but the national ids disagree
+        let expected = agreeing.identifying_score * 0.25 / 0.6;
+        assert!((conflicting.identifying_score - expected).abs() < 1e-9);
+    }
+}
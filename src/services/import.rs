@@ -0,0 +1,216 @@
+//! Bulk import of persons from external systems
+//!
+//! Each record becomes a new person: a `CreatePerson` command plus one
+//! `RecordAttribute` per imported value, run through the aggregate and
+//! appended to the event store in a single call. A record is therefore
+//! either imported completely or not at all, and the [`ImportReport`] says
+//! which, so a failed batch can be fixed up and re-run for just the failed
+//! records.
+
+use std::sync::Arc;
+use chrono::{NaiveDate, Utc};
+use cim_domain::formal_domain::Aggregate;
+
+use crate::aggregate::{Person, PersonId};
+use crate::commands::{CreatePerson, PersonCommand, RecordAttribute};
+use crate::events::PersonEvent;
+use crate::infrastructure::EventStore;
+use crate::value_objects::{
+    AttributeSource, AttributeType, AttributeValue, ConfidenceLevel, IdentifyingAttributeType,
+    PersonAttribute, PersonName, Provenance, TemporalValidity,
+};
+
+/// One person as delivered by an external system
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonImportRecord {
+    pub given_name: String,
+    pub family_name: String,
+    pub birth_date: Option<NaiveDate>,
+    /// Name of the system the record came from, recorded as provenance
+    pub source_system: String,
+}
+
+/// Why a record was not imported; nothing was written for it
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ImportFailure {
+    #[error("Invalid import record: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+
+    #[error("Import failed: {0}")]
+    Rejected(String),
+}
+
+/// Outcome of one record, by its position in the imported batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRecordResult {
+    pub index: usize,
+    pub result: Result<PersonId, ImportFailure>,
+}
+
+/// Per-record outcomes of an import, in input order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub records: Vec<ImportRecordResult>,
+}
+
+impl ImportReport {
+    /// Ids of the persons created
+    pub fn imported(&self) -> Vec<PersonId> {
+        self.records.iter().filter_map(|record| record.result.clone().ok()).collect()
+    }
+
+    /// Positions of the records that were not imported, with the reason
+    pub fn failures(&self) -> Vec<(usize, &ImportFailure)> {
+        self.records.iter()
+            .filter_map(|record| record.result.as_ref().err().map(|failure| (record.index, failure)))
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.records.iter().all(|record| record.result.is_ok())
+    }
+}
+
+/// Imports batches of external person records into the event store
+pub struct PersonImportService {
+    event_store: Arc<dyn EventStore>,
+}
+
+impl PersonImportService {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self { event_store }
+    }
+
+    /// Import every record, continuing past failed ones
+    pub async fn import(&self, records: Vec<PersonImportRecord>) -> ImportReport {
+        let mut report = ImportReport::default();
+        for (index, record) in records.into_iter().enumerate() {
+            let result = self.import_one(record).await;
+            report.records.push(ImportRecordResult { index, result });
+        }
+        report
+    }
+
+    async fn import_one(&self, record: PersonImportRecord) -> Result<PersonId, ImportFailure> {
+        let errors = validate(&record);
+        if !errors.is_empty() {
+            return Err(ImportFailure::Invalid(errors));
+        }
+
+        let person_id = PersonId::new();
+        let events = import_events(person_id, record)
+            .map_err(|e| ImportFailure::Rejected(e.to_string()))?;
+
+        // A fresh stream: expecting version 0 guards against id reuse
+        self.event_store
+            .append_events(person_id, events, Some(0))
+            .await
+            .map_err(|e| ImportFailure::Rejected(e.to_string()))?;
+        Ok(person_id)
+    }
+}
+
+fn validate(record: &PersonImportRecord) -> Vec<String> {
+    let mut errors = Vec::new();
+    if record.given_name.trim().is_empty() {
+        errors.push("given name must not be empty".to_string());
+    }
+    if record.family_name.trim().is_empty() {
+        errors.push("family name must not be empty".to_string());
+    }
+    if record.source_system.trim().is_empty() {
+        errors.push("source system must not be empty".to_string());
+    }
+    if let Some(birth_date) = record.birth_date {
+        if birth_date > Utc::now().date_naive() {
+            errors.push(format!("birth date {birth_date} is in the future"));
+        }
+    }
+    errors
+}
+
+/// Events creating the person, computed by the aggregate
+fn import_events(person_id: PersonId, record: PersonImportRecord) -> cim_domain::DomainResult<Vec<PersonEvent>> {
+    let provenance = Provenance::new(
+        AttributeSource::Imported { system: record.source_system.clone() },
+        ConfidenceLevel::Likely,
+    );
+    let mut commands = vec![PersonCommand::CreatePerson(CreatePerson {
+        person_id,
+        name: PersonName::new(record.given_name.trim().to_string(), record.family_name.trim().to_string()),
+        source: record.source_system,
+    })];
+    if let Some(birth_date) = record.birth_date {
+        commands.push(PersonCommand::RecordAttribute(RecordAttribute {
+            person_id,
+            attribute: PersonAttribute::new(
+                AttributeType::Identifying(IdentifyingAttributeType::BirthDate),
+                AttributeValue::Date(birth_date),
+                TemporalValidity::of(Utc::now()),
+                provenance,
+            ),
+        }));
+    }
+
+    let mut person = Person::empty();
+    let mut events = Vec::new();
+    for command in commands {
+        let (next, emitted) = person.handle(command)?;
+        person = next;
+        events.extend(emitted);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::InMemoryEventStore;
+
+    fn record(given: &str, family: &str, birth_date: Option<NaiveDate>) -> PersonImportRecord {
+        PersonImportRecord {
+            given_name: given.to_string(),
+            family_name: family.to_string(),
+            birth_date,
+            source_system: "legacy_crm".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_each_record_and_writes_only_valid_ones() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let service = PersonImportService::new(store.clone());
+        let born = NaiveDate::from_ymd_opt(1815, 12, 10).unwrap();
+        let future = Utc::now().date_naive() + chrono::Duration::days(30);
+
+        let report = service.import(vec![
+            record("Ada", "Lovelace", Some(born)),
+            record(" ", "Babbage", Some(future)),
+            record("Charles", "Babbage", None),
+        ]).await;
+
+        assert!(!report.is_complete());
+        let imported = report.imported();
+        assert_eq!(imported.len(), 2);
+        let failures = report.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, 1);
+        let ImportFailure::Invalid(errors) = failures[0].1 else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(errors.len(), 2);
+
+        // Creation and attributes land together, with imported provenance
+        let envelopes = store.get_events(imported[0]).await.unwrap();
+        assert_eq!(envelopes.len(), 2);
+        let PersonEvent::AttributeRecorded(recorded) = &envelopes[1].event else {
+            panic!("expected AttributeRecorded");
+        };
+        assert_eq!(recorded.attribute.value, AttributeValue::Date(born));
+        assert_eq!(
+            recorded.attribute.provenance.source,
+            AttributeSource::Imported { system: "legacy_crm".to_string() }
+        );
+        assert_eq!(store.get_events(imported[1]).await.unwrap().len(), 1);
+    }
+}
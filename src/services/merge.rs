@@ -0,0 +1,386 @@
+//! Carrying attributes over when one person is merged into another
+//!
+//! Merging only marks the source person as `MergedInto`; the data worth
+//! keeping is moved to the target as ordinary `RecordAttribute` commands.
+//! The command handler runs [`MergeService::carry_over`] before saving a
+//! `MergePersons` command's events, so a carry-over that fails leaves the
+//! source unmerged and the merge can simply be retried. Every carried-over attribute
+//! gets a [`TransformationTrace`] naming the source person, timestamped with
+//! the merge, so audits can tell which record each value came from.
+
+use crate::aggregate::{CanonicalPersonId, Person, PersonId};
+use crate::commands::{PersonCommand, RecordAttribute};
+use crate::events::{PersonEvent, PersonMergedInto};
+use crate::infrastructure::PersonRepository;
+use crate::value_objects::{PersonAttribute, TransformationTrace};
+use cim_domain::formal_domain::Aggregate;
+use cim_domain::{DomainError, DomainResult};
+use std::collections::HashSet;
+
+/// Transformation name recorded on carried-over attributes
+pub const MERGE_TRANSFORMATION: &str = "merged_from";
+
+/// Who applied the merge transformation
+pub const MERGE_APPLIED_BY: &str = "PersonMerge";
+
+/// Builds the commands that move a merged person's attributes to the target
+pub struct MergeService;
+
+impl MergeService {
+    /// Commands recording the source's currently valid attributes on the target
+    ///
+    /// Attribute types the target already holds a valid value for are left
+    /// alone, whether the values agree or conflict; the target's existing data
+    /// wins. When the source holds several valid values of one type, only the
+    /// most recently recorded is carried over.
+    pub fn carry_over_attributes(
+        source: &Person,
+        target: &Person,
+        merge: &PersonMergedInto,
+    ) -> Vec<PersonCommand> {
+        let target_attributes = target.attributes.currently_valid();
+        let mut carried = HashSet::new();
+
+        let mut attributes: Vec<PersonAttribute> = source.attributes.currently_valid().attributes
+            .into_iter()
+            .rev()
+            .filter(|attr| target_attributes.find_by_type(&attr.attribute_type).is_none())
+            .filter(|attr| carried.insert(attr.attribute_type.clone()))
+            .collect();
+        attributes.reverse();
+
+        attributes
+            .into_iter()
+            .map(|mut attribute| {
+                attribute.provenance.trace.push(TransformationTrace {
+                    transformation: format!(
+                        "{MERGE_TRANSFORMATION}:{}",
+                        merge.source_person_id.to_canonical_string()
+                    ),
+                    applied_at: merge.merged_at,
+                    applied_by: MERGE_APPLIED_BY.to_string(),
+                });
+                PersonCommand::RecordAttribute(RecordAttribute {
+                    person_id: merge.merged_into_id,
+                    attribute,
+                })
+            })
+            .collect()
+    }
+
+    /// Record the merged source's attributes on the target and save them
+    ///
+    /// Call it before the source's merge is saved. The target's events are
+    /// saved against the version it was loaded at, so a concurrent write to
+    /// the target fails the carry-over rather than being overwritten; since
+    /// only attributes the target lacks are carried, running it again after
+    /// a failure records nothing twice. A target that is not active would
+    /// ignore the attributes, so it is refused. Returns the target's new
+    /// events.
+    pub async fn carry_over(
+        repository: &PersonRepository,
+        source: &Person,
+        merge: &PersonMergedInto,
+    ) -> DomainResult<Vec<PersonEvent>> {
+        let target = repository.load(merge.merged_into_id).await?
+            .ok_or_else(|| DomainError::AggregateNotFound(format!("Person {}", merge.merged_into_id)))?;
+        if !target.is_active() {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot carry attributes over to person {}: it is not active",
+                merge.merged_into_id
+            )));
+        }
+        let expected_version = target.version;
+
+        let mut updated = target.clone();
+        let mut events = Vec::new();
+        for command in Self::carry_over_attributes(source, &target, merge) {
+            let (next, new_events) = updated.handle(command)?;
+            updated = next;
+            events.extend(new_events);
+        }
+
+        if !events.is_empty() {
+            repository.save_with_expected_version(&updated, events.clone(), expected_version).await?;
+        }
+        Ok(events)
+    }
+
+    /// The person an attribute was carried over from, if it arrived by merge
+    ///
+    /// Follows the most recent merge when an attribute was merged repeatedly.
+    pub fn merge_origin(attribute: &PersonAttribute) -> Option<PersonId> {
+        attribute.provenance.trace.iter().rev()
+            .filter(|trace| trace.applied_by == MERGE_APPLIED_BY)
+            .find_map(|trace| {
+                let source = trace.transformation.strip_prefix(MERGE_TRANSFORMATION)?.strip_prefix(':')?;
+                PersonId::parse(source).ok()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CreatePerson, MergePersons, MergeReason};
+    use crate::cross_domain::person_organization::EMPLOYMENT_ATTRIBUTE_CATEGORY;
+    use crate::infrastructure::nats_integration::execute_command;
+    use crate::infrastructure::{EventEnvelope, EventStore, InMemoryEventStore, InMemorySnapshotStore};
+    use crate::value_objects::{
+        AttributeSource, AttributeType, AttributeValue, ConfidenceLevel, CustomAttributeType,
+        IdentifyingAttributeType, PersonAttributeSet, PersonName, Provenance, TemporalValidity,
+    };
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    fn attribute(attribute_type: IdentifyingAttributeType, value: AttributeValue) -> PersonAttribute {
+        PersonAttribute::new(
+            AttributeType::Identifying(attribute_type),
+            value,
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::DocumentVerified, ConfidenceLevel::Certain),
+        )
+    }
+
+    #[test]
+    fn test_carried_over_attribute_traces_its_source() {
+        let mut source = Person::new(PersonId::new(), PersonName::new("Ada".to_string(), "Byron".to_string()));
+        source.attributes = PersonAttributeSet::from_vec(vec![
+            attribute(IdentifyingAttributeType::BirthPlace, AttributeValue::LocationReference("london".to_string())),
+            attribute(IdentifyingAttributeType::NationalId, AttributeValue::Text("123-45-6789".to_string())),
+        ]);
+        let mut target = Person::new(PersonId::new(), PersonName::new("Ada".to_string(), "Lovelace".to_string()));
+        target.attributes = PersonAttributeSet::of(
+            attribute(IdentifyingAttributeType::NationalId, AttributeValue::Text("987-65-4321".to_string())),
+        );
+
+        let merge = PersonMergedInto {
+            source_person_id: source.id,
+            merged_into_id: target.id,
+            merge_reason: MergeReason::DuplicateIdentity,
+            merged_at: Utc::now(),
+        };
+        let commands = MergeService::carry_over_attributes(&source, &target, &merge);
+
+        // The target's own national id is kept
+        assert_eq!(commands.len(), 1);
+        let PersonCommand::RecordAttribute(record) = &commands[0] else {
+            panic!("expected RecordAttribute");
+        };
+        assert_eq!(record.person_id, target.id);
+
+        let trace = record.attribute.provenance.trace.last().unwrap();
+        assert_eq!(trace.applied_at, merge.merged_at);
+        assert!(trace.transformation.contains(&source.id.to_canonical_string()));
+        assert_eq!(MergeService::merge_origin(&record.attribute), Some(source.id));
+
+        // Original provenance is preserved alongside the merge trace
+        assert_eq!(record.attribute.provenance.source, AttributeSource::DocumentVerified);
+        assert_eq!(MergeService::merge_origin(&target.attributes.attributes[0]), None);
+    }
+
+    fn employment(organization: &str, role: &str) -> PersonAttribute {
+        PersonAttribute::new(
+            AttributeType::Custom(CustomAttributeType {
+                organization: organization.to_string(),
+                attribute_name: "role".to_string(),
+                category: EMPLOYMENT_ATTRIBUTE_CATEGORY.to_string(),
+            }),
+            AttributeValue::Text(role.to_string()),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::Imported { system: "hr".to_string() }, ConfidenceLevel::Certain),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_merge_persons_moves_only_attributes_the_target_lacks() {
+        let repository = PersonRepository::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemorySnapshotStore::new()),
+            100,
+        );
+        let (source_id, target_id) = (PersonId::new(), PersonId::new());
+        let source_attributes = vec![
+            // Distinct: only the source knows these
+            attribute(IdentifyingAttributeType::BirthPlace, AttributeValue::LocationReference("london".to_string())),
+            employment("globex", "Analyst"),
+            // Overlapping: conflicting and agreeing values of types the target has
+            attribute(IdentifyingAttributeType::NationalId, AttributeValue::Text("123-45-6789".to_string())),
+            employment("acme", "Engineer"),
+        ];
+        let target_attributes = vec![
+            attribute(IdentifyingAttributeType::NationalId, AttributeValue::Text("987-65-4321".to_string())),
+            employment("acme", "Engineer"),
+        ];
+        for (person_id, family, attributes) in [
+            (source_id, "Byron", source_attributes),
+            (target_id, "Lovelace", target_attributes),
+        ] {
+            execute_command(&repository, PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), family.to_string()),
+                source: "test".to_string(),
+            })).await.unwrap();
+            for attribute in attributes {
+                execute_command(&repository, PersonCommand::RecordAttribute(RecordAttribute { person_id, attribute }))
+                    .await
+                    .unwrap();
+            }
+        }
+
+        execute_command(&repository, PersonCommand::MergePersons(MergePersons {
+            source_person_id: source_id,
+            target_person_id: target_id,
+            merge_reason: MergeReason::DuplicateIdentity,
+        })).await.unwrap();
+
+        let target = repository.load(target_id).await.unwrap().unwrap();
+        let valid = target.attributes.currently_valid();
+        assert_eq!(valid.attributes.len(), 4);
+
+        let origins: Vec<(AttributeType, Option<PersonId>)> = valid.attributes.iter()
+            .map(|attr| (attr.attribute_type.clone(), MergeService::merge_origin(attr)))
+            .collect();
+        assert!(origins.contains(&(
+            AttributeType::Identifying(IdentifyingAttributeType::BirthPlace),
+            Some(source_id),
+        )));
+        assert!(origins.contains(&(employment("globex", "Analyst").attribute_type, Some(source_id))));
+        assert!(origins.contains(&(employment("acme", "Engineer").attribute_type, None)));
+
+        let national_id = valid.find_by_type(&AttributeType::Identifying(IdentifyingAttributeType::NationalId)).unwrap();
+        assert_eq!(national_id.value, AttributeValue::Text("987-65-4321".to_string()));
+        assert_eq!(MergeService::merge_origin(national_id), None);
+    }
+
+    /// In-memory store whose appends to one person fail while it is set
+    struct FailingStore {
+        inner: InMemoryEventStore,
+        failing: std::sync::Mutex<Option<PersonId>>,
+    }
+
+    impl FailingStore {
+        fn check(&self, aggregate_id: PersonId) -> DomainResult<()> {
+            if *self.failing.lock().unwrap() == Some(aggregate_id) {
+                return Err(DomainError::ConcurrencyConflict { expected: 1, actual: 2 });
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl EventStore for FailingStore {
+        async fn append_events(
+            &self,
+            aggregate_id: PersonId,
+            events: Vec<PersonEvent>,
+            expected_version: Option<u64>,
+        ) -> DomainResult<()> {
+            self.check(aggregate_id)?;
+            self.inner.append_events(aggregate_id, events, expected_version).await
+        }
+
+        async fn append_events_caused_by(
+            &self,
+            aggregate_id: PersonId,
+            events: Vec<PersonEvent>,
+            expected_version: Option<u64>,
+            cause: &crate::nats::MessageIdentity,
+        ) -> DomainResult<()> {
+            self.check(aggregate_id)?;
+            self.inner.append_events_caused_by(aggregate_id, events, expected_version, cause).await
+        }
+
+        async fn get_events(&self, aggregate_id: PersonId) -> DomainResult<Vec<EventEnvelope>> {
+            self.inner.get_events(aggregate_id).await
+        }
+
+        async fn get_events_from_version(
+            &self,
+            aggregate_id: PersonId,
+            from_version: u64,
+        ) -> DomainResult<Vec<EventEnvelope>> {
+            self.inner.get_events_from_version(aggregate_id, from_version).await
+        }
+
+        async fn get_current_version(&self, aggregate_id: PersonId) -> DomainResult<u64> {
+            self.inner.get_current_version(aggregate_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_target_save_leaves_the_source_unmerged_and_retryable() {
+        let store = Arc::new(FailingStore {
+            inner: InMemoryEventStore::new(),
+            failing: std::sync::Mutex::new(None),
+        });
+        let repository = PersonRepository::new(store.clone(), Arc::new(InMemorySnapshotStore::new()), 100);
+        let (source_id, target_id) = (PersonId::new(), PersonId::new());
+        for (person_id, family) in [(source_id, "Byron"), (target_id, "Lovelace")] {
+            execute_command(&repository, PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), family.to_string()),
+                source: "test".to_string(),
+            })).await.unwrap();
+        }
+        let birth_place = attribute(IdentifyingAttributeType::BirthPlace, AttributeValue::LocationReference("london".to_string()));
+        execute_command(&repository, PersonCommand::RecordAttribute(RecordAttribute {
+            person_id: source_id,
+            attribute: birth_place.clone(),
+        })).await.unwrap();
+        let merge = PersonCommand::MergePersons(MergePersons {
+            source_person_id: source_id,
+            target_person_id: target_id,
+            merge_reason: MergeReason::DuplicateIdentity,
+        });
+
+        // The target's save fails: nothing is merged and nothing is lost
+        *store.failing.lock().unwrap() = Some(target_id);
+        let err = execute_command(&repository, merge.clone()).await.unwrap_err();
+        assert!(matches!(err, DomainError::ConcurrencyConflict { .. }));
+        let source = repository.load(source_id).await.unwrap().unwrap();
+        assert!(source.is_active());
+        assert!(source.attributes.currently_valid().find_by_type(&birth_place.attribute_type).is_some());
+        assert!(repository.load(target_id).await.unwrap().unwrap().attributes.attributes.is_empty());
+
+        // Retrying once the target can be written completes the merge
+        *store.failing.lock().unwrap() = None;
+        execute_command(&repository, merge).await.unwrap();
+        assert!(!repository.load(source_id).await.unwrap().unwrap().is_active());
+        let target = repository.load(target_id).await.unwrap().unwrap();
+        let carried = target.attributes.currently_valid().find_by_type(&birth_place.attribute_type).cloned().unwrap();
+        assert_eq!(MergeService::merge_origin(&carried), Some(source_id));
+    }
+
+    #[tokio::test]
+    async fn test_carry_over_refuses_an_inactive_target() {
+        let repository = PersonRepository::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemorySnapshotStore::new()),
+            100,
+        );
+        let (source_id, target_id, survivor_id) = (PersonId::new(), PersonId::new(), PersonId::new());
+        for (person_id, family) in [(source_id, "Byron"), (target_id, "Lovelace"), (survivor_id, "King")] {
+            execute_command(&repository, PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), family.to_string()),
+                source: "test".to_string(),
+            })).await.unwrap();
+        }
+        execute_command(&repository, PersonCommand::MergePersons(MergePersons {
+            source_person_id: target_id,
+            target_person_id: survivor_id,
+            merge_reason: MergeReason::DuplicateIdentity,
+        })).await.unwrap();
+
+        let source = repository.load(source_id).await.unwrap().unwrap();
+        let merge = PersonMergedInto {
+            source_person_id: source_id,
+            merged_into_id: target_id,
+            merge_reason: MergeReason::DuplicateIdentity,
+            merged_at: Utc::now(),
+        };
+        let err = MergeService::carry_over(&repository, &source, &merge).await.unwrap_err();
+        assert!(matches!(err, DomainError::ValidationError(_)));
+    }
+}
@@ -0,0 +1,360 @@
+//! Purpose-bound data minimization on read
+//!
+//! Consumers should only receive the data their purpose needs: a shipping
+//! integration needs a name and a shipping address, not a birth date or
+//! medical record number. A [`MinimizationProfile`] lists the fields released
+//! for one purpose and strips everything else from a [`Person`] or
+//! [`PersonSummary`] before it leaves the domain. Nothing is released unless
+//! the person's consent for the profile's purpose is currently granted in the
+//! [`ConsentProjection`].
+//!
+//! Addresses live in the Location domain; a person's addresses are referenced
+//! by custom attributes in the [`ADDRESS_ATTRIBUTE_CATEGORY`] named after
+//! their use ("shipping", "billing", ...), holding a location reference.
+
+use cim_domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+use crate::aggregate::{Person, PersonId};
+use crate::cross_domain::person_organization::EMPLOYMENT_ATTRIBUTE_CATEGORY;
+use crate::projections::{ConsentProjection, ConsentState, PersonSummary, SummaryStatus};
+use crate::value_objects::{
+    AttributeType, ConsentType, CustomAttributeType, IdentifyingAttributeType, PersonAttribute,
+    PersonName,
+};
+
+/// Custom attribute category referencing Location domain addresses
+pub const ADDRESS_ATTRIBUTE_CATEGORY: &str = "address";
+
+/// Attribute name of the shipping address reference
+pub const SHIPPING_ADDRESS_ATTRIBUTE: &str = "shipping";
+
+/// Attribute type of a person's shipping address reference
+pub fn shipping_address_type(organization: &str) -> AttributeType {
+    AttributeType::Custom(CustomAttributeType {
+        organization: organization.to_string(),
+        attribute_name: SHIPPING_ADDRESS_ATTRIBUTE.to_string(),
+        category: ADDRESS_ATTRIBUTE_CATEGORY.to_string(),
+    })
+}
+
+/// Which attributes a profile releases
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttributeSelector {
+    /// One exact attribute type
+    Type(AttributeType),
+    /// Custom attributes of a category, from any organization
+    CustomCategory(String),
+    /// Custom attributes with this category and name, from any organization
+    CustomNamed { category: String, attribute_name: String },
+}
+
+impl AttributeSelector {
+    pub fn matches(&self, attribute_type: &AttributeType) -> bool {
+        match (self, attribute_type) {
+            (AttributeSelector::Type(selected), _) => selected == attribute_type,
+            (AttributeSelector::CustomCategory(category), AttributeType::Custom(custom)) => {
+                &custom.category == category
+            }
+            (AttributeSelector::CustomNamed { category, attribute_name }, AttributeType::Custom(custom)) => {
+                &custom.category == category && &custom.attribute_name == attribute_name
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A piece of person data a profile may release
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MinimizedField {
+    Name,
+    BirthDate,
+    DeathDate,
+    Email,
+    Phone,
+    /// Current employer and role
+    Employment,
+    Location,
+    /// Skill and component counts
+    Counts,
+    Attributes(AttributeSelector),
+}
+
+/// The fields released to consumers acting for one purpose
+///
+/// Identifiers, lifecycle state and bookkeeping timestamps are always kept so
+/// the minimized record can still be correlated and versioned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinimizationProfile {
+    pub name: String,
+    /// Consent purpose the data is released under
+    pub purpose: ConsentType,
+    pub fields: Vec<MinimizedField>,
+}
+
+impl MinimizationProfile {
+    pub fn new(name: impl Into<String>, purpose: ConsentType) -> Self {
+        Self {
+            name: name.into(),
+            purpose,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn allowing(mut self, field: MinimizedField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Name and shipping address, for fulfilment integrations
+    pub fn shipping() -> Self {
+        Self::new("shipping", ConsentType::DataProcessing)
+            .allowing(MinimizedField::Name)
+            .allowing(MinimizedField::Attributes(AttributeSelector::CustomNamed {
+                category: ADDRESS_ATTRIBUTE_CATEGORY.to_string(),
+                attribute_name: SHIPPING_ADDRESS_ATTRIBUTE.to_string(),
+            }))
+    }
+
+    /// Name, contact details and employment, for a staff directory
+    pub fn directory() -> Self {
+        Self::new("directory", ConsentType::DataSharing)
+            .allowing(MinimizedField::Name)
+            .allowing(MinimizedField::Email)
+            .allowing(MinimizedField::Phone)
+            .allowing(MinimizedField::Employment)
+            .allowing(MinimizedField::Location)
+            .allowing(MinimizedField::Attributes(AttributeSelector::CustomCategory(
+                EMPLOYMENT_ATTRIBUTE_CATEGORY.to_string(),
+            )))
+    }
+
+    /// Name and birth date, for age checks
+    pub fn age_verification() -> Self {
+        [
+            IdentifyingAttributeType::BirthDateTime,
+            IdentifyingAttributeType::BirthDate,
+            IdentifyingAttributeType::BirthYear,
+            IdentifyingAttributeType::ApproximateBirthDate,
+        ]
+        .into_iter()
+        .fold(
+            Self::new("age_verification", ConsentType::DataProcessing)
+                .allowing(MinimizedField::Name)
+                .allowing(MinimizedField::BirthDate),
+            |profile, birth| {
+                profile.allowing(MinimizedField::Attributes(AttributeSelector::Type(
+                    AttributeType::Identifying(birth),
+                )))
+            },
+        )
+    }
+
+    pub fn allows(&self, field: &MinimizedField) -> bool {
+        self.fields.contains(field)
+    }
+
+    /// Whether an attribute is released by this profile
+    pub fn allows_attribute(&self, attribute: &PersonAttribute) -> bool {
+        self.fields.iter().any(|field| match field {
+            MinimizedField::Attributes(selector) => selector.matches(&attribute.attribute_type),
+            _ => false,
+        })
+    }
+
+    /// Fails unless the person's consent for this profile's purpose is granted
+    async fn check_consent(&self, person_id: &PersonId, consents: &ConsentProjection) -> DomainResult<()> {
+        match consents.consent_state(person_id, &self.purpose).await {
+            ConsentState::Granted { .. } => Ok(()),
+            ConsentState::Revoked { .. } => Err(DomainError::ValidationError(format!(
+                "Person {person_id} revoked {} consent; {} data is not released",
+                self.purpose, self.name
+            ))),
+            ConsentState::NotRecorded => Err(DomainError::ValidationError(format!(
+                "Person {person_id} has not consented to {}; {} data is not released",
+                self.purpose, self.name
+            ))),
+        }
+    }
+
+    /// The person minimized to this profile, if they consented to its purpose
+    pub async fn release_person(&self, person: &Person, consents: &ConsentProjection) -> DomainResult<Person> {
+        self.check_consent(&person.id, consents).await?;
+        Ok(self.apply_to_person(person))
+    }
+
+    /// The summary minimized to this profile, if the person consented to its purpose
+    pub async fn release_summary(
+        &self,
+        summary: &PersonSummary,
+        consents: &ConsentProjection,
+    ) -> DomainResult<PersonSummary> {
+        self.check_consent(&summary.person_id, consents).await?;
+        Ok(self.apply_to_summary(summary))
+    }
+
+    /// The person with everything outside the profile removed
+    ///
+    /// A withheld name is replaced by an empty one.
+    fn apply_to_person(&self, person: &Person) -> Person {
+        let mut minimized = person.clone();
+        if !self.allows(&MinimizedField::Name) {
+            minimized.core_identity.legal_name = PersonName::new(String::new(), String::new());
+        }
+        if !self.allows(&MinimizedField::BirthDate) {
+            minimized.core_identity.birth_date = None;
+        }
+        if !self.allows(&MinimizedField::DeathDate) {
+            minimized.core_identity.death_date = None;
+        }
+        minimized.attributes = minimized.attributes.filter(|attr| self.allows_attribute(attr));
+        minimized
+    }
+
+    /// The summary with everything outside the profile removed
+    fn apply_to_summary(&self, summary: &PersonSummary) -> PersonSummary {
+        let keep = |field: MinimizedField, value: &Option<String>| {
+            if self.allows(&field) { value.clone() } else { None }
+        };
+        let counts = self.allows(&MinimizedField::Counts);
+
+        PersonSummary {
+            person_id: summary.person_id,
+            name: if self.allows(&MinimizedField::Name) { summary.name.clone() } else { String::new() },
+            primary_email: keep(MinimizedField::Email, &summary.primary_email),
+            primary_phone: keep(MinimizedField::Phone, &summary.primary_phone),
+            current_employer: keep(MinimizedField::Employment, &summary.current_employer),
+            current_role: keep(MinimizedField::Employment, &summary.current_role),
+            location: keep(MinimizedField::Location, &summary.location),
+            skills_count: if counts { summary.skills_count } else { 0 },
+            component_count: if counts { summary.component_count } else { 0 },
+            last_updated: summary.last_updated,
+            merged_from: summary.merged_from,
+            status: summary.status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ConsentRecorded, PersonEvent};
+    use crate::projections::PersonProjection;
+    use crate::value_objects::{
+        AttributeSource, AttributeValue, ConfidenceLevel, ConsentStatus, HealthcareAttributeType,
+        PersonAttributeSet, Provenance, TemporalValidity,
+    };
+    use chrono::{NaiveDate, Utc};
+
+    fn attribute(attribute_type: AttributeType, value: AttributeValue) -> PersonAttribute {
+        PersonAttribute::new(
+            attribute_type,
+            value,
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+        )
+    }
+
+    #[test]
+    fn test_shipping_profile_keeps_name_and_shipping_address() {
+        let mut person = Person::new(PersonId::new(), PersonName::new("Ada".to_string(), "Lovelace".to_string()));
+        person.core_identity.birth_date = NaiveDate::from_ymd_opt(1815, 12, 10);
+        let shipping = attribute(shipping_address_type("shop"), AttributeValue::LocationReference("addr-1".to_string()));
+        person.attributes = PersonAttributeSet::from_vec(vec![
+            shipping.clone(),
+            attribute(
+                AttributeType::Custom(CustomAttributeType {
+                    organization: "shop".to_string(),
+                    attribute_name: "billing".to_string(),
+                    category: ADDRESS_ATTRIBUTE_CATEGORY.to_string(),
+                }),
+                AttributeValue::LocationReference("addr-2".to_string()),
+            ),
+            attribute(
+                AttributeType::Healthcare(HealthcareAttributeType::MedicalRecordNumber),
+                AttributeValue::Text("MRN-7".to_string()),
+            ),
+        ]);
+
+        let profile = MinimizationProfile::shipping();
+        let minimized = profile.apply_to_person(&person);
+        assert_eq!(minimized.core_identity.legal_name, person.core_identity.legal_name);
+        assert_eq!(minimized.core_identity.birth_date, None);
+        assert_eq!(minimized.attributes.attributes, vec![shipping]);
+
+        let summary = PersonSummary {
+            person_id: person.id,
+            name: "Ada Lovelace".to_string(),
+            primary_email: Some("ada@example.com".to_string()),
+            primary_phone: Some("+44 20 7946 0000".to_string()),
+            current_employer: Some("Analytical Engines".to_string()),
+            current_role: Some("Programmer".to_string()),
+            location: Some("London".to_string()),
+            skills_count: 3,
+            component_count: 5,
+            last_updated: Utc::now(),
+            merged_from: None,
+            status: SummaryStatus::Active,
+        };
+        let minimized = profile.apply_to_summary(&summary);
+        assert_eq!(minimized.name, "Ada Lovelace");
+        assert_eq!(minimized.primary_email, None);
+        assert_eq!(minimized.primary_phone, None);
+        assert_eq!(minimized.current_employer, None);
+        assert_eq!(minimized.location, None);
+        assert_eq!(minimized.skills_count, 0);
+    }
+
+    async fn record_consent(
+        consents: &ConsentProjection,
+        person_id: PersonId,
+        consent_type: ConsentType,
+        status: ConsentStatus,
+    ) {
+        let event = PersonEvent::ConsentRecorded(ConsentRecorded {
+            person_id,
+            consent_type,
+            status,
+            recorded_at: Utc::now(),
+            policy_version: None,
+        });
+        consents.handle_event(&event).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_release_requires_consent_for_the_profile_purpose() {
+        let person = Person::new(PersonId::new(), PersonName::new("Ada".to_string(), "Lovelace".to_string()));
+        let profile = MinimizationProfile::shipping();
+        let consents = ConsentProjection::new();
+
+        // Nothing recorded, or consent to another purpose only, releases nothing
+        assert!(profile.release_person(&person, &consents).await.is_err());
+        record_consent(&consents, person.id, ConsentType::Marketing, ConsentStatus::Granted).await;
+        assert!(profile.release_person(&person, &consents).await.is_err());
+
+        record_consent(&consents, person.id, ConsentType::DataProcessing, ConsentStatus::Granted).await;
+        let released = profile.release_person(&person, &consents).await.unwrap();
+        assert_eq!(released.core_identity.legal_name, person.core_identity.legal_name);
+
+        record_consent(&consents, person.id, ConsentType::DataProcessing, ConsentStatus::Revoked).await;
+        let summary = PersonSummary {
+            person_id: person.id,
+            name: "Ada Lovelace".to_string(),
+            primary_email: None,
+            primary_phone: None,
+            current_employer: None,
+            current_role: None,
+            location: None,
+            skills_count: 0,
+            component_count: 0,
+            last_updated: Utc::now(),
+            merged_from: None,
+            status: SummaryStatus::Active,
+        };
+        assert!(matches!(
+            profile.release_summary(&summary, &consents).await,
+            Err(DomainError::ValidationError(_))
+        ));
+        assert!(profile.release_person(&person, &consents).await.is_err());
+    }
+}
@@ -16,8 +16,25 @@ pub mod composition;
 pub mod views;
 pub mod network_analysis;
 pub mod person_service;
+pub mod identity_matching;
+pub mod export;
+pub mod dataset_generator;
+pub mod merge;
+pub mod minimization;
+pub mod pseudonym;
+pub mod import;
 
 pub use composition::PersonCompositionService;
-pub use views::{PersonIdentityView, PersonViewService, LifecycleStatus};
+pub use views::{PersonIdentityView, PersonViewService, LifecycleStatus, ExportFormat, PersonDataDocument, EmploymentSpan, DateRange};
 pub use network_analysis::*;
-pub use person_service::{PersonService, CommandOperation, QueryOperation}; 
\ No newline at end of file
+pub use person_service::{PersonService, CommandOperation, QueryOperation};
+pub use identity_matching::{IdentityMatcher, IdentityMatchScore, BirthDateEvidence};
+pub use export::{ExportService, ExportVisibility, PersonExport};
+pub use dataset_generator::{DatasetGenerator, DatasetConfig, Dataset, GeneratedSkill};
+pub use merge::{MergeService, MERGE_TRANSFORMATION, MERGE_APPLIED_BY};
+pub use minimization::{
+    MinimizationProfile, MinimizedField, AttributeSelector, ADDRESS_ATTRIBUTE_CATEGORY,
+    SHIPPING_ADDRESS_ATTRIBUTE, shipping_address_type,
+};
+pub use pseudonym::{pseudonym_for, PSEUDONYM_PREFIX};
+pub use import::{PersonImportService, PersonImportRecord, ImportReport, ImportRecordResult, ImportFailure};
@@ -1,14 +1,21 @@
 //! Network analysis service for professional relationships
 
+use crate::aggregate::PersonId;
+use crate::projections::{PersonNetworkProjection, PersonRelationship, RelationshipType};
 use crate::value_objects::{ProfessionalNetworkRelation, ProfessionalRelationType};
+use cim_domain::{DomainError, DomainResult};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Network analysis service for analyzing professional relationships
 pub struct NetworkAnalysisService {
     /// Graph of professional relationships
     relationships: HashMap<Uuid, Vec<ProfessionalNetworkRelation>>,
+    /// Relationship projection analysed by the graph algorithms
+    network: Option<Arc<PersonNetworkProjection>>,
 }
 
 /// Network metrics for a person
@@ -32,6 +39,119 @@ pub struct NetworkMetrics {
     pub connection_diversity: HashMap<String, usize>,
 }
 
+/// Centrality of one person in the relationship projection, each in [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CentralityScores {
+    /// Share of the other people they are directly related to
+    pub degree: f32,
+    /// Share of shortest paths between other pairs that pass through them
+    pub betweenness: f32,
+    /// Inverse average distance to the people they can reach, scaled by the
+    /// share of the network they can reach
+    pub closeness: f32,
+}
+
+/// Undirected snapshot of the projection with people numbered densely
+struct IndexedGraph {
+    index: HashMap<PersonId, usize>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl IndexedGraph {
+    fn undirected(relationships: &[PersonRelationship]) -> Self {
+        let mut index = HashMap::new();
+        let mut neighbors: Vec<HashSet<usize>> = Vec::new();
+        let mut node = |person: PersonId, neighbors: &mut Vec<HashSet<usize>>| {
+            *index.entry(person).or_insert_with(|| {
+                neighbors.push(HashSet::new());
+                neighbors.len() - 1
+            })
+        };
+
+        for rel in relationships {
+            let from = node(rel.from_person, &mut neighbors);
+            let to = node(rel.to_person, &mut neighbors);
+            if from != to {
+                neighbors[from].insert(to);
+                neighbors[to].insert(from);
+            }
+        }
+
+        Self {
+            index,
+            neighbors: neighbors.into_iter().map(|n| n.into_iter().collect()).collect(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// Hop distance from `source` to every node, `None` where unreachable
+    fn distances(&self, source: usize) -> Vec<Option<usize>> {
+        let mut distance = vec![None; self.len()];
+        let mut queue = VecDeque::from([source]);
+        distance[source] = Some(0);
+        while let Some(v) = queue.pop_front() {
+            let next = distance[v].map(|d| d + 1);
+            for &w in &self.neighbors[v] {
+                if distance[w].is_none() {
+                    distance[w] = next;
+                    queue.push_back(w);
+                }
+            }
+        }
+        distance
+    }
+
+    /// Brandes' dependency accumulation, summed over all sources, for one node
+    fn pair_dependency(&self, target: usize) -> f64 {
+        let n = self.len();
+        let mut total = 0.0;
+        let mut order = Vec::with_capacity(n);
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut paths = vec![0.0_f64; n];
+        let mut distance: Vec<Option<usize>> = vec![None; n];
+        let mut dependency = vec![0.0_f64; n];
+        let mut queue = VecDeque::new();
+
+        for source in (0..n).filter(|&s| s != target) {
+            order.clear();
+            predecessors.iter_mut().for_each(Vec::clear);
+            paths.fill(0.0);
+            distance.fill(None);
+            dependency.fill(0.0);
+
+            paths[source] = 1.0;
+            distance[source] = Some(0);
+            queue.push_back(source);
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+                let next = distance[v].map(|d| d + 1);
+                for &w in &self.neighbors[v] {
+                    if distance[w].is_none() {
+                        distance[w] = next;
+                        queue.push_back(w);
+                    }
+                    if distance[w] == next {
+                        paths[w] += paths[v];
+                        predecessors[w].push(v);
+                    }
+                }
+            }
+
+            while let Some(w) = order.pop() {
+                for &v in &predecessors[w] {
+                    dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+                }
+            }
+            total += dependency[target];
+        }
+
+        total
+    }
+}
+
 /// Path between two people in the network
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkPath {
@@ -43,6 +163,35 @@ pub struct NetworkPath {
     pub path: Vec<Uuid>,
     /// Total strength (product of edge strengths)
     pub strength: f32,
+    /// Accumulated edge cost; the hop count for unweighted paths
+    #[serde(default)]
+    pub cost: f64,
+}
+
+/// Dijkstra frontier entry, ordered so the cheapest pops first
+struct Frontier {
+    cost: f64,
+    person: PersonId,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
 }
 
 /// Community detection result
@@ -56,6 +205,124 @@ pub struct NetworkCommunity {
     pub cohesion: f32,
     /// Primary relationship types in community
     pub primary_types: Vec<String>,
+    /// This community's share of the partition's modularity; only computed by
+    /// `detect_communities_seeded`
+    #[serde(default)]
+    pub modularity: f32,
+}
+
+impl NetworkCommunity {
+    /// Members as person ids, ordered by id
+    pub fn member_ids(&self) -> Vec<PersonId> {
+        let mut members: Vec<Uuid> = self.members.iter().copied().collect();
+        members.sort();
+        members.into_iter().map(PersonId::from_uuid).collect()
+    }
+}
+
+/// Smallest modularity gain that counts as an improvement
+const GAIN_EPSILON: f64 = 1e-12;
+
+/// Deterministic pseudo-random rank of a node for a seed (SplitMix64)
+fn seeded_rank(seed: u64, node: usize) -> u64 {
+    let mut z = seed.wrapping_add((node as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// One Louvain level: move nodes between communities while modularity improves
+///
+/// `graph` is a symmetric weighted adjacency where a self-loop carries twice
+/// the weight inside an aggregated node. Returns each node's community and
+/// whether any node moved.
+fn louvain_local_moves(graph: &[BTreeMap<usize, f64>], seed: u64) -> (Vec<usize>, bool) {
+    let degree: Vec<f64> = graph.iter().map(|edges| edges.values().sum()).collect();
+    let total_weight: f64 = degree.iter().sum();
+    let mut community: Vec<usize> = (0..graph.len()).collect();
+    if total_weight <= 0.0 {
+        return (community, false);
+    }
+
+    let mut community_degree = degree.clone();
+    let mut order: Vec<usize> = (0..graph.len()).collect();
+    order.sort_by_key(|&node| (seeded_rank(seed, node), node));
+
+    let mut moved = false;
+    loop {
+        let mut improved = false;
+        for &node in &order {
+            let current = community[node];
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for (&neighbor, &weight) in &graph[node] {
+                if neighbor != node {
+                    *links.entry(community[neighbor]).or_default() += weight;
+                }
+            }
+
+            community_degree[current] -= degree[node];
+            let gain = |target: usize, weight: f64| {
+                weight - community_degree[target] * degree[node] / total_weight
+            };
+            let mut best = current;
+            let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+            for (&target, &weight) in &links {
+                let candidate = gain(target, weight);
+                if candidate > best_gain + GAIN_EPSILON {
+                    best = target;
+                    best_gain = candidate;
+                }
+            }
+            community_degree[best] += degree[node];
+
+            if best != current {
+                community[node] = best;
+                improved = true;
+                moved = true;
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    (community, moved)
+}
+
+/// Louvain community of every node, numbered in order of first appearance
+fn louvain(mut graph: Vec<BTreeMap<usize, f64>>, seed: u64) -> Vec<usize> {
+    let mut membership: Vec<usize> = (0..graph.len()).collect();
+    loop {
+        let (community, moved) = louvain_local_moves(&graph, seed);
+        if !moved {
+            return membership;
+        }
+
+        let mut labels = HashMap::new();
+        let renumbered: Vec<usize> = community
+            .iter()
+            .map(|c| {
+                let next = labels.len();
+                *labels.entry(*c).or_insert(next)
+            })
+            .collect();
+        membership = membership.iter().map(|&node| renumbered[node]).collect();
+
+        let mut aggregated = vec![BTreeMap::new(); labels.len()];
+        for (node, edges) in graph.iter().enumerate() {
+            for (&neighbor, &weight) in edges {
+                *aggregated[renumbered[node]].entry(renumbered[neighbor]).or_default() += weight;
+            }
+        }
+        graph = aggregated;
+    }
+}
+
+fn relationship_type_name(relationship_type: &RelationshipType) -> String {
+    match relationship_type {
+        RelationshipType::Other(name) => name.clone(),
+        other => format!("{other:?}"),
+    }
 }
 
 impl Default for NetworkAnalysisService {
@@ -68,8 +335,23 @@ impl NetworkAnalysisService {
     pub fn new() -> Self {
         Self {
             relationships: HashMap::new(),
+            network: None,
         }
     }
+
+    /// Analyse the relationships held by a network projection
+    pub fn with_network(mut self, network: Arc<PersonNetworkProjection>) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Snapshot of the attached projection's relationships
+    async fn network_relationships(&self) -> DomainResult<Vec<PersonRelationship>> {
+        let network = self.network.as_ref().ok_or_else(|| {
+            DomainError::ValidationError("No network projection attached to NetworkAnalysisService".to_string())
+        })?;
+        Ok(network.relationships().await)
+    }
     
     /// Add a relationship to the network
     pub fn add_relationship(&mut self, from: Uuid, relation: ProfessionalNetworkRelation) {
@@ -225,11 +507,14 @@ impl NetworkAnalysisService {
                 // Calculate path strength
                 let strength = self.calculate_path_strength(&path);
                 
+                let cost = (path.len() - 1) as f64;
+
                 return Some(NetworkPath {
                     from,
                     to,
                     path,
                     strength,
+                    cost,
                 });
             }
             
@@ -247,6 +532,114 @@ impl NetworkAnalysisService {
         None
     }
     
+    /// Cheapest path between two people in the attached network projection
+    ///
+    /// Relationships are followed in their recorded direction and costed by
+    /// `weight_fn`, e.g. `|rel| 1.0 - rel.strength as f64` to prefer strong
+    /// ties. Returns `Ok(None)` when `to` is unreachable, and an error if a
+    /// reachable relationship is given a negative or NaN weight.
+    pub async fn find_weighted_path(
+        &self,
+        from: PersonId,
+        to: PersonId,
+        weight_fn: impl Fn(&PersonRelationship) -> f64,
+    ) -> DomainResult<Option<NetworkPath>> {
+        let mut outgoing: HashMap<PersonId, Vec<PersonRelationship>> = HashMap::new();
+        for rel in self.network_relationships().await? {
+            outgoing.entry(rel.from_person).or_default().push(rel);
+        }
+
+        let mut best = HashMap::from([(from, 0.0)]);
+        let mut parent: HashMap<PersonId, (PersonId, f32)> = HashMap::new();
+        let mut frontier = BinaryHeap::from([Frontier { cost: 0.0, person: from }]);
+
+        while let Some(Frontier { cost, person }) = frontier.pop() {
+            if person == to {
+                let mut path = vec![*to.as_uuid()];
+                let mut strength = 1.0;
+                let mut node = to;
+                while let Some(&(previous, edge_strength)) = parent.get(&node) {
+                    path.push(*previous.as_uuid());
+                    strength *= edge_strength;
+                    node = previous;
+                }
+                path.reverse();
+
+                return Ok(Some(NetworkPath {
+                    from: *from.as_uuid(),
+                    to: *to.as_uuid(),
+                    strength: if path.len() < 2 { 0.0 } else { strength },
+                    path,
+                    cost,
+                }));
+            }
+
+            // Skip entries superseded by a cheaper route
+            if best.get(&person).is_some_and(|known| cost > *known) {
+                continue;
+            }
+
+            for rel in outgoing.get(&person).into_iter().flatten() {
+                let weight = weight_fn(rel);
+                if weight.is_nan() || weight < 0.0 {
+                    return Err(DomainError::ValidationError(format!(
+                        "Relationship {} -> {} has invalid weight {weight}; weights must be non-negative",
+                        rel.from_person, rel.to_person
+                    )));
+                }
+
+                let next = cost + weight;
+                if !best.get(&rel.to_person).is_some_and(|known| *known <= next) {
+                    best.insert(rel.to_person, next);
+                    parent.insert(rel.to_person, (person, rel.strength));
+                    frontier.push(Frontier { cost: next, person: rel.to_person });
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Degree, betweenness and closeness centrality in the attached projection
+    ///
+    /// Relationships are treated as undirected and unweighted. Degree and
+    /// closeness need one breadth-first search, O(V + E). Betweenness uses
+    /// Brandes' algorithm, one search per person, so O(V·E) time and O(V + E)
+    /// memory; for a sparse org chart of 10k people that is on the order of
+    /// 10^8 edge visits. A person without relationships scores zero throughout.
+    pub async fn compute_centrality(&self, person_id: PersonId) -> DomainResult<CentralityScores> {
+        let graph = IndexedGraph::undirected(&self.network_relationships().await?);
+        let Some(&target) = graph.index.get(&person_id).filter(|_| graph.len() > 1) else {
+            return Ok(CentralityScores { degree: 0.0, betweenness: 0.0, closeness: 0.0 });
+        };
+
+        let others = (graph.len() - 1) as f64;
+        let degree = graph.neighbors[target].len() as f64 / others;
+
+        // Wasserman-Faust closeness, defined on disconnected graphs too
+        let distances: Vec<usize> = graph.distances(target).into_iter().flatten().collect();
+        let reached = (distances.len() - 1) as f64;
+        let total_distance: usize = distances.iter().sum();
+        let closeness = if total_distance == 0 {
+            0.0
+        } else {
+            reached / total_distance as f64 * (reached / others)
+        };
+
+        // Each unordered pair is counted from both ends, matching (n-1)(n-2)
+        let betweenness = if graph.len() < 3 {
+            0.0
+        } else {
+            graph.pair_dependency(target) / (others * (others - 1.0))
+        };
+
+        Ok(CentralityScores {
+            degree: degree as f32,
+            betweenness: betweenness as f32,
+            closeness: closeness as f32,
+        })
+    }
+
     /// Calculate strength of a path
     fn calculate_path_strength(&self, path: &[Uuid]) -> f32 {
         if path.len() < 2 {
@@ -355,6 +748,7 @@ impl NetworkAnalysisService {
                         members: community,
                         cohesion,
                         primary_types: types,
+                        modularity: 0.0,
                     });
                 }
             }
@@ -363,6 +757,116 @@ impl NetworkAnalysisService {
         communities
     }
     
+    /// Louvain communities of the attached projection, reproducible per seed
+    ///
+    /// Relationships are treated as undirected, weighted by strength. The seed
+    /// fixes the order nodes are visited in, which is what decides ties, so the
+    /// same graph and seed always give the same partition. Community ids are
+    /// derived from the members alone: a community that survives unchanged
+    /// between two snapshots keeps its id. Results are ordered by their
+    /// smallest member id.
+    pub async fn detect_communities_seeded(&self, seed: u64) -> DomainResult<Vec<NetworkCommunity>> {
+        let relationships = self.network_relationships().await?;
+
+        let mut people: Vec<Uuid> = relationships
+            .iter()
+            .flat_map(|rel| [*rel.from_person.as_uuid(), *rel.to_person.as_uuid()])
+            .collect();
+        people.sort();
+        people.dedup();
+        let index: HashMap<Uuid, usize> = people.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+
+        let mut graph = vec![BTreeMap::new(); people.len()];
+        for rel in &relationships {
+            let from = index[rel.from_person.as_uuid()];
+            let to = index[rel.to_person.as_uuid()];
+            if from != to {
+                let weight = f64::from(rel.strength.max(0.0));
+                *graph[from].entry(to).or_insert(0.0) += weight;
+                *graph[to].entry(from).or_insert(0.0) += weight;
+            }
+        }
+
+        let membership = louvain(graph.clone(), seed);
+        let count = membership.iter().max().map_or(0, |max| max + 1);
+        let total_weight: f64 = graph.iter().flat_map(|edges| edges.values()).sum();
+
+        // Sum of A_ij within each community, and sum of degrees
+        let mut inner = vec![0.0; count];
+        let mut degree = vec![0.0; count];
+        let mut pairs = vec![0usize; count];
+        for (node, edges) in graph.iter().enumerate() {
+            let c = membership[node];
+            for (&neighbor, &weight) in edges {
+                degree[c] += weight;
+                if membership[neighbor] == c {
+                    inner[c] += weight;
+                    if node < neighbor {
+                        pairs[c] += 1;
+                    }
+                }
+            }
+        }
+
+        let mut strength = vec![(0usize, 0.0_f32); count];
+        let mut types: Vec<HashMap<String, usize>> = vec![HashMap::new(); count];
+        for rel in &relationships {
+            let c = membership[index[rel.from_person.as_uuid()]];
+            if membership[index[rel.to_person.as_uuid()]] == c {
+                strength[c].0 += 1;
+                strength[c].1 += rel.strength;
+                *types[c].entry(relationship_type_name(&rel.relationship_type)).or_insert(0) += 1;
+            }
+        }
+
+        let mut members = vec![Vec::new(); count];
+        for (node, &c) in membership.iter().enumerate() {
+            members[c].push(people[node]);
+        }
+
+        let mut communities: Vec<NetworkCommunity> = members
+            .into_iter()
+            .enumerate()
+            .map(|(c, members)| {
+                let mut hasher = blake3::Hasher::new();
+                for member in &members {
+                    hasher.update(member.as_bytes());
+                }
+                let mut id = [0u8; 16];
+                id.copy_from_slice(&hasher.finalize().as_bytes()[..16]);
+
+                let size = members.len();
+                let cohesion = if size < 2 || strength[c].0 == 0 {
+                    0.0
+                } else {
+                    let density = pairs[c] as f32 / (size * (size - 1) / 2) as f32;
+                    density * strength[c].1 / strength[c].0 as f32
+                };
+
+                let mut primary_types: Vec<(String, usize)> = types[c].drain().collect();
+                primary_types.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                primary_types.truncate(3);
+
+                let modularity = if total_weight > 0.0 {
+                    inner[c] / total_weight - (degree[c] / total_weight).powi(2)
+                } else {
+                    0.0
+                };
+
+                NetworkCommunity {
+                    id: uuid::Builder::from_random_bytes(id).into_uuid(),
+                    members: members.into_iter().collect(),
+                    cohesion,
+                    primary_types: primary_types.into_iter().map(|(name, _)| name).collect(),
+                    modularity: modularity as f32,
+                }
+            })
+            .collect();
+
+        communities.sort_by_key(|community| community.member_ids().first().map(|id| *id.as_uuid()));
+        Ok(communities)
+    }
+
     /// Explore a community starting from a person
     fn explore_community(&self, start: Uuid, visited: &mut HashSet<Uuid>) -> HashSet<Uuid> {
         let mut community = HashSet::new();
@@ -528,5 +1032,122 @@ mod tests {
         assert_eq!(path.path[0], person1);
         assert_eq!(path.path[3], person4);
         assert!(path.strength > 0.0);
+        assert_eq!(path.cost, 3.0);
+    }
+
+    fn relationship(from_person: PersonId, to_person: PersonId, strength: f32) -> PersonRelationship {
+        PersonRelationship {
+            from_person,
+            to_person,
+            relationship_type: crate::projections::RelationshipType::Colleague,
+            strength,
+            established_at: chrono::Utc::now(),
+            last_interaction: None,
+            interaction_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weighted_path_prefers_strong_ties() {
+        let network = Arc::new(PersonNetworkProjection::new());
+        let [a, b, c, d, loner] = [(); 5].map(|_| PersonId::new());
+        for rel in [
+            relationship(a, d, 0.1),
+            relationship(a, b, 0.9),
+            relationship(b, d, 0.9),
+            relationship(a, c, 0.2),
+            relationship(c, d, 0.2),
+        ] {
+            network.add_relationship(rel).await;
+        }
+        let service = NetworkAnalysisService::new().with_network(network.clone());
+        let weakness = |rel: &PersonRelationship| 1.0 - rel.strength as f64;
+
+        // Two strong hops beat one weak direct edge
+        let path = service.find_weighted_path(a, d, weakness).await.unwrap().unwrap();
+        assert_eq!(path.path, vec![*a.as_uuid(), *b.as_uuid(), *d.as_uuid()]);
+        assert!((path.cost - 0.2).abs() < 1e-6);
+        assert!((path.strength - 0.81).abs() < 1e-6);
+
+        assert!(service.find_weighted_path(a, loner, weakness).await.unwrap().is_none());
+        assert!(service.find_weighted_path(a, d, |_| -1.0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_centrality_of_star_and_line() {
+        let close = |actual: f32, expected: f32| (actual - expected).abs() < 1e-5;
+
+        // Star: a hub and four leaves
+        let network = Arc::new(PersonNetworkProjection::new());
+        let hub = PersonId::new();
+        let leaves = [(); 4].map(|_| PersonId::new());
+        for &leaf in &leaves {
+            network.add_relationship(relationship(hub, leaf, 0.5)).await;
+        }
+        let service = NetworkAnalysisService::new().with_network(network);
+
+        let scores = service.compute_centrality(hub).await.unwrap();
+        assert!(close(scores.degree, 1.0));
+        assert!(close(scores.betweenness, 1.0));
+        assert!(close(scores.closeness, 1.0));
+        let scores = service.compute_centrality(leaves[0]).await.unwrap();
+        assert!(close(scores.degree, 0.25));
+        assert!(close(scores.betweenness, 0.0));
+        assert!(close(scores.closeness, 4.0 / 7.0));
+
+        // Line: p0 - p1 - p2 - p3 - p4, with one edge recorded in reverse
+        let network = Arc::new(PersonNetworkProjection::new());
+        let line = [(); 5].map(|_| PersonId::new());
+        for pair in line.windows(2) {
+            network.add_relationship(relationship(pair[0], pair[1], 0.5)).await;
+        }
+        network.add_relationship(relationship(line[2], line[1], 0.5)).await;
+        let service = NetworkAnalysisService::new().with_network(network);
+
+        let middle = service.compute_centrality(line[2]).await.unwrap();
+        assert!(close(middle.degree, 0.5));
+        assert!(close(middle.betweenness, 4.0 / 6.0));
+        assert!(close(middle.closeness, 4.0 / 6.0));
+        let second = service.compute_centrality(line[1]).await.unwrap();
+        assert!(close(second.betweenness, 3.0 / 6.0));
+        let end = service.compute_centrality(line[0]).await.unwrap();
+        assert!(close(end.betweenness, 0.0));
+        assert!(close(end.closeness, 0.4));
+
+        let stranger = service.compute_centrality(PersonId::new()).await.unwrap();
+        assert_eq!(stranger.degree, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_seeded_communities_are_stable() {
+        // Two triangles joined by a weak bridge
+        let network = Arc::new(PersonNetworkProjection::new());
+        let left = [(); 3].map(|_| PersonId::new());
+        let right = [(); 3].map(|_| PersonId::new());
+        for group in [left, right] {
+            for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+                network.add_relationship(relationship(group[i], group[j], 1.0)).await;
+            }
+        }
+        network.add_relationship(relationship(left[0], right[0], 0.1)).await;
+        let service = NetworkAnalysisService::new().with_network(network);
+
+        let communities = service.detect_communities_seeded(7).await.unwrap();
+        assert_eq!(communities.len(), 2);
+        let mut groups: Vec<Vec<PersonId>> = communities.iter().map(|c| c.member_ids()).collect();
+        groups.sort_by_key(|g| g.contains(&left[0]));
+        assert_eq!(groups[0].iter().copied().collect::<HashSet<_>>(), right.into_iter().collect::<HashSet<_>>());
+        assert_eq!(groups[1].iter().copied().collect::<HashSet<_>>(), left.into_iter().collect::<HashSet<_>>());
+
+        // m = 6.1: each triangle contributes 6 / 12.2 - (6.1 / 12.2)^2
+        for community in &communities {
+            assert!((community.modularity - (6.0 / 12.2 - 0.25) as f32).abs() < 1e-5);
+            assert_eq!(community.primary_types, vec!["Colleague".to_string()]);
+        }
+
+        let again = service.detect_communities_seeded(7).await.unwrap();
+        let ids = |c: &[NetworkCommunity]| c.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids(&communities), ids(&again));
+        assert_eq!(ids(&communities), ids(&service.detect_communities_seeded(8).await.unwrap()));
     }
 } 
\ No newline at end of file
@@ -39,7 +39,7 @@ use std::sync::Arc;
 use cim_domain::DomainResult;
 
 use crate::aggregate::PersonId;
-use crate::commands::PersonCommand;
+use crate::commands::{PersonCommand, TagMany, UntagMany};
 use crate::handlers::{AsyncCommandProcessor, PersonCommandProcessor, CommandResult};
 use crate::queries::{
     PersonQueryService, PersonSummaryQuery, PersonSearchQuery,
@@ -120,6 +120,27 @@ impl PersonService {
             .await
     }
 
+    /// Tag every person in the batch
+    ///
+    /// Persons who already have the tag produce a result with no events.
+    /// Stops at the first person that cannot be tagged.
+    pub async fn tag_many(&self, batch: TagMany) -> DomainResult<Vec<CommandResult>> {
+        self.execute_all(batch.into_commands()).await
+    }
+
+    /// Untag every person in the batch; persons without the tag are unchanged
+    pub async fn untag_many(&self, batch: UntagMany) -> DomainResult<Vec<CommandResult>> {
+        self.execute_all(batch.into_commands()).await
+    }
+
+    async fn execute_all(&self, commands: Vec<PersonCommand>) -> DomainResult<Vec<CommandResult>> {
+        let mut results = Vec::with_capacity(commands.len());
+        for command in commands {
+            results.push(self.commands.process_command(command).await?);
+        }
+        Ok(results)
+    }
+
     // ========================================================================
     // Query Side (Reads)
     // ========================================================================
@@ -207,7 +228,9 @@ impl PersonService {
             Ok(filtered)
         } else if let Some(skill_name) = &query.skill_name {
             // Find people with this skill
-            let _person_ids = self.queries.find_people_with_skill(skill_name).await;
+            let _person_ids = self.queries
+                .find_people_with_skill_matching(skill_name, query.include_descendants)
+                .await;
 
             // For each person, get their skill summary for this specific skill
             // (This is a simplified implementation)
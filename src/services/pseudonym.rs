@@ -0,0 +1,73 @@
+//! Consumer-specific pseudonymous person ids
+//!
+//! Downstream systems that only need to recognise a returning person get a
+//! pseudonym instead of the real [`PersonId`]. The pseudonym is a keyed MAC
+//! (BLAKE3 in keyed mode) of the consumer id and the person id, so:
+//!
+//! - the same person always maps to the same pseudonym for one consumer;
+//! - two consumers see unrelated pseudonyms for the same person and cannot
+//!   join their data on it;
+//! - without the secret, nobody can map a pseudonym back to a person or
+//!   compute the pseudonym another consumer would see.
+//!
+//! Rotating the secret re-keys every consumer at once.
+
+use crate::aggregate::PersonId;
+
+/// Prefix marking an id as a pseudonym rather than a canonical person id
+pub const PSEUDONYM_PREFIX: &str = "psn_";
+
+/// Key derivation context; changing it changes every pseudonym
+const PSEUDONYM_CONTEXT: &str = "cim-domain-person 2026 consumer pseudonym v1";
+
+/// Hex digits of the MAC kept in the pseudonym (128 bits)
+const PSEUDONYM_HEX_LEN: usize = 32;
+
+/// Deterministic pseudonym of `person_id` for `consumer_id`
+///
+/// `secret` is the deployment's pseudonymization key and must not be shared
+/// with consumers.
+pub fn pseudonym_for(person_id: &PersonId, consumer_id: &str, secret: &[u8]) -> String {
+    let key = blake3::derive_key(PSEUDONYM_CONTEXT, secret);
+    let mut mac = blake3::Hasher::new_keyed(&key);
+    // Length prefix keeps consumer ids from running into the person id bytes
+    mac.update(&(consumer_id.len() as u64).to_le_bytes());
+    mac.update(consumer_id.as_bytes());
+    mac.update(person_id.as_uuid().as_bytes());
+    let digest = mac.finalize().to_hex();
+    format!("{PSEUDONYM_PREFIX}{}", &digest[..PSEUDONYM_HEX_LEN])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const SECRET: &[u8] = b"test pseudonymization secret";
+
+    #[test]
+    fn test_pseudonym_is_stable_per_consumer() {
+        let person_id = PersonId::new();
+        let first = pseudonym_for(&person_id, "billing", SECRET);
+        assert_eq!(first, pseudonym_for(&person_id, "billing", SECRET));
+        assert!(first.starts_with(PSEUDONYM_PREFIX));
+        assert_eq!(first.len(), PSEUDONYM_PREFIX.len() + PSEUDONYM_HEX_LEN);
+        assert!(!first.contains(&person_id.as_uuid().simple().to_string()));
+    }
+
+    #[test]
+    fn test_pseudonyms_do_not_correlate_across_consumers() {
+        let persons: Vec<PersonId> = (0..50).map(|_| PersonId::new()).collect();
+        let billing: HashSet<String> = persons.iter().map(|p| pseudonym_for(p, "billing", SECRET)).collect();
+        let analytics: HashSet<String> = persons.iter().map(|p| pseudonym_for(p, "analytics", SECRET)).collect();
+
+        // Distinct within a consumer, and no id shared between consumers
+        assert_eq!(billing.len(), persons.len());
+        assert_eq!(analytics.len(), persons.len());
+        assert!(billing.is_disjoint(&analytics));
+
+        // A different secret yields unrelated pseudonyms for the same consumer
+        let rotated: HashSet<String> = persons.iter().map(|p| pseudonym_for(p, "billing", b"rotated")).collect();
+        assert!(billing.is_disjoint(&rotated));
+    }
+}
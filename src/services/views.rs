@@ -2,11 +2,29 @@
 //!
 //! Provides read-only views of person identity.
 //! Cross-domain data (employment, skills, etc.) should be queried from their respective domains.
+//!
+//! [`PersonViewService::export`] serializes a person's full current state for
+//! data subject requests. Components are owned by other domains and are not
+//! part of the person aggregate, so the export covers identity, attributes,
+//! tags and the relationships known to the network projection.
+//!
+//! [`PersonViewService::employment_timeline`] reads the person's employment
+//! attributes, which organization membership events record and invalidate,
+//! as a chronological career history, and
+//! [`PersonViewService::employment_gaps`] finds the periods between them.
 
-use crate::aggregate::Person;
-use cim_domain::DomainResult;
+use std::sync::Arc;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use crate::aggregate::{Person, PersonId};
+use crate::cross_domain::person_organization::EMPLOYMENT_ATTRIBUTE_CATEGORY;
+use crate::infrastructure::PersonRepository;
+use crate::projections::{PersonNetworkProjection, PersonRelationship};
+use crate::value_objects::{AttributeType, AttributeValue, PersonAttribute, Tag};
+use cim_domain::{DomainError, DomainResult};
 use serde::{Deserialize, Serialize};
 
+use super::export::{ExportService, ExportVisibility};
+
 /// Basic person identity view
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonIdentityView {
@@ -41,8 +59,67 @@ pub enum LifecycleStatus {
     Merged,
 }
 
+/// Serialization of a person data export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// One structured JSON document
+    Json,
+    /// Flattened rows of `section,field,value,valid_from,valid_until,source`
+    Csv,
+}
+
+/// Everything exported for one person
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonDataDocument {
+    pub identity: PersonIdentityView,
+    pub attributes: Vec<PersonAttribute>,
+    pub tags: Vec<Tag>,
+    /// Relationships from and to the person
+    pub relationships: Vec<PersonRelationship>,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// One employment in a person's career history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmploymentSpan {
+    /// Organization the person worked for
+    pub employer: String,
+    pub role: String,
+    pub start: NaiveDate,
+    /// Last day of the employment, `None` while it is ongoing
+    pub end: Option<NaiveDate>,
+    /// Time from start to end, or to today while ongoing
+    pub tenure: Duration,
+    /// Whether another employment ran at the same time
+    pub overlaps: bool,
+}
+
+impl EmploymentSpan {
+    pub fn is_ongoing(&self) -> bool {
+        self.end.is_none()
+    }
+}
+
+/// Days from `start` to `end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
 /// Service for creating person views
-pub struct PersonViewService;
+#[derive(Clone)]
+pub struct PersonViewService {
+    repository: Option<Arc<PersonRepository>>,
+    network: Option<Arc<PersonNetworkProjection>>,
+    include_history: bool,
+}
 
 impl Default for PersonViewService {
     fn default() -> Self {
@@ -53,7 +130,142 @@ impl Default for PersonViewService {
 impl PersonViewService {
     /// Create a new view service
     pub fn new() -> Self {
-        Self
+        Self {
+            repository: None,
+            network: None,
+            include_history: false,
+        }
+    }
+
+    /// Load persons for [`export`](Self::export) from `repository`
+    pub fn with_repository(mut self, repository: Arc<PersonRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Include the person's relationships in exports
+    pub fn with_network(mut self, network: Arc<PersonNetworkProjection>) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Also export attributes that are no longer valid
+    pub fn include_history(mut self, include_history: bool) -> Self {
+        self.include_history = include_history;
+        self
+    }
+
+    /// Export a person's current state as `format`
+    ///
+    /// Attributes follow the subject-access rules of [`ExportService`].
+    /// Attributes whose validity has ended, as invalidation leaves them, are
+    /// left out unless history is included.
+    pub async fn export(&self, person_id: PersonId, format: ExportFormat) -> DomainResult<Vec<u8>> {
+        let person = self.load(person_id, "Person export").await?;
+
+        let export = ExportService::new().export_person(&person, ExportVisibility::SubjectAccess)?;
+        let today = Utc::now().date_naive();
+        let attributes = export.attributes.into_iter()
+            .filter(|attr| self.include_history || attr.temporal.valid_until.map_or(true, |end| end > today))
+            .collect();
+        let relationships = match &self.network {
+            Some(network) => {
+                let mut relationships = network.get_connections(&person_id).await;
+                relationships.extend(network.get_incoming_connections(&person_id).await);
+                relationships
+            }
+            None => Vec::new(),
+        };
+
+        let document = PersonDataDocument {
+            identity: export.identity,
+            attributes,
+            tags: person.tags.iter().cloned().collect(),
+            relationships,
+            exported_at: export.exported_at,
+        };
+        match format {
+            ExportFormat::Json => serde_json::to_vec_pretty(&document)
+                .map_err(|e| DomainError::SerializationError(e.to_string())),
+            ExportFormat::Csv => Ok(to_csv(&document).into_bytes()),
+        }
+    }
+
+    /// A person's employments, oldest first
+    pub async fn employment_timeline(&self, person_id: PersonId) -> DomainResult<Vec<EmploymentSpan>> {
+        let person = self.load(person_id, "Employment timeline").await?;
+        Ok(Self::employment_spans(&person, Utc::now().date_naive()))
+    }
+
+    /// Employment spans from a person's employment attributes, as of `today`
+    ///
+    /// An employment starts on its attribute's `valid_from`, or the day it was
+    /// recorded, and ends on its `valid_until`. Spans are sorted by start date,
+    /// ongoing ones last among equal starts. Two employments overlap when one
+    /// starts before the other ends; a new job starting on the day the last
+    /// one ended is a handover, not an overlap.
+    pub fn employment_spans(person: &Person, today: NaiveDate) -> Vec<EmploymentSpan> {
+        let mut spans: Vec<EmploymentSpan> = person.attributes.attributes.iter()
+            .filter_map(|attr| {
+                let AttributeType::Custom(custom) = &attr.attribute_type else {
+                    return None;
+                };
+                if custom.category != EMPLOYMENT_ATTRIBUTE_CATEGORY {
+                    return None;
+                }
+                let start = attr.temporal.valid_from.unwrap_or_else(|| attr.temporal.recorded_at.date_naive());
+                let end = attr.temporal.valid_until;
+                let role = match &attr.value {
+                    AttributeValue::Text(role) => role.clone(),
+                    other => plain(other),
+                };
+                Some(EmploymentSpan {
+                    employer: custom.organization.clone(),
+                    role,
+                    start,
+                    end,
+                    tenure: (end.unwrap_or(today) - start).max(Duration::zero()),
+                    overlaps: false,
+                })
+            })
+            .collect();
+        spans.sort_by(|a, b| {
+            a.start.cmp(&b.start)
+                .then_with(|| a.end.unwrap_or(NaiveDate::MAX).cmp(&b.end.unwrap_or(NaiveDate::MAX)))
+                .then_with(|| a.employer.cmp(&b.employer))
+        });
+
+        for i in 0..spans.len() {
+            let end = spans[i].end.unwrap_or(NaiveDate::MAX);
+            // Later spans start no earlier, so each overlapping pair is found once
+            for j in i + 1..spans.len() {
+                if spans[j].start >= end {
+                    break;
+                }
+                spans[i].overlaps = true;
+                spans[j].overlaps = true;
+            }
+        }
+        spans
+    }
+
+    /// Periods longer than `min_gap` in which the person had no employment
+    ///
+    /// A gap runs from the day the last employment ended to the day the next
+    /// one started. Time before the first employment is not a gap. A person
+    /// who is not currently employed has a trailing gap ending today; one who
+    /// is has none.
+    pub async fn employment_gaps(&self, person_id: PersonId, min_gap: Duration) -> DomainResult<Vec<DateRange>> {
+        let today = Utc::now().date_naive();
+        let person = self.load(person_id, "Employment gaps").await?;
+        Ok(gaps_between(&Self::employment_spans(&person, today), min_gap, today))
+    }
+
+    async fn load(&self, person_id: PersonId, purpose: &str) -> DomainResult<Person> {
+        let repository = self.repository.as_ref()
+            .ok_or_else(|| DomainError::generic(format!("{purpose} needs a repository")))?;
+        repository.load(person_id).await?
+            .ok_or_else(|| DomainError::AggregateNotFound(format!("Person {person_id}")))
     }
 
     /// Create an identity view from a person
@@ -87,11 +299,146 @@ impl PersonViewService {
     }
 }
 
+/// Gaps between spans sorted by start date
+fn gaps_between(spans: &[EmploymentSpan], min_gap: Duration, today: NaiveDate) -> Vec<DateRange> {
+    let mut gaps = Vec::new();
+    // End of the time covered so far, `None` once an ongoing span is reached
+    let mut covered_until = match spans.first() {
+        Some(first) => first.end,
+        None => return gaps,
+    };
+    for span in &spans[1..] {
+        let Some(until) = covered_until else {
+            break;
+        };
+        let gap = DateRange { start: until, end: span.start };
+        if gap.duration() > min_gap {
+            gaps.push(gap);
+        }
+        covered_until = span.end.map(|end| end.max(until));
+    }
+    if let Some(until) = covered_until {
+        let trailing = DateRange { start: until, end: today };
+        if trailing.duration() > min_gap {
+            gaps.push(trailing);
+        }
+    }
+    gaps
+}
+
+/// Plain text of a value: strings as-is, anything else as compact JSON
+fn plain<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => text,
+        Ok(serde_json::Value::Null) => String::new(),
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn to_csv(document: &PersonDataDocument) -> String {
+    let date = |date: Option<NaiveDate>| date.map(|d| d.to_string()).unwrap_or_default();
+    let identity = &document.identity;
+    let mut rows: Vec<[String; 6]> = vec![
+        ["section", "field", "value", "valid_from", "valid_until", "source"].map(String::from),
+    ];
+    let mut identity_row = |field: &str, value: String| {
+        rows.push(["identity".to_string(), field.to_string(), value, String::new(), String::new(), String::new()]);
+    };
+    identity_row("person_id", identity.person_id.to_string());
+    identity_row("given_name", identity.given_name.clone());
+    identity_row("family_name", identity.family_name.clone());
+    identity_row("display_name", identity.display_name.clone());
+    identity_row("birth_date", date(identity.birth_date));
+    identity_row("date_of_death", date(identity.date_of_death));
+    identity_row("status", plain(&identity.status));
+
+    for attr in &document.attributes {
+        rows.push([
+            "attribute".to_string(),
+            plain(&attr.attribute_type),
+            plain(&attr.value),
+            date(attr.temporal.valid_from),
+            date(attr.temporal.valid_until),
+            plain(&attr.provenance.source),
+        ]);
+    }
+    for tag in &document.tags {
+        rows.push(["tag".to_string(), tag.category.clone(), tag.name.clone(), String::new(), String::new(), String::new()]);
+    }
+    for relationship in &document.relationships {
+        rows.push([
+            "relationship".to_string(),
+            plain(&relationship.relationship_type),
+            format!("{} -> {}", relationship.from_person, relationship.to_person),
+            relationship.established_at.date_naive().to_string(),
+            String::new(),
+            String::new(),
+        ]);
+    }
+
+    rows.iter()
+        .map(|row| row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","))
+        .map(|line| line + "\n")
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::value_objects::PersonName;
     use crate::aggregate::PersonId;
+    use crate::events::{AttributeRecorded, PersonCreated, PersonEvent};
+    use crate::infrastructure::{EventStore, InMemoryEventStore, InMemorySnapshotStore};
+    use crate::value_objects::{
+        AttributeSource, ConfidenceLevel, CustomAttributeType, Provenance, TemporalValidity,
+    };
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn employment(employer: &str, role: &str, start: NaiveDate, end: Option<NaiveDate>) -> PersonAttribute {
+        PersonAttribute::new(
+            AttributeType::Custom(CustomAttributeType {
+                organization: employer.to_string(),
+                attribute_name: "role".to_string(),
+                category: EMPLOYMENT_ATTRIBUTE_CATEGORY.to_string(),
+            }),
+            AttributeValue::Text(role.to_string()),
+            TemporalValidity::new(Utc::now(), Some(start), end),
+            Provenance::new(
+                AttributeSource::Imported { system: "identity".to_string() },
+                ConfidenceLevel::Certain,
+            ),
+        )
+    }
+
+    /// A view service over one stored person holding `employments`
+    async fn service_with_employments(employments: Vec<PersonAttribute>) -> (PersonViewService, PersonId) {
+        let person_id = PersonId::new();
+        let mut events = vec![PersonEvent::PersonCreated(PersonCreated {
+            person_id,
+            name: PersonName::new("Grace".to_string(), "Hopper".to_string()),
+            source: "test".to_string(),
+            created_at: Utc::now(),
+        })];
+        events.extend(employments.into_iter().map(|attribute| {
+            PersonEvent::AttributeRecorded(AttributeRecorded { person_id, attribute, recorded_at: Utc::now() })
+        }));
+        let store = Arc::new(InMemoryEventStore::new());
+        store.append_events(person_id, events, None).await.unwrap();
+        let repository = Arc::new(PersonRepository::new(store, Arc::new(InMemorySnapshotStore::new()), 100));
+        (PersonViewService::new().with_repository(repository), person_id)
+    }
 
     #[test]
     fn test_create_identity_view() {
@@ -107,4 +454,136 @@ mod tests {
         assert_eq!(view.display_name, "John");
         assert_eq!(view.status, LifecycleStatus::Active);
     }
+
+    #[tokio::test]
+    async fn test_export_leaves_out_invalidated_attributes_unless_history_is_asked_for() {
+        use cim_domain::formal_domain::Aggregate;
+        use crate::commands::{AddTag, CreatePerson, InvalidateAttribute, PersonCommand, RecordAttribute};
+        use crate::value_objects::DemographicAttributeType;
+
+        let person_id = PersonId::new();
+        let attribute = |kind, value: &str| PersonAttribute::new(
+            AttributeType::Demographic(kind),
+            AttributeValue::Text(value.to_string()),
+            TemporalValidity::of(chrono::Utc::now()),
+            Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+        );
+        let commands = vec![
+            PersonCommand::CreatePerson(CreatePerson {
+                person_id,
+                name: PersonName::new("Ada".to_string(), "Lovelace, Countess".to_string()),
+                source: "test".to_string(),
+            }),
+            PersonCommand::RecordAttribute(RecordAttribute {
+                person_id,
+                attribute: attribute(DemographicAttributeType::PreferredLanguage, "en"),
+            }),
+            PersonCommand::RecordAttribute(RecordAttribute {
+                person_id,
+                attribute: attribute(DemographicAttributeType::Nationality, "British"),
+            }),
+            PersonCommand::InvalidateAttribute(InvalidateAttribute {
+                person_id,
+                attribute_type: AttributeType::Demographic(DemographicAttributeType::Nationality),
+                reason: Some("entered in error".to_string()),
+                attribute_recorded_at: None,
+            }),
+            PersonCommand::AddTag(AddTag { person_id, tag: Tag::new("segment", "vip").unwrap() }),
+        ];
+        let mut person = Person::empty();
+        let mut events = Vec::new();
+        for command in commands {
+            let (next, emitted) = person.handle(command).unwrap();
+            person = next;
+            events.extend(emitted);
+        }
+        let store = Arc::new(InMemoryEventStore::new());
+        store.append_events(person_id, events, None).await.unwrap();
+        let repository = Arc::new(PersonRepository::new(store, Arc::new(InMemorySnapshotStore::new()), 100));
+        let service = PersonViewService::new().with_repository(repository);
+
+        let json = service.export(person_id, ExportFormat::Json).await.unwrap();
+        let current: PersonDataDocument = serde_json::from_slice(&json).unwrap();
+        assert_eq!(current.attributes.len(), 1);
+        assert_eq!(current.attributes[0].value, AttributeValue::Text("en".to_string()));
+        assert_eq!(current.tags, vec![Tag::new("segment", "vip").unwrap()]);
+        assert!(current.relationships.is_empty());
+
+        let with_history = service.clone().include_history(true).export(person_id, ExportFormat::Json).await.unwrap();
+        let history: PersonDataDocument = serde_json::from_slice(&with_history).unwrap();
+        assert_eq!(history.attributes.len(), 2);
+
+        let csv = String::from_utf8(service.export(person_id, ExportFormat::Csv).await.unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "section,field,value,valid_from,valid_until,source");
+        assert!(lines.contains(&"identity,given_name,Ada,,,"));
+        assert!(lines.contains(&"identity,family_name,\"Lovelace, Countess\",,,"));
+        assert!(lines.contains(&"tag,segment,vip,,,"));
+        assert_eq!(lines.iter().filter(|line| line.starts_with("attribute,")).count(), 1);
+
+        let missing = service.export(PersonId::new(), ExportFormat::Json).await;
+        assert!(matches!(missing, Err(DomainError::AggregateNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_employment_timeline_flags_concurrent_employments() {
+        let (service, person_id) = service_with_employments(vec![
+            employment("initech", "Consultant", date(2020, 3, 1), None),
+            employment("acme", "Engineer", date(2015, 1, 1), Some(date(2018, 1, 1))),
+            employment("globex", "Lead", date(2018, 1, 1), Some(date(2020, 6, 30))),
+        ]).await;
+
+        let timeline = service.employment_timeline(person_id).await.unwrap();
+
+        let employers: Vec<&str> = timeline.iter().map(|span| span.employer.as_str()).collect();
+        assert_eq!(employers, vec!["acme", "globex", "initech"]);
+        assert_eq!(timeline[0].role, "Engineer");
+        assert_eq!(timeline[0].tenure, Duration::days(1096));
+        // Globex starting the day Acme ended is a handover
+        assert!(!timeline[0].overlaps);
+        // Initech began while Globex was still running
+        assert!(timeline[1].overlaps);
+        assert!(timeline[2].overlaps);
+        assert!(timeline[2].is_ongoing());
+        assert!(timeline[2].tenure > Duration::days(365));
+
+        let missing = service.employment_timeline(PersonId::new()).await;
+        assert!(matches!(missing, Err(DomainError::AggregateNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_no_employment_gaps_in_continuous_history() {
+        let (service, person_id) = service_with_employments(vec![
+            employment("acme", "Engineer", date(2015, 1, 1), Some(date(2018, 1, 1))),
+            employment("globex", "Lead", date(2018, 1, 1), Some(date(2020, 6, 30))),
+            employment("initech", "Consultant", date(2020, 3, 1), None),
+        ]).await;
+
+        let gaps = service.employment_gaps(person_id, Duration::zero()).await.unwrap();
+        assert!(gaps.is_empty(), "unexpected gaps {gaps:?}");
+    }
+
+    #[tokio::test]
+    async fn test_six_month_employment_gap() {
+        let (service, person_id) = service_with_employments(vec![
+            employment("acme", "Engineer", date(2015, 1, 1), Some(date(2019, 1, 1))),
+            // Ends inside the Acme employment, so it does not shorten the gap
+            employment("freelance", "Writer", date(2016, 1, 1), Some(date(2017, 1, 1))),
+            employment("globex", "Lead", date(2019, 7, 1), None),
+        ]).await;
+
+        let gaps = service.employment_gaps(person_id, Duration::days(30)).await.unwrap();
+        assert_eq!(gaps, vec![DateRange { start: date(2019, 1, 1), end: date(2019, 7, 1) }]);
+        assert_eq!(gaps[0].duration(), Duration::days(181));
+
+        let gaps = service.employment_gaps(person_id, Duration::days(365)).await.unwrap();
+        assert!(gaps.is_empty());
+
+        // Without a current employment the gap runs until today
+        let (service, person_id) = service_with_employments(vec![
+            employment("acme", "Engineer", date(2015, 1, 1), Some(date(2019, 1, 1))),
+        ]).await;
+        let gaps = service.employment_gaps(person_id, Duration::days(30)).await.unwrap();
+        assert_eq!(gaps, vec![DateRange { start: date(2019, 1, 1), end: Utc::now().date_naive() }]);
+    }
 }
@@ -0,0 +1,196 @@
+//! Strict email address parsing
+//!
+//! [`EmailAddress::new`] only looks for an `@` and a `.`. [`EmailAddress::parse_strict`]
+//! checks the address against the RFC 5321 rules that matter for delivery:
+//! length limits, dot-atom local parts and hostname-style domain labels.
+//! Quoted local parts and IP address literals are valid in the RFC but
+//! rejected here, as mail systems rarely accept them.
+
+use super::EmailAddress;
+
+/// Longest address that fits a SMTP forward path
+const MAX_ADDRESS_LEN: usize = 254;
+const MAX_LOCAL_LEN: usize = 64;
+const MAX_DOMAIN_LEN: usize = 253;
+const MAX_LABEL_LEN: usize = 63;
+
+/// Why an email address failed strict parsing
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EmailError {
+    #[error("Email address is empty")]
+    Empty,
+
+    #[error("Email address has no '@'")]
+    MissingAt,
+
+    #[error("Email address is {len} characters, at most 254 allowed")]
+    AddressTooLong { len: usize },
+
+    #[error("Email local part is empty")]
+    EmptyLocalPart,
+
+    #[error("Email local part is {len} characters, at most 64 allowed")]
+    LocalPartTooLong { len: usize },
+
+    #[error("Email local part contains invalid character {0:?}")]
+    InvalidLocalCharacter(char),
+
+    #[error("Email {part} starts or ends with a dot")]
+    LeadingOrTrailingDot { part: &'static str },
+
+    #[error("Email {part} contains consecutive dots")]
+    ConsecutiveDots { part: &'static str },
+
+    #[error("Email domain is {len} characters, at most 253 allowed")]
+    DomainTooLong { len: usize },
+
+    #[error("Email domain needs a top-level domain: {0}")]
+    MissingTopLevelDomain(String),
+
+    #[error("Invalid email domain label: {0}")]
+    InvalidDomainLabel(String),
+}
+
+impl EmailAddress {
+    /// Parse an unverified email address, rejecting malformed ones
+    pub fn parse_strict(address: &str) -> Result<Self, EmailError> {
+        let address = address.trim();
+        if address.is_empty() {
+            return Err(EmailError::Empty);
+        }
+        let (local, domain) = address.rsplit_once('@').ok_or(EmailError::MissingAt)?;
+        if address.len() > MAX_ADDRESS_LEN {
+            return Err(EmailError::AddressTooLong { len: address.len() });
+        }
+        validate_local_part(local)?;
+        validate_domain(domain)?;
+
+        Ok(Self {
+            address: address.to_string(),
+            verified: false,
+        })
+    }
+
+    /// The part after the last `@`, if there is one
+    pub fn domain(&self) -> Option<&str> {
+        self.address.rsplit_once('@').map(|(_, domain)| domain)
+    }
+
+    /// The same address with its domain lowercased
+    ///
+    /// Domains are case-insensitive; the local part is left alone because
+    /// the receiving server decides whether it is.
+    pub fn normalize(&self) -> Self {
+        let address = match self.address.rsplit_once('@') {
+            Some((local, domain)) => format!("{local}@{}", domain.to_ascii_lowercase()),
+            None => self.address.clone(),
+        };
+        Self { address, verified: self.verified }
+    }
+}
+
+fn check_dots(part: &'static str, text: &str) -> Result<(), EmailError> {
+    if text.starts_with('.') || text.ends_with('.') {
+        return Err(EmailError::LeadingOrTrailingDot { part });
+    }
+    if text.contains("..") {
+        return Err(EmailError::ConsecutiveDots { part });
+    }
+    Ok(())
+}
+
+fn validate_local_part(local: &str) -> Result<(), EmailError> {
+    if local.is_empty() {
+        return Err(EmailError::EmptyLocalPart);
+    }
+    if local.len() > MAX_LOCAL_LEN {
+        return Err(EmailError::LocalPartTooLong { len: local.len() });
+    }
+    if let Some(invalid) = local.chars().find(|c| !c.is_ascii_alphanumeric() && !".!#$%&'*+-/=?^_`{|}~".contains(*c)) {
+        return Err(EmailError::InvalidLocalCharacter(invalid));
+    }
+    check_dots("local part", local)
+}
+
+fn validate_domain(domain: &str) -> Result<(), EmailError> {
+    if domain.len() > MAX_DOMAIN_LEN {
+        return Err(EmailError::DomainTooLong { len: domain.len() });
+    }
+    check_dots("domain", domain)?;
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return Err(EmailError::MissingTopLevelDomain(domain.to_string()));
+    }
+    for label in &labels {
+        let valid = !label.is_empty()
+            && label.len() <= MAX_LABEL_LEN
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !label.starts_with('-')
+            && !label.ends_with('-');
+        if !valid {
+            return Err(EmailError::InvalidDomainLabel(label.to_string()));
+        }
+    }
+    if labels.last().is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit())) {
+        return Err(EmailError::InvalidDomainLabel(labels[labels.len() - 1].to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_well_formed_addresses_parse() {
+        for address in ["ada@example.com", "ada.lovelace+notes@mail.example.co.uk", "o'brien@ex-ample.org"] {
+            let email = EmailAddress::parse_strict(address).unwrap();
+            assert_eq!(email.address, address);
+            assert!(!email.verified);
+        }
+        assert_eq!(EmailAddress::parse_strict("  ada@example.com ").unwrap().address, "ada@example.com");
+    }
+
+    #[test]
+    fn test_malformed_addresses_report_why() {
+        assert_eq!(EmailAddress::parse_strict(""), Err(EmailError::Empty));
+        assert_eq!(EmailAddress::parse_strict("ada.example.com"), Err(EmailError::MissingAt));
+        assert_eq!(EmailAddress::parse_strict("@example.com"), Err(EmailError::EmptyLocalPart));
+        assert_eq!(
+            EmailAddress::parse_strict("ada..lovelace@example.com"),
+            Err(EmailError::ConsecutiveDots { part: "local part" }),
+        );
+        assert_eq!(
+            EmailAddress::parse_strict(".ada@example.com"),
+            Err(EmailError::LeadingOrTrailingDot { part: "local part" }),
+        );
+        assert_eq!(
+            EmailAddress::parse_strict("ada@example..com"),
+            Err(EmailError::ConsecutiveDots { part: "domain" }),
+        );
+        assert_eq!(EmailAddress::parse_strict("ada lovelace@example.com"), Err(EmailError::InvalidLocalCharacter(' ')));
+        assert!(matches!(EmailAddress::parse_strict("ada@localhost"), Err(EmailError::MissingTopLevelDomain(_))));
+        assert!(matches!(EmailAddress::parse_strict("ada@-example.com"), Err(EmailError::InvalidDomainLabel(_))));
+        assert!(matches!(EmailAddress::parse_strict("ada@exa_mple.com"), Err(EmailError::InvalidDomainLabel(_))));
+        assert!(matches!(EmailAddress::parse_strict("ada@10.0.0.1"), Err(EmailError::InvalidDomainLabel(_))));
+
+        let long_local = format!("{}@example.com", "a".repeat(65));
+        assert_eq!(EmailAddress::parse_strict(&long_local), Err(EmailError::LocalPartTooLong { len: 65 }));
+        let long_label = format!("ada@{}.com", "a".repeat(64));
+        assert!(matches!(EmailAddress::parse_strict(&long_label), Err(EmailError::InvalidDomainLabel(_))));
+
+        // The lenient constructor still accepts it
+        assert!(EmailAddress::new("ada..lovelace@example.com".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_lowercases_only_the_domain() {
+        let email = EmailAddress::parse_strict("Ada.Lovelace@Example.COM").unwrap();
+        assert_eq!(email.domain(), Some("Example.COM"));
+
+        let normalized = email.normalize();
+        assert_eq!(normalized.address, "Ada.Lovelace@example.com");
+        assert_eq!(normalized.domain(), Some("example.com"));
+        assert_eq!(EmailAddress::verified("no-domain".to_string()).domain(), None);
+    }
+}
@@ -33,16 +33,29 @@ pub use person_name::{
 
 pub mod person_attribute;
 pub use person_attribute::{
-    PersonAttribute, PersonAttributeSet, AttributeType, AttributeValue,
+    PersonAttribute, PersonAttributeSet, AttributeType, AttributeValue, AttributeValueRange,
     IdentifyingAttributeType, PhysicalAttributeType, HealthcareAttributeType,
     DemographicAttributeType, CustomAttributeType, TemporalValidity,
     Provenance, AttributeSource, ConfidenceLevel, TransformationTrace,
     DatePrecision, BloodTypeValue, EyeColorValue, HairColorValue,
-    BiologicalSexValue, HandednessValue,
+    BiologicalSexValue, HandednessValue, NotProvidedReason, AttributeStatus,
+    DateZone, age_on, DocumentReference,
+};
+
+pub mod national_id;
+pub(crate) mod json_in_binary;
+pub use national_id::{
+    NationalIdValidator, NationalIdScheme, NationalIdCheck, NationalIdError,
+    InvalidNationalIdPolicy,
 };
 
 // ===== Contact Information =====
 
+pub mod email_address;
+pub mod phone_number;
+pub use email_address::EmailError;
+pub use phone_number::PhoneError;
+
 /// Email address with verification status
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EmailAddress {
@@ -52,6 +65,8 @@ pub struct EmailAddress {
 
 impl EmailAddress {
     /// Create a new unverified email
+    ///
+    /// Only checks for an `@` and a `.`; use [`EmailAddress::parse_strict`] to validate.
     pub fn new(address: String) -> Result<Self, String> {
         // Simple validation
         if address.contains('@') && address.contains('.') {
@@ -95,6 +110,8 @@ pub struct PhoneNumber {
 
 impl PhoneNumber {
     /// Create a simple phone number
+    ///
+    /// Only checks for digits; use [`PhoneNumber::parse_e164`] for strict parsing.
     pub fn new(number: String) -> Result<Self, String> {
         // Simple validation - check if it has digits
         if number.chars().any(|c| c.is_numeric()) {
@@ -163,6 +180,107 @@ pub enum EmploymentType {
     Other,
 }
 
+// ===== Life Events =====
+
+/// Kind of life event annotated on a person's timeline
+///
+/// Life events are not otherwise modeled in the Person domain; they are
+/// recorded purely so the timeline can tell a person's story.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LifeEventKind {
+    Graduated,
+    Married,
+    Divorced,
+    Relocated,
+    Retired,
+    Other(String),
+}
+
+impl fmt::Display for LifeEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifeEventKind::Graduated => write!(f, "graduated"),
+            LifeEventKind::Married => write!(f, "married"),
+            LifeEventKind::Divorced => write!(f, "divorced"),
+            LifeEventKind::Relocated => write!(f, "relocated"),
+            LifeEventKind::Retired => write!(f, "retired"),
+            LifeEventKind::Other(kind) => write!(f, "{kind}"),
+        }
+    }
+}
+
+// ===== Consent =====
+
+/// Purpose a person can grant or revoke consent for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ConsentType {
+    DataProcessing,
+    Marketing,
+    DataSharing,
+    Research,
+    Other(String),
+}
+
+impl fmt::Display for ConsentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsentType::DataProcessing => write!(f, "data_processing"),
+            ConsentType::Marketing => write!(f, "marketing"),
+            ConsentType::DataSharing => write!(f, "data_sharing"),
+            ConsentType::Research => write!(f, "research"),
+            ConsentType::Other(kind) => write!(f, "{kind}"),
+        }
+    }
+}
+
+/// Whether consent was given or withdrawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ConsentStatus {
+    Granted,
+    Revoked,
+}
+
+// ===== Tags =====
+
+/// A label applied to persons, namespaced by the category that owns it
+///
+/// Teams pick their own tag names; the category keeps "sales:vip" and
+/// "support:vip" apart. Both parts are trimmed and lowercased.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Tag {
+    pub category: String,
+    pub name: String,
+}
+
+impl Tag {
+    /// Create a tag, rejecting empty parts and a ':' in the category
+    pub fn new(category: &str, name: &str) -> Result<Self, String> {
+        let category = category.trim().to_lowercase();
+        let name = name.trim().to_lowercase();
+        if category.is_empty() || name.is_empty() {
+            return Err("Tag category and name must not be empty".to_string());
+        }
+        if category.contains(':') {
+            return Err(format!("Tag category '{category}' must not contain ':'"));
+        }
+        Ok(Self { category, name })
+    }
+
+    /// Parse the "category:name" form produced by `Display`
+    pub fn parse(tag: &str) -> Result<Self, String> {
+        let (category, name) = tag
+            .split_once(':')
+            .ok_or_else(|| format!("Tag '{tag}' is not of the form category:name"))?;
+        Self::new(category, name)
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.category, self.name)
+    }
+}
+
 // ===== Skills & Qualifications (Now managed as components) =====
 
 /// Proficiency levels (used by skill components)
@@ -0,0 +1,313 @@
+//! National identification number validation
+//!
+//! Checks national IDs against the format and checksum rules of the issuing
+//! country before they are stored as a `NationalId` attribute. Passing
+//! validation only means the number is well-formed, not that it was issued.
+//!
+//! A `NationalId` attribute value is either `Text` (interpreted with the
+//! validator's default country) or `Json` of the form
+//! `{"country": "NL", "number": "111222333"}`.
+
+use super::{
+    AttributeType, AttributeValue, ConfidenceLevel, IdentifyingAttributeType, PersonAttribute,
+};
+use cim_domain::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Why a national ID failed validation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NationalIdError {
+    #[error("National ID has an invalid format: {0}")]
+    InvalidFormat(String),
+
+    #[error("National ID failed its checksum")]
+    ChecksumMismatch,
+
+    #[error("National ID uses a reserved or never-issued range: {0}")]
+    ReservedRange(String),
+}
+
+/// Format and checksum rules for one country's national ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NationalIdScheme {
+    /// US Social Security Number (`AAA-GG-SSSS`)
+    UsSocialSecurityNumber,
+    /// Dutch Burgerservicenummer (9 digits, "11-proof")
+    DutchBsn,
+    /// Swedish personnummer (`YYMMDD-NNNC`, Luhn check digit)
+    SwedishPersonnummer,
+}
+
+impl NationalIdScheme {
+    pub fn validate(&self, id: &str) -> Result<(), NationalIdError> {
+        match self {
+            NationalIdScheme::UsSocialSecurityNumber => validate_us_ssn(id),
+            NationalIdScheme::DutchBsn => validate_dutch_bsn(id),
+            NationalIdScheme::SwedishPersonnummer => validate_swedish_personnummer(id),
+        }
+    }
+}
+
+/// What to do with a national ID that fails validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InvalidNationalIdPolicy {
+    /// Record it with `Uncertain` confidence
+    #[default]
+    LowerConfidence,
+    /// Reject the attribute
+    Reject,
+}
+
+/// Result of checking a national ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NationalIdCheck {
+    Valid,
+    Invalid(NationalIdError),
+    /// No rule is configured for the country (or no country is known)
+    Unchecked,
+}
+
+/// Validates national IDs with per-country rules
+#[derive(Debug, Clone)]
+pub struct NationalIdValidator {
+    /// Rules keyed by ISO 3166-1 alpha-2 country code
+    schemes: HashMap<String, NationalIdScheme>,
+    default_country: Option<String>,
+    policy: InvalidNationalIdPolicy,
+}
+
+impl Default for NationalIdValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NationalIdValidator {
+    /// Validator with the built-in rules for US, NL and SE
+    pub fn new() -> Self {
+        let schemes = [
+            ("US", NationalIdScheme::UsSocialSecurityNumber),
+            ("NL", NationalIdScheme::DutchBsn),
+            ("SE", NationalIdScheme::SwedishPersonnummer),
+        ]
+        .into_iter()
+        .map(|(country, scheme)| (country.to_string(), scheme))
+        .collect();
+
+        Self {
+            schemes,
+            default_country: None,
+            policy: InvalidNationalIdPolicy::default(),
+        }
+    }
+
+    /// Country assumed for national IDs recorded as plain text
+    pub fn with_default_country(mut self, country: impl Into<String>) -> Self {
+        self.default_country = Some(country.into().to_uppercase());
+        self
+    }
+
+    pub fn with_policy(mut self, policy: InvalidNationalIdPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Use `scheme` for `country`, replacing any existing rule
+    pub fn with_scheme(mut self, country: impl Into<String>, scheme: NationalIdScheme) -> Self {
+        self.schemes.insert(country.into().to_uppercase(), scheme);
+        self
+    }
+
+    /// Check a national ID issued by `country`
+    pub fn check(&self, country: &str, id: &str) -> NationalIdCheck {
+        match self.schemes.get(&country.to_uppercase()) {
+            Some(scheme) => match scheme.validate(id) {
+                Ok(()) => NationalIdCheck::Valid,
+                Err(error) => NationalIdCheck::Invalid(error),
+            },
+            None => NationalIdCheck::Unchecked,
+        }
+    }
+
+    /// Apply validation to an attribute about to be recorded
+    ///
+    /// Attributes other than `NationalId` pass through unchanged. Invalid
+    /// national IDs are rejected or downgraded to `Uncertain` confidence
+    /// according to the configured policy.
+    pub fn screen_attribute(&self, attribute: PersonAttribute) -> DomainResult<PersonAttribute> {
+        if attribute.attribute_type != AttributeType::Identifying(IdentifyingAttributeType::NationalId) {
+            return Ok(attribute);
+        }
+
+        let check = match self.country_and_number(&attribute.value) {
+            Some((country, number)) => self.check(&country, &number),
+            None => NationalIdCheck::Unchecked,
+        };
+
+        match (check, self.policy) {
+            (NationalIdCheck::Invalid(error), InvalidNationalIdPolicy::Reject) => {
+                Err(DomainError::ValidationError(error.to_string()))
+            }
+            (NationalIdCheck::Invalid(error), InvalidNationalIdPolicy::LowerConfidence) => {
+                let mut attribute = attribute;
+                attribute.provenance.confidence = ConfidenceLevel::Uncertain;
+                attribute.provenance = attribute.provenance.trace_transformation(
+                    format!("national_id_validation_failed: {error}"),
+                    "NationalIdValidator".to_string(),
+                );
+                Ok(attribute)
+            }
+            _ => Ok(attribute),
+        }
+    }
+
+    fn country_and_number(&self, value: &AttributeValue) -> Option<(String, String)> {
+        match value {
+            AttributeValue::Text(number) => {
+                Some((self.default_country.clone()?, number.clone()))
+            }
+            AttributeValue::Json(json) => {
+                let country = json.get("country")?.as_str()?;
+                let number = json.get("number")?.as_str()?;
+                Some((country.to_string(), number.to_string()))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn digits(s: &str) -> Option<Vec<u32>> {
+    s.chars().map(|c| c.to_digit(10)).collect()
+}
+
+fn validate_us_ssn(id: &str) -> Result<(), NationalIdError> {
+    let compact = match id.split('-').collect::<Vec<_>>().as_slice() {
+        [area, group, serial] if area.len() == 3 && group.len() == 2 && serial.len() == 4 => {
+            format!("{area}{group}{serial}")
+        }
+        [compact] if compact.len() == 9 => compact.to_string(),
+        _ => return Err(NationalIdError::InvalidFormat("expected AAA-GG-SSSS".to_string())),
+    };
+    if digits(&compact).is_none() {
+        return Err(NationalIdError::InvalidFormat("expected digits only".to_string()));
+    }
+
+    let (area, group, serial) = (&compact[0..3], &compact[3..5], &compact[5..9]);
+    if area == "000" || area == "666" || area.starts_with('9') {
+        return Err(NationalIdError::ReservedRange(format!("area number {area}")));
+    }
+    if group == "00" {
+        return Err(NationalIdError::ReservedRange("group number 00".to_string()));
+    }
+    if serial == "0000" {
+        return Err(NationalIdError::ReservedRange("serial number 0000".to_string()));
+    }
+    Ok(())
+}
+
+fn validate_dutch_bsn(id: &str) -> Result<(), NationalIdError> {
+    let digits = digits(id)
+        .filter(|digits| digits.len() == 9)
+        .ok_or_else(|| NationalIdError::InvalidFormat("expected 9 digits".to_string()))?;
+
+    // Weights 9..2 for the first eight digits, -1 for the last
+    let sum: i64 = digits[..8].iter()
+        .zip((2..=9).rev())
+        .map(|(digit, weight)| i64::from(*digit) * weight)
+        .sum::<i64>()
+        - i64::from(digits[8]);
+
+    if sum == 0 || sum % 11 != 0 {
+        return Err(NationalIdError::ChecksumMismatch);
+    }
+    Ok(())
+}
+
+fn validate_swedish_personnummer(id: &str) -> Result<(), NationalIdError> {
+    let compact: String = id.chars().filter(|c| *c != '-' && *c != '+').collect();
+    let digits = digits(&compact)
+        .filter(|digits| digits.len() == 10 || digits.len() == 12)
+        .ok_or_else(|| NationalIdError::InvalidFormat("expected YYMMDD-NNNC".to_string()))?;
+    // The century is not part of the checksum
+    let digits = &digits[digits.len() - 10..];
+
+    let month = digits[2] * 10 + digits[3];
+    let day = digits[4] * 10 + digits[5];
+    // Coordination numbers add 60 to the day
+    if !(1..=12).contains(&month) || !((1..=31).contains(&day) || (61..=91).contains(&day)) {
+        return Err(NationalIdError::InvalidFormat("invalid birth date".to_string()));
+    }
+
+    let sum: u32 = digits[..9].iter()
+        .enumerate()
+        .map(|(i, digit)| {
+            let product = if i % 2 == 0 { digit * 2 } else { *digit };
+            product / 10 + product % 10
+        })
+        .sum();
+
+    if (10 - sum % 10) % 10 != digits[9] {
+        return Err(NationalIdError::ChecksumMismatch);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::{AttributeSource, Provenance, TemporalValidity};
+    use chrono::Utc;
+
+    fn national_id(value: AttributeValue) -> PersonAttribute {
+        PersonAttribute::new(
+            AttributeType::Identifying(IdentifyingAttributeType::NationalId),
+            value,
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Likely),
+        )
+    }
+
+    #[test]
+    fn test_us_social_security_numbers() {
+        let validator = NationalIdValidator::new();
+
+        assert_eq!(validator.check("US", "123-45-6789"), NationalIdCheck::Valid);
+        assert_eq!(validator.check("us", "123456789"), NationalIdCheck::Valid);
+        assert!(matches!(validator.check("US", "666-45-6789"), NationalIdCheck::Invalid(NationalIdError::ReservedRange(_))));
+        assert!(matches!(validator.check("US", "123-00-6789"), NationalIdCheck::Invalid(NationalIdError::ReservedRange(_))));
+        assert!(matches!(validator.check("US", "12-345-6789"), NationalIdCheck::Invalid(NationalIdError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_checksummed_numbers() {
+        let validator = NationalIdValidator::new();
+
+        assert_eq!(validator.check("NL", "111222333"), NationalIdCheck::Valid);
+        assert_eq!(validator.check("NL", "111222334"), NationalIdCheck::Invalid(NationalIdError::ChecksumMismatch));
+
+        assert_eq!(validator.check("SE", "811218-9876"), NationalIdCheck::Valid);
+        assert_eq!(validator.check("SE", "811218-9875"), NationalIdCheck::Invalid(NationalIdError::ChecksumMismatch));
+
+        assert_eq!(validator.check("FR", "anything"), NationalIdCheck::Unchecked);
+    }
+
+    #[test]
+    fn test_policy_applies_to_recorded_attributes() {
+        let malformed = national_id(AttributeValue::Json(serde_json::json!({ "country": "NL", "number": "111222334" })));
+
+        let lenient = NationalIdValidator::new();
+        let screened = lenient.screen_attribute(malformed.clone()).unwrap();
+        assert_eq!(screened.provenance.confidence, ConfidenceLevel::Uncertain);
+        assert_eq!(screened.provenance.trace.len(), 1);
+
+        let strict = NationalIdValidator::new()
+            .with_default_country("US")
+            .with_policy(InvalidNationalIdPolicy::Reject);
+        assert!(strict.screen_attribute(malformed).is_err());
+
+        let well_formed = national_id(AttributeValue::Text("123-45-6789".to_string()));
+        let screened = strict.screen_attribute(well_formed).unwrap();
+        assert_eq!(screened.provenance.confidence, ConfidenceLevel::Likely);
+    }
+}
@@ -10,8 +10,10 @@
 //! - PersonAttributeSet is a Free Monad
 //! - All transformations preserve structure
 
-use chrono::{DateTime, NaiveDate, Utc};
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
 use crate::aggregate::PersonId;
 
 // ============================================================================
@@ -162,7 +164,31 @@ pub enum AttributeValue {
     /// List of text values
     TextList(Vec<String>),
     /// JSON value for complex data
-    Json(serde_json::Value),
+    Json(#[serde(with = "crate::value_objects::json_in_binary")] serde_json::Value),
+    /// Value was solicited but not given (distinct from never recorded)
+    NotProvided { reason: NotProvidedReason },
+}
+
+/// Why a solicited value was not provided
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NotProvidedReason {
+    /// Person declined to answer ("prefer not to say")
+    Declined,
+    /// Person did not know the answer
+    Unknown,
+    /// Question did not apply to the person
+    NotApplicable,
+}
+
+/// Whether an attribute type has a value for a person
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttributeStatus {
+    /// A value is recorded
+    Provided,
+    /// The value was asked for but withheld
+    NotProvided(NotProvidedReason),
+    /// Nothing is recorded, the value was never asked for
+    Missing,
 }
 
 /// Date precision levels
@@ -180,6 +206,70 @@ pub enum DatePrecision {
     Century,
 }
 
+impl DatePrecision {
+    /// First and last day that agree with `date` at this precision
+    pub fn bounds(self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let years = |first: i32, last: i32| {
+            NaiveDate::from_ymd_opt(first, 1, 1)
+                .zip(NaiveDate::from_ymd_opt(last, 12, 31))
+                .unwrap_or((date, date))
+        };
+        match self {
+            DatePrecision::Exact => (date, date),
+            DatePrecision::Month => {
+                let first = date.with_day(1);
+                let last = first
+                    .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+                    .and_then(|next| next.pred_opt());
+                first.zip(last).unwrap_or((date, date))
+            }
+            DatePrecision::Year => years(date.year(), date.year()),
+            DatePrecision::Decade => {
+                let first = date.year().div_euclid(10) * 10;
+                years(first, first + 9)
+            }
+            DatePrecision::Century => {
+                let first = date.year().div_euclid(100) * 100;
+                years(first, first + 99)
+            }
+        }
+    }
+}
+
+/// Inclusive range of attribute values, for range queries
+///
+/// A range only matches values of its own kind: a `Length` range never
+/// matches a `Number` or `Text` value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AttributeValueRange {
+    Number { min: f64, max: f64 },
+    Integer { min: i64, max: i64 },
+    /// In meters
+    Length { min: f64, max: f64 },
+    /// In kilograms
+    Mass { min: f64, max: f64 },
+    /// Matches any date-valued attribute; a date known only to a month,
+    /// year or coarser matches when every day it may stand for is in range
+    Date { from: NaiveDate, to: NaiveDate },
+}
+
+impl AttributeValueRange {
+    /// Whether the attribute's value lies in the range
+    pub fn contains(&self, attribute: &PersonAttribute) -> bool {
+        match (self, &attribute.value) {
+            (Self::Number { min, max }, AttributeValue::Number(value))
+            | (Self::Length { min, max }, AttributeValue::Length(value))
+            | (Self::Mass { min, max }, AttributeValue::Mass(value)) => min <= value && value <= max,
+            (Self::Integer { min, max }, AttributeValue::Integer(value)) => min <= value && value <= max,
+            (Self::Date { from, to }, _) => attribute.date_with_precision().is_some_and(|(date, precision)| {
+                let (earliest, latest) = precision.bounds(date);
+                *from <= earliest && latest <= *to
+            }),
+            _ => false,
+        }
+    }
+}
+
 /// Blood type values
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BloodTypeValue {
@@ -315,6 +405,91 @@ impl TemporalValidity {
         let today = Utc::now().date_naive();
         self.is_valid_on(today)
     }
+
+    /// Check if both validity periods share at least one day
+    pub fn overlaps(&self, other: &TemporalValidity) -> bool {
+        let starts_before_other_ends = match (self.valid_from, other.valid_until) {
+            (Some(start), Some(end)) => start <= end,
+            _ => true,
+        };
+        let other_starts_before_end = match (other.valid_from, self.valid_until) {
+            (Some(start), Some(end)) => start <= end,
+            _ => true,
+        };
+        starts_before_other_ends && other_starts_before_end
+    }
+}
+
+// ============================================================================
+// Date Zones
+// ============================================================================
+
+/// Timezone a date-only value was recorded in
+///
+/// A `NaiveDate` names a calendar day, not an instant: someone born on
+/// 1 January in Auckland is a year older while it is still 31 December in
+/// London. Attributes carrying a zone have their dates compared against the
+/// local calendar of that zone. Attributes without one follow the crate-wide
+/// convention that dates are UTC days, the same reading as
+/// `Utc::now().date_naive()`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DateZone {
+    /// Offset east of UTC, in seconds
+    pub utc_offset_seconds: i32,
+    /// Locale the date was captured in, e.g. "en-NZ"
+    pub locale: Option<String>,
+}
+
+impl Default for DateZone {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
+
+impl DateZone {
+    /// The convention used when no zone is recorded
+    pub fn utc() -> Self {
+        Self {
+            utc_offset_seconds: 0,
+            locale: None,
+        }
+    }
+
+    pub fn from_offset(offset: FixedOffset) -> Self {
+        Self {
+            utc_offset_seconds: offset.local_minus_utc(),
+            locale: None,
+        }
+    }
+
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// The zone's offset; out-of-range stored offsets read as UTC
+    pub fn offset(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.utc_offset_seconds)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).expect("zero offset is valid"))
+    }
+
+    /// Calendar date in this zone at `instant`
+    pub fn local_date(&self, instant: DateTime<Utc>) -> NaiveDate {
+        instant.with_timezone(&self.offset()).date_naive()
+    }
+}
+
+/// Completed years between a birth date and a later calendar date
+///
+/// Both dates must be on the same calendar; see [`DateZone`]. A
+/// 29 February birthday is reached on 1 March in non-leap years. `None`
+/// if `on` is before `birth_date`.
+pub fn age_on(birth_date: NaiveDate, on: NaiveDate) -> Option<u32> {
+    if on < birth_date {
+        return None;
+    }
+    let had_birthday = (on.month(), on.day()) >= (birth_date.month(), birth_date.day());
+    u32::try_from(on.year() - birth_date.year() - i32::from(!had_birthday)).ok()
 }
 
 // ============================================================================
@@ -346,7 +521,7 @@ pub enum ConfidenceLevel {
 }
 
 /// Provenance tracking for attributes
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct Provenance {
     /// Source of this attribute
     pub source: AttributeSource,
@@ -356,6 +531,57 @@ pub struct Provenance {
     pub recorded_by: Option<String>,
     /// Trace of transformations applied
     pub trace: Vec<TransformationTrace>,
+    /// Document that verified the value, for `DocumentVerified` attributes
+    #[serde(default)]
+    pub document_reference: Option<DocumentReference>,
+}
+
+/// JSON leaves out an absent `document_reference`; binary formats read
+/// fields by position, so they always carry it
+impl Serialize for Provenance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let omit_document = self.document_reference.is_none() && serializer.is_human_readable();
+        let mut provenance = serializer.serialize_struct("Provenance", if omit_document { 4 } else { 5 })?;
+        provenance.serialize_field("source", &self.source)?;
+        provenance.serialize_field("confidence", &self.confidence)?;
+        provenance.serialize_field("recorded_by", &self.recorded_by)?;
+        provenance.serialize_field("trace", &self.trace)?;
+        if omit_document {
+            provenance.skip_field("document_reference")?;
+        } else {
+            provenance.serialize_field("document_reference", &self.document_reference)?;
+        }
+        provenance.end()
+    }
+}
+
+/// Reference to a document held outside this domain (passport, birth
+/// certificate, ...) that proves an attribute value
+///
+/// The hash pins the exact document revision that was checked, so an audit
+/// can detect a document replaced after verification.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DocumentReference {
+    /// Identifier in the document store
+    pub document_id: String,
+    /// Kind of document, e.g. "passport"
+    pub document_type: String,
+    /// Content hash of the verified document, e.g. "sha256:..."
+    pub content_hash: String,
+}
+
+impl DocumentReference {
+    pub fn new(
+        document_id: impl Into<String>,
+        document_type: impl Into<String>,
+        content_hash: impl Into<String>,
+    ) -> Self {
+        Self {
+            document_id: document_id.into(),
+            document_type: document_type.into(),
+            content_hash: content_hash.into(),
+        }
+    }
 }
 
 /// Record of a transformation applied to an attribute
@@ -377,9 +603,17 @@ impl Provenance {
             confidence,
             recorded_by: None,
             trace: Vec::new(),
+            document_reference: None,
         }
     }
 
+    /// Mark as verified by a document
+    pub fn verified_by(mut self, document: DocumentReference) -> Self {
+        self.source = AttributeSource::DocumentVerified;
+        self.document_reference = Some(document);
+        self
+    }
+
     /// Add a transformation to the trace
     pub fn trace_transformation(
         mut self,
@@ -404,7 +638,7 @@ impl Provenance {
 /// This is a Functor that satisfies functor laws:
 /// - Identity: `attribute.map(|x| x) == attribute`
 /// - Composition: `attribute.map(f).map(g) == attribute.map(|x| g(f(x)))`
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PersonAttribute {
     /// Type of attribute
     pub attribute_type: AttributeType,
@@ -414,6 +648,28 @@ pub struct PersonAttribute {
     pub temporal: TemporalValidity,
     /// Provenance information
     pub provenance: Provenance,
+    /// Zone the attribute's dates were recorded in; UTC when absent
+    #[serde(default)]
+    pub date_zone: Option<DateZone>,
+}
+
+/// JSON leaves out an absent `date_zone`; binary formats read fields by
+/// position, so they always carry it
+impl Serialize for PersonAttribute {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let omit_zone = self.date_zone.is_none() && serializer.is_human_readable();
+        let mut attribute = serializer.serialize_struct("PersonAttribute", if omit_zone { 4 } else { 5 })?;
+        attribute.serialize_field("attribute_type", &self.attribute_type)?;
+        attribute.serialize_field("value", &self.value)?;
+        attribute.serialize_field("temporal", &self.temporal)?;
+        attribute.serialize_field("provenance", &self.provenance)?;
+        if omit_zone {
+            attribute.skip_field("date_zone")?;
+        } else {
+            attribute.serialize_field("date_zone", &self.date_zone)?;
+        }
+        attribute.end()
+    }
 }
 
 impl PersonAttribute {
@@ -429,9 +685,21 @@ impl PersonAttribute {
             value,
             temporal,
             provenance,
+            date_zone: None,
         }
     }
 
+    /// Record the zone the attribute's dates are local to
+    pub fn with_date_zone(mut self, zone: DateZone) -> Self {
+        self.date_zone = Some(zone);
+        self
+    }
+
+    /// The recorded zone, or the UTC convention when there is none
+    pub fn effective_date_zone(&self) -> DateZone {
+        self.date_zone.clone().unwrap_or_default()
+    }
+
     /// Functor map - transform the value while preserving structure
     pub fn map<F>(self, f: F) -> Self
     where
@@ -442,6 +710,7 @@ impl PersonAttribute {
             value: f(self.value),
             temporal: self.temporal,
             provenance: self.provenance,
+            date_zone: self.date_zone,
         }
     }
 
@@ -463,6 +732,7 @@ impl PersonAttribute {
             value: f(self.value),
             temporal: self.temporal,
             provenance: self.provenance.trace_transformation(transformation, applied_by),
+            date_zone: self.date_zone,
         }
     }
 
@@ -471,9 +741,37 @@ impl PersonAttribute {
         self.temporal.is_valid_on(date)
     }
 
+    /// Check if valid at an instant, on the attribute's local calendar
+    pub fn is_valid_at(&self, instant: DateTime<Utc>) -> bool {
+        self.temporal.is_valid_on(self.effective_date_zone().local_date(instant))
+    }
+
     /// Check if currently valid
     pub fn is_currently_valid(&self) -> bool {
-        self.temporal.is_currently_valid()
+        self.is_valid_at(Utc::now())
+    }
+
+    /// Age at `instant` if the value is a date of birth
+    ///
+    /// Date values are read in the attribute's zone, so the birthday starts
+    /// at local midnight rather than UTC midnight.
+    pub fn age_at(&self, instant: DateTime<Utc>) -> Option<u32> {
+        let zone = self.effective_date_zone();
+        let birth_date = match &self.value {
+            AttributeValue::Date(date) => *date,
+            AttributeValue::ApproximateDate { date, .. } => *date,
+            AttributeValue::DateTime(born_at) => zone.local_date(*born_at),
+            _ => return None,
+        };
+        age_on(birth_date, zone.local_date(instant))
+    }
+
+    /// The value as a calendar date and the precision it is known to
+    ///
+    /// Date-times are read in the attribute's zone; year-month and year
+    /// values stand for their first day.
+    pub fn date_with_precision(&self) -> Option<(NaiveDate, DatePrecision)> {
+        dated(self)
     }
 
     /// Check if this is a healthcare-relevant attribute
@@ -495,6 +793,14 @@ impl PersonAttribute {
     pub fn is_demographic(&self) -> bool {
         matches!(self.attribute_type, AttributeType::Demographic(_))
     }
+
+    /// Provided or withheld; records never report `Missing`
+    pub fn status(&self) -> AttributeStatus {
+        match self.value {
+            AttributeValue::NotProvided { reason } => AttributeStatus::NotProvided(reason),
+            _ => AttributeStatus::Provided,
+        }
+    }
 }
 
 // ============================================================================
@@ -578,10 +884,14 @@ impl PersonAttributeSet {
         }
     }
 
+    /// Get attributes valid at an instant, each on its own local calendar
+    pub fn valid_at(&self, instant: DateTime<Utc>) -> Self {
+        self.clone().filter(|attr| attr.is_valid_at(instant))
+    }
+
     /// Get currently valid attributes
     pub fn currently_valid(&self) -> Self {
-        let today = Utc::now().date_naive();
-        self.valid_on(today)
+        self.valid_at(Utc::now())
     }
 
     /// Find attribute by type
@@ -589,6 +899,13 @@ impl PersonAttributeSet {
         self.attributes.iter().find(|attr| &attr.attribute_type == attr_type)
     }
 
+    /// Status of the currently valid attribute of `attr_type`
+    pub fn status_of(&self, attr_type: &AttributeType) -> AttributeStatus {
+        self.attributes.iter()
+            .find(|attr| &attr.attribute_type == attr_type && attr.is_currently_valid())
+            .map_or(AttributeStatus::Missing, PersonAttribute::status)
+    }
+
     /// Get all attributes of a specific category
     pub fn identifying_attributes(&self) -> Self {
         self.clone().filter(|attr| attr.is_identifying())
@@ -608,6 +925,166 @@ impl PersonAttributeSet {
     pub fn demographic_attributes(&self) -> Self {
         self.clone().filter(|attr| attr.is_demographic())
     }
+
+    /// Likelihood in `0.0..=1.0` that two attribute sets describe the same person
+    ///
+    /// Only identifying attributes count, each kind weighted by how strongly
+    /// it identifies a person (national id highest, eye color lowest). Kinds
+    /// missing from either set, withheld, or recorded for periods that don't
+    /// overlap are left out rather than counted as disagreement; with no
+    /// comparable evidence at all the score is 0. Birth dates are compared at
+    /// the coarser of the two precisions and earn less credit the coarser it
+    /// is, so agreeing only on the year counts partly.
+    ///
+    /// [`IdentityMatcher`](crate::services::IdentityMatcher) uses this as
+    /// the identifying share (40% by default) of its score.
+    pub fn match_score(&self, other: &PersonAttributeSet) -> f64 {
+        self.match_score_with_birth_date(other, None)
+    }
+
+    /// [`PersonAttributeSet::match_score`], with the birth date evidence
+    /// replaced by `birth_date_similarity` when that is known
+    pub(crate) fn match_score_with_birth_date(
+        &self,
+        other: &PersonAttributeSet,
+        birth_date_similarity: Option<f64>,
+    ) -> f64 {
+        let mut best: HashMap<IdentifyingAttributeType, f64> = HashMap::new();
+        for ours in &self.attributes {
+            let AttributeType::Identifying(our_type) = &ours.attribute_type else {
+                continue;
+            };
+            for theirs in &other.attributes {
+                let AttributeType::Identifying(their_type) = &theirs.attribute_type else {
+                    continue;
+                };
+                let kind = evidence_kind(our_type);
+                if kind != evidence_kind(their_type) || !ours.temporal.overlaps(&theirs.temporal) {
+                    continue;
+                }
+                if let Some(similarity) = attribute_similarity(&kind, ours, theirs) {
+                    let entry = best.entry(kind).or_insert(0.0);
+                    *entry = entry.max(similarity);
+                }
+            }
+        }
+
+        if let Some(similarity) = birth_date_similarity {
+            best.insert(IdentifyingAttributeType::BirthDate, similarity);
+        }
+
+        let total_weight: f64 = best.keys().map(identifying_weight).sum();
+        if total_weight == 0.0 {
+            return 0.0;
+        }
+        best.iter()
+            .map(|(kind, similarity)| identifying_weight(kind) * similarity)
+            .sum::<f64>()
+            / total_weight
+    }
+}
+
+// ============================================================================
+// Duplicate Scoring
+// ============================================================================
+
+/// The kind of evidence an identifying attribute gives; birth types are one kind
+fn evidence_kind(attribute_type: &IdentifyingAttributeType) -> IdentifyingAttributeType {
+    match attribute_type {
+        IdentifyingAttributeType::BirthDateTime
+        | IdentifyingAttributeType::BirthDate
+        | IdentifyingAttributeType::BirthYear
+        | IdentifyingAttributeType::ApproximateBirthDate => IdentifyingAttributeType::BirthDate,
+        other => other.clone(),
+    }
+}
+
+/// Weight of each kind of evidence; the weights sum to 1
+fn identifying_weight(kind: &IdentifyingAttributeType) -> f64 {
+    match kind {
+        IdentifyingAttributeType::NationalId => 0.35,
+        IdentifyingAttributeType::BirthDateTime
+        | IdentifyingAttributeType::BirthDate
+        | IdentifyingAttributeType::BirthYear
+        | IdentifyingAttributeType::ApproximateBirthDate => 0.25,
+        IdentifyingAttributeType::MotherId | IdentifyingAttributeType::FatherId => 0.10,
+        IdentifyingAttributeType::BirthPlace => 0.08,
+        IdentifyingAttributeType::BiologicalSex => 0.05,
+        IdentifyingAttributeType::BloodType => 0.04,
+        IdentifyingAttributeType::EyeColor => 0.03,
+    }
+}
+
+/// Similarity of two attributes of the same kind, `None` if either is withheld
+fn attribute_similarity(kind: &IdentifyingAttributeType, a: &PersonAttribute, b: &PersonAttribute) -> Option<f64> {
+    if a.status() != AttributeStatus::Provided || b.status() != AttributeStatus::Provided {
+        return None;
+    }
+
+    if *kind == IdentifyingAttributeType::BirthDate {
+        let ((a_date, a_precision), (b_date, b_precision)) = (dated(a)?, dated(b)?);
+        let precision = if precision_rank(a_precision) >= precision_rank(b_precision) { a_precision } else { b_precision };
+        return Some(if same_at_precision(a_date, b_date, precision) { precision_credit(precision) } else { 0.0 });
+    }
+
+    let same = match (&a.value, &b.value) {
+        // National ids and the like are often formatted differently
+        (AttributeValue::Text(x), AttributeValue::Text(y)) => {
+            let normalize = |s: &str| -> String {
+                s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+            };
+            normalize(x) == normalize(y)
+        }
+        (x, y) => x == y,
+    };
+    Some(if same { 1.0 } else { 0.0 })
+}
+
+/// A birth value as a date and the precision it is known to
+fn dated(attribute: &PersonAttribute) -> Option<(NaiveDate, DatePrecision)> {
+    match &attribute.value {
+        AttributeValue::DateTime(instant) => {
+            Some((attribute.effective_date_zone().local_date(*instant), DatePrecision::Exact))
+        }
+        AttributeValue::Date(date) => Some((*date, DatePrecision::Exact)),
+        AttributeValue::ApproximateDate { date, precision } => Some((*date, *precision)),
+        AttributeValue::YearMonth(year, month) => {
+            NaiveDate::from_ymd_opt(*year, *month, 1).map(|date| (date, DatePrecision::Month))
+        }
+        AttributeValue::Year(year) => NaiveDate::from_ymd_opt(*year, 1, 1).map(|date| (date, DatePrecision::Year)),
+        _ => None,
+    }
+}
+
+fn precision_rank(precision: DatePrecision) -> u8 {
+    match precision {
+        DatePrecision::Exact => 0,
+        DatePrecision::Month => 1,
+        DatePrecision::Year => 2,
+        DatePrecision::Decade => 3,
+        DatePrecision::Century => 4,
+    }
+}
+
+/// Credit for two dates agreeing at a precision
+fn precision_credit(precision: DatePrecision) -> f64 {
+    match precision {
+        DatePrecision::Exact => 1.0,
+        DatePrecision::Month => 0.7,
+        DatePrecision::Year => 0.4,
+        DatePrecision::Decade => 0.15,
+        DatePrecision::Century => 0.05,
+    }
+}
+
+fn same_at_precision(a: NaiveDate, b: NaiveDate, precision: DatePrecision) -> bool {
+    match precision {
+        DatePrecision::Exact => a == b,
+        DatePrecision::Month => (a.year(), a.month()) == (b.year(), b.month()),
+        DatePrecision::Year => a.year() == b.year(),
+        DatePrecision::Decade => a.year().div_euclid(10) == b.year().div_euclid(10),
+        DatePrecision::Century => a.year().div_euclid(100) == b.year().div_euclid(100),
+    }
 }
 
 /// Monoid append operation via Add trait
@@ -1127,4 +1604,155 @@ mod tests {
         assert_eq!(transformed.provenance.trace[0].transformation, "meters_to_centimeters");
         assert_eq!(transformed.provenance.trace[0].applied_by, "conversion_service");
     }
+
+    // ========================================================================
+    // Date Zones
+    // ========================================================================
+
+    fn birth_date_attribute(date: NaiveDate) -> PersonAttribute {
+        PersonAttribute::new(
+            AttributeType::Identifying(IdentifyingAttributeType::BirthDate),
+            AttributeValue::Date(date),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::DocumentVerified, ConfidenceLevel::Certain),
+        )
+    }
+
+    #[test]
+    fn test_age_across_timezone_boundary() {
+        let born = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        // Noon UTC on New Year's Eve is already New Year's Day in Auckland
+        let instant = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+            .and_hms_opt(12, 0, 0).unwrap()
+            .and_utc();
+        let auckland = DateZone::from_offset(FixedOffset::east_opt(13 * 3600).unwrap()).with_locale("en-NZ");
+
+        let unzoned = birth_date_attribute(born);
+        assert_eq!(unzoned.age_at(instant), Some(24));
+
+        let zoned = birth_date_attribute(born).with_date_zone(auckland.clone());
+        assert_eq!(zoned.age_at(instant), Some(25));
+
+        // West of UTC the birthday starts later than UTC midnight
+        let honolulu = DateZone::from_offset(FixedOffset::west_opt(10 * 3600).unwrap());
+        let new_year_utc = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().and_hms_opt(5, 0, 0).unwrap().and_utc();
+        assert_eq!(birth_date_attribute(born).age_at(new_year_utc), Some(25));
+        assert_eq!(birth_date_attribute(born).with_date_zone(honolulu).age_at(new_year_utc), Some(24));
+
+        // The zone survives serialization
+        let json = serde_json::to_string(&zoned).unwrap();
+        let restored: PersonAttribute = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.date_zone, Some(auckland));
+    }
+
+    #[test]
+    fn test_validity_uses_local_calendar() {
+        let instant = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+            .and_hms_opt(20, 0, 0).unwrap()
+            .and_utc();
+        let starts_new_year = |zone: Option<DateZone>| PersonAttribute {
+            temporal: TemporalValidity::new(Utc::now(), NaiveDate::from_ymd_opt(2025, 1, 1), None),
+            date_zone: zone,
+            ..birth_date_attribute(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap())
+        };
+
+        assert!(!starts_new_year(None).is_valid_at(instant));
+        let tokyo = DateZone::from_offset(FixedOffset::east_opt(9 * 3600).unwrap());
+        assert!(starts_new_year(Some(tokyo)).is_valid_at(instant));
+    }
+
+    #[test]
+    fn test_age_on_leap_day_birthday() {
+        let born = NaiveDate::from_ymd_opt(2004, 2, 29).unwrap();
+        assert_eq!(age_on(born, NaiveDate::from_ymd_opt(2005, 2, 28).unwrap()), Some(0));
+        assert_eq!(age_on(born, NaiveDate::from_ymd_opt(2005, 3, 1).unwrap()), Some(1));
+        assert_eq!(age_on(born, NaiveDate::from_ymd_opt(2003, 1, 1).unwrap()), None);
+    }
+
+    fn identifying(attribute_type: IdentifyingAttributeType, value: AttributeValue) -> PersonAttribute {
+        PersonAttribute::new(
+            AttributeType::Identifying(attribute_type),
+            value,
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::Imported { system: "crm".to_string() }, ConfidenceLevel::Likely),
+        )
+    }
+
+    #[test]
+    fn test_match_score_weights_identifying_evidence() {
+        let born = NaiveDate::from_ymd_opt(1985, 6, 15).unwrap();
+        let original = PersonAttributeSet::from_vec(vec![
+            identifying(IdentifyingAttributeType::NationalId, AttributeValue::Text("123-45-6789".to_string())),
+            identifying(IdentifyingAttributeType::BirthDate, AttributeValue::Date(born)),
+            identifying(IdentifyingAttributeType::BiologicalSex, AttributeValue::BiologicalSex(BiologicalSexValue::Female)),
+        ]);
+
+        let reformatted = PersonAttributeSet::from_vec(vec![
+            identifying(IdentifyingAttributeType::NationalId, AttributeValue::Text("123456789".to_string())),
+            identifying(IdentifyingAttributeType::BirthDateTime, AttributeValue::DateTime(born.and_hms_opt(9, 30, 0).unwrap().and_utc())),
+            identifying(IdentifyingAttributeType::BiologicalSex, AttributeValue::BiologicalSex(BiologicalSexValue::Female)),
+        ]);
+        assert!((original.match_score(&reformatted) - 1.0).abs() < 1e-9);
+
+        // Only the birth year is known: partial credit for the birth evidence
+        let year_only = PersonAttributeSet::from_vec(vec![
+            identifying(IdentifyingAttributeType::NationalId, AttributeValue::Text("123456789".to_string())),
+            identifying(IdentifyingAttributeType::BirthYear, AttributeValue::Year(1985)),
+        ]);
+        let score = original.match_score(&year_only);
+        assert!((score - (0.35 + 0.25 * 0.4) / 0.6).abs() < 1e-9);
+
+        let different_person = PersonAttributeSet::from_vec(vec![
+            identifying(IdentifyingAttributeType::NationalId, AttributeValue::Text("987-65-4321".to_string())),
+            identifying(
+                IdentifyingAttributeType::ApproximateBirthDate,
+                AttributeValue::ApproximateDate { date: born, precision: DatePrecision::Decade },
+            ),
+        ]);
+        let score = original.match_score(&different_person);
+        assert!(score > 0.0 && score < 0.1);
+
+        assert_eq!(original.match_score(&PersonAttributeSet::empty()), 0.0);
+    }
+
+    #[test]
+    fn test_match_score_ignores_non_overlapping_validity() {
+        let place = |location: &str, from: (i32, u32, u32), until: Option<(i32, u32, u32)>| PersonAttribute {
+            temporal: TemporalValidity::new(
+                Utc::now(),
+                NaiveDate::from_ymd_opt(from.0, from.1, from.2),
+                until.and_then(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d)),
+            ),
+            ..identifying(IdentifyingAttributeType::BirthPlace, AttributeValue::LocationReference(location.to_string()))
+        };
+        let national_id = identifying(IdentifyingAttributeType::NationalId, AttributeValue::Text("A1".to_string()));
+
+        let old = PersonAttributeSet::from_vec(vec![national_id.clone(), place("paris", (1990, 1, 1), Some((1999, 12, 31)))]);
+        let new = PersonAttributeSet::from_vec(vec![national_id, place("lyon", (2005, 1, 1), None)]);
+        assert!((old.match_score(&new) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_absent_optional_fields_are_left_out_of_json() {
+        let attribute = PersonAttribute::new(
+            AttributeType::Identifying(IdentifyingAttributeType::BirthDate),
+            AttributeValue::Date(NaiveDate::from_ymd_opt(1815, 12, 10).unwrap()),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+        );
+        let json = serde_json::to_value(&attribute).unwrap();
+        assert!(json.get("date_zone").is_none());
+        assert!(json["provenance"].get("document_reference").is_none());
+        assert_eq!(serde_json::from_value::<PersonAttribute>(json).unwrap(), attribute);
+
+        let verified = PersonAttribute {
+            provenance: attribute.provenance.clone().verified_by(DocumentReference::new("doc-1", "passport", "sha256:ab")),
+            ..attribute
+        }
+        .with_date_zone(DateZone::utc());
+        let json = serde_json::to_value(&verified).unwrap();
+        assert_eq!(json["date_zone"]["utc_offset_seconds"], 0);
+        assert_eq!(json["provenance"]["document_reference"]["document_id"], "doc-1");
+        assert_eq!(serde_json::from_value::<PersonAttribute>(json).unwrap(), verified);
+    }
 }
@@ -11,7 +11,8 @@
 //! 4. **Minimum requirement**: At least one character (what noise gets your attention)
 //! 5. **Immutable value objects** (name changes → new Name)
 
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 use std::fmt;
 use chrono::NaiveDate;
 use cim_domain::DomainResult;
@@ -103,7 +104,7 @@ pub enum NamingConvention {
 }
 
 /// Display policies for different contexts
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NameDisplayPolicy {
     /// Full formal name with all components
     /// "Jane Elizabeth Smith"
@@ -125,13 +126,17 @@ pub enum NameDisplayPolicy {
     /// Spanish: "Pablo Ruiz y Picasso"
     /// Chinese: "李明"
     Cultural,
+
+    /// First given name in full, middle names as initials
+    /// "Jane M. R. Smith"; family-first conventions: "Li Ming W."
+    GivenInitialsMiddle,
 }
 
 /// PersonName - Immutable value object representing how a person is named
 ///
 /// This does NOT include temporal titles/honorifics (Dr., Sir, etc.)
 /// Those are tracked separately as PersonTitle with award/revoke dates.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct PersonName {
     /// The structural components of the name
     pub components: NameComponents,
@@ -141,6 +146,29 @@ pub struct PersonName {
 
     /// Cultural convention for proper display
     pub naming_convention: NamingConvention,
+
+    /// Policy `display_name()` uses when there is no preferred form;
+    /// informal when absent
+    #[serde(default)]
+    pub display_policy: Option<NameDisplayPolicy>,
+}
+
+/// JSON leaves out an absent `display_policy`; binary formats read fields by
+/// position, so they always carry it
+impl Serialize for PersonName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let omit_policy = self.display_policy.is_none() && serializer.is_human_readable();
+        let mut name = serializer.serialize_struct("PersonName", if omit_policy { 3 } else { 4 })?;
+        name.serialize_field("components", &self.components)?;
+        name.serialize_field("preferred_form", &self.preferred_form)?;
+        name.serialize_field("naming_convention", &self.naming_convention)?;
+        if omit_policy {
+            name.skip_field("display_policy")?;
+        } else {
+            name.serialize_field("display_policy", &self.display_policy)?;
+        }
+        name.end()
+    }
 }
 
 impl PersonName {
@@ -165,6 +193,7 @@ impl PersonName {
             },
             preferred_form: None,
             naming_convention: NamingConvention::Western,
+            display_policy: None,
         }
     }
 
@@ -188,6 +217,7 @@ impl PersonName {
             },
             preferred_form: None,
             naming_convention: NamingConvention::Mononymic,
+            display_policy: None,
         }
     }
 
@@ -505,7 +535,7 @@ impl PersonName {
             return preferred.clone();
         }
 
-        self.display(NameDisplayPolicy::Informal)
+        self.display(self.display_policy.unwrap_or(NameDisplayPolicy::Informal))
     }
 
     /// Get full formal name
@@ -521,9 +551,38 @@ impl PersonName {
             NameDisplayPolicy::Legal => self.format_legal(),
             NameDisplayPolicy::Alphabetical => self.format_alphabetical(),
             NameDisplayPolicy::Cultural => self.format_cultural(),
+            NameDisplayPolicy::GivenInitialsMiddle => self.format_given_initials_middle(),
         }
     }
 
+    fn format_given_initials_middle(&self) -> String {
+        let given = &self.components.given_names;
+        let mut given_parts: Vec<String> = given.iter().take(1).cloned().collect();
+        given_parts.extend(given.iter().skip(1).filter_map(|middle| {
+            middle.chars().next().map(|initial| format!("{}.", initial.to_uppercase()))
+        }));
+
+        let mut family_parts = self.components.prefixes.clone();
+        family_parts.extend(self.components.family_names.clone());
+
+        let mut parts = match self.naming_convention {
+            NamingConvention::EastAsian => {
+                let mut parts = family_parts;
+                parts.extend(given_parts);
+                parts
+            }
+            _ => {
+                let mut parts = given_parts;
+                parts.extend(self.components.patronymic.clone());
+                parts.extend(self.components.matronymic.clone());
+                parts.extend(family_parts);
+                parts
+            }
+        };
+        parts.extend(self.components.suffixes.clone());
+        parts.join(" ")
+    }
+
     fn format_formal(&self) -> String {
         let mut parts = Vec::new();
 
@@ -678,6 +737,7 @@ pub struct PersonNameBuilder {
     suffixes: Vec<String>,
     preferred_form: Option<String>,
     naming_convention: NamingConvention,
+    display_policy: Option<NameDisplayPolicy>,
 }
 
 impl PersonNameBuilder {
@@ -691,6 +751,7 @@ impl PersonNameBuilder {
             suffixes: Vec::new(),
             preferred_form: None,
             naming_convention: NamingConvention::Western,
+            display_policy: None,
         }
     }
 
@@ -744,6 +805,12 @@ impl PersonNameBuilder {
         self
     }
 
+    /// Policy `display_name()` falls back to when no preferred form is set
+    pub fn default_display_policy(mut self, policy: NameDisplayPolicy) -> Self {
+        self.display_policy = Some(policy);
+        self
+    }
+
     pub fn build(self) -> DomainResult<PersonName> {
         let name = PersonName {
             components: NameComponents {
@@ -756,6 +823,7 @@ impl PersonNameBuilder {
             },
             preferred_form: self.preferred_form,
             naming_convention: self.naming_convention,
+            display_policy: self.display_policy,
         };
 
         name.validate()?;
@@ -893,6 +961,31 @@ mod tests {
         assert_eq!(name.display(NameDisplayPolicy::Cultural), "Pablo Ruiz y Picasso");
     }
 
+    #[test]
+    fn test_given_initials_middle() {
+        let name = PersonName::builder()
+            .given_names(vec!["Jane", "mary", "Rose"])
+            .family_name("Smith")
+            .suffix("Jr.")
+            .build()
+            .unwrap();
+        assert_eq!(name.display(NameDisplayPolicy::GivenInitialsMiddle), "Jane M. R. Smith Jr.");
+        assert_eq!(name.display_name(), "Jane");
+
+        let family_first = PersonName::builder()
+            .family_name("Li")
+            .given_names(vec!["Ming", "Wei"])
+            .naming_convention(NamingConvention::EastAsian)
+            .default_display_policy(NameDisplayPolicy::GivenInitialsMiddle)
+            .build()
+            .unwrap();
+        assert_eq!(family_first.display_name(), "Li Ming W.");
+        assert_eq!(family_first.to_string(), "Li Ming W.");
+
+        let single_given = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        assert_eq!(single_given.display(NameDisplayPolicy::GivenInitialsMiddle), "Ada Lovelace");
+    }
+
     #[test]
     fn test_mononym() {
         let name = PersonName::mononym("Suharto".to_string());
@@ -916,4 +1009,17 @@ mod tests {
         title.revoked_date = Some(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
         assert!(!title.is_valid_on(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()));
     }
+
+    #[test]
+    fn test_absent_display_policy_is_left_out_of_json() {
+        let name = PersonName::new("Ada".to_string(), "Lovelace".to_string());
+        let json = serde_json::to_value(&name).unwrap();
+        assert!(json.get("display_policy").is_none());
+        assert_eq!(serde_json::from_value::<PersonName>(json).unwrap(), name);
+
+        let formal = PersonName { display_policy: Some(NameDisplayPolicy::Formal), ..name };
+        let json = serde_json::to_value(&formal).unwrap();
+        assert!(json.get("display_policy").is_some());
+        assert_eq!(serde_json::from_value::<PersonName>(json).unwrap(), formal);
+    }
 }
@@ -0,0 +1,218 @@
+//! Strict phone number parsing into E.164 form
+//!
+//! [`PhoneNumber::new`] accepts anything containing a digit and stays as the
+//! lenient constructor. [`PhoneNumber::parse_e164`] instead normalizes the
+//! input, splits out the country calling code and rejects numbers whose
+//! length cannot be right for their country. Like national ID validation,
+//! passing only means the number is well-formed, not that it is in service.
+//!
+//! Numbers written with a leading `+` or `00` carry their own country code;
+//! anything else is read as a national number of the default region.
+
+use super::PhoneNumber;
+
+/// Why a phone number could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PhoneError {
+    #[error("Phone number is empty")]
+    Empty,
+
+    #[error("Phone number contains invalid characters: {0}")]
+    InvalidCharacters(String),
+
+    #[error("Unsupported region: {0}")]
+    UnknownRegion(String),
+
+    #[error("Unsupported country calling code in: {0}")]
+    UnknownCountryCode(String),
+
+    #[error("Phone number is too short: {digits} digits, expected at least {min}")]
+    TooShort { digits: usize, min: usize },
+
+    #[error("Phone number is too long: {digits} digits, expected at most {max}")]
+    TooLong { digits: usize, max: usize },
+
+    #[error("Phone number is not valid in its region: {0}")]
+    InvalidForRegion(String),
+
+    #[error("Invalid phone extension: {0}")]
+    InvalidExtension(String),
+}
+
+/// Numbering rules for one region
+struct Region {
+    /// ISO 3166-1 alpha-2 code
+    code: &'static str,
+    calling_code: &'static str,
+    /// Prefix dialled before national numbers within the country
+    trunk_prefix: &'static str,
+    /// Digits in the national significant number, inclusive
+    national_digits: (usize, usize),
+}
+
+const REGIONS: &[Region] = &[
+    Region { code: "US", calling_code: "1", trunk_prefix: "1", national_digits: (10, 10) },
+    Region { code: "CA", calling_code: "1", trunk_prefix: "1", national_digits: (10, 10) },
+    Region { code: "GB", calling_code: "44", trunk_prefix: "0", national_digits: (9, 10) },
+    Region { code: "DE", calling_code: "49", trunk_prefix: "0", national_digits: (6, 11) },
+    Region { code: "FR", calling_code: "33", trunk_prefix: "0", national_digits: (9, 9) },
+    Region { code: "NL", calling_code: "31", trunk_prefix: "0", national_digits: (9, 9) },
+    Region { code: "SE", calling_code: "46", trunk_prefix: "0", national_digits: (7, 9) },
+    Region { code: "AU", calling_code: "61", trunk_prefix: "0", national_digits: (9, 9) },
+];
+
+/// E.164 allows at most 15 digits including the country code
+const MAX_E164_DIGITS: usize = 15;
+
+const EXTENSION_MARKERS: [&str; 5] = [";ext=", "ext.", "ext", "x", "#"];
+
+impl PhoneNumber {
+    /// Parse and normalize a phone number to E.164
+    ///
+    /// `number` holds the national significant number and `country_code` the
+    /// calling code, so [`e164`](Self::e164) gives the normalized form.
+    /// `default_region` (ISO 3166-1 alpha-2) is only consulted for numbers
+    /// without an international prefix.
+    pub fn parse_e164(input: &str, default_region: &str) -> Result<PhoneNumber, PhoneError> {
+        let (main, extension) = split_extension(input.trim())?;
+        if main.is_empty() {
+            return Err(PhoneError::Empty);
+        }
+
+        let international = main.starts_with('+') || main.starts_with("00");
+        let digits = digits_of(main.strip_prefix('+').unwrap_or(main))?;
+        if digits.is_empty() {
+            return Err(PhoneError::Empty);
+        }
+
+        let (region, national) = if international {
+            let digits = if main.starts_with('+') { &digits[..] } else { &digits[2..] };
+            let region = REGIONS.iter()
+                .find(|region| digits.starts_with(region.calling_code))
+                .ok_or_else(|| PhoneError::UnknownCountryCode(input.to_string()))?;
+            let national = &digits[region.calling_code.len()..];
+            // "+44 (0)20 ..." repeats the trunk prefix after the country code
+            let national = match national.strip_prefix(region.trunk_prefix) {
+                Some(rest) if region.trunk_prefix == "0" => rest,
+                _ => national,
+            };
+            (region, national.to_string())
+        } else {
+            let region = REGIONS.iter()
+                .find(|region| region.code.eq_ignore_ascii_case(default_region.trim()))
+                .ok_or_else(|| PhoneError::UnknownRegion(default_region.to_string()))?;
+            let (_, max) = region.national_digits;
+            let national = match digits.strip_prefix(region.trunk_prefix) {
+                Some(rest) if digits.len() > max || region.trunk_prefix == "0" => rest,
+                _ => &digits[..],
+            };
+            (region, national.to_string())
+        };
+
+        check_length(region, &national)?;
+        if region.calling_code == "1" && national.starts_with(['0', '1']) {
+            return Err(PhoneError::InvalidForRegion(format!("area code cannot start with {}", &national[..1])));
+        }
+
+        Ok(PhoneNumber {
+            number: national,
+            country_code: Some(region.calling_code.to_string()),
+            extension,
+            sms_capable: false,
+        })
+    }
+
+    /// `+<country code><number>`, if the country code is known
+    pub fn e164(&self) -> Option<String> {
+        self.country_code.as_ref().map(|cc| format!("+{cc}{}", self.number))
+    }
+}
+
+/// Split a trailing extension such as " ext. 204", " x204" or ";ext=204"
+fn split_extension(input: &str) -> Result<(&str, Option<String>), PhoneError> {
+    let lower = input.to_ascii_lowercase();
+    for marker in EXTENSION_MARKERS {
+        let Some(at) = lower.rfind(marker) else { continue };
+        let extension = input[at + marker.len()..].trim();
+        if extension.is_empty() || !extension.chars().all(|c| c.is_ascii_digit()) {
+            return Err(PhoneError::InvalidExtension(extension.to_string()));
+        }
+        return Ok((input[..at].trim(), Some(extension.to_string())));
+    }
+    Ok((input, None))
+}
+
+/// Digits of a number, allowing the usual separators
+fn digits_of(input: &str) -> Result<String, PhoneError> {
+    if let Some(invalid) = input.chars().find(|c| !c.is_ascii_digit() && !" -.()/".contains(*c)) {
+        return Err(PhoneError::InvalidCharacters(invalid.to_string()));
+    }
+    Ok(input.chars().filter(|c| c.is_ascii_digit()).collect())
+}
+
+fn check_length(region: &Region, national: &str) -> Result<(), PhoneError> {
+    let (min, max) = region.national_digits;
+    let max = max.min(MAX_E164_DIGITS - region.calling_code.len());
+    let digits = national.len();
+    if digits < min {
+        return Err(PhoneError::TooShort { digits, min });
+    }
+    if digits > max {
+        return Err(PhoneError::TooLong { digits, max });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn e164(input: &str, region: &str) -> String {
+        PhoneNumber::parse_e164(input, region).unwrap().e164().unwrap()
+    }
+
+    #[test]
+    fn test_us_numbers_in_national_and_international_form() {
+        assert_eq!(e164("(415) 555-0123", "US"), "+14155550123");
+        assert_eq!(e164("1-415-555-0123", "us"), "+14155550123");
+        assert_eq!(e164("+1 415.555.0123", "GB"), "+14155550123");
+
+        let phone = PhoneNumber::parse_e164("415 555 0123", "US").unwrap();
+        assert_eq!(phone.country_code.as_deref(), Some("1"));
+        assert_eq!(phone.number, "4155550123");
+
+        assert_eq!(PhoneNumber::parse_e164("555-0123", "US"), Err(PhoneError::TooShort { digits: 7, min: 10 }));
+        assert!(matches!(PhoneNumber::parse_e164("(015) 555-0123", "US"), Err(PhoneError::InvalidForRegion(_))));
+    }
+
+    #[test]
+    fn test_uk_numbers_drop_the_trunk_prefix() {
+        assert_eq!(e164("020 7946 0000", "GB"), "+442079460000");
+        assert_eq!(e164("+44 (0)20 7946 0000", "US"), "+442079460000");
+        assert_eq!(e164("0044 7700 900123", "US"), "+447700900123");
+        assert_eq!(
+            PhoneNumber::parse_e164("020 7946 0000 12", "GB"),
+            Err(PhoneError::TooLong { digits: 12, max: 10 }),
+        );
+    }
+
+    #[test]
+    fn test_extensions_are_split_out() {
+        for input in ["+1 415 555 0123 ext. 204", "(415) 555-0123 x204", "+14155550123;ext=204", "415-555-0123 #204"] {
+            let phone = PhoneNumber::parse_e164(input, "US").unwrap();
+            assert_eq!(phone.e164().as_deref(), Some("+14155550123"), "{input}");
+            assert_eq!(phone.extension.as_deref(), Some("204"), "{input}");
+        }
+        assert!(matches!(PhoneNumber::parse_e164("415 555 0123 ext", "US"), Err(PhoneError::InvalidExtension(_))));
+    }
+
+    #[test]
+    fn test_unparseable_input_is_rejected() {
+        assert_eq!(PhoneNumber::parse_e164("  ", "US"), Err(PhoneError::Empty));
+        assert!(matches!(PhoneNumber::parse_e164("1-800-FLOWERS", "US"), Err(PhoneError::InvalidCharacters(_))));
+        assert!(matches!(PhoneNumber::parse_e164("020 7946 0000", "ZZ"), Err(PhoneError::UnknownRegion(_))));
+        assert!(matches!(PhoneNumber::parse_e164("+999 1234 5678", "US"), Err(PhoneError::UnknownCountryCode(_))));
+        // The lenient constructor still accepts it
+        assert!(PhoneNumber::new("555-0123".to_string()).is_ok());
+    }
+}
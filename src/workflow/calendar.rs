@@ -0,0 +1,172 @@
+//! Business calendar for workflow due dates
+//!
+//! Human tasks express their deadline in business days. A calendar turns
+//! that into a concrete instant, skipping weekends and holidays as seen from
+//! the assignee's timezone.
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use std::collections::BTreeSet;
+
+/// Working days, holidays and timezone used to compute due dates
+#[derive(Debug, Clone)]
+pub struct BusinessCalendar {
+    timezone: FixedOffset,
+    working_days: Vec<Weekday>,
+    holidays: BTreeSet<NaiveDate>,
+    end_of_day: NaiveTime,
+}
+
+impl Default for BusinessCalendar {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl BusinessCalendar {
+    /// Monday to Friday in UTC, no holidays, tasks due at 17:00
+    pub fn standard() -> Self {
+        Self {
+            timezone: FixedOffset::east_opt(0).expect("zero offset is valid"),
+            working_days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            holidays: BTreeSet::new(),
+            end_of_day: NaiveTime::from_hms_opt(17, 0, 0).expect("17:00 is valid"),
+        }
+    }
+
+    pub fn with_timezone(mut self, timezone: FixedOffset) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    pub fn with_working_days(mut self, working_days: Vec<Weekday>) -> Self {
+        self.working_days = working_days;
+        self
+    }
+
+    pub fn with_holiday(mut self, date: NaiveDate) -> Self {
+        self.holidays.insert(date);
+        self
+    }
+
+    pub fn with_holidays(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(dates);
+        self
+    }
+
+    pub fn with_end_of_day(mut self, end_of_day: NaiveTime) -> Self {
+        self.end_of_day = end_of_day;
+        self
+    }
+
+    pub fn timezone(&self) -> FixedOffset {
+        self.timezone
+    }
+
+    /// Whether work happens on this (local) date
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.working_days.contains(&date.weekday()) && !self.holidays.contains(&date)
+    }
+
+    /// End of the business day `days` working days after `from`
+    ///
+    /// Counting starts on the local day after `from`, so one business day
+    /// from a Friday is the following Monday. Zero days means the end of
+    /// the current working day, or of the next one if today is not a
+    /// working day. Returns `None` if the calendar has no working days.
+    pub fn add_business_days(&self, from: DateTime<Utc>, days: u32) -> Option<DateTime<Utc>> {
+        if self.working_days.is_empty() {
+            return None;
+        }
+
+        let mut date = from.with_timezone(&self.timezone).date_naive();
+        let mut remaining = days;
+
+        if remaining == 0 {
+            while !self.is_working_day(date) {
+                date = date.succ_opt()?;
+            }
+        }
+
+        while remaining > 0 {
+            date = date.succ_opt()?;
+            if self.is_working_day(date) {
+                remaining -= 1;
+            }
+        }
+
+        self.timezone
+            .from_local_datetime(&date.and_time(self.end_of_day))
+            .single()
+            .map(|local| local.with_timezone(&Utc))
+    }
+}
+
+/// Resolve a human task's due date
+///
+/// A business-day deadline takes precedence over a fixed `due_date`.
+pub fn resolve_due_date(
+    calendar: &BusinessCalendar,
+    due_date: Option<DateTime<Utc>>,
+    due_in_business_days: Option<u32>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    match due_in_business_days {
+        Some(days) => calendar.add_business_days(now, days),
+        None => due_date,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_one_business_day_from_friday_is_monday() {
+        let calendar = BusinessCalendar::standard();
+        // 2024-03-01 is a Friday
+        let due = calendar.add_business_days(utc(2024, 3, 1, 10), 1).unwrap();
+        assert_eq!(due.date_naive(), NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+        assert_eq!(due.date_naive().weekday(), Weekday::Mon);
+    }
+
+    #[test]
+    fn test_holidays_are_skipped() {
+        let calendar = BusinessCalendar::standard()
+            .with_holiday(NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+        let due = calendar.add_business_days(utc(2024, 3, 1, 10), 1).unwrap();
+        assert_eq!(due.date_naive(), NaiveDate::from_ymd_opt(2024, 3, 5).unwrap());
+    }
+
+    #[test]
+    fn test_timezone_decides_the_local_day() {
+        // 23:00 UTC Thursday is already Friday in UTC+10
+        let calendar = BusinessCalendar::standard()
+            .with_timezone(FixedOffset::east_opt(10 * 3600).unwrap());
+        let due = calendar.add_business_days(utc(2024, 2, 29, 23), 1).unwrap();
+        let local = due.with_timezone(&calendar.timezone());
+        assert_eq!(local.date_naive(), NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+    }
+
+    #[test]
+    fn test_business_days_take_precedence_over_fixed_due_date() {
+        let calendar = BusinessCalendar::standard();
+        let now = utc(2024, 3, 1, 10);
+        let fixed = Some(utc(2024, 12, 25, 0));
+
+        assert_eq!(resolve_due_date(&calendar, fixed, None, now), fixed);
+        assert_eq!(
+            resolve_due_date(&calendar, fixed, Some(1), now),
+            calendar.add_business_days(now, 1),
+        );
+    }
+}
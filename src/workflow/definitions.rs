@@ -85,6 +85,24 @@ pub struct WorkflowNode {
     pub timeout: Option<std::time::Duration>,
     /// Retry policy for the node
     pub retry_policy: Option<RetryPolicy>,
+    /// Action that undoes this node's effect if the workflow later fails
+    #[serde(default)]
+    pub compensation: Option<NodeType>,
+}
+
+impl WorkflowNode {
+    /// The compensation as a node of its own, ready to execute
+    pub fn compensation_node(&self) -> Option<WorkflowNode> {
+        self.compensation.as_ref().map(|node_type| WorkflowNode {
+            id: format!("{}.compensation", self.id),
+            name: format!("Compensate {}", self.name),
+            node_type: node_type.clone(),
+            configuration: self.configuration.clone(),
+            timeout: self.timeout,
+            retry_policy: None,
+            compensation: None,
+        })
+    }
 }
 
 /// Types of workflow nodes
@@ -105,6 +123,9 @@ pub enum NodeType {
     /// Parallel execution gateway
     ParallelGateway {
         branches: Vec<ParallelBranch>,
+        /// How many branches must succeed before the gateway is left
+        #[serde(default)]
+        join_policy: JoinPolicy,
     },
     /// Wait node for external events
     WaitForEvent {
@@ -116,6 +137,14 @@ pub enum NodeType {
         assignee: Option<String>,
         form_definition: Option<String>,
         due_date: Option<DateTime<Utc>>,
+        /// Deadline in business days, resolved against the assignee's
+        /// calendar when the task is created; overrides `due_date`
+        #[serde(default)]
+        due_in_business_days: Option<u32>,
+        /// Workflow variable that receives the decision's `approved` flag,
+        /// for transitions written against a flat variable name
+        #[serde(default)]
+        decision_variable: Option<String>,
     },
     /// Script execution node
     Script {
@@ -151,6 +180,9 @@ pub enum ConditionExpression {
     /// Simple boolean expression
     Boolean(bool),
     /// Comparison expression
+    ///
+    /// `left` names a context variable. `right` is a literal, or another
+    /// variable when written as `{"var": "name"}`.
     Comparison {
         left: String,
         operator: ComparisonOperator,
@@ -203,6 +235,22 @@ pub struct Branch {
 pub struct ParallelBranch {
     pub name: String,
     pub nodes: Vec<String>,
+    /// Longest the whole branch may run; when it expires the branch fails
+    #[serde(default)]
+    pub timeout: Option<std::time::Duration>,
+}
+
+/// Join condition of a parallel gateway
+///
+/// The gateway always waits for every branch to finish, fail or time out;
+/// the policy only decides whether that outcome lets the workflow continue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JoinPolicy {
+    /// Every branch must succeed
+    #[default]
+    All,
+    /// At least one branch must succeed
+    Any,
 }
 
 /// Script types supported in workflows
@@ -284,6 +332,232 @@ pub struct WorkflowDefinition {
     pub metadata: WorkflowMetadata,
 }
 
+/// Builder for [`WorkflowDefinition`] that checks node references at `build()` time
+///
+/// ```rust,ignore
+/// let workflow = WorkflowBuilder::new("Onboarding", PersonWorkflowType::PersonOnboarding)
+///     .node(start_node)
+///     .node(finish_node)
+///     .transition("start", "finish", None)
+///     .start("start")
+///     .end("finish")
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct WorkflowBuilder {
+    name: String,
+    version: String,
+    workflow_type: PersonWorkflowType,
+    description: Option<String>,
+    nodes: Vec<WorkflowNode>,
+    transitions: Vec<WorkflowTransition>,
+    start_node_id: Option<String>,
+    end_node_ids: Vec<String>,
+    global_config: WorkflowGlobalConfig,
+    metadata: WorkflowMetadata,
+}
+
+impl WorkflowBuilder {
+    pub fn new(name: impl Into<String>, workflow_type: PersonWorkflowType) -> Self {
+        Self {
+            name: name.into(),
+            version: "1.0".to_string(),
+            workflow_type,
+            description: None,
+            nodes: Vec::new(),
+            transitions: Vec::new(),
+            start_node_id: None,
+            end_node_ids: Vec::new(),
+            global_config: WorkflowGlobalConfig::default(),
+            metadata: WorkflowMetadata::default(),
+        }
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn node(mut self, node: WorkflowNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Add a transition with the default priority of 1
+    pub fn transition(
+        self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        condition: Option<ConditionExpression>,
+    ) -> Self {
+        self.transition_with_priority(from, to, condition, 1)
+    }
+
+    pub fn transition_with_priority(
+        mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        condition: Option<ConditionExpression>,
+        priority: i32,
+    ) -> Self {
+        self.transitions.push(WorkflowTransition {
+            from: from.into(),
+            to: to.into(),
+            condition,
+            priority,
+        });
+        self
+    }
+
+    pub fn start(mut self, node_id: impl Into<String>) -> Self {
+        self.start_node_id = Some(node_id.into());
+        self
+    }
+
+    pub fn end(mut self, node_id: impl Into<String>) -> Self {
+        self.end_node_ids.push(node_id.into());
+        self
+    }
+
+    pub fn global_config(mut self, global_config: WorkflowGlobalConfig) -> Self {
+        self.global_config = global_config;
+        self
+    }
+
+    pub fn metadata(mut self, metadata: WorkflowMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Validate every node reference and produce the definition
+    pub fn build(self) -> Result<WorkflowDefinition, WorkflowDefinitionError> {
+        let known: std::collections::HashSet<&str> =
+            self.nodes.iter().map(|n| n.id.as_str()).collect();
+        let mut dangling = Vec::new();
+
+        match &self.start_node_id {
+            Some(start) if known.contains(start.as_str()) => {}
+            Some(start) => dangling.push(DanglingReference::StartNode(start.clone())),
+            None => dangling.push(DanglingReference::MissingStartNode),
+        }
+
+        for end in &self.end_node_ids {
+            if !known.contains(end.as_str()) {
+                dangling.push(DanglingReference::EndNode(end.clone()));
+            }
+        }
+
+        for transition in &self.transitions {
+            if !known.contains(transition.from.as_str()) {
+                dangling.push(DanglingReference::TransitionSource {
+                    from: transition.from.clone(),
+                    to: transition.to.clone(),
+                });
+            }
+            if !known.contains(transition.to.as_str()) {
+                dangling.push(DanglingReference::TransitionTarget {
+                    from: transition.from.clone(),
+                    to: transition.to.clone(),
+                });
+            }
+        }
+
+        for node in &self.nodes {
+            match &node.node_type {
+                NodeType::DecisionGateway { branches, .. } => {
+                    for branch in branches {
+                        if !known.contains(branch.target_node_id.as_str()) {
+                            dangling.push(DanglingReference::BranchTarget {
+                                node_id: node.id.clone(),
+                                branch: branch.name.clone(),
+                                target: branch.target_node_id.clone(),
+                            });
+                        }
+                    }
+                }
+                NodeType::ParallelGateway { branches, .. } => {
+                    for branch in branches {
+                        for branch_node in &branch.nodes {
+                            if !known.contains(branch_node.as_str()) {
+                                dangling.push(DanglingReference::ParallelBranchNode {
+                                    node_id: node.id.clone(),
+                                    branch: branch.name.clone(),
+                                    node: branch_node.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !dangling.is_empty() {
+            return Err(WorkflowDefinitionError { dangling });
+        }
+
+        Ok(WorkflowDefinition {
+            id: WorkflowId::new(),
+            name: self.name,
+            version: self.version,
+            workflow_type: self.workflow_type,
+            description: self.description,
+            nodes: self.nodes,
+            transitions: self.transitions,
+            start_node_id: self.start_node_id.unwrap_or_default(),
+            end_node_ids: self.end_node_ids,
+            global_config: self.global_config,
+            metadata: self.metadata,
+        })
+    }
+}
+
+/// A node id referenced by a workflow definition that names no node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DanglingReference {
+    MissingStartNode,
+    StartNode(String),
+    EndNode(String),
+    TransitionSource { from: String, to: String },
+    TransitionTarget { from: String, to: String },
+    BranchTarget { node_id: String, branch: String, target: String },
+    ParallelBranchNode { node_id: String, branch: String, node: String },
+}
+
+impl std::fmt::Display for DanglingReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingStartNode => write!(f, "no start node set"),
+            Self::StartNode(id) => write!(f, "start node '{id}'"),
+            Self::EndNode(id) => write!(f, "end node '{id}'"),
+            Self::TransitionSource { from, to } => write!(f, "source '{from}' of transition {from} -> {to}"),
+            Self::TransitionTarget { from, to } => write!(f, "target '{to}' of transition {from} -> {to}"),
+            Self::BranchTarget { node_id, branch, target } => {
+                write!(f, "target '{target}' of branch '{branch}' on node '{node_id}'")
+            }
+            Self::ParallelBranchNode { node_id, branch, node } => {
+                write!(f, "node '{node}' of parallel branch '{branch}' on node '{node_id}'")
+            }
+        }
+    }
+}
+
+/// Workflow definition failed validation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Workflow definition has dangling node references: {}", list_references(.dangling))]
+pub struct WorkflowDefinitionError {
+    pub dangling: Vec<DanglingReference>,
+}
+
+fn list_references(dangling: &[DanglingReference]) -> String {
+    dangling.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(", ")
+}
+
 /// Global configuration for workflows
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowGlobalConfig {
@@ -414,6 +688,10 @@ pub struct WorkflowContext {
     /// Input data for the workflow
     pub input_data: HashMap<String, serde_json::Value>,
     /// Current variables in the workflow
+    ///
+    /// Seeded from the definition's global variables, then updated with the
+    /// output of every completed node and with human task decisions.
+    /// Conditions read these first and fall back to `input_data`.
     pub variables: HashMap<String, serde_json::Value>,
     /// Output data from the workflow
     pub output_data: HashMap<String, serde_json::Value>,
@@ -423,6 +701,13 @@ pub struct WorkflowContext {
     pub actor: String,
 }
 
+impl WorkflowContext {
+    /// Value of a variable as conditions see it
+    pub fn resolve(&self, name: &str) -> Option<&serde_json::Value> {
+        self.variables.get(name).or_else(|| self.input_data.get(name))
+    }
+}
+
 /// Record of workflow node execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowExecution {
@@ -452,6 +737,8 @@ pub enum ExecutionStatus {
     Failed,
     Skipped,
     Retrying,
+    /// A completed node's effect was undone after the workflow failed
+    Compensated,
 }
 
 /// Performance metrics for node execution
@@ -467,6 +754,93 @@ pub struct ExecutionMetrics {
     pub retry_count: u32,
 }
 
+/// Outcome of a human task, supplied when the task is completed
+///
+/// Bound into the workflow variables as `<node_id>.approved`,
+/// `<node_id>.reason` and `<node_id>.comments` so later nodes and
+/// transitions can see why a task was approved or rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HumanTaskDecision {
+    pub approved: bool,
+    /// Structured reason code (e.g. `failed_reference_check`)
+    pub reason: Option<String>,
+    /// Free-text comments from the decider
+    pub comments: Option<String>,
+    pub decided_by: String,
+    pub decided_at: DateTime<Utc>,
+}
+
+impl HumanTaskDecision {
+    pub fn approve(decided_by: impl Into<String>) -> Self {
+        Self {
+            approved: true,
+            reason: None,
+            comments: None,
+            decided_by: decided_by.into(),
+            decided_at: Utc::now(),
+        }
+    }
+
+    pub fn reject(decided_by: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            approved: false,
+            reason: Some(reason.into()),
+            ..Self::approve(decided_by)
+        }
+    }
+
+    pub fn with_comments(mut self, comments: impl Into<String>) -> Self {
+        self.comments = Some(comments.into());
+        self
+    }
+
+    /// The decision as node output, keyed without the node prefix
+    pub fn to_output(&self) -> HashMap<String, serde_json::Value> {
+        HashMap::from([
+            ("approved".to_string(), serde_json::json!(self.approved)),
+            ("reason".to_string(), serde_json::json!(self.reason)),
+            ("comments".to_string(), serde_json::json!(self.comments)),
+            ("decided_by".to_string(), serde_json::json!(self.decided_by)),
+            ("decided_at".to_string(), serde_json::json!(self.decided_at)),
+        ])
+    }
+}
+
+impl WorkflowInstance {
+    /// Bind a human task's decision into the variables and record it in the history
+    pub fn record_human_decision(
+        &mut self,
+        node_id: &str,
+        decision_variable: Option<&str>,
+        decision: &HumanTaskDecision,
+    ) {
+        let output = decision.to_output();
+        let variables = &mut self.context.variables;
+        for key in ["approved", "reason", "comments"] {
+            variables.insert(format!("{node_id}.{key}"), output[key].clone());
+        }
+        if let Some(variable) = decision_variable {
+            variables.insert(variable.to_string(), serde_json::json!(decision.approved));
+        }
+
+        self.execution_history.push(WorkflowExecution {
+            node_id: node_id.to_string(),
+            started_at: decision.decided_at,
+            ended_at: Some(decision.decided_at),
+            status: ExecutionStatus::Completed,
+            input_data: HashMap::new(),
+            output_data: output,
+            error: None,
+            metrics: ExecutionMetrics {
+                duration_ms: 0,
+                memory_usage_bytes: None,
+                cpu_usage_percent: None,
+                retry_count: 0,
+            },
+        });
+    }
+}
+
 /// Workflow error information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowError {
@@ -516,6 +890,86 @@ mod tests {
         assert_eq!(workflow.workflow_type, PersonWorkflowType::PersonOnboarding);
     }
     
+    fn service_node(id: &str) -> WorkflowNode {
+        WorkflowNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            node_type: NodeType::ServiceInvocation {
+                service: "PersonService".to_string(),
+                operation: id.to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            },
+            configuration: NodeConfiguration::default(),
+            timeout: None,
+            retry_policy: None,
+            compensation: None,
+        }
+    }
+
+    #[test]
+    fn test_workflow_builder_builds_valid_definition() {
+        let workflow = WorkflowBuilder::new("Onboarding", PersonWorkflowType::PersonOnboarding)
+            .node(service_node("start"))
+            .node(service_node("verify"))
+            .node(service_node("done"))
+            .transition("start", "verify", None)
+            .transition("verify", "done", Some(ConditionExpression::Boolean(true)))
+            .start("start")
+            .end("done")
+            .build()
+            .unwrap();
+
+        assert_eq!(workflow.start_node_id, "start");
+        assert_eq!(workflow.transitions.len(), 2);
+        assert_eq!(workflow.end_node_ids, vec!["done".to_string()]);
+    }
+
+    #[test]
+    fn test_workflow_builder_catches_typo_in_target_node() {
+        let err = WorkflowBuilder::new("Onboarding", PersonWorkflowType::PersonOnboarding)
+            .node(service_node("start"))
+            .node(service_node("verify_identity"))
+            .transition("start", "verify_identiy", None)
+            .start("start")
+            .end("verify_identity")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(err.dangling, vec![DanglingReference::TransitionTarget {
+            from: "start".to_string(),
+            to: "verify_identiy".to_string(),
+        }]);
+        assert!(err.to_string().contains("verify_identiy"));
+
+        let err = WorkflowBuilder::new("Onboarding", PersonWorkflowType::PersonOnboarding)
+            .node(service_node("start"))
+            .node(service_node("provision_email"))
+            .node(WorkflowNode {
+                node_type: NodeType::ParallelGateway {
+                    branches: vec![ParallelBranch {
+                        name: "accounts".to_string(),
+                        nodes: vec!["provision_email".to_string(), "provision_vpm".to_string()],
+                        timeout: None,
+                    }],
+                    join_policy: JoinPolicy::default(),
+                },
+                ..service_node("provision")
+            })
+            .transition("start", "provision", None)
+            .start("start")
+            .end("provision")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(err.dangling, vec![DanglingReference::ParallelBranchNode {
+            node_id: "provision".to_string(),
+            branch: "accounts".to_string(),
+            node: "provision_vpm".to_string(),
+        }]);
+        assert!(err.to_string().contains("provision_vpm"));
+    }
+
     #[test]
     fn test_condition_expression() {
         let condition = ConditionExpression::Comparison {
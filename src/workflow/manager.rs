@@ -3,10 +3,10 @@
 //! Provides workflow orchestration, execution, and monitoring capabilities
 //! for person-related business processes.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use async_trait::async_trait;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{Mutex, RwLock, mpsc};
 use futures::StreamExt;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -14,6 +14,8 @@ use serde_json;
 
 use crate::nats::{PersonSubject, PersonEventType, PersonAggregate, PersonActor};
 use super::definitions::*;
+use super::calendar::{BusinessCalendar, resolve_due_date};
+use super::store::{InMemoryWorkflowStore, WorkflowStore};
 
 /// Errors that can occur during workflow management
 #[derive(Debug, thiserror::Error)]
@@ -44,6 +46,9 @@ pub enum WorkflowError {
     
     #[error("External service error: {service}: {message}")]
     ExternalServiceError { service: String, message: String },
+    
+    #[error("Unresolved variable: {name}")]
+    UnresolvedVariable { name: String },
 }
 
 pub type WorkflowResult<T> = Result<T, WorkflowError>;
@@ -74,6 +79,12 @@ pub enum WorkflowEvent {
         node_id: String,
         error: String,
     },
+    /// Human task completed with a decision
+    HumanTaskCompleted {
+        instance_id: Uuid,
+        node_id: String,
+        decision: HumanTaskDecision,
+    },
     /// Workflow instance completed
     InstanceCompleted {
         instance_id: Uuid,
@@ -89,6 +100,18 @@ pub enum WorkflowEvent {
         instance_id: Uuid,
         reason: String,
     },
+    /// A completed node's compensation ran after the instance failed
+    Compensated {
+        instance_id: Uuid,
+        node_id: String,
+        output: HashMap<String, serde_json::Value>,
+    },
+    /// A completed node's compensation itself failed
+    CompensationFailed {
+        instance_id: Uuid,
+        node_id: String,
+        error: String,
+    },
 }
 
 /// Trait for workflow execution engines
@@ -115,12 +138,26 @@ pub trait WorkflowEngine: Send + Sync {
         script_content: &str,
         context: &WorkflowContext,
     ) -> WorkflowResult<serde_json::Value>;
+
+    /// Undo a completed node by running its compensation, if it declares one
+    async fn compensate_node(
+        &self,
+        node: &WorkflowNode,
+        context: &mut WorkflowContext,
+    ) -> WorkflowResult<Option<HashMap<String, serde_json::Value>>> {
+        match node.compensation_node() {
+            Some(compensation) => self.execute_node(&compensation, context).await.map(Some),
+            None => Ok(None),
+        }
+    }
 }
 
 /// Default workflow execution engine
 pub struct DefaultWorkflowEngine {
     service_registry: Arc<dyn ServiceRegistry>,
     nats_client: async_nats::Client,
+    calendar: BusinessCalendar,
+    assignee_calendars: HashMap<String, BusinessCalendar>,
 }
 
 impl DefaultWorkflowEngine {
@@ -128,8 +165,28 @@ impl DefaultWorkflowEngine {
         Self {
             service_registry,
             nats_client,
+            calendar: BusinessCalendar::standard(),
+            assignee_calendars: HashMap::new(),
         }
     }
+
+    /// Use this calendar for assignees without a calendar of their own
+    pub fn with_calendar(mut self, calendar: BusinessCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Compute an assignee's due dates in their own timezone and holidays
+    pub fn with_assignee_calendar(mut self, assignee: impl Into<String>, calendar: BusinessCalendar) -> Self {
+        self.assignee_calendars.insert(assignee.into(), calendar);
+        self
+    }
+
+    fn calendar_for(&self, assignee: Option<&str>) -> &BusinessCalendar {
+        assignee
+            .and_then(|a| self.assignee_calendars.get(a))
+            .unwrap_or(&self.calendar)
+    }
 }
 
 #[async_trait]
@@ -152,8 +209,10 @@ impl WorkflowEngine for DefaultWorkflowEngine {
             NodeType::WaitForEvent { event_pattern, timeout } => {
                 self.execute_wait_for_event(event_pattern, *timeout, context).await
             },
-            NodeType::HumanTask { assignee, form_definition, due_date } => {
-                self.execute_human_task(assignee.as_deref(), form_definition.as_deref(), *due_date, context).await
+            NodeType::HumanTask { assignee, form_definition, due_date, due_in_business_days, .. } => {
+                let calendar = self.calendar_for(assignee.as_deref());
+                let due_date = resolve_due_date(calendar, *due_date, *due_in_business_days, Utc::now());
+                self.execute_human_task(assignee.as_deref(), form_definition.as_deref(), due_date, context).await
             },
             NodeType::Script { script_type, script_content } => {
                 let result = self.execute_script(script_type, script_content, context).await?;
@@ -161,7 +220,7 @@ impl WorkflowEngine for DefaultWorkflowEngine {
                 output.insert("script_result".to_string(), result);
                 Ok(output)
             },
-            NodeType::ParallelGateway { branches } => {
+            NodeType::ParallelGateway { branches, .. } => {
                 self.execute_parallel_gateway(branches, context).await
             },
         }
@@ -331,13 +390,15 @@ impl DefaultWorkflowEngine {
         right: &serde_json::Value,
         context: &WorkflowContext,
     ) -> WorkflowResult<bool> {
-        let left_value = context.variables.get(left)
-            .cloned()
-            .unwrap_or(serde_json::json!(null));
+        let left_value = resolve_variable(left, context)?;
+        let right = match right.get("var").and_then(|name| name.as_str()) {
+            Some(name) if right.as_object().is_some_and(|obj| obj.len() == 1) => resolve_variable(name, context)?,
+            _ => right,
+        };
         
         let result = match operator {
-            ComparisonOperator::Equal => left_value == *right,
-            ComparisonOperator::NotEqual => left_value != *right,
+            ComparisonOperator::Equal => left_value == right,
+            ComparisonOperator::NotEqual => left_value != right,
             ComparisonOperator::GreaterThan => {
                 if let (Some(l), Some(r)) = (left_value.as_f64(), right.as_f64()) {
                     l > r
@@ -369,6 +430,8 @@ impl DefaultWorkflowEngine {
             ComparisonOperator::Contains => {
                 if let (Some(l), Some(r)) = (left_value.as_str(), right.as_str()) {
                     l.contains(r)
+                } else if let Some(items) = left_value.as_array() {
+                    items.contains(right)
                 } else {
                     false
                 }
@@ -428,6 +491,12 @@ impl DefaultWorkflowEngine {
     }
 }
 
+/// A variable a condition refers to, which must exist
+fn resolve_variable<'a>(name: &str, context: &'a WorkflowContext) -> WorkflowResult<&'a serde_json::Value> {
+    context.resolve(name)
+        .ok_or_else(|| WorkflowError::UnresolvedVariable { name: name.to_string() })
+}
+
 /// Service registry for workflow services
 #[async_trait]
 pub trait ServiceRegistry: Send + Sync {
@@ -445,9 +514,17 @@ pub trait WorkflowService: Send + Sync {
 }
 
 /// Main workflow manager
+///
+/// Instances are saved to the [`WorkflowStore`] after every state
+/// transition. Definitions are not stored: after a restart they must be
+/// registered again under the same ids before instances can resume.
 pub struct WorkflowManager {
     workflows: Arc<RwLock<HashMap<WorkflowId, WorkflowDefinition>>>,
-    instances: Arc<RwLock<HashMap<Uuid, WorkflowInstance>>>,
+    store: Arc<dyn WorkflowStore>,
+    /// Held from loading an instance to saving it wherever the instance's
+    /// current (stored) state decides whether the transition is allowed,
+    /// including each save of a running instance's progress
+    transitions: Mutex<()>,
     engine: Arc<dyn WorkflowEngine>,
     event_sender: mpsc::UnboundedSender<WorkflowEvent>,
     #[allow(dead_code)] // Reserved for future NATS event publishing
@@ -463,7 +540,8 @@ impl WorkflowManager {
         
         let manager = Self {
             workflows: Arc::new(RwLock::new(HashMap::new())),
-            instances: Arc::new(RwLock::new(HashMap::new())),
+            store: Arc::new(InMemoryWorkflowStore::new()),
+            transitions: Mutex::new(()),
             engine,
             event_sender,
             nats_client,
@@ -471,6 +549,17 @@ impl WorkflowManager {
         
         (manager, event_receiver)
     }
+
+    /// Keep instances in `store` instead of the default in-memory store
+    pub fn with_store(mut self, store: Arc<dyn WorkflowStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    async fn load(&self, instance_id: Uuid) -> WorkflowResult<WorkflowInstance> {
+        self.store.load_instance(instance_id).await?
+            .ok_or(WorkflowError::InstanceNotFound { instance_id })
+    }
     
     /// Register a workflow definition
     pub async fn register_workflow(&self, workflow: WorkflowDefinition) -> WorkflowResult<()> {
@@ -516,10 +605,7 @@ impl WorkflowManager {
             error: None,
         };
         
-        {
-            let mut instances = self.instances.write().await;
-            instances.insert(instance_id, instance);
-        }
+        self.store.save_instance(&instance).await?;
         
         // Send event
         let _ = self.event_sender.send(WorkflowEvent::InstanceStarted {
@@ -536,19 +622,14 @@ impl WorkflowManager {
     
     /// Execute workflow instance
     async fn execute_workflow(&self, instance_id: Uuid) -> WorkflowResult<()> {
-        let (workflow, mut instance) = {
+        let mut instance = self.load(instance_id).await?;
+        let workflow = {
             let workflows = self.workflows.read().await;
-            let instances = self.instances.read().await;
-            
-            let instance = instances.get(&instance_id)
-                .ok_or_else(|| WorkflowError::InstanceNotFound { instance_id })?;
-            
-            let workflow = workflows.get(&instance.workflow_id)
+            workflows.get(&instance.workflow_id)
                 .ok_or_else(|| WorkflowError::WorkflowNotFound { 
                     workflow_id: instance.workflow_id.clone() 
-                })?;
-            
-            (workflow.clone(), instance.clone())
+                })?
+                .clone()
         };
         
         while let Some(current_node_id) = &instance.current_node_id {
@@ -572,30 +653,34 @@ impl WorkflowManager {
                 node_id: current_node_id.clone(),
             });
             
-            // Execute node
+            // Execute node; parallel branches are nodes of this workflow,
+            // so the manager runs them itself and records each in the history
             let execution_start = Utc::now();
-            match self.engine.execute_node(current_node, &mut instance.context).await {
+            let result = match &current_node.node_type {
+                NodeType::ParallelGateway { branches, join_policy } => {
+                    self.execute_parallel_branches(
+                        instance_id,
+                        &workflow,
+                        branches,
+                        *join_policy,
+                        &instance.context,
+                        &mut instance.execution_history,
+                    ).await
+                }
+                _ => self.engine.execute_node(current_node, &mut instance.context).await,
+            };
+            match result {
                 Ok(output) => {
-                    let execution_end = Utc::now();
-                    
+                    instance.context.variables.extend(output.clone());
+
                     // Record execution
-                    let execution = WorkflowExecution {
-                        node_id: current_node_id.clone(),
-                        started_at: execution_start,
-                        ended_at: Some(execution_end),
-                        status: ExecutionStatus::Completed,
-                        input_data: HashMap::new(), // Could capture actual inputs
-                        output_data: output.clone(),
-                        error: None,
-                        metrics: ExecutionMetrics {
-                            duration_ms: (execution_end - execution_start).num_milliseconds() as u64,
-                            memory_usage_bytes: None,
-                            cpu_usage_percent: None,
-                            retry_count: 0,
-                        },
-                    };
-                    
-                    instance.execution_history.push(execution);
+                    instance.execution_history.push(execution_record(
+                        current_node_id,
+                        execution_start,
+                        ExecutionStatus::Completed,
+                        output.clone(),
+                        None,
+                    ));
                     
                     // Send node completed event
                     let _ = self.event_sender.send(WorkflowEvent::NodeCompleted {
@@ -603,44 +688,39 @@ impl WorkflowManager {
                         node_id: current_node_id.clone(),
                         output,
                     });
+
+                    // Human tasks wait for complete_human_task
+                    if matches!(current_node.node_type, NodeType::HumanTask { .. }) {
+                        instance.state = WorkflowState::Waiting;
+                        break;
+                    }
                     
                     // Find next node
-                    let next_node_id = self.find_next_node(&workflow, current_node_id, &instance.context).await?;
-                    instance.current_node_id = next_node_id;
+                    match self.find_next_node(&workflow, current_node_id, &instance.context).await {
+                        Ok(next_node_id) => {
+                            instance.current_node_id = next_node_id;
+                            if !self.save_if_running(&instance).await? {
+                                return Ok(());
+                            }
+                        }
+                        Err(e) => {
+                            let node_id = current_node_id.clone();
+                            self.fail_instance(&mut instance, &workflow, &node_id, "CONDITION_ERROR", &e).await;
+                            break;
+                        }
+                    }
                 },
                 Err(e) => {
-                    instance.state = WorkflowState::Failed;
-                    instance.ended_at = Some(Utc::now());
-                    instance.error = Some(super::definitions::WorkflowError {
-                        code: "EXECUTION_ERROR".to_string(),
-                        message: e.to_string(),
-                        node_id: Some(current_node_id.clone()),
-                        details: None,
-                        timestamp: Utc::now(),
-                        recoverable: false,
-                    });
-                    
-                    // Send failure events
-                    let _ = self.event_sender.send(WorkflowEvent::NodeFailed {
-                        instance_id,
-                        node_id: current_node_id.clone(),
-                        error: e.to_string(),
-                    });
-                    
-                    let _ = self.event_sender.send(WorkflowEvent::InstanceFailed {
-                        instance_id,
-                        error: e.to_string(),
-                    });
-                    
+                    let node_id = current_node_id.clone();
+                    self.fail_instance(&mut instance, &workflow, &node_id, "EXECUTION_ERROR", &e).await;
                     break;
                 }
             }
         }
         
         // Update instance in storage
-        {
-            let mut instances = self.instances.write().await;
-            instances.insert(instance_id, instance.clone());
+        if !self.save_if_running(&instance).await? {
+            return Ok(());
         }
         
         // Send completion event if successful
@@ -654,6 +734,235 @@ impl WorkflowManager {
         Ok(())
     }
     
+    /// Save a running instance's progress unless it stopped running meanwhile
+    ///
+    /// Returns false, leaving the stored instance alone, when it was
+    /// cancelled or otherwise moved on while a node executed.
+    async fn save_if_running(&self, instance: &WorkflowInstance) -> WorkflowResult<bool> {
+        let _transition = self.transitions.lock().await;
+        if self.load(instance.instance_id).await?.state != WorkflowState::Running {
+            return Ok(false);
+        }
+        self.store.save_instance(instance).await?;
+        Ok(true)
+    }
+
+    /// Mark an instance failed at `node_id`, compensate it and report it
+    ///
+    /// Covers both a node that failed and one whose outgoing transitions
+    /// could not be evaluated; the caller saves the instance.
+    async fn fail_instance(
+        &self,
+        instance: &mut WorkflowInstance,
+        workflow: &WorkflowDefinition,
+        node_id: &str,
+        code: &str,
+        e: &WorkflowError,
+    ) {
+        let instance_id = instance.instance_id;
+        instance.state = WorkflowState::Failed;
+        instance.ended_at = Some(Utc::now());
+        instance.error = Some(super::definitions::WorkflowError {
+            code: code.to_string(),
+            message: e.to_string(),
+            node_id: Some(node_id.to_string()),
+            details: None,
+            timestamp: Utc::now(),
+            recoverable: false,
+        });
+
+        // Send failure events
+        let _ = self.event_sender.send(WorkflowEvent::NodeFailed {
+            instance_id,
+            node_id: node_id.to_string(),
+            error: e.to_string(),
+        });
+
+        self.compensate(
+            instance_id,
+            workflow,
+            &mut instance.context,
+            &mut instance.execution_history,
+        ).await;
+
+        let _ = self.event_sender.send(WorkflowEvent::InstanceFailed {
+            instance_id,
+            error: e.to_string(),
+        });
+    }
+
+    /// Fork every branch of a parallel gateway and join them under `join_policy`
+    ///
+    /// Branches run concurrently, each on its own copy of the context, and a
+    /// branch stops at its first failing node or when its timeout expires.
+    /// The join waits for all of them. Outputs of the successful branches
+    /// become the gateway's output, in branch order; the completed nodes of
+    /// every branch stay in the history so they are compensated if the
+    /// workflow fails later.
+    async fn execute_parallel_branches(
+        &self,
+        instance_id: Uuid,
+        workflow: &WorkflowDefinition,
+        branches: &[ParallelBranch],
+        join_policy: JoinPolicy,
+        context: &WorkflowContext,
+        history: &mut Vec<WorkflowExecution>,
+    ) -> WorkflowResult<HashMap<String, serde_json::Value>> {
+        let outcomes = futures::future::join_all(
+            branches.iter().map(|branch| self.execute_branch(instance_id, workflow, branch, context.clone())),
+        ).await;
+
+        let mut output = HashMap::new();
+        let mut failures = Vec::new();
+        for (branch, outcome) in branches.iter().zip(outcomes) {
+            history.extend(outcome.executions);
+            match outcome.result {
+                Ok(branch_output) => output.extend(branch_output),
+                Err(e) => failures.push(format!("{}: {}", branch.name, e)),
+            }
+        }
+
+        let joined = match join_policy {
+            JoinPolicy::All => failures.is_empty(),
+            JoinPolicy::Any => failures.len() < branches.len() || branches.is_empty(),
+        };
+        if !joined {
+            return Err(WorkflowError::ExecutionError {
+                message: format!("Parallel branches failed: {}", failures.join("; ")),
+            });
+        }
+
+        output.insert("parallel_completed".to_string(), serde_json::json!(true));
+        output.insert("failed_branches".to_string(), serde_json::json!(failures));
+        Ok(output)
+    }
+
+    async fn execute_branch(
+        &self,
+        instance_id: Uuid,
+        workflow: &WorkflowDefinition,
+        branch: &ParallelBranch,
+        mut context: WorkflowContext,
+    ) -> BranchOutcome {
+        let mut executions = Vec::new();
+        let mut output = HashMap::new();
+        let mut running: Option<(String, DateTime<Utc>)> = None;
+
+        let run = async {
+            for node_id in &branch.nodes {
+                let node = workflow.nodes.iter()
+                    .find(|n| &n.id == node_id)
+                    .ok_or_else(|| WorkflowError::NodeNotFound { node_id: node_id.clone() })?;
+
+                let _ = self.event_sender.send(WorkflowEvent::NodeStarted {
+                    instance_id,
+                    node_id: node_id.clone(),
+                });
+
+                let started_at = Utc::now();
+                running = Some((node_id.clone(), started_at));
+                let result = self.engine.execute_node(node, &mut context).await;
+                running = None;
+
+                match result {
+                    Ok(node_output) => {
+                        context.variables.extend(node_output.clone());
+                        output.extend(node_output.clone());
+                        executions.push(execution_record(node_id, started_at, ExecutionStatus::Completed, node_output.clone(), None));
+                        let _ = self.event_sender.send(WorkflowEvent::NodeCompleted {
+                            instance_id,
+                            node_id: node_id.clone(),
+                            output: node_output,
+                        });
+                    }
+                    Err(e) => {
+                        executions.push(execution_record(node_id, started_at, ExecutionStatus::Failed, HashMap::new(), Some(e.to_string())));
+                        let _ = self.event_sender.send(WorkflowEvent::NodeFailed {
+                            instance_id,
+                            node_id: node_id.clone(),
+                            error: e.to_string(),
+                        });
+                        return Err(e);
+                    }
+                }
+            }
+            Ok::<(), WorkflowError>(())
+        };
+
+        let result = match branch.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, run).await.unwrap_or_else(|_| {
+                Err(WorkflowError::ExecutionError {
+                    message: format!("Branch {} timed out after {:?}", branch.name, timeout),
+                })
+            }),
+            None => run.await,
+        };
+
+        // A timeout cancels the node mid-flight; record it as failed
+        if let (Some((node_id, started_at)), Err(e)) = (running, &result) {
+            executions.push(execution_record(&node_id, started_at, ExecutionStatus::Failed, HashMap::new(), Some(e.to_string())));
+            let _ = self.event_sender.send(WorkflowEvent::NodeFailed {
+                instance_id,
+                node_id,
+                error: e.to_string(),
+            });
+        }
+
+        BranchOutcome {
+            result: result.map(|()| output),
+            executions,
+        }
+    }
+
+    /// Undo a failed instance's completed nodes, most recent first
+    ///
+    /// Each completed node that declares a compensation gets it run once,
+    /// however many completed entries it has in the history: a human task
+    /// records both its run and its decision. A failing compensation is recorded and reported but does not
+    /// stop the rest: each one undoes a separate side effect.
+    async fn compensate(
+        &self,
+        instance_id: Uuid,
+        workflow: &WorkflowDefinition,
+        context: &mut WorkflowContext,
+        history: &mut Vec<WorkflowExecution>,
+    ) {
+        let mut seen = HashSet::new();
+        let completed: Vec<String> = history.iter()
+            .rev()
+            .filter(|execution| matches!(execution.status, ExecutionStatus::Completed))
+            .map(|execution| execution.node_id.clone())
+            .filter(|node_id| seen.insert(node_id.clone()))
+            .collect();
+
+        for node_id in completed {
+            let Some(node) = workflow.nodes.iter().find(|n| n.id == node_id) else {
+                continue;
+            };
+
+            let started_at = Utc::now();
+            match self.engine.compensate_node(node, context).await {
+                Ok(None) => {}
+                Ok(Some(output)) => {
+                    history.push(execution_record(&node_id, started_at, ExecutionStatus::Compensated, output.clone(), None));
+                    let _ = self.event_sender.send(WorkflowEvent::Compensated {
+                        instance_id,
+                        node_id,
+                        output,
+                    });
+                }
+                Err(e) => {
+                    history.push(execution_record(&node_id, started_at, ExecutionStatus::Failed, HashMap::new(), Some(e.to_string())));
+                    let _ = self.event_sender.send(WorkflowEvent::CompensationFailed {
+                        instance_id,
+                        node_id,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+    }
+    
     /// Find the next node to execute
     async fn find_next_node(
         &self,
@@ -686,36 +995,140 @@ impl WorkflowManager {
         Ok(None)
     }
     
+    /// Complete the human task an instance is waiting on and resume it
+    ///
+    /// The decision is bound into the instance variables and recorded in its
+    /// execution history before the next node is chosen.
+    pub async fn complete_human_task(
+        &self,
+        instance_id: Uuid,
+        decision: HumanTaskDecision,
+    ) -> WorkflowResult<()> {
+        {
+            let _transition = self.transitions.lock().await;
+            let workflows = self.workflows.read().await;
+            let mut instance = self.load(instance_id).await?;
+            if instance.state != WorkflowState::Waiting {
+                return Err(WorkflowError::InvalidStateTransition {
+                    from: instance.state.clone(),
+                    to: WorkflowState::Running,
+                });
+            }
+            let workflow = workflows.get(&instance.workflow_id)
+                .ok_or_else(|| WorkflowError::WorkflowNotFound {
+                    workflow_id: instance.workflow_id.clone(),
+                })?;
+
+            let node_id = instance.current_node_id.clone().unwrap_or_default();
+            let decision_variable = match workflow.nodes.iter().find(|n| n.id == node_id) {
+                Some(WorkflowNode { node_type: NodeType::HumanTask { decision_variable, .. }, .. }) => {
+                    decision_variable.clone()
+                }
+                _ => return Err(WorkflowError::NodeNotFound { node_id }),
+            };
+
+            instance.record_human_decision(&node_id, decision_variable.as_deref(), &decision);
+            match self.find_next_node(workflow, &node_id, &instance.context).await {
+                Ok(next_node_id) => {
+                    instance.current_node_id = next_node_id;
+                    instance.state = WorkflowState::Running;
+                }
+                Err(e) => {
+                    self.fail_instance(&mut instance, workflow, &node_id, "CONDITION_ERROR", &e).await;
+                }
+            }
+            self.store.save_instance(&instance).await?;
+
+            let _ = self.event_sender.send(WorkflowEvent::HumanTaskCompleted {
+                instance_id,
+                node_id,
+                decision,
+            });
+            if instance.state == WorkflowState::Failed {
+                return Ok(());
+            }
+        }
+
+        self.execute_workflow(instance_id).await
+    }
+
     /// Get workflow instance
     pub async fn get_instance(&self, instance_id: Uuid) -> WorkflowResult<WorkflowInstance> {
-        let instances = self.instances.read().await;
-        instances.get(&instance_id)
-            .cloned()
-            .ok_or_else(|| WorkflowError::InstanceNotFound { instance_id })
+        self.load(instance_id).await
     }
     
     /// Cancel workflow instance
     pub async fn cancel_instance(&self, instance_id: Uuid, reason: String) -> WorkflowResult<()> {
-        let mut instances = self.instances.write().await;
-        if let Some(instance) = instances.get_mut(&instance_id) {
+        {
+            let _transition = self.transitions.lock().await;
+            let mut instance = self.load(instance_id).await?;
             instance.state = WorkflowState::Cancelled;
             instance.ended_at = Some(Utc::now());
-            
-            let _ = self.event_sender.send(WorkflowEvent::InstanceCancelled {
-                instance_id,
-                reason,
-            });
-            
-            Ok(())
-        } else {
-            Err(WorkflowError::InstanceNotFound { instance_id })
+            self.store.save_instance(&instance).await?;
         }
+        
+        let _ = self.event_sender.send(WorkflowEvent::InstanceCancelled {
+            instance_id,
+            reason,
+        });
+        
+        Ok(())
     }
     
-    /// List workflow instances
-    pub async fn list_instances(&self) -> Vec<WorkflowInstance> {
-        let instances = self.instances.read().await;
-        instances.values().cloned().collect()
+    /// List workflow instances, as kept in the store
+    pub async fn list_instances(&self) -> WorkflowResult<Vec<WorkflowInstance>> {
+        self.store.list_instances().await
+    }
+
+    /// Instances that have not finished, as kept in the store
+    pub async fn list_active_instances(&self) -> WorkflowResult<Vec<WorkflowInstance>> {
+        self.store.list_active().await
+    }
+
+    /// Continue a running instance loaded from the store, e.g. after a restart
+    ///
+    /// Waiting instances resume through `complete_human_task` instead.
+    pub async fn resume_instance(&self, instance_id: Uuid) -> WorkflowResult<()> {
+        let instance = self.load(instance_id).await?;
+        if instance.state != WorkflowState::Running {
+            return Err(WorkflowError::InvalidStateTransition {
+                from: instance.state,
+                to: WorkflowState::Running,
+            });
+        }
+        self.execute_workflow(instance_id).await
+    }
+}
+
+/// What one parallel branch did
+struct BranchOutcome {
+    result: WorkflowResult<HashMap<String, serde_json::Value>>,
+    executions: Vec<WorkflowExecution>,
+}
+
+/// History entry for a node that finished just now
+fn execution_record(
+    node_id: &str,
+    started_at: DateTime<Utc>,
+    status: ExecutionStatus,
+    output: HashMap<String, serde_json::Value>,
+    error: Option<String>,
+) -> WorkflowExecution {
+    let ended_at = Utc::now();
+    WorkflowExecution {
+        node_id: node_id.to_string(),
+        started_at,
+        ended_at: Some(ended_at),
+        status,
+        input_data: HashMap::new(), // Could capture actual inputs
+        output_data: output,
+        error,
+        metrics: ExecutionMetrics {
+            duration_ms: (ended_at - started_at).num_milliseconds() as u64,
+            memory_usage_bytes: None,
+            cpu_usage_percent: None,
+            retry_count: 0,
+        },
     }
 }
 
@@ -788,4 +1201,679 @@ mod tests {
             assert!(matches!(operator, ComparisonOperator::GreaterThan));
         }
     }
-}
\ No newline at end of file
+    /// Engine that records the variables each service node sees
+    #[derive(Default)]
+    struct RecordingEngine {
+        seen: tokio::sync::Mutex<HashMap<String, HashMap<String, serde_json::Value>>>,
+    }
+
+    #[async_trait]
+    impl WorkflowEngine for RecordingEngine {
+        async fn execute_node(
+            &self,
+            node: &WorkflowNode,
+            context: &mut WorkflowContext,
+        ) -> WorkflowResult<HashMap<String, serde_json::Value>> {
+            self.seen.lock().await.insert(node.id.clone(), context.variables.clone());
+            Ok(HashMap::new())
+        }
+
+        async fn evaluate_condition(
+            &self,
+            condition: &ConditionExpression,
+            context: &WorkflowContext,
+        ) -> WorkflowResult<bool> {
+            match condition {
+                ConditionExpression::Comparison { left, right, .. } => {
+                    Ok(context.variables.get(left) == Some(right))
+                }
+                _ => Ok(true),
+            }
+        }
+
+        async fn execute_script(
+            &self,
+            _script_type: &ScriptType,
+            _script_content: &str,
+            _context: &WorkflowContext,
+        ) -> WorkflowResult<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    fn node(id: &str, node_type: NodeType) -> WorkflowNode {
+        WorkflowNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            node_type,
+            configuration: NodeConfiguration::default(),
+            timeout: None,
+            retry_policy: None,
+            compensation: None,
+        }
+    }
+
+    fn service(id: &str) -> WorkflowNode {
+        node(id, NodeType::ServiceInvocation {
+            service: "HrService".to_string(),
+            operation: id.to_string(),
+            input_mapping: None,
+            output_mapping: None,
+        })
+    }
+
+    fn approved_is(value: bool) -> Option<ConditionExpression> {
+        Some(ConditionExpression::Comparison {
+            left: "hr_approved".to_string(),
+            operator: ComparisonOperator::Equal,
+            right: serde_json::json!(value),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_rejection_reason_reaches_next_node_and_history() {
+        // Never used by the recording engine; no server is needed
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let engine = Arc::new(RecordingEngine::default());
+        let (manager, _events) = WorkflowManager::new(engine.clone(), nats_client);
+
+        let workflow = WorkflowBuilder::new("Hire", PersonWorkflowType::EmploymentLifecycle)
+            .node(node("hr_approval", NodeType::HumanTask {
+                assignee: Some("hr-manager".to_string()),
+                form_definition: None,
+                due_date: None,
+                due_in_business_days: None,
+                decision_variable: Some("hr_approved".to_string()),
+            }))
+            .node(service("create_employment_record"))
+            .node(service("notify_rejection"))
+            .node(service("end"))
+            .transition("hr_approval", "create_employment_record", approved_is(true))
+            .transition("hr_approval", "notify_rejection", approved_is(false))
+            .transition("notify_rejection", "end", None)
+            .transition("create_employment_record", "end", None)
+            .start("hr_approval")
+            .end("end")
+            .build()
+            .unwrap();
+        let workflow_id = workflow.id.clone();
+        manager.register_workflow(workflow).await.unwrap();
+
+        let instance_id = manager
+            .start_workflow(&workflow_id, HashMap::new(), PersonActor::System("test".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(manager.get_instance(instance_id).await.unwrap().state, WorkflowState::Waiting);
+
+        let decision = HumanTaskDecision::reject("hr-manager", "failed_reference_check")
+            .with_comments("Two references could not be verified");
+        manager.complete_human_task(instance_id, decision).await.unwrap();
+
+        let seen = engine.seen.lock().await;
+        let next = &seen["notify_rejection"];
+        assert_eq!(next["hr_approval.reason"], serde_json::json!("failed_reference_check"));
+        assert_eq!(next["hr_approved"], serde_json::json!(false));
+        assert!(!seen.contains_key("create_employment_record"));
+
+        let instance = manager.get_instance(instance_id).await.unwrap();
+        assert_eq!(instance.state, WorkflowState::Completed);
+        let recorded = instance.execution_history.iter()
+            .find(|execution| execution.node_id == "hr_approval" && execution.output_data.contains_key("reason"))
+            .expect("decision recorded in history");
+        assert_eq!(recorded.output_data["reason"], serde_json::json!("failed_reference_check"));
+        assert_eq!(recorded.output_data["comments"], serde_json::json!("Two references could not be verified"));
+    }
+
+    /// Services that log every call and fail one operation
+    struct ScriptedRegistry {
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+        failing_operation: &'static str,
+    }
+
+    struct ScriptedService {
+        name: String,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+        failing_operation: &'static str,
+    }
+
+    #[async_trait]
+    impl ServiceRegistry for ScriptedRegistry {
+        async fn get_service(&self, name: &str) -> Result<Arc<dyn WorkflowService>, Box<dyn std::error::Error>> {
+            Ok(Arc::new(ScriptedService {
+                name: name.to_string(),
+                calls: self.calls.clone(),
+                failing_operation: self.failing_operation,
+            }))
+        }
+    }
+
+    #[async_trait]
+    impl WorkflowService for ScriptedService {
+        async fn execute(
+            &self,
+            operation: &str,
+            _inputs: &HashMap<String, serde_json::Value>,
+        ) -> Result<HashMap<String, serde_json::Value>, Box<dyn std::error::Error>> {
+            self.calls.lock().unwrap().push(format!("{}.{}", self.name, operation));
+            if operation == self.failing_operation {
+                return Err(format!("{} unavailable", self.name).into());
+            }
+            Ok(HashMap::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_systems_setup_compensates_completed_steps() {
+        // Only the human task publishes, and that is buffered; no server is needed
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let registry = Arc::new(ScriptedRegistry { calls: calls.clone(), failing_operation: "enroll" });
+        let engine = Arc::new(DefaultWorkflowEngine::new(registry, nats_client.clone()));
+        let (manager, mut events) = WorkflowManager::new(engine, nats_client);
+
+        let mut workflow = crate::workflow::create_employment_lifecycle_workflow();
+        // Stands in for the background check service's verdict
+        workflow.global_config.variables.insert("background_check_passed".to_string(), serde_json::json!(true));
+        let workflow_id = workflow.id.clone();
+        manager.register_workflow(workflow).await.unwrap();
+
+        let instance_id = manager
+            .start_workflow(&workflow_id, HashMap::new(), PersonActor::System("test".to_string()))
+            .await
+            .unwrap();
+        manager.complete_human_task(instance_id, HumanTaskDecision::approve("hr-manager")).await.unwrap();
+
+        let instance = manager.get_instance(instance_id).await.unwrap();
+        assert_eq!(instance.state, WorkflowState::Failed);
+        assert_eq!(instance.error.unwrap().node_id.as_deref(), Some("setup_employee_systems"));
+
+        let mut compensated = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let WorkflowEvent::Compensated { node_id, .. } = event {
+                compensated.push(node_id);
+            }
+        }
+        assert_eq!(compensated, ["setup_payroll", "provision_it_access", "create_employment_record", "start_employment"]);
+
+        let calls = calls.lock().unwrap();
+        assert!(calls.contains(&"ITService.revoke_access".to_string()));
+        assert!(calls.contains(&"PayrollService.remove_payroll".to_string()));
+        // The enrollment failed, so there is nothing to cancel
+        assert!(!calls.contains(&"BenefitsService.cancel_enrollment".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_human_task_is_compensated_once() {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let registry = Arc::new(ScriptedRegistry { calls: calls.clone(), failing_operation: "enroll" });
+        let engine = Arc::new(DefaultWorkflowEngine::new(registry, nats_client.clone()));
+        let (manager, mut events) = WorkflowManager::new(engine, nats_client);
+
+        let mut approval = node("hr_approval", NodeType::HumanTask {
+            assignee: Some("hr-manager".to_string()),
+            form_definition: None,
+            due_date: None,
+            due_in_business_days: None,
+            decision_variable: Some("hr_approved".to_string()),
+        });
+        approval.compensation = Some(NodeType::ServiceInvocation {
+            service: "HrService".to_string(),
+            operation: "withdraw_offer".to_string(),
+            input_mapping: None,
+            output_mapping: None,
+        });
+        let workflow = WorkflowBuilder::new("Hire", PersonWorkflowType::EmploymentLifecycle)
+            .node(approval)
+            .node(service("enroll"))
+            .node(service("end"))
+            .transition("hr_approval", "enroll", None)
+            .transition("enroll", "end", None)
+            .start("hr_approval")
+            .end("end")
+            .build()
+            .unwrap();
+        let workflow_id = workflow.id.clone();
+        manager.register_workflow(workflow).await.unwrap();
+
+        let instance_id = manager
+            .start_workflow(&workflow_id, HashMap::new(), PersonActor::System("test".to_string()))
+            .await
+            .unwrap();
+        manager.complete_human_task(instance_id, HumanTaskDecision::approve("hr-manager")).await.unwrap();
+        assert_eq!(manager.get_instance(instance_id).await.unwrap().state, WorkflowState::Failed);
+
+        let mut compensated = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let WorkflowEvent::Compensated { node_id, .. } = event {
+                compensated.push(node_id);
+            }
+        }
+        assert_eq!(compensated, ["hr_approval"]);
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.iter().filter(|call| *call == "HrService.withdraw_offer").count(), 1);
+    }
+
+    fn compare(left: &str, operator: ComparisonOperator, right: serde_json::Value) -> Box<ConditionExpression> {
+        Box::new(ConditionExpression::Comparison { left: left.to_string(), operator, right })
+    }
+
+    #[tokio::test]
+    async fn test_conditions_resolve_against_context() {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let engine = DefaultWorkflowEngine::new(Arc::new(MockServiceRegistry), nats_client);
+        let context = WorkflowContext {
+            input_data: HashMap::from([("min_age".to_string(), serde_json::json!(18))]),
+            variables: HashMap::from([
+                ("identity_valid".to_string(), serde_json::json!(true)),
+                ("age".to_string(), serde_json::json!(21)),
+                ("name".to_string(), serde_json::json!("Ada Lovelace")),
+                ("roles".to_string(), serde_json::json!(["engineer", "author"])),
+            ]),
+            output_data: HashMap::new(),
+            correlation_id: "test".to_string(),
+            actor: "test".to_string(),
+        };
+        let eval = |condition: ConditionExpression| {
+            let engine = &engine;
+            let context = &context;
+            async move { engine.evaluate_condition(&condition, context).await }
+        };
+
+        use ComparisonOperator::*;
+        let holds = [
+            compare("identity_valid", Equal, serde_json::json!(true)),
+            compare("age", NotEqual, serde_json::json!(30)),
+            compare("age", GreaterThan, serde_json::json!({"var": "min_age"})),
+            compare("age", GreaterThanOrEqual, serde_json::json!(21)),
+            compare("age", LessThan, serde_json::json!(65)),
+            compare("age", LessThanOrEqual, serde_json::json!(21)),
+            compare("name", Contains, serde_json::json!("Love")),
+            compare("roles", Contains, serde_json::json!("author")),
+            compare("name", StartsWith, serde_json::json!("Ada")),
+            compare("name", EndsWith, serde_json::json!("lace")),
+        ];
+        for condition in holds {
+            assert!(eval(*condition.clone()).await.unwrap(), "{condition:?}");
+        }
+        assert!(eval(ConditionExpression::Boolean(true)).await.unwrap());
+
+        // (age < 18 OR identity_valid) AND NOT name starts with "Bob"
+        let nested = ConditionExpression::Logical {
+            operator: LogicalOperator::And,
+            operands: vec![
+                Box::new(ConditionExpression::Logical {
+                    operator: LogicalOperator::Or,
+                    operands: vec![compare("age", LessThan, serde_json::json!(18)), compare("identity_valid", Equal, serde_json::json!(true))],
+                }),
+                Box::new(ConditionExpression::Logical {
+                    operator: LogicalOperator::Not,
+                    operands: vec![compare("name", StartsWith, serde_json::json!("Bob"))],
+                }),
+            ],
+        };
+        assert!(eval(nested).await.unwrap());
+
+        for missing in [
+            *compare("identity_verified", Equal, serde_json::json!(true)),
+            *compare("age", GreaterThan, serde_json::json!({"var": "max_age"})),
+        ] {
+            assert!(matches!(
+                eval(missing).await,
+                Err(WorkflowError::UnresolvedVariable { name }) if name == "identity_verified" || name == "max_age"
+            ));
+        }
+    }
+
+    /// Engine whose "slow" node never finishes in time
+    struct DelayEngine;
+
+    #[async_trait]
+    impl WorkflowEngine for DelayEngine {
+        async fn execute_node(
+            &self,
+            node: &WorkflowNode,
+            _context: &mut WorkflowContext,
+        ) -> WorkflowResult<HashMap<String, serde_json::Value>> {
+            if node.id == "slow" {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+            }
+            Ok(HashMap::from([(format!("{}_done", node.id), serde_json::json!(true))]))
+        }
+
+        async fn evaluate_condition(
+            &self,
+            _condition: &ConditionExpression,
+            _context: &WorkflowContext,
+        ) -> WorkflowResult<bool> {
+            Ok(true)
+        }
+
+        async fn execute_script(
+            &self,
+            _script_type: &ScriptType,
+            _script_content: &str,
+            _context: &WorkflowContext,
+        ) -> WorkflowResult<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    async fn run_fork(join_policy: JoinPolicy) -> WorkflowInstance {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let (manager, _events) = WorkflowManager::new(Arc::new(DelayEngine), nats_client);
+
+        let branch = |name: &str, timeout: Option<std::time::Duration>| ParallelBranch {
+            name: name.to_string(),
+            nodes: vec![name.to_string()],
+            timeout,
+        };
+        let workflow = WorkflowBuilder::new("Fork", PersonWorkflowType::PersonOnboarding)
+            .node(node("fork", NodeType::ParallelGateway {
+                branches: vec![
+                    branch("fast", None),
+                    branch("steady", Some(std::time::Duration::from_secs(5))),
+                    branch("slow", Some(std::time::Duration::from_millis(20))),
+                ],
+                join_policy,
+            }))
+            .node(service("fast"))
+            .node(service("steady"))
+            .node(service("slow"))
+            .node(service("end"))
+            .transition("fork", "end", None)
+            .start("fork")
+            .end("end")
+            .build()
+            .unwrap();
+        let workflow_id = workflow.id.clone();
+        manager.register_workflow(workflow).await.unwrap();
+
+        let instance_id = manager
+            .start_workflow(&workflow_id, HashMap::new(), PersonActor::System("test".to_string()))
+            .await
+            .unwrap();
+        manager.get_instance(instance_id).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_branch_timeout_under_any_and_all_joins() {
+        let instance = run_fork(JoinPolicy::Any).await;
+        assert_eq!(instance.state, WorkflowState::Completed);
+        assert_eq!(instance.context.variables["fast_done"], serde_json::json!(true));
+        assert_eq!(instance.context.variables["steady_done"], serde_json::json!(true));
+        assert!(!instance.context.variables.contains_key("slow_done"));
+        let slow = instance.execution_history.iter().find(|e| e.node_id == "slow").unwrap();
+        assert!(matches!(slow.status, ExecutionStatus::Failed));
+        assert!(slow.error.as_deref().unwrap().contains("timed out"));
+
+        let instance = run_fork(JoinPolicy::All).await;
+        assert_eq!(instance.state, WorkflowState::Failed);
+        let error = instance.error.unwrap();
+        assert_eq!(error.node_id.as_deref(), Some("fork"));
+        assert!(error.message.contains("slow: Execution error: Branch slow timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_waiting_instance_survives_manager_restart() {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let store: Arc<dyn WorkflowStore> = Arc::new(InMemoryWorkflowStore::new());
+        let workflow = WorkflowBuilder::new("Hire", PersonWorkflowType::EmploymentLifecycle)
+            .node(node("hr_approval", NodeType::HumanTask {
+                assignee: Some("hr-manager".to_string()),
+                form_definition: None,
+                due_date: None,
+                due_in_business_days: None,
+                decision_variable: Some("hr_approved".to_string()),
+            }))
+            .node(service("create_employment_record"))
+            .node(service("end"))
+            .transition("hr_approval", "create_employment_record", approved_is(true))
+            .transition("create_employment_record", "end", None)
+            .start("hr_approval")
+            .end("end")
+            .build()
+            .unwrap();
+        let workflow_id = workflow.id.clone();
+
+        let instance_id = {
+            let (manager, _events) = WorkflowManager::new(Arc::new(RecordingEngine::default()), nats_client.clone());
+            let manager = manager.with_store(store.clone());
+            manager.register_workflow(workflow.clone()).await.unwrap();
+            manager
+                .start_workflow(&workflow_id, HashMap::new(), PersonActor::System("test".to_string()))
+                .await
+                .unwrap()
+        };
+
+        // A fresh manager over the same store picks the instance back up
+        let engine = Arc::new(RecordingEngine::default());
+        let (manager, _events) = WorkflowManager::new(engine.clone(), nats_client);
+        let manager = manager.with_store(store);
+        let active = manager.list_active_instances().await.unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].state, WorkflowState::Waiting);
+        assert_eq!(active[0].current_node_id.as_deref(), Some("hr_approval"));
+
+        manager.register_workflow(workflow).await.unwrap();
+        manager.complete_human_task(instance_id, HumanTaskDecision::approve("hr-manager")).await.unwrap();
+
+        assert!(engine.seen.lock().await.contains_key("create_employment_record"));
+        assert_eq!(manager.get_instance(instance_id).await.unwrap().state, WorkflowState::Completed);
+        assert!(manager.list_active_instances().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_decisions_complete_a_human_task_once() {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let workflow = WorkflowBuilder::new("Hire", PersonWorkflowType::EmploymentLifecycle)
+            .node(node("hr_approval", NodeType::HumanTask {
+                assignee: Some("hr-manager".to_string()),
+                form_definition: None,
+                due_date: None,
+                due_in_business_days: None,
+                decision_variable: Some("hr_approved".to_string()),
+            }))
+            .node(service("end"))
+            .transition("hr_approval", "end", None)
+            .start("hr_approval")
+            .end("end")
+            .build()
+            .unwrap();
+        let workflow_id = workflow.id.clone();
+
+        let engine = Arc::new(RecordingEngine::default());
+        let (manager, _events) = WorkflowManager::new(engine, nats_client);
+        manager.register_workflow(workflow).await.unwrap();
+        let instance_id = manager
+            .start_workflow(&workflow_id, HashMap::new(), PersonActor::System("test".to_string()))
+            .await
+            .unwrap();
+
+        let (first, second) = tokio::join!(
+            manager.complete_human_task(instance_id, HumanTaskDecision::approve("hr-manager")),
+            manager.complete_human_task(instance_id, HumanTaskDecision::approve("hr-deputy")),
+        );
+        assert_eq!([&first, &second].iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(matches!(
+            first.err().or(second.err()),
+            Some(WorkflowError::InvalidStateTransition { .. })
+        ));
+
+        let instances = manager.list_instances().await.unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].state, WorkflowState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_unresolved_transition_condition_fails_the_instance() {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let engine = Arc::new(DefaultWorkflowEngine::new(Arc::new(MockServiceRegistry), nats_client.clone()));
+        let (manager, mut events) = WorkflowManager::new(engine, nats_client);
+        let unknown = Some(*compare("identity_verified", ComparisonOperator::Equal, serde_json::json!(true)));
+
+        let automated = WorkflowBuilder::new("Verify", PersonWorkflowType::PersonOnboarding)
+            .node(service("check_identity"))
+            .node(service("end"))
+            .transition("check_identity", "end", unknown.clone())
+            .start("check_identity")
+            .end("end")
+            .build()
+            .unwrap();
+        let reviewed = WorkflowBuilder::new("Review", PersonWorkflowType::PersonOnboarding)
+            .node(node("review", NodeType::HumanTask {
+                assignee: Some("hr-manager".to_string()),
+                form_definition: None,
+                due_date: None,
+                due_in_business_days: None,
+                decision_variable: Some("hr_approved".to_string()),
+            }))
+            .node(service("end"))
+            .transition("review", "end", unknown)
+            .start("review")
+            .end("end")
+            .build()
+            .unwrap();
+        let (automated_id, reviewed_id) = (automated.id.clone(), reviewed.id.clone());
+        manager.register_workflow(automated).await.unwrap();
+        manager.register_workflow(reviewed).await.unwrap();
+
+        let actor = || PersonActor::System("test".to_string());
+        let failed_at_check = manager.start_workflow(&automated_id, HashMap::new(), actor()).await.unwrap();
+        let failed_at_review = manager.start_workflow(&reviewed_id, HashMap::new(), actor()).await.unwrap();
+        manager.complete_human_task(failed_at_review, HumanTaskDecision::approve("hr-manager")).await.unwrap();
+
+        for (instance_id, node_id) in [(failed_at_check, "check_identity"), (failed_at_review, "review")] {
+            let instance = manager.get_instance(instance_id).await.unwrap();
+            assert_eq!(instance.state, WorkflowState::Failed);
+            assert!(instance.ended_at.is_some());
+            let error = instance.error.expect("failure recorded");
+            assert_eq!(error.code, "CONDITION_ERROR");
+            assert_eq!(error.node_id.as_deref(), Some(node_id));
+            assert!(error.message.contains("identity_verified"));
+        }
+
+        let mut failed = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let WorkflowEvent::InstanceFailed { instance_id, .. } = event {
+                failed.push(instance_id);
+            }
+        }
+        assert_eq!(failed, [failed_at_check, failed_at_review]);
+        assert!(manager.list_active_instances().await.unwrap().is_empty());
+    }
+
+    /// Engine whose "slow" node waits until the test lets it finish
+    #[derive(Default)]
+    struct GateEngine {
+        started: tokio::sync::Notify,
+        proceed: tokio::sync::Notify,
+        executed: tokio::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl WorkflowEngine for GateEngine {
+        async fn execute_node(
+            &self,
+            node: &WorkflowNode,
+            _context: &mut WorkflowContext,
+        ) -> WorkflowResult<HashMap<String, serde_json::Value>> {
+            if node.id == "slow" {
+                self.started.notify_one();
+                self.proceed.notified().await;
+            }
+            self.executed.lock().await.push(node.id.clone());
+            Ok(HashMap::new())
+        }
+
+        async fn evaluate_condition(
+            &self,
+            _condition: &ConditionExpression,
+            _context: &WorkflowContext,
+        ) -> WorkflowResult<bool> {
+            Ok(true)
+        }
+
+        async fn execute_script(
+            &self,
+            _script_type: &ScriptType,
+            _script_content: &str,
+            _context: &WorkflowContext,
+        ) -> WorkflowResult<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_during_execution_is_not_overwritten() {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://localhost:4222")
+            .await
+            .unwrap();
+        let workflow = WorkflowBuilder::new("Onboard", PersonWorkflowType::PersonOnboarding)
+            .node(service("slow"))
+            .node(service("provision"))
+            .node(service("end"))
+            .transition("slow", "provision", None)
+            .transition("provision", "end", None)
+            .start("slow")
+            .end("end")
+            .build()
+            .unwrap();
+        let workflow_id = workflow.id.clone();
+
+        let engine = Arc::new(GateEngine::default());
+        let (manager, mut events) = WorkflowManager::new(engine.clone(), nats_client);
+        manager.register_workflow(workflow).await.unwrap();
+
+        let (started, ()) = tokio::join!(
+            manager.start_workflow(&workflow_id, HashMap::new(), PersonActor::System("test".to_string())),
+            async {
+                engine.started.notified().await;
+                let instance_id = manager.list_instances().await.unwrap()[0].instance_id;
+                manager.cancel_instance(instance_id, "withdrawn".to_string()).await.unwrap();
+                engine.proceed.notify_one();
+            },
+        );
+
+        let instance = manager.get_instance(started.unwrap()).await.unwrap();
+        assert_eq!(instance.state, WorkflowState::Cancelled);
+        assert_eq!(*engine.executed.lock().await, ["slow"]);
+        while let Ok(event) = events.try_recv() {
+            assert!(!matches!(event, WorkflowEvent::InstanceCompleted { .. }));
+        }
+    }
+}
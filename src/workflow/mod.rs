@@ -3,16 +3,21 @@
 //! This module provides workflow orchestration for person-related processes
 //! including onboarding, verification, employment transitions, and privacy operations.
 
+pub mod calendar;
 pub mod definitions;
 pub mod manager;
 pub mod person_workflows;
+pub mod store;
 
 // Re-export specific items to avoid conflicts
 pub use definitions::{
     WorkflowId, WorkflowState, PersonWorkflowType, WorkflowDefinition, WorkflowInstance,
+    WorkflowBuilder, WorkflowDefinitionError, DanglingReference, HumanTaskDecision,
 };
+pub use calendar::{BusinessCalendar, resolve_due_date};
 pub use manager::{
     WorkflowManager, WorkflowEngine, DefaultWorkflowEngine,
     WorkflowError, WorkflowEvent, // Both are in manager
 };
+pub use store::{WorkflowStore, InMemoryWorkflowStore};
 pub use person_workflows::*;
\ No newline at end of file
@@ -26,6 +26,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // Validate identity
@@ -47,6 +48,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
                 multiplier: 2.0,
                 retry_conditions: vec![RetryCondition::AnyError],
             }),
+            compensation: None,
         },
         
         // Decision: Identity valid?
@@ -75,6 +77,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: None,
             retry_policy: None,
+            compensation: None,
         },
         
         // Create profile
@@ -90,6 +93,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // Setup preferences
@@ -100,10 +104,13 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
                 assignee: None,
                 form_definition: Some("preferences_form".to_string()),
                 due_date: Some(Utc::now() + Duration::days(7)),
+                due_in_business_days: None,
+                decision_variable: None,
             },
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(604800)), // 7 days
             retry_policy: None,
+            compensation: None,
         },
         
         // Complete onboarding
@@ -119,6 +126,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // Identity verification failed
@@ -134,6 +142,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // End nodes
@@ -149,6 +158,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         WorkflowNode {
@@ -163,6 +173,7 @@ pub fn create_person_onboarding_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
     ];
     
@@ -262,6 +273,12 @@ pub fn create_employment_lifecycle_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: Some(NodeType::ServiceInvocation {
+                service: "EmploymentService".to_string(),
+                operation: "cancel_employment".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            }),
         },
         
         // Background check
@@ -283,6 +300,7 @@ pub fn create_employment_lifecycle_workflow() -> WorkflowDefinition {
                 multiplier: 2.0,
                 retry_conditions: vec![RetryCondition::Timeout, RetryCondition::AnyError],
             }),
+            compensation: None,
         },
         
         // Wait for HR approval
@@ -292,11 +310,14 @@ pub fn create_employment_lifecycle_workflow() -> WorkflowDefinition {
             node_type: NodeType::HumanTask {
                 assignee: Some("hr-manager".to_string()),
                 form_definition: Some("employment_approval_form".to_string()),
-                due_date: Some(Utc::now() + Duration::days(3)),
+                due_date: None,
+                due_in_business_days: Some(3),
+                decision_variable: Some("hr_approved".to_string()),
             },
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(259200)), // 3 days
             retry_policy: None,
+            compensation: None,
         },
         
         // Create employment record
@@ -312,6 +333,12 @@ pub fn create_employment_lifecycle_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(60)),
             retry_policy: None,
+            compensation: Some(NodeType::ServiceInvocation {
+                service: "EmploymentService".to_string(),
+                operation: "void_employment_record".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            }),
         },
         
         // Setup employee systems
@@ -323,20 +350,86 @@ pub fn create_employment_lifecycle_workflow() -> WorkflowDefinition {
                     ParallelBranch {
                         name: "it_provisioning".to_string(),
                         nodes: vec!["provision_it_access".to_string()],
+                        timeout: None,
                     },
                     ParallelBranch {
                         name: "benefits_enrollment".to_string(),
                         nodes: vec!["enroll_benefits".to_string()],
+                        timeout: None,
                     },
                     ParallelBranch {
                         name: "payroll_setup".to_string(),
                         nodes: vec!["setup_payroll".to_string()],
+                        timeout: None,
                     },
                 ],
+                join_policy: JoinPolicy::All,
             },
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(1800)), // 30 minutes
             retry_policy: None,
+            compensation: None,
+        },
+        
+        // Branches of the systems setup; each undoes itself if the hire fails
+        WorkflowNode {
+            id: "provision_it_access".to_string(),
+            name: "Provision IT Access".to_string(),
+            node_type: NodeType::ServiceInvocation {
+                service: "ITService".to_string(),
+                operation: "provision_access".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            },
+            configuration: NodeConfiguration::default(),
+            timeout: Some(std::time::Duration::from_secs(600)),
+            retry_policy: None,
+            compensation: Some(NodeType::ServiceInvocation {
+                service: "ITService".to_string(),
+                operation: "revoke_access".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            }),
+        },
+        
+        WorkflowNode {
+            id: "enroll_benefits".to_string(),
+            name: "Enroll in Benefits".to_string(),
+            node_type: NodeType::ServiceInvocation {
+                service: "BenefitsService".to_string(),
+                operation: "enroll".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            },
+            configuration: NodeConfiguration::default(),
+            timeout: Some(std::time::Duration::from_secs(600)),
+            retry_policy: None,
+            compensation: Some(NodeType::ServiceInvocation {
+                service: "BenefitsService".to_string(),
+                operation: "cancel_enrollment".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            }),
+        },
+        
+        WorkflowNode {
+            id: "setup_payroll".to_string(),
+            name: "Set Up Payroll".to_string(),
+            node_type: NodeType::ServiceInvocation {
+                service: "PayrollService".to_string(),
+                operation: "setup_payroll".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            },
+            configuration: NodeConfiguration::default(),
+            timeout: Some(std::time::Duration::from_secs(600)),
+            retry_policy: None,
+            compensation: Some(NodeType::ServiceInvocation {
+                service: "PayrollService".to_string(),
+                operation: "remove_payroll".to_string(),
+                input_mapping: None,
+                output_mapping: None,
+            }),
         },
         
         // Complete employment setup
@@ -352,6 +445,7 @@ pub fn create_employment_lifecycle_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // End node
@@ -367,6 +461,7 @@ pub fn create_employment_lifecycle_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
     ];
     
@@ -454,6 +549,7 @@ pub fn create_skills_certification_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // Skill assessment
@@ -469,6 +565,7 @@ pub fn create_skills_certification_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(1800)), // 30 minutes
             retry_policy: None,
+            compensation: None,
         },
         
         // Peer review
@@ -479,10 +576,13 @@ pub fn create_skills_certification_workflow() -> WorkflowDefinition {
                 assignee: None, // Will be assigned based on skill domain
                 form_definition: Some("peer_review_form".to_string()),
                 due_date: Some(Utc::now() + Duration::days(5)),
+                due_in_business_days: None,
+                decision_variable: Some("peer_review_approved".to_string()),
             },
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(432000)), // 5 days
             retry_policy: None,
+            compensation: None,
         },
         
         // Issue certification
@@ -498,6 +598,7 @@ pub fn create_skills_certification_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(60)),
             retry_policy: None,
+            compensation: None,
         },
         
         // Update skill profile
@@ -513,6 +614,7 @@ pub fn create_skills_certification_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // End node
@@ -528,6 +630,7 @@ pub fn create_skills_certification_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
     ];
     
@@ -609,6 +712,7 @@ pub fn create_privacy_compliance_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // Validate request
@@ -624,6 +728,7 @@ pub fn create_privacy_compliance_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(60)),
             retry_policy: None,
+            compensation: None,
         },
         
         // Decision: What type of request?
@@ -656,6 +761,7 @@ pub fn create_privacy_compliance_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: None,
             retry_policy: None,
+            compensation: None,
         },
         
         // Export data
@@ -677,6 +783,7 @@ pub fn create_privacy_compliance_workflow() -> WorkflowDefinition {
                 multiplier: 2.0,
                 retry_conditions: vec![RetryCondition::AnyError],
             }),
+            compensation: None,
         },
         
         // Delete data
@@ -698,6 +805,7 @@ pub fn create_privacy_compliance_workflow() -> WorkflowDefinition {
                 multiplier: 2.0,
                 retry_conditions: vec![RetryCondition::AnyError],
             }),
+            compensation: None,
         },
         
         // Notify completion
@@ -713,6 +821,7 @@ pub fn create_privacy_compliance_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
         
         // End node
@@ -728,6 +837,7 @@ pub fn create_privacy_compliance_workflow() -> WorkflowDefinition {
             configuration: NodeConfiguration::default(),
             timeout: Some(std::time::Duration::from_secs(30)),
             retry_policy: None,
+            compensation: None,
         },
     ];
     
@@ -0,0 +1,144 @@
+//! Storage for workflow instances
+//!
+//! [`WorkflowManager`](super::WorkflowManager) saves an instance after every
+//! state transition, so an in-flight workflow survives a restart as long as
+//! the store does and its definition is registered again under the same id.
+
+use std::collections::HashMap;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::definitions::{WorkflowInstance, WorkflowState};
+use super::manager::{WorkflowError, WorkflowResult};
+
+/// Persistence for workflow instances
+#[async_trait]
+pub trait WorkflowStore: Send + Sync {
+    /// Insert or replace an instance
+    async fn save_instance(&self, instance: &WorkflowInstance) -> WorkflowResult<()>;
+
+    /// Load an instance, if it was ever saved
+    async fn load_instance(&self, instance_id: Uuid) -> WorkflowResult<Option<WorkflowInstance>>;
+
+    /// Every saved instance, finished or not
+    async fn list_instances(&self) -> WorkflowResult<Vec<WorkflowInstance>>;
+
+    /// Instances that have not finished: running, waiting or suspended
+    async fn list_active(&self) -> WorkflowResult<Vec<WorkflowInstance>> {
+        let mut instances = self.list_instances().await?;
+        instances.retain(|instance| is_active(&instance.state));
+        Ok(instances)
+    }
+}
+
+/// Whether an instance in this state can still make progress
+pub fn is_active(state: &WorkflowState) -> bool {
+    matches!(
+        state,
+        WorkflowState::Pending | WorkflowState::Running | WorkflowState::Waiting | WorkflowState::Suspended
+    )
+}
+
+/// In-memory store keeping each instance as its serialized JSON
+///
+/// Storing the encoded form rather than the value means instances take the
+/// same serialization round trip they would through a durable backend.
+#[derive(Default)]
+pub struct InMemoryWorkflowStore {
+    instances: RwLock<HashMap<Uuid, String>>,
+}
+
+impl InMemoryWorkflowStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn decode(json: &str) -> WorkflowResult<WorkflowInstance> {
+    serde_json::from_str(json).map_err(|e| WorkflowError::SerializationError { message: e.to_string() })
+}
+
+#[async_trait]
+impl WorkflowStore for InMemoryWorkflowStore {
+    async fn save_instance(&self, instance: &WorkflowInstance) -> WorkflowResult<()> {
+        let json = serde_json::to_string(instance)
+            .map_err(|e| WorkflowError::SerializationError { message: e.to_string() })?;
+        self.instances.write().await.insert(instance.instance_id, json);
+        Ok(())
+    }
+
+    async fn load_instance(&self, instance_id: Uuid) -> WorkflowResult<Option<WorkflowInstance>> {
+        self.instances.read().await.get(&instance_id).map(|json| decode(json)).transpose()
+    }
+
+    async fn list_instances(&self) -> WorkflowResult<Vec<WorkflowInstance>> {
+        let mut instances = self.instances.read().await
+            .values()
+            .map(|json| decode(json))
+            .collect::<WorkflowResult<Vec<_>>>()?;
+        instances.sort_by_key(|instance| instance.instance_id);
+        Ok(instances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::definitions::{WorkflowContext, WorkflowId};
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn instance(state: WorkflowState) -> WorkflowInstance {
+        WorkflowInstance {
+            instance_id: Uuid::now_v7(),
+            workflow_id: WorkflowId::new(),
+            state,
+            current_node_id: Some("hr_approval".to_string()),
+            context: WorkflowContext {
+                input_data: HashMap::from([("person_id".to_string(), json!("p-1"))]),
+                variables: HashMap::from([
+                    ("hr_approved".to_string(), json!(true)),
+                    ("benefits".to_string(), json!({"plans": ["dental", "vision"]})),
+                ]),
+                output_data: HashMap::new(),
+                correlation_id: "corr".to_string(),
+                actor: "system:test".to_string(),
+            },
+            execution_history: Vec::new(),
+            started_at: Some(Utc::now()),
+            ended_at: None,
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_instances_round_trip_and_only_unfinished_are_active() {
+        let store = InMemoryWorkflowStore::new();
+        let waiting = instance(WorkflowState::Waiting);
+        let suspended = instance(WorkflowState::Suspended);
+        let completed = instance(WorkflowState::Completed);
+        for instance in [&waiting, &suspended, &completed] {
+            store.save_instance(instance).await.unwrap();
+        }
+
+        let loaded = store.load_instance(waiting.instance_id).await.unwrap().unwrap();
+        assert_eq!(loaded.state, WorkflowState::Waiting);
+        assert_eq!(loaded.current_node_id.as_deref(), Some("hr_approval"));
+        assert_eq!(loaded.context.variables, waiting.context.variables);
+        assert_eq!(loaded.context.input_data, waiting.context.input_data);
+        assert!(store.load_instance(Uuid::now_v7()).await.unwrap().is_none());
+
+        let active: Vec<Uuid> = store.list_active().await.unwrap().iter().map(|i| i.instance_id).collect();
+        let mut expected = vec![waiting.instance_id, suspended.instance_id];
+        expected.sort();
+        assert_eq!(active, expected);
+        assert_eq!(store.list_instances().await.unwrap().len(), 3);
+
+        // Saving again replaces the stored state
+        let mut finished = waiting.clone();
+        finished.state = WorkflowState::Completed;
+        store.save_instance(&finished).await.unwrap();
+        assert_eq!(store.list_active().await.unwrap().len(), 1);
+    }
+}
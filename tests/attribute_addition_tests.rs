@@ -7,11 +7,11 @@
 
 use cim_domain_person::{
     aggregate::{Person, PersonId},
-    commands::{PersonCommand, RecordAttribute},
+    commands::{PersonCommand, RecordAttribute, RecordAttributeWithDocument},
     value_objects::{
         PersonName, PersonAttribute, AttributeType, AttributeValue,
         IdentifyingAttributeType, PhysicalAttributeType, HealthcareAttributeType,
-        TemporalValidity, Provenance, AttributeSource, ConfidenceLevel,
+        TemporalValidity, Provenance, AttributeSource, ConfidenceLevel, DocumentReference,
     },
 };
 use chrono::{Utc, NaiveDate};
@@ -264,3 +264,36 @@ fn test_version_increments_with_each_attribute() {
 
     assert_eq!(person.version, initial_version + 2);
 }
+
+#[test]
+fn test_document_reference_survives_event() {
+    let person_id = PersonId::new();
+    let mut person = Person::new(person_id, PersonName::new("Test".to_string(), "Person".to_string()));
+
+    let passport = DocumentReference::new("doc-42", "passport", "sha256:9f86d081884c7d65");
+    let command = PersonCommand::RecordAttributeWithDocument(RecordAttributeWithDocument {
+        person_id,
+        attribute: PersonAttribute::new(
+            AttributeType::Identifying(IdentifyingAttributeType::BirthDate),
+            AttributeValue::Date(NaiveDate::from_ymd_opt(1990, 1, 1).unwrap()),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::SelfReported, ConfidenceLevel::Certain),
+        ),
+        document: passport.clone(),
+    });
+
+    let current_state = person.lifecycle.clone();
+    let events = MealyStateMachine::output(&person, current_state.into(), command);
+    assert_eq!(events.len(), 1);
+
+    // Through serialization, as the event store would
+    let json = serde_json::to_string(&events[0]).unwrap();
+    let event = serde_json::from_str(&json).unwrap();
+    person = person.apply_event_pure(&event).unwrap();
+
+    let attr = person
+        .get_attribute(&AttributeType::Identifying(IdentifyingAttributeType::BirthDate))
+        .unwrap();
+    assert_eq!(attr.provenance.source, AttributeSource::DocumentVerified);
+    assert_eq!(attr.provenance.document_reference, Some(passport));
+}
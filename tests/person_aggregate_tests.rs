@@ -2,7 +2,7 @@
 //! Based on user stories US-3.1, US-3.2, US-4.1 through US-4.4, US-5.4, US-6.1, US-6.2
 
 use cim_domain_person::{
-    aggregate::{Person, PersonId, PersonLifecycle},
+    aggregate::{Person, PersonId, PersonLifecycle, DeathRecordError, PersonCommandError},
     commands::{CreatePerson, MergeReason},
     events::{PersonEvent, PersonCreated, AttributeRecorded, AttributeUpdated, AttributeInvalidated},
     value_objects::{PersonName, PersonAttribute, AttributeType, AttributeValue, IdentifyingAttributeType, PhysicalAttributeType, TemporalValidity, Provenance, AttributeSource, ConfidenceLevel},
@@ -245,6 +245,7 @@ fn test_invalidate_attribute_event() {
         attribute_type: AttributeType::Identifying(IdentifyingAttributeType::BirthDate),
         invalidated_at: Utc::now(),
         reason: Some("data entry error".to_string()),
+        attribute_recorded_at: None,
     });
 
     // Verify event structure
@@ -654,3 +655,159 @@ fn test_category_query_methods() {
     assert_eq!(identifying.attributes.len(), 1);
     assert_eq!(healthcare.attributes.len(), 1);
 }
+
+// ===== Conditional execution (compare-and-set) =====
+
+#[test]
+fn test_stale_expected_version_is_rejected_without_state_change() {
+    use cim_domain_person::commands::{PersonCommand, PreconditionFailed, UpdateName, VersionedCommand};
+
+    let person_id = PersonId::new();
+    let person = Person::empty()
+        .handle_versioned(VersionedCommand::unconditional(PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Alice".to_string(), "Smith".to_string()),
+            source: "test".to_string(),
+        })))
+        .unwrap()
+        .0;
+    assert_eq!(person.version, 1);
+
+    let rename = |given: &str| PersonCommand::UpdateName(UpdateName {
+        person_id,
+        name: PersonName::new(given.to_string(), "Smith".to_string()),
+        reason: None,
+    });
+
+    // Client read version 0, but the aggregate has moved on to version 1
+    let result = person.handle_versioned(VersionedCommand::expecting(rename("Alicia"), 0));
+    assert!(matches!(
+        result,
+        Err(PersonCommandError::PreconditionFailed(PreconditionFailed { expected: 0, actual: 1 }))
+    ));
+    assert_eq!(person.version, 1);
+    assert_eq!(person.core_identity.legal_name.components.given_names[0], "Alice");
+
+    // The current version is accepted
+    let (renamed, events) = person
+        .handle_versioned(VersionedCommand::expecting(rename("Alicia"), 1))
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(renamed.version, 2);
+}
+
+// ===== Date of death consistency =====
+
+fn created_person(person_id: PersonId) -> Person {
+    use cim_domain::formal_domain::Aggregate;
+    use cim_domain_person::commands::PersonCommand;
+
+    Person::empty()
+        .handle(PersonCommand::CreatePerson(CreatePerson {
+            person_id,
+            name: PersonName::new("Ada".to_string(), "Lovelace".to_string()),
+            source: "test".to_string(),
+        }))
+        .unwrap()
+        .0
+}
+
+fn record_death(person: Person, date_of_death: NaiveDate) -> Result<(Person, Vec<PersonEvent>), PersonCommandError> {
+    use cim_domain_person::commands::{PersonCommand, RecordDeath};
+
+    let person_id = person.id;
+    person.try_handle(PersonCommand::RecordDeath(RecordDeath { person_id, date_of_death }))
+}
+
+fn assert_rejected(err: PersonCommandError, expected: DeathRecordError) {
+    assert!(
+        matches!(&err, PersonCommandError::DeathRecord(cause) if *cause == expected),
+        "expected {expected:?}, got {err:?}"
+    );
+}
+
+#[test]
+fn test_death_without_known_birth_date_is_recorded() {
+    let person = created_person(PersonId::new());
+    let date = NaiveDate::from_ymd_opt(1852, 11, 27).unwrap();
+
+    let (person, events) = record_death(person, date).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(person.core_identity.death_date, Some(date));
+    assert_eq!(person.lifecycle, PersonLifecycle::Deceased { date_of_death: date });
+}
+
+#[test]
+fn test_death_before_birth_is_rejected() {
+    use cim_domain::formal_domain::Aggregate;
+    use cim_domain_person::commands::{PersonCommand, SetBirthDate};
+
+    let person_id = PersonId::new();
+    let birth_date = NaiveDate::from_ymd_opt(1815, 12, 10).unwrap();
+    let (person, _) = created_person(person_id)
+        .handle(PersonCommand::SetBirthDate(SetBirthDate { person_id, birth_date }))
+        .unwrap();
+
+    let date_of_death = NaiveDate::from_ymd_opt(1815, 12, 9).unwrap();
+    let err = record_death(person.clone(), date_of_death).unwrap_err();
+    assert_rejected(err, DeathRecordError::BeforeBirth { date_of_death, birth_date });
+
+    // Dying on the day of birth is consistent
+    let (person, _) = record_death(person, birth_date).unwrap();
+    assert_eq!(person.core_identity.death_date, Some(birth_date));
+}
+
+#[test]
+fn test_death_cannot_be_recorded_twice() {
+    let date = NaiveDate::from_ymd_opt(1852, 11, 27).unwrap();
+    let (person, _) = record_death(created_person(PersonId::new()), date).unwrap();
+
+    let err = record_death(person.clone(), NaiveDate::from_ymd_opt(1853, 1, 1).unwrap()).unwrap_err();
+    assert_rejected(err, DeathRecordError::AlreadyRecorded { date_of_death: date });
+    assert_eq!(person.core_identity.death_date, Some(date));
+}
+
+// ===== Typed lifecycle rejections =====
+
+#[test]
+fn test_commands_on_inactive_persons_are_rejected_with_the_cause() {
+    use cim_domain::{formal_domain::Aggregate, DomainError};
+    use cim_domain_person::commands::{AddTag, DeactivatePerson, MergePersons, PersonCommand, UpdateName};
+    use cim_domain_person::value_objects::Tag;
+
+    let person_id = PersonId::new();
+    let rename = PersonCommand::UpdateName(UpdateName {
+        person_id,
+        name: PersonName::new("Augusta".to_string(), "King".to_string()),
+        reason: None,
+    });
+
+    let (deactivated, _) = created_person(person_id)
+        .handle(PersonCommand::DeactivatePerson(DeactivatePerson { person_id, reason: "audit".to_string() }))
+        .unwrap();
+    let err = deactivated.clone().try_handle(rename.clone()).unwrap_err();
+    assert!(
+        matches!(&err, PersonCommandError::InactivePerson { reason } if reason == "audit"),
+        "got {err:?}"
+    );
+    assert!(matches!(deactivated.handle(rename.clone()), Err(DomainError::ValidationError(_))));
+
+    let target_id = PersonId::new();
+    let (merged, _) = created_person(person_id)
+        .handle(PersonCommand::MergePersons(MergePersons {
+            source_person_id: person_id,
+            target_person_id: target_id,
+            merge_reason: MergeReason::DuplicateIdentity,
+        }))
+        .unwrap();
+    let tag = PersonCommand::AddTag(AddTag { person_id, tag: Tag::new("segment", "vip").unwrap() });
+    for command in [rename, tag] {
+        let err = merged.clone().try_handle(command).unwrap_err();
+        assert!(
+            matches!(&err, PersonCommandError::AlreadyMerged { merged_into } if *merged_into == target_id),
+            "got {err:?}"
+        );
+        // The message is the one can_be_modified always reported
+        assert_eq!(err.to_string(), "Cannot modify a merged person");
+    }
+}
@@ -865,3 +865,29 @@ fn test_all_blood_type_variants() {
         assert!(matches!(attr.value, AttributeValue::BloodType(_)));
     }
 }
+
+#[test]
+fn test_person_age_prefers_zoned_birth_attribute() {
+    use cim_domain_person::aggregate::{Person, PersonId};
+    use cim_domain_person::value_objects::{DateZone, PersonName};
+    use chrono::FixedOffset;
+
+    let born = NaiveDate::from_ymd_opt(1990, 7, 1).unwrap();
+    // 22:00 UTC on 30 June is already 1 July in Sydney (UTC+10)
+    let instant = NaiveDate::from_ymd_opt(2020, 6, 30).unwrap().and_hms_opt(22, 0, 0).unwrap().and_utc();
+
+    let mut person = Person::new(PersonId::new(), PersonName::new("Ada".to_string(), "Lovelace".to_string()));
+    person.core_identity.birth_date = Some(born);
+    assert_eq!(person.age_at(instant), Some(29));
+
+    person.attributes = PersonAttributeSet::of(
+        PersonAttribute::new(
+            AttributeType::Identifying(IdentifyingAttributeType::BirthDate),
+            AttributeValue::Date(born),
+            TemporalValidity::of(Utc::now()),
+            Provenance::new(AttributeSource::DocumentVerified, ConfidenceLevel::Certain),
+        )
+        .with_date_zone(DateZone::from_offset(FixedOffset::east_opt(10 * 3600).unwrap())),
+    );
+    assert_eq!(person.age_at(instant), Some(30));
+}